@@ -0,0 +1,135 @@
+//! Benchmarks for the hot paths of working with manifests:
+//! hashing names into [`Id`]s, looking items up, and processing raw manifests in bulk.
+
+use bevy::{prelude::*, utils::HashMap};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use leafwing_manifest::{
+    identifier::{Id, IdMap},
+    manifest::{Manifest, ManifestFormat},
+};
+use serde::Deserialize;
+
+/// The number of items stored in the manifests used for lookup and processing benchmarks.
+const MANIFEST_SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)] // Properties are for demonstration purposes only.
+struct Item {
+    name: String,
+    value: i32,
+}
+
+#[derive(Debug, Asset, TypePath, Deserialize)]
+struct RawItemManifest {
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Resource)]
+struct ItemManifest {
+    items: IdMap<Item, Item>,
+}
+
+impl Manifest for ItemManifest {
+    type Item = Item;
+    type RawItem = Item;
+    type RawManifest = RawItemManifest;
+    type ConversionError = std::convert::Infallible;
+
+    const FORMAT: ManifestFormat = ManifestFormat::Custom;
+
+    fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+        self.items.get(&id)
+    }
+
+    fn from_raw_manifest(
+        raw_manifest: Self::RawManifest,
+        _world: &mut World,
+    ) -> Result<Self, Self::ConversionError> {
+        let items = raw_manifest
+            .items
+            .into_iter()
+            .map(|item| (Id::from_name(&item.name), item))
+            .collect();
+
+        Ok(ItemManifest { items })
+    }
+}
+
+fn raw_items(n: usize) -> Vec<Item> {
+    (0..n)
+        .map(|i| Item {
+            name: format!("item_{i}"),
+            value: i as i32,
+        })
+        .collect()
+}
+
+fn from_name(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Id::from_name");
+    for name in ["sword", "enchanted_sword_of_the_ancient_kings"] {
+        group.bench_with_input(BenchmarkId::from_parameter(name), name, |b, name| {
+            b.iter(|| Id::<Item>::from_name(black_box(name)))
+        });
+    }
+    group.finish();
+}
+
+fn lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for size in MANIFEST_SIZES {
+        let items = raw_items(size);
+        let ids: Vec<Id<Item>> = items.iter().map(|item| Id::from_name(&item.name)).collect();
+        let names: Vec<String> = items.iter().map(|item| item.name.clone()).collect();
+
+        let id_map: IdMap<Item, Item> = ids.iter().copied().zip(items.iter().cloned()).collect();
+        let default_map: HashMap<Id<Item>, Item> =
+            ids.iter().copied().zip(items.iter().cloned()).collect();
+        let manifest = ItemManifest { items: id_map };
+
+        group.bench_with_input(BenchmarkId::new("IdMap::get", size), &ids, |b, ids| {
+            b.iter(|| {
+                for id in ids {
+                    black_box(manifest.get(*id));
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("HashMap::get", size), &ids, |b, ids| {
+            b.iter(|| {
+                for id in ids {
+                    black_box(default_map.get(id));
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("get_by_name", size), &names, |b, names| {
+            b.iter(|| {
+                for name in names {
+                    black_box(manifest.get_by_name(name.as_str()));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn processing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_raw_manifest");
+    for size in MANIFEST_SIZES {
+        let items = raw_items(size);
+        let mut world = World::new();
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &items, |b, items| {
+            b.iter(|| {
+                let raw_manifest = RawItemManifest {
+                    items: items.clone(),
+                };
+                ItemManifest::from_raw_manifest(raw_manifest, &mut world).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_name, lookups, processing);
+criterion_main!(benches);
@@ -20,9 +20,9 @@ use manifest_definition::{ItemManifest, RawItemManifest};
 mod manifest_definition {
     use std::path::PathBuf;
 
-    use bevy::{prelude::*, utils::HashMap};
+    use bevy::prelude::*;
     use leafwing_manifest::{
-        identifier::Id,
+        identifier::{Id, IdMap},
         manifest::{Manifest, ManifestFormat},
     };
     use serde::{Deserialize, Serialize};
@@ -50,7 +50,7 @@ mod manifest_definition {
 
     #[derive(Debug, Resource, PartialEq)]
     pub struct ItemManifest {
-        items: HashMap<Id<Item>, Item>,
+        items: IdMap<Item, Item>,
     }
 
     #[derive(Debug, Asset, TypePath, Serialize, Deserialize, PartialEq, Clone)]
@@ -76,7 +76,7 @@ mod manifest_definition {
         ) -> Result<Self, Self::ConversionError> {
             let asset_server = world.resource::<AssetServer>();
 
-            let items: HashMap<_, _> = raw_manifest
+            let items: IdMap<_, _> = raw_manifest
                 .items
                 .into_iter()
                 .map(|raw_item| {
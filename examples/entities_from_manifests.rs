@@ -11,13 +11,9 @@
 //! If you need to spawn a scene hierarchy (such as for levels or 3D models), storing a handle to that scene can work well,
 //! or a scene bundle can be added to your custom bundle type.
 
-use bevy::{prelude::*, sprite::Mesh2dHandle, utils::HashMap};
-use leafwing_manifest::{
-    asset_state::SimpleAssetState,
-    identifier::Id,
-    manifest::{Manifest, ManifestFormat},
-    plugin::{ManifestPlugin, RegisterManifest},
-};
+use bevy::{prelude::*, sprite::Mesh2dHandle};
+use leafwing_manifest::prelude::*;
+use leafwing_manifest::procedural_assets::ProceduralAssets;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -72,8 +68,8 @@ impl TileBundle {
         Self {
             id: Id::from_name(&tile.name),
             tile_type: tile.tile_type,
-            // We can use weak clones here and save a tiny bit of work,
-            // since the manifest will always store a canonical strong handle to the assets.
+            // The manifest's asset store keeps a canonical strong handle to the assets,
+            // so weak handles are all we need here.
             material: tile.color_material.clone_weak(),
             // While the value of the mesh is the same for all tiles, passing around `&Assets<Mesh>` everywhere
             // is miserable. Instead, we sacrifice a little bit of memory to redundantly store the mesh handle in the manifest:
@@ -91,7 +87,7 @@ pub struct RawTileManifest {
 
 #[derive(Resource, Default)]
 pub struct TileManifest {
-    tiles: HashMap<Id<Tile>, Tile>,
+    tiles: IdMap<Tile, Tile>,
 }
 
 impl Manifest for TileManifest {
@@ -110,12 +106,14 @@ impl Manifest for TileManifest {
         raw_manifest: Self::RawManifest,
         world: &mut World,
     ) -> Result<Self, Self::ConversionError> {
-        let mut meshes = world.resource_mut::<Assets<Mesh>>();
-        let mesh = meshes.add(Mesh::from(Rectangle::new(1.0, 1.0)));
+        // `ProceduralAssets` keeps a strong handle to each asset alive in the manifest's asset store,
+        // so the assets aren't dropped as soon as this function returns.
+        let mesh =
+            ProceduralAssets::<Self, Mesh>::new(world).add(Mesh::from(Rectangle::new(1.0, 1.0)));
         // This is a thin wrapper around a `Handle<Mesh>`, used in 2D rendering.
-        let mesh_2d = Mesh2dHandle::from(mesh.clone());
+        let mesh_2d = Mesh2dHandle::from(mesh);
 
-        let mut color_materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let mut color_materials = ProceduralAssets::<Self, ColorMaterial>::new(world);
 
         let mut manifest = TileManifest::default();
 
@@ -124,15 +122,17 @@ impl Manifest for TileManifest {
             // driven by hand-tuned parameters in the manifest.
             // In a real game, you might use a more complex system to generate the assets,
             // but the general pattern is very effective for creating cohesive but varied content.
-            let color_material = color_materials.add(Color::rgb_from_array(raw_tile.color));
+            // Tiles with the same color share a single material: floats can't be hashed, so we hash their bits instead.
+            let color_material = color_materials
+                .get_or_add(raw_tile.color.map(f32::to_bits), || {
+                    ColorMaterial::from(Color::rgb_from_array(raw_tile.color))
+                });
 
             manifest.tiles.insert(
                 Id::from_name(&raw_tile.name),
                 Tile {
                     name: raw_tile.name,
                     color_material,
-                    // We need to store strong handles here: otherwise the procedural mesh will be dropped immediately
-                    // when the original declaration goes out of scope.
                     mesh: mesh_2d.clone(),
                     tile_type: raw_tile.tile_type,
                 },
@@ -0,0 +1,74 @@
+//! This example demonstrates loading manifests in a headless dedicated server.
+//!
+//! Servers typically only use the [`MinimalPlugins`], and must not accept any connections until their game data is ready.
+//! Rather than handing control over to [`App::run`], this example drives the app manually,
+//! using [`drive_manifest_loading`] to wait until every manifest has been processed.
+//!
+//! The manifest used here is the same as in the `simple.rs` example.
+
+use std::time::Duration;
+
+use bevy::{log::LogPlugin, prelude::*};
+use leafwing_manifest::plugin::drive_manifest_loading;
+use leafwing_manifest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[allow(dead_code)] // Properties are for demonstration purposes only.
+struct Item {
+    name: String,
+    description: String,
+    value: i32,
+    weight: f32,
+    max_stack: u8,
+}
+
+#[derive(Debug, Resource, Asset, TypePath, Serialize, Deserialize, PartialEq)]
+struct ItemManifest {
+    items: IdMap<Item, Item>,
+}
+
+impl Manifest for ItemManifest {
+    type Item = Item;
+    type RawItem = Item;
+    type RawManifest = ItemManifest;
+    type ConversionError = std::convert::Infallible;
+
+    const FORMAT: ManifestFormat = ManifestFormat::Ron;
+
+    fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+        self.items.get(&id)
+    }
+
+    fn from_raw_manifest(
+        raw_manifest: Self::RawManifest,
+        _world: &mut World,
+    ) -> Result<Self, Self::ConversionError> {
+        Ok(raw_manifest)
+    }
+}
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), LogPlugin::default()))
+        // The server advances the loading state itself, via `drive_manifest_loading`.
+        .add_plugins(ManifestPlugin::<SimpleAssetState>::new().manual_state_advancement())
+        .register_manifest::<ItemManifest>("items.ron");
+
+    // Manifests are required before we can accept any connections,
+    // so keep updating the app until they're ready.
+    loop {
+        app.update();
+
+        match drive_manifest_loading::<SimpleAssetState>(&mut app.world) {
+            SimpleAssetState::Ready => break,
+            SimpleAssetState::Failed => panic!("The server's manifests failed to load."),
+            _ => std::thread::sleep(Duration::from_millis(1)),
+        }
+    }
+
+    let item_count = app.world.resource::<ItemManifest>().items.len();
+    info!("Loaded {item_count} items. Accepting connections.");
+
+    // A real server would now start listening for connections, and call `app.update()` on every tick.
+}
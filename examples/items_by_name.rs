@@ -12,13 +12,8 @@
 //!
 //! This code is largely copied from the `simple.rs` example: we're just adding constants and a new system to demonstrate the name-based lookups.
 
-use bevy::{log::LogPlugin, prelude::*, utils::HashMap};
-use leafwing_manifest::{
-    asset_state::SimpleAssetState,
-    identifier::Id,
-    manifest::{Manifest, ManifestFormat},
-    plugin::{ManifestPlugin, RegisterManifest},
-};
+use bevy::{log::LogPlugin, prelude::*};
+use leafwing_manifest::prelude::*;
 use serde::{Deserialize, Serialize};
 
 // While you *can* simply use the name directly via the various name-based methods on the Manifest trait,
@@ -42,7 +37,7 @@ struct Item {
 
 #[derive(Debug, Resource, Asset, TypePath, Serialize, Deserialize, PartialEq)]
 struct ItemManifest {
-    items: HashMap<Id<Item>, Item>,
+    items: IdMap<Item, Item>,
 }
 
 impl Manifest for ItemManifest {
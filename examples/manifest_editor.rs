@@ -0,0 +1,124 @@
+//! This example demonstrates editing the items of a manifest at runtime, using the egui editor enabled by the `editor` feature.
+//!
+//! The editor window lists every item in the manifest.
+//! Select an item to edit its fields, type a name and press "Create" to add a new item from its default value,
+//! press "Validate" to check the items against the lint rules, and press "Save" to write the changes back to `assets/items.ron`.
+//!
+//! Run it with `cargo run --example manifest_editor --features editor`.
+
+use bevy::prelude::*;
+use leafwing_manifest::{
+    editor::ManifestEditorPlugin,
+    lints::{LintRules, ManifestLintPlugin},
+    prelude::*,
+    write_back::WriteBackManifest,
+};
+use serde::{Deserialize, Serialize};
+
+/// The data for a single item that might be held in the player's inventory.
+///
+/// Editing relies on reflection, and new items are created from the reflected [`Default`] value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Reflect)]
+#[reflect(Default)]
+struct Item {
+    name: String,
+    description: String,
+    value: i32,
+    weight: f32,
+    max_stack: u8,
+}
+
+/// A data-driven manifest, which contains the canonical data for all the items in the game.
+#[derive(Debug, Resource, Asset, TypePath, Serialize, Deserialize, PartialEq)]
+struct ItemManifest {
+    items: IdMap<Item, Item>,
+}
+
+impl Manifest for ItemManifest {
+    type Item = Item;
+    type RawItem = Item;
+    type RawManifest = ItemManifest;
+    type ConversionError = std::convert::Infallible;
+
+    const FORMAT: ManifestFormat = ManifestFormat::Ron;
+
+    fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+        self.items.get(&id)
+    }
+
+    fn from_raw_manifest(
+        raw_manifest: Self::RawManifest,
+        _world: &mut World,
+    ) -> Result<Self, Self::ConversionError> {
+        // Registering the names lets the editor list items by name, rather than by id.
+        for item in raw_manifest.items.values() {
+            NameRegistry::register::<Item>(&item.name);
+        }
+
+        Ok(raw_manifest)
+    }
+}
+
+impl IterableManifest for ItemManifest {
+    fn iter(&self) -> impl Iterator<Item = (Id<Item>, &Item)> {
+        self.items.iter().map(|(id, item)| (*id, item))
+    }
+}
+
+impl MutableManifest for ItemManifest {
+    fn insert(&mut self, item: Item) -> Result<Id<Item>, ManifestModificationError<Self>> {
+        let id = Id::from_name(&item.name);
+        if self.items.contains_key(&id) {
+            return Err(ManifestModificationError::DuplicateName(item.name));
+        }
+
+        self.items.insert(id, item);
+        Ok(id)
+    }
+
+    // New items are created under the id of the name entered in the editor.
+    fn id_of(&self, item: &Item) -> Option<Id<Item>> {
+        Some(Id::from_name(&item.name))
+    }
+
+    fn remove(&mut self, id: &Id<Item>) -> Result<Id<Item>, ManifestModificationError<Self>> {
+        self.items.remove(id);
+        Ok(*id)
+    }
+
+    fn get_mut(&mut self, id: Id<Item>) -> Option<&mut Item> {
+        self.items.get_mut(&id)
+    }
+}
+
+// The processed manifest is identical to the raw manifest, so writing it back is a simple copy.
+impl WriteBackManifest for ItemManifest {
+    fn to_raw_manifest(&self) -> ItemManifest {
+        ItemManifest {
+            items: self.items.clone(),
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .init_state::<SimpleAssetState>()
+        // The `editor` feature enables the `mutable` feature, so manifests are not frozen once processed.
+        .add_plugins(ManifestPlugin::<SimpleAssetState>::default())
+        .register_type::<Item>()
+        .register_manifest::<ItemManifest>("items.ron")
+        // The rules checked by the "Validate" button.
+        .add_plugins(ManifestLintPlugin::new(
+            LintRules::<ItemManifest>::new()
+                .non_empty("description", |item: &Item| &item.description),
+        ))
+        .add_plugins(ManifestEditorPlugin::<ItemManifest>::default())
+        .add_systems(Startup, spawn_camera)
+        .run();
+}
+
+/// egui is drawn on top of the output of a camera.
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
@@ -13,13 +13,8 @@
 
 use std::path::PathBuf;
 
-use bevy::{app::AppExit, prelude::*, utils::HashMap};
-use leafwing_manifest::{
-    asset_state::SimpleAssetState,
-    identifier::Id,
-    manifest::{Manifest, ManifestFormat},
-    plugin::{ManifestPlugin, RegisterManifest},
-};
+use bevy::{app::AppExit, prelude::*};
+use leafwing_manifest::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// The data for as single item that might be held in the player's inventory.
@@ -57,7 +52,7 @@ struct RawItem {
 /// This is the bevy [`Resource`] that our [`Item`]s will be stored in after they are loaded
 #[derive(Debug, Resource, PartialEq)]
 struct ItemManifest {
-    items: HashMap<Id<Item>, Item>,
+    items: IdMap<Item, Item>,
 }
 
 /// The raw format for [`ItemManifest`]
@@ -96,7 +91,7 @@ impl Manifest for ItemManifest {
         // Asset server to load our sprite assets
         let asset_server = world.resource::<AssetServer>();
 
-        let items: HashMap<_, _> = raw_manifest
+        let items: IdMap<_, _> = raw_manifest
             .items
             .into_iter()
             .map(|raw_item| {
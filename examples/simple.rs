@@ -6,13 +6,8 @@
 //! See the other examples for more advanced use cases!
 //! The `raw_manifest.rs` example is a good next step that builds upon this example.
 
-use bevy::{app::AppExit, log::LogPlugin, prelude::*, utils::HashMap};
-use leafwing_manifest::{
-    asset_state::SimpleAssetState,
-    identifier::Id,
-    manifest::{Manifest, ManifestFormat},
-    plugin::{ManifestPlugin, RegisterManifest},
-};
+use bevy::{app::AppExit, log::LogPlugin, prelude::*};
+use leafwing_manifest::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// The data for as single item that might be held in the player's inventory.
@@ -35,7 +30,7 @@ struct Item {
 /// A data-driven manifest, which contains the canonical data for all the items in the game.
 #[derive(Debug, Resource, Asset, TypePath, Serialize, Deserialize, PartialEq)]
 struct ItemManifest {
-    items: HashMap<Id<Item>, Item>,
+    items: IdMap<Item, Item>,
 }
 
 impl Manifest for ItemManifest {
@@ -106,7 +101,7 @@ mod tests {
 
     #[test]
     fn generate_item_manifest() {
-        let mut items = HashMap::default();
+        let mut items = IdMap::default();
 
         items.insert(
             Id::from_name("sword".into()),
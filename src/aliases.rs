@@ -0,0 +1,370 @@
+//! Old names for renamed items, so that saves and other content referring to them keep working.
+//!
+//! Renaming an item changes its [`Id`], since [`Id::from_name`] hashes the name.
+//! To keep the old name working, raw items can list the names they were previously known by:
+//!
+//! ```ron
+//! (name: "iron_sword", aliases: ["rusty_sword", "sword"])
+//! ```
+//!
+//! Implement [`AliasedRawItem`] for the raw item type, record the aliases with [`record_item_aliases`] in
+//! [`Manifest::from_raw_manifest`], and look items up through an [`AliasedManifest`]:
+//!
+//! ```rust
+//! use bevy::ecs::system::SystemState;
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     aliases::{record_item_aliases, AliasedManifest, AliasedRawItem, ItemAliases},
+//!     asset_state::SimpleAssetState,
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct RawItem {
+//!     name: String,
+//!     #[serde(default)]
+//!     aliases: Vec<String>,
+//! }
+//!
+//! impl AliasedRawItem for RawItem {
+//!     fn aliases(&self) -> &[String] {
+//!         &self.aliases
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! struct Item {
+//!     name: String,
+//! }
+//!
+//! #[derive(Asset, TypePath, Deserialize)]
+//! struct RawItemManifest {
+//!     items: Vec<RawItem>,
+//! }
+//!
+//! #[derive(Resource)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = RawItem;
+//!     type RawManifest = RawItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: RawItemManifest,
+//!         world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         record_item_aliases::<Self, _>(
+//!             world,
+//!             raw_manifest.items.iter().map(|raw| (Id::from_name(&raw.name), raw)),
+//!         );
+//!
+//!         let items = raw_manifest
+//!             .items
+//!             .into_iter()
+//!             .map(|raw| (Id::from_name(&raw.name), Item { name: raw.name }))
+//!             .collect();
+//!         Ok(Self { items })
+//!     }
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.insert_raw_manifest::<ItemManifest>(RawItemManifest {
+//!     items: vec![RawItem {
+//!         name: "iron_sword".to_string(),
+//!         aliases: vec!["rusty_sword".to_string(), "sword".to_string()],
+//!     }],
+//! });
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! let mut system_state = SystemState::<AliasedManifest<ItemManifest>>::new(&mut app.world);
+//! let items = system_state.get(&app.world);
+//! // Resolves to the "iron_sword" item, logging a deprecation warning.
+//! assert_eq!(items.get(Id::from_name("rusty_sword")).unwrap().name, "iron_sword");
+//! // Lookups through the manifest itself do not resolve aliases.
+//! assert!(items.get_by_name("sword").is_some());
+//! assert!(app.world.resource::<ItemManifest>().get_by_name("sword").is_none());
+//!
+//! let used = app.world.resource::<ItemAliases<ItemManifest>>().used();
+//! assert_eq!(used.len(), 2);
+//! assert_eq!(used[0].alias, "rusty_sword");
+//! ```
+//!
+//! Adding an [`ItemAliasPlugin`] also lists every alias that was used in the [`ValidationReport`],
+//! so the content referring to old names can be found and updated.
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::log::warn;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::Manifest;
+use crate::migrations::IdMigrations;
+use crate::missing_lookups::{report_missing_lookup, MissingLookupPolicy, MissingLookups};
+use crate::name_registry::NameRegistry;
+use crate::validation::ValidationReport;
+
+/// A raw item that can list the old names it was previously known by.
+pub trait AliasedRawItem {
+    /// The previous names of this item, which should still resolve to it.
+    ///
+    /// Raw items typically store these in a `#[serde(default)] aliases: Vec<String>` field.
+    fn aliases(&self) -> &[String];
+}
+
+/// A use of a deprecated alias, as part of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasLookup {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The old name that was used.
+    pub alias: String,
+    /// The current name of the item that the alias resolved to.
+    ///
+    /// Items without a name in the [`NameRegistry`] are listed by their [`Id`].
+    pub target: String,
+}
+
+/// The aliases of the items in the manifest `M`, mapping the [`Id`] of each old name to the current item.
+///
+/// This resource is replaced each time the manifest is processed by [`record_item_aliases`].
+#[derive(Resource)]
+pub struct ItemAliases<M: Manifest> {
+    aliases: IdMap<M::Item, (String, Id<M::Item>)>,
+    // Lookups happen through shared references, so this needs interior mutability.
+    used: Mutex<Vec<Id<M::Item>>>,
+}
+
+impl<M: Manifest> ItemAliases<M> {
+    /// Records that `alias` is an old name of the item with the [`Id`] `target`.
+    pub fn record(&mut self, alias: impl Into<String>, target: Id<M::Item>) {
+        let alias = alias.into();
+        self.aliases.insert(Id::from_name(&alias), (alias, target));
+    }
+
+    /// Returns the [`Id`] of the item that `id` is an alias for, if it is an alias.
+    ///
+    /// This does not record the lookup: see [`ItemAliases::resolve`].
+    #[must_use]
+    pub fn target(&self, id: Id<M::Item>) -> Option<Id<M::Item>> {
+        self.aliases.get(&id).map(|(_, target)| *target)
+    }
+
+    /// Returns the [`Id`] of the item that `id` is an alias for, recording that the alias was used.
+    ///
+    /// A deprecation warning is logged the first time each alias is used.
+    /// If `id` is not an alias, it is returned unchanged.
+    pub fn resolve(&self, id: Id<M::Item>) -> Id<M::Item> {
+        let Some((alias, target)) = self.aliases.get(&id) else {
+            return id;
+        };
+
+        if let Ok(mut used) = self.used.lock() {
+            if !used.contains(&id) {
+                used.push(id);
+                warn!(
+                    "The name {alias} in {} is deprecated: use {} instead.",
+                    std::any::type_name::<M>(),
+                    NameRegistry::name_of(*target).unwrap_or_else(|| format!("{target:?}"))
+                );
+            }
+        }
+
+        *target
+    }
+
+    /// Returns the old names of the item with the given `id`.
+    pub fn aliases_of(&self, id: Id<M::Item>) -> impl Iterator<Item = &str> {
+        self.aliases
+            .values()
+            .filter(move |(_, target)| *target == id)
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    /// Returns every alias that has been used, in the order they were first used.
+    #[must_use]
+    pub fn used(&self) -> Vec<AliasLookup> {
+        let Ok(used) = self.used.lock() else {
+            return Vec::new();
+        };
+
+        used.iter()
+            .filter_map(|id| self.aliases.get(id))
+            .map(|(alias, target)| AliasLookup {
+                type_name: std::any::type_name::<M>(),
+                alias: alias.clone(),
+                target: NameRegistry::name_of(*target).unwrap_or_else(|| format!("{target:?}")),
+            })
+            .collect()
+    }
+
+    /// The number of aliases.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.aliases.len()
+    }
+
+    /// Returns true if there are no aliases.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
+impl<M: Manifest> Default for ItemAliases<M> {
+    fn default() -> Self {
+        Self {
+            aliases: IdMap::default(),
+            used: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ItemAliases<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemAliases")
+            .field("aliases", &self.aliases.len())
+            .finish()
+    }
+}
+
+impl<'a, M: Manifest, R: AliasedRawItem + 'a> FromIterator<(Id<M::Item>, &'a R)>
+    for ItemAliases<M>
+{
+    fn from_iter<I: IntoIterator<Item = (Id<M::Item>, &'a R)>>(raw_items: I) -> Self {
+        let mut aliases = Self::default();
+        for (id, raw_item) in raw_items {
+            for alias in raw_item.aliases() {
+                aliases.record(alias.as_str(), id);
+            }
+        }
+        aliases
+    }
+}
+
+/// Replaces the [`ItemAliases`] of the manifest `M` with the aliases of the supplied raw items.
+///
+/// Call this from [`Manifest::from_raw_manifest`], pairing each raw item with the [`Id`] of the item it becomes.
+pub fn record_item_aliases<'a, M: Manifest, R: AliasedRawItem + 'a>(
+    world: &mut World,
+    raw_items: impl IntoIterator<Item = (Id<M::Item>, &'a R)>,
+) {
+    world.insert_resource(raw_items.into_iter().collect::<ItemAliases<M>>());
+}
+
+/// A [`SystemParam`] for reading the manifest `M`, which resolves the old names recorded in its [`ItemAliases`].
+///
+/// This dereferences to the manifest, so every other method of `M` continues to work unchanged.
+/// Only lookups made via [`AliasedManifest::get`] (and [`AliasedManifest::get_by_name`]) resolve aliases.
+///
+/// If an [`IdMigrations`] resource exists for the item type, old [`Id`]s are migrated to their replacements too.
+#[derive(SystemParam)]
+pub struct AliasedManifest<'w, M: Manifest> {
+    manifest: Res<'w, M>,
+    aliases: Option<Res<'w, ItemAliases<M>>>,
+    // The derive does not carry the `Manifest` bound into the state it generates, so the item type is fully qualified.
+    migrations: Option<Res<'w, IdMigrations<<M as Manifest>::Item>>>,
+    lookups: Option<Res<'w, MissingLookups>>,
+}
+
+impl<'w, M: Manifest> AliasedManifest<'w, M> {
+    /// Returns the item with the given `id`, or the item that `id` is an alias for.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        let item = self.manifest.get(self.resolve(id));
+        if item.is_none() {
+            let policy = self
+                .lookups
+                .as_ref()
+                .map_or(MissingLookupPolicy::Ignore, |lookups| lookups.policy::<M>());
+            report_missing_lookup(policy, &*self.manifest, id);
+        }
+        item
+    }
+
+    /// Returns the item with the given `name`, or the item that `name` is an alias for.
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&M::Item> {
+        self.get(Id::from_name(name))
+    }
+
+    /// Returns the current [`Id`] for `id`, following any alias or migration.
+    ///
+    /// Items which exist under their own [`Id`] take priority over aliases, which take priority over migrations.
+    #[must_use]
+    pub fn resolve(&self, id: Id<M::Item>) -> Id<M::Item> {
+        if self.manifest.get(id).is_some() {
+            return id;
+        }
+
+        if let Some(aliases) = &self.aliases {
+            if aliases.target(id).is_some() {
+                return aliases.resolve(id);
+            }
+        }
+
+        match &self.migrations {
+            Some(migrations) => migrations.resolve_migrated(id),
+            None => id,
+        }
+    }
+}
+
+impl<'w, M: Manifest> Deref for AliasedManifest<'w, M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.manifest
+    }
+}
+
+/// A plugin which records each alias of the manifest `M` that has been used in the [`ValidationReport`].
+pub struct ItemAliasPlugin<M: Manifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ItemAliasPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> Plugin for ItemAliasPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ValidationReport>().add_systems(
+            PostUpdate,
+            report_alias_lookups::<M>.run_if(resource_exists::<ItemAliases<M>>),
+        );
+    }
+}
+
+/// Adds any newly used aliases of the manifest `M` to the [`ValidationReport`].
+pub fn report_alias_lookups<M: Manifest>(
+    aliases: Res<ItemAliases<M>>,
+    mut report: ResMut<ValidationReport>,
+) {
+    for lookup in aliases.used() {
+        if !report.alias_lookups.contains(&lookup) {
+            report.alias_lookups.push(lookup);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Keeps assets created while processing a manifest alive, without storing strong handles in every item.
+//!
+//! Assets that are created inside [`Manifest::from_raw_manifest`], such as procedural meshes or materials,
+//! are dropped as soon as their last strong [`Handle`] is.
+//! Rather than storing strong handles in each item, push them into the [`ManifestAssetStore`] for the manifest,
+//! and store the returned weak handle instead:
+//!
+//! ```rust,ignore
+//! fn from_raw_manifest(raw_manifest: Self::RawManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     let mesh = world.resource_mut::<Assets<Mesh>>().add(Circle::new(1.0));
+//!     let mesh = world.resource_mut::<ManifestAssetStore<Self>>().keep_alive(mesh);
+//!     // ...
+//! }
+//! ```
+//!
+//! Handles are first kept as pending.
+//! Once the manifest has been processed successfully, they replace the handles kept by the previous version of the manifest (if any),
+//! so reloading a manifest releases the assets that are no longer used.
+//! If processing fails, the pending handles are discarded, and the previous handles are kept.
+//!
+//! To create assets from the parameters in the raw manifest, without duplicating identical assets,
+//! see the [`procedural_assets`](crate::procedural_assets) module.
+
+use std::marker::PhantomData;
+
+use bevy::asset::{Asset, AssetId, Handle, UntypedHandle};
+use bevy::ecs::prelude::*;
+
+use crate::manifest::Manifest;
+
+/// Strong handles to the assets used by the manifest `M`.
+///
+/// This resource is inserted when the manifest is registered, and is available during [`Manifest::from_raw_manifest`].
+/// See the [module docs](crate::asset_store) for more information.
+#[derive(Resource)]
+pub struct ManifestAssetStore<M: Manifest> {
+    handles: Vec<UntypedHandle>,
+    pending: Vec<UntypedHandle>,
+    generation: u64,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestAssetStore<M> {
+    fn default() -> Self {
+        Self {
+            handles: Vec::new(),
+            pending: Vec::new(),
+            generation: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestAssetStore<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestAssetStore")
+            .field("handles", &self.handles.len())
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestAssetStore<M> {
+    /// Stores a strong handle to the asset, returning a weak handle to it.
+    ///
+    /// The asset is kept alive until the manifest is reprocessed or unloaded.
+    pub fn keep_alive<A: Asset>(&mut self, handle: Handle<A>) -> Handle<A> {
+        let weak = handle.clone_weak();
+        self.pending.push(handle.untyped());
+        weak
+    }
+
+    /// Stores a strong untyped handle to the asset, returning a weak handle to it.
+    pub fn keep_alive_untyped(&mut self, handle: UntypedHandle) -> UntypedHandle {
+        let weak = handle.clone_weak();
+        self.pending.push(handle);
+        weak
+    }
+
+    /// Replaces the kept handles with the pending handles, releasing any assets that are no longer used.
+    ///
+    /// This is called automatically once the manifest has been processed successfully.
+    pub fn promote_pending(&mut self) {
+        self.handles = std::mem::take(&mut self.pending);
+        self.generation += 1;
+    }
+
+    /// Drops the pending handles, keeping the handles from the last successful processing.
+    ///
+    /// This is called automatically if the manifest fails to process.
+    pub fn discard_pending(&mut self) {
+        self.pending.clear();
+        self.generation += 1;
+    }
+
+    /// Drops every handle, allowing the assets to be unloaded.
+    ///
+    /// Call this when removing the manifest resource.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+        self.pending.clear();
+    }
+
+    /// The number of handles being kept alive, not including any pending handles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns true if no handles are being kept alive, not including any pending handles.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// The number of times the pending handles have been promoted or discarded.
+    ///
+    /// Handles kept alive during the same round of processing share a generation.
+    /// This is used by [`ProceduralAssets`](crate::procedural_assets::ProceduralAssets) to tell when a memoized asset must be kept alive again.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Iterates over the handles being kept alive.
+    pub fn iter(&self) -> impl Iterator<Item = &UntypedHandle> {
+        self.handles.iter()
+    }
+
+    /// Returns a strong handle to the asset with the given `id`, if it is being kept alive, including as a pending handle.
+    #[must_use]
+    pub fn strong_handle<A: Asset>(&self, id: AssetId<A>) -> Option<Handle<A>> {
+        let id = id.untyped();
+        self.handles
+            .iter()
+            .chain(&self.pending)
+            .find(|handle| handle.id() == id)
+            .map(|handle| handle.clone().typed::<A>())
+    }
+}
+
+/// Promotes or discards the pending handles in the [`ManifestAssetStore`] for `M`, depending on whether processing `succeeded`.
+pub(crate) fn settle_asset_store<M: Manifest>(world: &mut World, succeeded: bool) {
+    if let Some(mut store) = world.get_resource_mut::<ManifestAssetStore<M>>() {
+        if succeeded {
+            store.promote_pending();
+        } else {
+            store.discard_pending();
+        }
+    }
+}
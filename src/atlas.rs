@@ -0,0 +1,307 @@
+//! Packs the sprites declared by manifest items into a single texture atlas.
+//!
+//! Sprite-heavy 2D games usually render from texture atlases, but keeping a hand-written table of atlas indices
+//! in sync with the content is tedious and error-prone.
+//! Instead, raw items can declare the path of their sprite, implementing [`SpriteRawItem`],
+//! and [`record_atlas_sprites`] assigns each of them an index in the [`ManifestAtlas`] of the manifest:
+//!
+//! ```rust,ignore
+//! impl SpriteRawItem for RawItem {
+//!     fn sprite_path(&self) -> &str {
+//!         &self.sprite
+//!     }
+//! }
+//!
+//! fn from_raw_manifest(raw_manifest: RawItemManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     record_atlas_sprites::<Self, _>(
+//!         world,
+//!         AtlasSettings::default(),
+//!         raw_manifest.items.iter().map(|raw| (Id::from_name(&raw.name), raw)),
+//!     );
+//!
+//!     let atlas = world.resource::<ManifestAtlas<Self>>();
+//!     let items = raw_manifest.items.into_iter().map(|raw| {
+//!         let id = Id::from_name(&raw.name);
+//!         // Each item stores the shared layout and its own index, rather than a handle to its own image.
+//!         (id, Item { name: raw.name, sprite: atlas.sprite(id).unwrap(), texture: atlas.texture() })
+//!     });
+//!     // ...
+//! }
+//!
+//! app.add_plugins(ManifestAtlasPlugin::<ItemManifest>::default());
+//! ```
+//!
+//! Indices are assigned in the order the raw items are supplied, so they are known immediately,
+//! and items with the same sprite path share an index.
+//! The atlas itself is packed by the [`ManifestAtlasPlugin`] once every sprite image has loaded:
+//! until then, the [layout](ManifestAtlas::layout) and [texture](ManifestAtlas::texture) handles are reserved but empty.
+//! To avoid this delay, return the sprite paths from [`Manifest::asset_dependencies`] so they are loaded alongside the raw manifest.
+//!
+//! This module requires the `atlas` feature.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::asset::{AssetPath, AssetServer, Assets, Handle, LoadState};
+use bevy::ecs::prelude::*;
+use bevy::log::error;
+use bevy::math::{UVec2, Vec2};
+use bevy::render::texture::Image;
+use bevy::sprite::{TextureAtlas, TextureAtlasBuilder, TextureAtlasLayout};
+use bevy::utils::HashMap;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::Manifest;
+use crate::plugin::manifest_schedules;
+
+/// A raw item that declares the path of its sprite image.
+pub trait SpriteRawItem {
+    /// The asset path of the sprite image for this item.
+    fn sprite_path(&self) -> &str;
+}
+
+/// How the sprites of a [`ManifestAtlas`] are packed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasSettings {
+    /// The initial size of the atlas texture, which grows as needed.
+    ///
+    /// Defaults to 256 by 256 pixels.
+    pub initial_size: Vec2,
+    /// The largest the atlas texture may grow to.
+    ///
+    /// Defaults to 2048 by 2048 pixels.
+    pub max_size: Vec2,
+    /// The empty space added between sprites, to avoid texture bleeding.
+    ///
+    /// Defaults to no padding.
+    pub padding: UVec2,
+}
+
+impl Default for AtlasSettings {
+    fn default() -> Self {
+        Self {
+            initial_size: Vec2::splat(256.),
+            max_size: Vec2::splat(2048.),
+            padding: UVec2::ZERO,
+        }
+    }
+}
+
+/// The progress of packing a [`ManifestAtlas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AtlasState {
+    /// Waiting for the sprite images to load.
+    Pending,
+    /// The layout and texture are ready.
+    Built,
+    /// A sprite failed to load, or the sprites did not fit in the [maximum size](AtlasSettings::max_size).
+    Failed(String),
+}
+
+/// The texture atlas containing the sprite of each item in the manifest `M`.
+///
+/// This resource is replaced each time the manifest is processed by [`record_atlas_sprites`],
+/// and packed by the [`ManifestAtlasPlugin`].
+#[derive(Resource)]
+pub struct ManifestAtlas<M: Manifest> {
+    layout: Handle<TextureAtlasLayout>,
+    texture: Handle<Image>,
+    settings: AtlasSettings,
+    /// The sprite images, in index order, until the atlas is packed.
+    sprites: Vec<(AssetPath<'static>, Handle<Image>)>,
+    indices: IdMap<M::Item, usize>,
+    state: AtlasState,
+}
+
+impl<M: Manifest> ManifestAtlas<M> {
+    /// The handle to the layout of the atlas, which is shared by all items.
+    ///
+    /// This is reserved immediately, but the layout is only added once the atlas is packed.
+    #[must_use]
+    pub fn layout(&self) -> Handle<TextureAtlasLayout> {
+        self.layout.clone()
+    }
+
+    /// The handle to the packed atlas texture.
+    ///
+    /// This is reserved immediately, but the image is only added once the atlas is packed.
+    #[must_use]
+    pub fn texture(&self) -> Handle<Image> {
+        self.texture.clone()
+    }
+
+    /// The index in the atlas of the sprite for the item with the given `id`.
+    #[must_use]
+    pub fn index_of(&self, id: Id<M::Item>) -> Option<usize> {
+        self.indices.get(&id).copied()
+    }
+
+    /// The [`TextureAtlas`] component for the sprite of the item with the given `id`.
+    #[must_use]
+    pub fn sprite(&self, id: Id<M::Item>) -> Option<TextureAtlas> {
+        self.index_of(id).map(|index| TextureAtlas {
+            layout: self.layout.clone(),
+            index,
+        })
+    }
+
+    /// The progress of packing the atlas.
+    #[must_use]
+    pub fn state(&self) -> &AtlasState {
+        &self.state
+    }
+
+    /// The settings used to pack the atlas.
+    #[must_use]
+    pub fn settings(&self) -> AtlasSettings {
+        self.settings
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestAtlas<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestAtlas")
+            .field("layout", &self.layout)
+            .field("texture", &self.texture)
+            .field("sprites", &self.indices.len())
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Replaces the [`ManifestAtlas`] of the manifest `M`, assigning an index to the sprite of each supplied raw item.
+///
+/// Call this from [`Manifest::from_raw_manifest`], pairing each raw item with the [`Id`] of the item it becomes.
+/// The sprite images start loading immediately, and are packed by the [`ManifestAtlasPlugin`] once they have all loaded.
+pub fn record_atlas_sprites<'a, M: Manifest, R: SpriteRawItem + 'a>(
+    world: &mut World,
+    settings: AtlasSettings,
+    raw_items: impl IntoIterator<Item = (Id<M::Item>, &'a R)>,
+) {
+    let asset_server = world.resource::<AssetServer>().clone();
+
+    let mut sprites: Vec<(AssetPath<'static>, Handle<Image>)> = Vec::new();
+    let mut by_path: HashMap<String, usize> = HashMap::default();
+    let mut indices = IdMap::default();
+    for (id, raw_item) in raw_items {
+        let path = raw_item.sprite_path();
+        let index = *by_path.entry(path.to_string()).or_insert_with(|| {
+            let path = AssetPath::from(path.to_string());
+            sprites.push((path.clone(), asset_server.load(path)));
+            sprites.len() - 1
+        });
+        indices.insert(id, index);
+    }
+
+    let layout = world
+        .resource::<Assets<TextureAtlasLayout>>()
+        .reserve_handle();
+    let texture = world.resource::<Assets<Image>>().reserve_handle();
+    world.insert_resource(ManifestAtlas::<M> {
+        layout,
+        texture,
+        settings,
+        sprites,
+        indices,
+        state: AtlasState::Pending,
+    });
+}
+
+/// A plugin which packs the [`ManifestAtlas`] of the manifest `M` once its sprite images have loaded.
+pub struct ManifestAtlasPlugin<M: Manifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestAtlasPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestAtlasPlugin<M> {
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        app.add_systems(
+            schedules.loading,
+            build_manifest_atlas::<M>.run_if(resource_exists::<ManifestAtlas<M>>),
+        );
+    }
+}
+
+/// Packs the sprites of the [`ManifestAtlas`] for `M` into its layout and texture, once they have all loaded.
+///
+/// Sprites are placed in the layout in index order, so the indices assigned by [`record_atlas_sprites`] stay valid.
+pub fn build_manifest_atlas<M: Manifest>(
+    mut atlas: ResMut<ManifestAtlas<M>>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if atlas.state != AtlasState::Pending {
+        return;
+    }
+
+    if let Some((path, _)) = atlas
+        .sprites
+        .iter()
+        .find(|(_, handle)| asset_server.load_state(handle.id()) == LoadState::Failed)
+    {
+        let message = format!("The sprite {path} could not be loaded.");
+        error!(
+            "Could not build the atlas for {}: {message}",
+            std::any::type_name::<M>()
+        );
+        atlas.state = AtlasState::Failed(message);
+        return;
+    }
+
+    if !atlas
+        .sprites
+        .iter()
+        .all(|(_, handle)| images.contains(handle))
+    {
+        return;
+    }
+
+    let settings = atlas.settings;
+    let mut builder = TextureAtlasBuilder::default()
+        .initial_size(settings.initial_size)
+        .max_size(settings.max_size)
+        .padding(settings.padding);
+    for (_, handle) in &atlas.sprites {
+        if let Some(image) = images.get(handle) {
+            builder.add_texture(Some(handle.id()), image);
+        }
+    }
+
+    let (packed, texture) = match builder.finish() {
+        Ok(result) => result,
+        Err(error) => {
+            let message = error.to_string();
+            error!(
+                "Could not build the atlas for {}: {message}",
+                std::any::type_name::<M>()
+            );
+            atlas.state = AtlasState::Failed(message);
+            return;
+        }
+    };
+
+    // The builder places sprites in whatever order packs best, so reorder them to match the assigned indices.
+    let mut layout = TextureAtlasLayout::new_empty(packed.size);
+    for (_, handle) in &atlas.sprites {
+        let packed_index = packed
+            .get_texture_index(handle.id())
+            .expect("Every sprite was added to the atlas builder.");
+        layout.add_texture(packed.textures[packed_index]);
+    }
+
+    layouts.insert(atlas.layout.id(), layout);
+    images.insert(atlas.texture.id(), texture);
+
+    // The sprites have been copied into the atlas texture, so the individual images are no longer needed.
+    atlas.sprites.clear();
+    atlas.state = AtlasState::Built;
+}
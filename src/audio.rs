@@ -0,0 +1,352 @@
+//! A ready-made manifest of sound effects and music, with per-clip volume, pitch variation and categories.
+//!
+//! Almost every game builds a table like this: a name for each sound, the file it plays,
+//! and a few playback settings that designers want to tweak without touching code.
+//! [`AudioCollectionManifest`] is a complete implementation, loaded from a file like:
+//!
+//! ```ron
+//! (
+//!     clips: [
+//!         (name: "footstep", path: "audio/footstep.ogg", volume: 0.6, pitch: (0.9, 1.1), category: Some("sfx")),
+//!         (name: "theme", path: "audio/theme.ogg", category: Some("music")),
+//!     ],
+//! )
+//! ```
+//!
+//! ```rust,ignore
+//! app.register_manifest::<AudioCollectionManifest>("audio.ron");
+//!
+//! fn play_footstep(mut commands: Commands, audio: Res<AudioCollectionManifest>) {
+//!     // The second argument picks the pitch within the clip's range: pass a random number for natural variation.
+//!     if let Some(bundle) = audio.play_by_name("footstep", 0.5) {
+//!         commands.spawn(bundle);
+//!     }
+//! }
+//! ```
+//!
+//! Each clip is converted on its own, so the manifest supports incremental hot reloading with an
+//! [`IncrementalReloadPlugin`](crate::incremental::IncrementalReloadPlugin).
+//! The audio files are preloaded alongside the raw manifest via [`Manifest::asset_dependencies`].
+//!
+//! By default, the manifest is read as RON.
+//! Other formats can be used with [`RawManifestOptions::with_format`](crate::plugin::RawManifestOptions::with_format).
+//!
+//! This module requires the `audio` feature.
+
+use bevy::asset::{Asset, AssetPath, AssetServer, Handle};
+use bevy::audio::{AudioBundle, AudioSource, PlaybackSettings, Volume};
+use bevy::ecs::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use crate::identifier::{Id, IdMap};
+use crate::incremental::IncrementalManifest;
+use crate::manifest::{
+    IterableManifest, Manifest, ManifestFormat, ManifestModificationError, MutableManifest,
+};
+
+fn default_volume() -> f32 {
+    1.0
+}
+
+fn default_pitch() -> (f32, f32) {
+    (1.0, 1.0)
+}
+
+/// A single clip in a [`RawAudioCollection`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RawAudioClip {
+    /// The unique name of the clip.
+    pub name: String,
+    /// The asset path of the audio file.
+    pub path: String,
+    /// The volume the clip is played at, where 1.0 is unchanged.
+    ///
+    /// Defaults to 1.0.
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+    /// The lowest and highest playback speed, which also changes the pitch.
+    ///
+    /// Defaults to `(1.0, 1.0)`: no variation.
+    #[serde(default = "default_pitch")]
+    pub pitch: (f32, f32),
+    /// The name of the category the clip belongs to, such as "music" or "sfx".
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+/// The raw manifest of an [`AudioCollectionManifest`].
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Deserialize)]
+pub struct RawAudioCollection {
+    /// The clips in the collection.
+    pub clips: Vec<RawAudioClip>,
+}
+
+/// A marker type for the [`Id`]s of audio categories, as used by [`AudioClip::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioCategory;
+
+/// A sound or piece of music in an [`AudioCollectionManifest`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioClip {
+    /// The unique name of the clip.
+    pub name: String,
+    /// The handle to the audio file.
+    pub source: Handle<AudioSource>,
+    /// The volume the clip is played at, where 1.0 is unchanged.
+    pub volume: f32,
+    /// The lowest and highest playback speed, which also changes the pitch.
+    pub pitch: (f32, f32),
+    /// The category the clip belongs to, if any.
+    pub category: Option<Id<AudioCategory>>,
+}
+
+impl AudioClip {
+    /// The [`PlaybackSettings`] to play this clip once.
+    ///
+    /// The playback speed is interpolated within the clip's [`pitch`](AudioClip::pitch) range by `t`, which is clamped between 0.0 and 1.0.
+    /// Pass a random number to vary the pitch each time the clip is played.
+    #[must_use]
+    pub fn playback_settings(&self, t: f32) -> PlaybackSettings {
+        let (low, high) = self.pitch;
+        PlaybackSettings::ONCE
+            .with_volume(Volume::new(self.volume))
+            .with_speed(low + (high - low) * t.clamp(0.0, 1.0))
+    }
+
+    /// An [`AudioBundle`] which plays this clip once, with the pitch chosen by `t` as in [`AudioClip::playback_settings`].
+    #[must_use]
+    pub fn bundle(&self, t: f32) -> AudioBundle {
+        AudioBundle {
+            source: self.source.clone(),
+            settings: self.playback_settings(t),
+        }
+    }
+}
+
+/// An error encountered while converting a [`RawAudioClip`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AudioManifestError {
+    /// The volume of the clip was negative, or not a number.
+    #[error("The clip {name} has an invalid volume of {volume}.")]
+    InvalidVolume {
+        /// The name of the clip.
+        name: String,
+        /// The invalid volume.
+        volume: f32,
+    },
+    /// The pitch range of the clip was empty, or included speeds that are not positive.
+    #[error("The clip {name} has an invalid pitch range of {low} to {high}.")]
+    InvalidPitch {
+        /// The name of the clip.
+        name: String,
+        /// The lowest speed.
+        low: f32,
+        /// The highest speed.
+        high: f32,
+    },
+    /// Two clips had the same name.
+    #[error("The clip name {0} is used more than once.")]
+    DuplicateName(String),
+}
+
+/// A manifest of sound effects and music, organized into categories.
+///
+/// See the [module docs](crate::audio) for more information.
+#[derive(Resource, Debug, Default)]
+pub struct AudioCollectionManifest {
+    clips: IdMap<AudioClip, AudioClip>,
+    categories: HashMap<Id<AudioCategory>, Vec<Id<AudioClip>>>,
+}
+
+impl AudioCollectionManifest {
+    /// An [`AudioBundle`] which plays the clip with the given `id` once, as in [`AudioClip::bundle`].
+    #[must_use]
+    pub fn play(&self, id: Id<AudioClip>, t: f32) -> Option<AudioBundle> {
+        self.get(id).map(|clip| clip.bundle(t))
+    }
+
+    /// An [`AudioBundle`] which plays the clip with the given `name` once, as in [`AudioClip::bundle`].
+    #[must_use]
+    pub fn play_by_name(&self, name: &str, t: f32) -> Option<AudioBundle> {
+        self.play(Id::from_name(name), t)
+    }
+
+    /// The clips in the category with the given `name`, sorted by [`Id`].
+    ///
+    /// This is useful for picking a random variant of a sound, or for muting a whole category.
+    #[must_use]
+    pub fn clips_in(&self, category: &str) -> &[Id<AudioClip>] {
+        self.categories
+            .get(&Id::from_name(category))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The [`Id`] of each category which contains at least one clip.
+    pub fn categories(&self) -> impl Iterator<Item = Id<AudioCategory>> + '_ {
+        self.categories.keys().copied()
+    }
+
+    fn add_to_category(&mut self, id: Id<AudioClip>, category: Option<Id<AudioCategory>>) {
+        if let Some(category) = category {
+            let clips = self.categories.entry(category).or_default();
+            if let Err(index) = clips.binary_search(&id) {
+                clips.insert(index, id);
+            }
+        }
+    }
+
+    fn remove_from_category(&mut self, id: Id<AudioClip>, category: Option<Id<AudioCategory>>) {
+        let Some(category) = category else {
+            return;
+        };
+        if let Some(clips) = self.categories.get_mut(&category) {
+            clips.retain(|clip| *clip != id);
+            if clips.is_empty() {
+                self.categories.remove(&category);
+            }
+        }
+    }
+}
+
+impl Manifest for AudioCollectionManifest {
+    type RawManifest = RawAudioCollection;
+    type RawItem = RawAudioClip;
+    type Item = AudioClip;
+    type ConversionError = AudioManifestError;
+
+    #[cfg(feature = "ron")]
+    const FORMAT: ManifestFormat = ManifestFormat::Ron;
+    #[cfg(not(feature = "ron"))]
+    const FORMAT: ManifestFormat = ManifestFormat::Custom;
+
+    fn from_raw_manifest(
+        raw_manifest: RawAudioCollection,
+        world: &mut World,
+    ) -> Result<Self, AudioManifestError> {
+        let mut manifest = Self::default();
+        for raw_clip in &raw_manifest.clips {
+            let clip = Self::convert_item(raw_clip, world)?;
+            manifest
+                .insert(clip)
+                .map_err(|_| AudioManifestError::DuplicateName(raw_clip.name.clone()))?;
+        }
+
+        Ok(manifest)
+    }
+
+    fn get(&self, id: Id<AudioClip>) -> Option<&AudioClip> {
+        self.clips.get(&id)
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.clips.len())
+    }
+
+    fn asset_dependencies(raw_manifest: &RawAudioCollection) -> Vec<AssetPath<'static>> {
+        raw_manifest
+            .clips
+            .iter()
+            .map(|clip| AssetPath::from(clip.path.clone()))
+            .collect()
+    }
+}
+
+impl IterableManifest for AudioCollectionManifest {
+    fn iter(&self) -> impl Iterator<Item = (Id<AudioClip>, &AudioClip)> {
+        self.clips.iter().map(|(id, clip)| (*id, clip))
+    }
+}
+
+impl MutableManifest for AudioCollectionManifest {
+    fn insert(
+        &mut self,
+        item: AudioClip,
+    ) -> Result<Id<AudioClip>, ManifestModificationError<Self>> {
+        let id = Id::from_name(&item.name);
+        if self.clips.contains_key(&id) {
+            return Err(ManifestModificationError::DuplicateName(item.name));
+        }
+
+        self.add_to_category(id, item.category);
+        self.clips.insert(id, item);
+        Ok(id)
+    }
+
+    fn upsert(
+        &mut self,
+        id: Id<AudioClip>,
+        item: AudioClip,
+    ) -> Result<Option<AudioClip>, ManifestModificationError<Self>> {
+        // Overridden to keep the categories up to date, which `get_mut` cannot do.
+        let replaced = self.clips.remove(&id);
+        if let Some(replaced) = &replaced {
+            self.remove_from_category(id, replaced.category);
+        }
+
+        self.add_to_category(id, item.category);
+        self.clips.insert(id, item);
+        Ok(replaced)
+    }
+
+    fn remove(
+        &mut self,
+        id: &Id<AudioClip>,
+    ) -> Result<Id<AudioClip>, ManifestModificationError<Self>> {
+        let clip = self
+            .clips
+            .remove(id)
+            .ok_or(ManifestModificationError::NotFound(*id))?;
+        self.remove_from_category(*id, clip.category);
+        Ok(*id)
+    }
+
+    /// Gets a mutable reference to a clip.
+    ///
+    /// Changing the [`category`](AudioClip::category) of the clip this way is not reflected in [`AudioCollectionManifest::clips_in`]:
+    /// use [`MutableManifest::upsert`] instead.
+    fn get_mut(&mut self, id: Id<AudioClip>) -> Option<&mut AudioClip> {
+        self.clips.get_mut(&id)
+    }
+}
+
+impl IncrementalManifest for AudioCollectionManifest {
+    fn raw_items(raw_manifest: &RawAudioCollection) -> Vec<(Id<AudioClip>, RawAudioClip)> {
+        raw_manifest
+            .clips
+            .iter()
+            .map(|clip| (Id::from_name(&clip.name), clip.clone()))
+            .collect()
+    }
+
+    fn convert_item(
+        raw_item: &RawAudioClip,
+        world: &mut World,
+    ) -> Result<AudioClip, AudioManifestError> {
+        if raw_item.volume.is_nan() || raw_item.volume < 0.0 {
+            return Err(AudioManifestError::InvalidVolume {
+                name: raw_item.name.clone(),
+                volume: raw_item.volume,
+            });
+        }
+
+        let (low, high) = raw_item.pitch;
+        if low.is_nan() || high.is_nan() || low <= 0.0 || low > high {
+            return Err(AudioManifestError::InvalidPitch {
+                name: raw_item.name.clone(),
+                low,
+                high,
+            });
+        }
+
+        let source = world.resource::<AssetServer>().load(raw_item.path.clone());
+        Ok(AudioClip {
+            name: raw_item.name.clone(),
+            source,
+            volume: raw_item.volume,
+            pitch: raw_item.pitch,
+            category: raw_item.category.as_deref().map(Id::from_name),
+        })
+    }
+}
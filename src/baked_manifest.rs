@@ -0,0 +1,215 @@
+//! Caching of fully-processed manifests, to skip expensive conversion work on startup.
+//!
+//! Some manifests do a lot of work in [`Manifest::from_raw_manifest`]: procedural generation, heavy validation,
+//! or building lookup tables.
+//! When iterating on a large project, repeating this on every launch adds up.
+//!
+//! Manifests which implement [`BakedManifest`] can be registered via [`RegisterBakedManifest::register_baked_manifest`].
+//! After the manifest is processed for the first time, its final form is serialized to the cache directory
+//! specified in [`ManifestCacheSettings`].
+//! On subsequent runs, if the cached copy is newer than the raw manifest file, it is loaded directly,
+//! and the raw manifest is never loaded or processed.
+//!
+//! This module is only available when the `baked_manifests` feature is enabled,
+//! and is not available on platforms without a filesystem, such as the web.
+
+use std::any::type_name;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::app::App;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::ecs::prelude::*;
+use bevy::log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::Manifest;
+use crate::plugin::{file_name_of, manifest_schedules, RawManifestTracker, RegisterManifest};
+
+/// A [`Manifest`] whose processed form can be serialized to disk and loaded back directly.
+///
+/// The serialized form is written using `bincode`, so it must round-trip without relying on self-describing formats:
+/// avoid `#[serde(flatten)]` and untagged enums.
+pub trait BakedManifest: Manifest + Serialize + DeserializeOwned {
+    /// The version of the baked format.
+    ///
+    /// Caches written with a different version are ignored and regenerated.
+    /// Increment this whenever the layout of your manifest (or the items it contains) changes.
+    const BAKE_VERSION: u32 = 0;
+}
+
+/// Configures where baked manifests are read from and written to.
+///
+/// This resource is initialized with its default values when the first baked manifest is registered.
+/// Insert it before then to customize it.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestCacheSettings {
+    /// The directory that baked manifests are stored in.
+    ///
+    /// Defaults to `target/manifest_cache`, relative to the root of your project.
+    pub cache_dir: PathBuf,
+    /// The directory that raw manifest paths are relative to.
+    ///
+    /// This should match the file path used by the [`AssetPlugin`](bevy::asset::AssetPlugin).
+    /// Defaults to the `assets` folder.
+    pub asset_dir: PathBuf,
+    /// If false, cached manifests are never loaded, and the raw manifests are always processed.
+    ///
+    /// Newly processed manifests are still written to the cache.
+    ///
+    /// Defaults to `true`.
+    pub use_cache: bool,
+}
+
+impl Default for ManifestCacheSettings {
+    fn default() -> Self {
+        let base_path = FileAssetReader::get_base_path();
+
+        Self {
+            cache_dir: base_path.join("target").join("manifest_cache"),
+            asset_dir: base_path.join("assets"),
+            use_cache: true,
+        }
+    }
+}
+
+impl ManifestCacheSettings {
+    /// Returns the path that the baked form of `M` is stored at.
+    ///
+    /// The file is named after the full type name of `M`, so manifests with the same name in different modules do not collide.
+    #[must_use]
+    pub fn cache_path<M: BakedManifest>(&self) -> PathBuf {
+        self.cache_dir
+            .join(format!("{}.baked", file_name_of::<M>()))
+    }
+}
+
+/// The on-disk representation of a baked manifest.
+#[derive(Serialize, Deserialize)]
+struct BakedFile<M> {
+    version: u32,
+    manifest: M,
+}
+
+/// Errors that can occur when reading or writing a baked manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum BakeError {
+    /// The cache file could not be read or written.
+    #[error("Could not access the manifest cache: {0}")]
+    Io(#[from] io::Error),
+    /// The manifest could not be serialized or deserialized.
+    #[error("Could not (de)serialize the baked manifest: {0}")]
+    Serialization(#[from] bincode::Error),
+    /// The cache was written with a different [`BakedManifest::BAKE_VERSION`].
+    #[error("The baked manifest has version {found}, but version {expected} was expected")]
+    VersionMismatch {
+        /// The version of the cache file on disk.
+        found: u32,
+        /// The current [`BakedManifest::BAKE_VERSION`].
+        expected: u32,
+    },
+    /// The raw manifest has been modified since the cache was written.
+    #[error("The raw manifest has been modified since it was baked")]
+    Stale,
+}
+
+/// Loads the baked form of `M` from the cache, if it exists and is newer than the raw manifest at `raw_path`.
+pub fn load_baked_manifest<M: BakedManifest>(
+    settings: &ManifestCacheSettings,
+    raw_path: &Path,
+) -> Result<M, BakeError> {
+    let cache_path = settings.cache_path::<M>();
+
+    let cache_modified = fs::metadata(&cache_path)?.modified()?;
+    // If the raw manifest can't be found, we can't tell if the cache is stale,
+    // so we play it safe and process the raw manifest, which will report the error properly.
+    let raw_modified = fs::metadata(settings.asset_dir.join(raw_path))?.modified()?;
+    if raw_modified > cache_modified {
+        return Err(BakeError::Stale);
+    }
+
+    let bytes = fs::read(&cache_path)?;
+    let baked: BakedFile<M> = bincode::deserialize(&bytes)?;
+    if baked.version != M::BAKE_VERSION {
+        return Err(BakeError::VersionMismatch {
+            found: baked.version,
+            expected: M::BAKE_VERSION,
+        });
+    }
+
+    Ok(baked.manifest)
+}
+
+/// Serializes `manifest` and writes it to the cache.
+pub fn save_baked_manifest<M: BakedManifest>(
+    settings: &ManifestCacheSettings,
+    manifest: &M,
+) -> Result<(), BakeError> {
+    let baked = BakedFile {
+        version: M::BAKE_VERSION,
+        manifest,
+    };
+    let bytes = bincode::serialize(&baked)?;
+
+    fs::create_dir_all(&settings.cache_dir)?;
+    fs::write(settings.cache_path::<M>(), bytes)?;
+    Ok(())
+}
+
+/// An extension trait for registering [`BakedManifest`]s with an [`App`].
+pub trait RegisterBakedManifest {
+    /// Registers the manifest `M`, loading it from the cache if a fresh copy is available.
+    ///
+    /// Otherwise, this behaves like [`RegisterManifest::register_manifest`],
+    /// and the processed manifest is written to the cache for next time.
+    fn register_baked_manifest<M: BakedManifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+}
+
+impl RegisterBakedManifest for App {
+    fn register_baked_manifest<M: BakedManifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        let path: PathBuf = path.into();
+        self.init_resource::<ManifestCacheSettings>();
+
+        let settings = self.world.resource::<ManifestCacheSettings>();
+        if settings.use_cache {
+            match load_baked_manifest::<M>(settings, &path) {
+                Ok(mut manifest) => {
+                    info!("Loaded baked manifest of type {}.", type_name::<M>());
+
+                    let mut raw_manifest_tracker = self.world.resource_mut::<RawManifestTracker>();
+                    if raw_manifest_tracker.freeze_manifests() {
+                        manifest.freeze();
+                    }
+                    raw_manifest_tracker.register_baked::<M>(path, manifest.item_count());
+                    self.world.insert_resource(manifest);
+                    return self;
+                }
+                Err(err) => {
+                    info!(
+                        "Not using baked manifest of type {}: {err}",
+                        type_name::<M>()
+                    );
+                }
+            }
+        }
+
+        let schedules = manifest_schedules(self);
+        self.register_manifest::<M>(path).add_systems(
+            schedules.loading,
+            bake_manifest::<M>.run_if(resource_added::<M>),
+        )
+    }
+}
+
+/// Writes the freshly processed manifest `M` to the cache.
+pub fn bake_manifest<M: BakedManifest>(manifest: Res<M>, settings: Res<ManifestCacheSettings>) {
+    match save_baked_manifest(settings.as_ref(), manifest.as_ref()) {
+        Ok(()) => info!("Baked manifest of type {}.", type_name::<M>()),
+        Err(err) => warn!(
+            "Failed to bake manifest of type {}: {err}",
+            type_name::<M>()
+        ),
+    }
+}
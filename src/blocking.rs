@@ -0,0 +1,118 @@
+//! Loading manifests synchronously, without running an [`App`](bevy::app::App).
+//!
+//! Command-line tools, one-off scripts and test setups often just want the processed manifest,
+//! without setting up an app and running frames until the [`AssetServer`](bevy::asset::AssetServer) has finished.
+//! The functions in this module bypass the asset server entirely:
+//! the file is read directly from disk, decompressed if needed, deserialized according to [`Manifest::FORMAT`]
+//! and then processed with [`Manifest::from_raw_manifest`].
+//!
+//! ```rust
+//! # #[cfg(feature = "ron")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use bevy::prelude::*;
+//! # use leafwing_manifest::{
+//! #     blocking::load_manifest_blocking,
+//! #     identifier::{Id, IdMap},
+//! #     manifest::{Manifest, ManifestFormat},
+//! # };
+//! # use serde::Deserialize;
+//! #
+//! # #[derive(Debug, Deserialize)]
+//! # struct Item {
+//! #     name: String,
+//! # }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct ItemManifest {
+//! #     items: IdMap<Item, Item>,
+//! # }
+//! #
+//! # impl Manifest for ItemManifest {
+//! #     type Item = Item;
+//! #     type RawItem = Item;
+//! #     type RawManifest = ItemManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//! #
+//! #     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+//! #         self.items.get(&id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(
+//! #         raw_manifest: Self::RawManifest,
+//! #         _world: &mut World,
+//! #     ) -> Result<Self, Self::ConversionError> {
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//! let manifest = load_manifest_blocking::<ItemManifest>("assets/items.ron")?;
+//! assert_eq!(manifest.get_by_name("sword").unwrap().name, "sword");
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "ron"))]
+//! # fn main() {}
+//! ```
+//!
+//! Unlike [`RegisterManifest::register_manifest`](crate::plugin::RegisterManifest::register_manifest),
+//! paths are relative to the current working directory, rather than the `assets` folder.
+//!
+//! To reload a manifest whenever its file is edited, enable the `file_watch` feature and see the `file_watch` module.
+
+use std::path::Path;
+
+use bevy::ecs::world::World;
+
+use crate::loader::{deserialize_raw_manifest, Compression, ManifestLoaderError};
+use crate::manifest::{Manifest, ManifestFormat};
+
+/// Errors that can occur when loading a manifest with [`load_manifest_blocking`].
+#[derive(Debug, thiserror::Error)]
+pub enum BlockingLoadError<E: std::error::Error> {
+    /// The raw manifest could not be read or deserialized.
+    #[error(transparent)]
+    Load(#[from] ManifestLoaderError),
+    /// The raw manifest could not be converted into the manifest.
+    #[error("Could not process the raw manifest: {0}")]
+    Conversion(E),
+}
+
+/// Reads the raw manifest for `M` from the file at `path`, without processing it.
+///
+/// The file is read as [`Manifest::FORMAT`], and decompressed based on its extension.
+pub fn load_raw_manifest_blocking<M: Manifest>(
+    path: impl AsRef<Path>,
+) -> Result<M::RawManifest, ManifestLoaderError> {
+    load_raw_manifest_blocking_as::<M>(path, M::FORMAT)
+}
+
+/// Reads the raw manifest for `M` from the file at `path`, using the supplied `format` rather than [`Manifest::FORMAT`].
+pub fn load_raw_manifest_blocking_as<M: Manifest>(
+    path: impl AsRef<Path>,
+    format: ManifestFormat,
+) -> Result<M::RawManifest, ManifestLoaderError> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)?;
+    let bytes = Compression::from_path(path).decompress(bytes)?;
+    deserialize_raw_manifest(format, &bytes)
+}
+
+/// Reads the raw manifest for `M` from the file at `path` and processes it, using a fresh [`World`].
+///
+/// If processing relies on resources (such as other manifests or the asset server), use [`load_manifest_blocking_in`] instead.
+pub fn load_manifest_blocking<M: Manifest>(
+    path: impl AsRef<Path>,
+) -> Result<M, BlockingLoadError<M::ConversionError>> {
+    load_manifest_blocking_in::<M>(path, &mut World::new())
+}
+
+/// Reads the raw manifest for `M` from the file at `path` and processes it using the supplied `world`.
+///
+/// The manifest is returned, rather than inserted into the `world`.
+pub fn load_manifest_blocking_in<M: Manifest>(
+    path: impl AsRef<Path>,
+    world: &mut World,
+) -> Result<M, BlockingLoadError<M::ConversionError>> {
+    let raw_manifest = load_raw_manifest_blocking::<M>(path)?;
+    M::from_raw_manifest(raw_manifest, world).map_err(BlockingLoadError::Conversion)
+}
@@ -0,0 +1,223 @@
+//! Automatically adds components to entities based on the manifest item that they are identified by.
+//!
+//! Spawning a game object usually means looking up its manifest item and building a bundle from the data.
+//! Instead, implement [`ToBundle`] for the item type, and add the [`ManifestBundlePlugin`].
+//! Then, spawning an entity with only an [`Id`] is enough:
+//!
+//! ```rust
+//! use bevy::ecs::system::RunSystemOnce;
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     bundles::{ManifestBundlePlugin, ToBundle},
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Tile {
+//!     name: String,
+//!     movement_cost: u32,
+//! }
+//!
+//! #[derive(Component, Debug, PartialEq)]
+//! struct MovementCost(u32);
+//!
+//! impl ToBundle for Tile {
+//!     type Bundle = (Name, MovementCost);
+//!
+//!     fn to_bundle(&self) -> Self::Bundle {
+//!         (Name::new(self.name.clone()), MovementCost(self.movement_cost))
+//!     }
+//! }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct TileManifest {
+//! #     tiles: IdMap<Tile, Tile>,
+//! # }
+//! #
+//! # impl Manifest for TileManifest {
+//! #     type Item = Tile;
+//! #     type RawItem = Tile;
+//! #     type RawManifest = TileManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//! #
+//! #     fn get(&self, id: Id<Tile>) -> Option<&Tile> {
+//! #         self.tiles.get(&id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(raw_manifest: Self, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//!
+//! let grass = Tile {
+//!     name: "grass".to_string(),
+//!     movement_cost: 1,
+//! };
+//! let mut app = manifest_test_app();
+//! app.add_plugins(ManifestBundlePlugin::<TileManifest>::default())
+//!     .insert_raw_manifest::<TileManifest>(TileManifest {
+//!         tiles: IdMap::from_iter([(Id::from_name(&grass.name), grass)]),
+//!     });
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! fn spawn_grass(mut commands: Commands) {
+//!     commands.spawn(Id::<Tile>::from_name("grass"));
+//! }
+//!
+//! app.world.run_system_once(spawn_grass);
+//! app.update();
+//! let cost = app.world.query::<&MovementCost>().single(&app.world);
+//! assert_eq!(cost, &MovementCost(1));
+//!
+//! // Changing the manifest updates every entity identified by the changed item.
+//! let mut tiles = app.world.resource_mut::<TileManifest>();
+//! tiles.tiles.get_mut(&Id::from_name("grass")).unwrap().movement_cost = 2;
+//! app.update();
+//! let cost = app.world.query::<&MovementCost>().single(&app.world);
+//! assert_eq!(cost, &MovementCost(2));
+//! ```
+//!
+//! When the manifest changes (for example, due to hot reloading), the bundle is re-inserted for every identified entity,
+//! so they stay in sync with the data on disk.
+//!
+//! Enable [`ManifestBundlePlugin::with_names`] to also label each entity with a [`Name`] taken from the [`NameRegistry`],
+//! which makes data-driven entities much easier to find in inspectors and logs.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::core::Name;
+use bevy::ecs::prelude::*;
+use bevy::log::warn;
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+
+/// A manifest item which can be converted into a bundle of components for the entities that it identifies.
+///
+/// Unlike a [`Prototype`](crate::prototype::Prototype), the bundle is re-inserted whenever the manifest changes,
+/// so it should only contain data that is fully determined by the manifest.
+pub trait ToBundle: Send + Sync + 'static {
+    /// The components that are inserted.
+    type Bundle: Bundle;
+
+    /// Builds the bundle of components from this item.
+    fn to_bundle(&self) -> Self::Bundle;
+}
+
+/// A plugin which inserts the [`ToBundle::Bundle`] for every entity with an [`Id`] of the manifest `M`'s items.
+pub struct ManifestBundlePlugin<M: Manifest> {
+    insert_names: bool,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestBundlePlugin<M> {
+    fn default() -> Self {
+        Self {
+            insert_names: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> ManifestBundlePlugin<M> {
+    /// Sets whether identified entities should be labeled with a [`Name`], via [`name_identified_entities`].
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn with_names(mut self, insert_names: bool) -> Self {
+        self.insert_names = insert_names;
+        self
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestBundlePlugin<M>
+where
+    M::Item: ToBundle,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                insert_manifest_bundles::<M>,
+                reinsert_manifest_bundles::<M>.run_if(resource_changed::<M>),
+            )
+                .chain()
+                .run_if(resource_exists::<M>),
+        );
+
+        if self.insert_names {
+            app.add_systems(PostUpdate, name_identified_entities::<M>);
+        }
+    }
+}
+
+/// Inserts the [`ToBundle::Bundle`] for every entity which has just had an [`Id`] of the manifest `M`'s items added.
+pub fn insert_manifest_bundles<M: Manifest>(
+    manifest: Res<M>,
+    query: Query<(Entity, &Id<M::Item>), Added<Id<M::Item>>>,
+    mut commands: Commands,
+) where
+    M::Item: ToBundle,
+{
+    for (entity, &id) in query.iter() {
+        insert_bundle(&*manifest, entity, id, &mut commands);
+    }
+}
+
+/// Re-inserts the [`ToBundle::Bundle`] for every entity with an [`Id`] of the manifest `M`'s items.
+///
+/// This is run whenever the manifest changes, such as when it is reprocessed after a hot reload.
+pub fn reinsert_manifest_bundles<M: Manifest>(
+    manifest: Res<M>,
+    query: Query<(Entity, &Id<M::Item>)>,
+    mut commands: Commands,
+) where
+    M::Item: ToBundle,
+{
+    for (entity, &id) in query.iter() {
+        insert_bundle(&*manifest, entity, id, &mut commands);
+    }
+}
+
+/// Inserts the bundle for the item with the given `id` on the `entity`, if it exists in the `manifest`.
+pub(crate) fn insert_bundle<M: Manifest>(
+    manifest: &M,
+    entity: Entity,
+    id: Id<M::Item>,
+    commands: &mut Commands,
+) where
+    M::Item: ToBundle,
+{
+    match manifest.get(id) {
+        Some(item) => {
+            commands.entity(entity).insert(item.to_bundle());
+        }
+        None => warn!(
+            "Could not insert the bundle for {entity:?}: no item with ID {id:?} was found in the {} manifest.",
+            std::any::type_name::<M>()
+        ),
+    }
+}
+
+/// Inserts a [`Name`] for every entity which has just had an [`Id`] of the manifest `M`'s items added.
+///
+/// The name is looked up in the [`NameRegistry`]: entities whose [`Id`] has no registered name,
+/// or which already have a [`Name`], are left unchanged.
+pub fn name_identified_entities<M: Manifest>(
+    query: Query<(Entity, &Id<M::Item>), (Added<Id<M::Item>>, Without<Name>)>,
+    mut commands: Commands,
+) {
+    for (entity, &id) in query.iter() {
+        if let Some(name) = NameRegistry::name_of(id) {
+            commands.entity(entity).insert(Name::new(name));
+        }
+    }
+}
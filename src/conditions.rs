@@ -0,0 +1,141 @@
+//! Per-item conditions, for content that should only be included in some builds or events.
+//!
+//! Seasonal content, demo versions and platform-specific items are all easier to manage
+//! when they live alongside the rest of the data, rather than in separate manifest files.
+//! Raw items can list the flags that they require:
+//!
+//! ```ron
+//! [
+//!     (name: "sword"),
+//!     (name: "pumpkin_helmet", enabled_if: ["halloween_event"]),
+//!     (name: "legendary_sword", enabled_if: ["!demo"]),
+//! ]
+//! ```
+//!
+//! A condition of `"flag"` requires the flag to be set, while `"!flag"` requires it to be unset.
+//! Items are only enabled if every one of their conditions is met.
+//!
+//! Implement [`Conditional`] for your raw item type, insert a [`ManifestFlags`] resource,
+//! and call [`filter_enabled`] at the start of [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashSet;
+
+/// The set of flags used to evaluate the conditions of [`Conditional`] raw items.
+///
+/// Insert this resource before manifests are processed.
+/// If it does not exist, every flag is treated as unset.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestFlags {
+    flags: HashSet<String>,
+}
+
+impl ManifestFlags {
+    /// Creates an empty set of flags.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the flag `flag`, returning `self` for chaining.
+    #[must_use]
+    pub fn with(mut self, flag: impl Into<String>) -> Self {
+        self.set(flag);
+        self
+    }
+
+    /// Sets the flag `flag`.
+    pub fn set(&mut self, flag: impl Into<String>) {
+        self.flags.insert(flag.into());
+    }
+
+    /// Unsets the flag `flag`.
+    pub fn unset(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+
+    /// Returns true if the flag `flag` is set.
+    #[must_use]
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Returns true if the single condition is met.
+    ///
+    /// Conditions starting with `!` are met if the named flag is unset.
+    #[must_use]
+    pub fn meets(&self, condition: &str) -> bool {
+        match condition.trim().strip_prefix('!') {
+            Some(flag) => !self.is_set(flag.trim()),
+            None => self.is_set(condition.trim()),
+        }
+    }
+
+    /// Returns true if every one of the `conditions` is met.
+    ///
+    /// An empty list of conditions is always met.
+    #[must_use]
+    pub fn meets_all<S: AsRef<str>>(&self, conditions: &[S]) -> bool {
+        conditions
+            .iter()
+            .all(|condition| self.meets(condition.as_ref()))
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for ManifestFlags {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            flags: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A raw item which is only included in the manifest if its conditions are met.
+///
+/// ```rust
+/// use leafwing_manifest::conditions::Conditional;
+///
+/// struct RawItem {
+///     name: String,
+///     enabled_if: Vec<String>,
+/// }
+///
+/// impl Conditional for RawItem {
+///     fn conditions(&self) -> &[String] {
+///         &self.enabled_if
+///     }
+/// }
+/// ```
+pub trait Conditional {
+    /// The conditions which must all be met for this item to be enabled.
+    fn conditions(&self) -> &[String];
+
+    /// Returns true if this item is enabled by the supplied `flags`.
+    fn is_enabled(&self, flags: &ManifestFlags) -> bool {
+        flags.meets_all(self.conditions())
+    }
+}
+
+/// Removes every item whose conditions are not met, keeping the remaining items in their original order.
+pub fn filter_enabled<T: Conditional>(
+    items: impl IntoIterator<Item = T>,
+    flags: &ManifestFlags,
+) -> Vec<T> {
+    items
+        .into_iter()
+        .filter(|item| item.is_enabled(flags))
+        .collect()
+}
+
+/// Like [`filter_enabled`], but uses the [`ManifestFlags`] stored in the [`World`].
+///
+/// If the resource does not exist, every flag is treated as unset.
+pub fn filter_enabled_in<T: Conditional>(
+    items: impl IntoIterator<Item = T>,
+    world: &World,
+) -> Vec<T> {
+    match world.get_resource::<ManifestFlags>() {
+        Some(flags) => filter_enabled(items, flags),
+        None => filter_enabled(items, &ManifestFlags::default()),
+    }
+}
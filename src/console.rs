@@ -0,0 +1,342 @@
+//! Manifest commands for developer consoles.
+//!
+//! Add a [`ManifestConsolePlugin`] for each manifest that should be available from the console.
+//! Lines of input can then be run with [`run_console_command`], which understands:
+//!
+//! - `manifest list`: lists each manifest and how many items it has.
+//! - `manifest show <type> <name>`: prints the item with the given name, after its [`ItemDocs`] if it has any.
+//! - `manifest reload <type>`: reloads the raw manifest from disk.
+//! - `manifest spawn <type> <name>`: spawns an entity with the [`Id`] of the named item.
+//!
+//! Manifests are referred to by the short name of their type (such as `ItemManifest`), ignoring case,
+//! or by a custom name set with [`ManifestConsolePlugin::with_name`].
+//!
+//! This is designed to be forwarded to from whichever dev-console crate you already use:
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestConsolePlugin::<ItemManifest>::default().with_name("items"));
+//!
+//! fn on_console_input(world: &mut World, line: &str) {
+//!     match run_console_command(world, line) {
+//!         Ok(output) => info!("{output}"),
+//!         Err(err) => warn!("{err}"),
+//!     }
+//! }
+//! ```
+//!
+//! On native platforms, the [`StdinConsolePlugin`] provides a minimal built-in console instead,
+//! which reads commands from the terminal and logs the results.
+//!
+//! Spawned entities only have an [`Id`] component: pair this with a [`ManifestBundlePlugin`](crate::bundles::ManifestBundlePlugin)
+//! to build the rest of the entity from the item.
+//! Reloading requires either [`hot_reload`](crate::registration::ManifestRegistration::hot_reload) to be enabled,
+//! or the manifest to allow [late registration](crate::late_registration).
+
+use std::fmt::{Debug, Write};
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::asset::AssetServer;
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::Command;
+use bevy::utils::get_short_name;
+use thiserror::Error;
+
+use crate::identifier::Id;
+use crate::item_docs::ItemDocs;
+use crate::late_registration::{LateManifestRegistration, RegisterManifestCommand};
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+use crate::plugin::RawManifestTracker;
+
+/// The usage string printed when a console command is not recognized.
+pub const CONSOLE_USAGE: &str = "Usage: manifest list | manifest show <type> <name> | manifest reload <type> | manifest spawn <type> <name>";
+
+/// An error returned by [`run_console_command`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConsoleError {
+    /// The command was not recognized, or had the wrong number of arguments.
+    #[error("{}", CONSOLE_USAGE)]
+    InvalidCommand,
+    /// No manifest with this name was added with a [`ManifestConsolePlugin`].
+    #[error("Unknown manifest {0}. Use `manifest list` to see the available manifests.")]
+    UnknownManifest(String),
+    /// The manifest has not been processed yet.
+    #[error("The manifest {0} has not been loaded.")]
+    NotLoaded(String),
+    /// No item with this name exists in the manifest.
+    #[error("No item named {name} was found in the manifest {manifest}.")]
+    ItemNotFound {
+        /// The name of the manifest that was searched.
+        manifest: String,
+        /// The name of the item that was requested.
+        name: String,
+    },
+    /// The manifest could not be reloaded.
+    #[error("The manifest {0} cannot be reloaded, as it was never registered.")]
+    NotRegistered(String),
+}
+
+/// The console operations for a single manifest type, as registered by a [`ManifestConsolePlugin`].
+struct ConsoleManifest {
+    name: String,
+    type_name: &'static str,
+    item_count: fn(&World) -> Option<Option<usize>>,
+    show: fn(&World, &str) -> Option<Option<String>>,
+    reload: fn(&mut World) -> Option<String>,
+    spawn: fn(&mut World, &str) -> Option<Option<Entity>>,
+}
+
+/// The manifests which can be inspected with [`run_console_command`].
+///
+/// Each manifest is added by its [`ManifestConsolePlugin`].
+#[derive(Resource, Default)]
+pub struct ManifestConsole {
+    manifests: Vec<ConsoleManifest>,
+}
+
+impl ManifestConsole {
+    /// Returns the console names of the registered manifests, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.manifests.iter().map(|manifest| manifest.name.as_str())
+    }
+
+    fn find(&self, name: &str) -> Result<&ConsoleManifest, ConsoleError> {
+        self.manifests
+            .iter()
+            .find(|manifest| manifest.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| ConsoleError::UnknownManifest(name.to_string()))
+    }
+
+    fn execute(&self, world: &mut World, line: &str) -> Result<String, ConsoleError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["manifest", "list"] => Ok(self.list(world)),
+            ["manifest", "show", manifest, name] => {
+                let manifest = self.find(manifest)?;
+                match (manifest.show)(world, name) {
+                    None => Err(ConsoleError::NotLoaded(manifest.name.clone())),
+                    Some(None) => Err(item_not_found(manifest, name)),
+                    Some(Some(item)) => Ok(item),
+                }
+            }
+            ["manifest", "reload", manifest] => {
+                let manifest = self.find(manifest)?;
+                match (manifest.reload)(world) {
+                    None => Err(ConsoleError::NotRegistered(manifest.name.clone())),
+                    Some(path) => Ok(format!("Reloading {} from {path}.", manifest.name)),
+                }
+            }
+            ["manifest", "spawn", manifest, name] => {
+                let manifest = self.find(manifest)?;
+                match (manifest.spawn)(world, name) {
+                    None => Err(ConsoleError::NotLoaded(manifest.name.clone())),
+                    Some(None) => Err(item_not_found(manifest, name)),
+                    Some(Some(entity)) => Ok(format!("Spawned {name} as {entity:?}.")),
+                }
+            }
+            _ => Err(ConsoleError::InvalidCommand),
+        }
+    }
+
+    fn list(&self, world: &World) -> String {
+        let mut output = format!("{} manifests:", self.manifests.len());
+        for manifest in &self.manifests {
+            let status = match (manifest.item_count)(world) {
+                None => "not loaded".to_string(),
+                Some(None) => "loaded".to_string(),
+                Some(Some(count)) => format!("{count} items"),
+            };
+            // Writing to a String cannot fail.
+            let _ = write!(
+                output,
+                "\n  {} ({}): {status}",
+                manifest.name, manifest.type_name
+            );
+        }
+        output
+    }
+}
+
+impl Debug for ManifestConsole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestConsole")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn item_not_found(manifest: &ConsoleManifest, name: &str) -> ConsoleError {
+    ConsoleError::ItemNotFound {
+        manifest: manifest.name.clone(),
+        name: name.to_string(),
+    }
+}
+
+/// Runs a single line of console input, such as `manifest show ItemManifest sword`, returning the text to display.
+///
+/// See the [module documentation](self) for the supported commands.
+pub fn run_console_command(world: &mut World, line: &str) -> Result<String, ConsoleError> {
+    world.init_resource::<ManifestConsole>();
+    world.resource_scope(|world, console: Mut<ManifestConsole>| console.execute(world, line))
+}
+
+/// A plugin which makes the manifest `M` available to [`run_console_command`].
+pub struct ManifestConsolePlugin<M: Manifest> {
+    name: Option<String>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestConsolePlugin<M> {
+    fn default() -> Self {
+        Self {
+            name: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> ManifestConsolePlugin<M> {
+    /// Sets the name that console commands use to refer to this manifest.
+    ///
+    /// Defaults to the short name of the manifest type.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestConsolePlugin<M>
+where
+    M::Item: Debug,
+{
+    fn build(&self, app: &mut App) {
+        let type_name = std::any::type_name::<M>();
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| get_short_name(type_name));
+
+        app.init_resource::<ManifestConsole>()
+            .world
+            .resource_mut::<ManifestConsole>()
+            .manifests
+            .push(ConsoleManifest {
+                name,
+                type_name,
+                item_count: |world| {
+                    world
+                        .get_resource::<M>()
+                        .map(|manifest| manifest.item_count())
+                },
+                show: show_item::<M>,
+                reload: reload_manifest::<M>,
+                spawn: spawn_item::<M>,
+            });
+    }
+}
+
+fn show_item<M: Manifest>(world: &World, name: &str) -> Option<Option<String>>
+where
+    M::Item: Debug,
+{
+    let manifest = world.get_resource::<M>()?;
+    let docs = world
+        .get_resource::<ItemDocs<M>>()
+        .and_then(|docs| docs.get_by_name(name));
+
+    Some(manifest.get_by_name(name).map(|item| match docs {
+        Some(docs) => format!("{docs}\n{item:#?}"),
+        None => format!("{item:#?}"),
+    }))
+}
+
+fn reload_manifest<M: Manifest>(world: &mut World) -> Option<String> {
+    let path = world
+        .get_resource::<RawManifestTracker>()?
+        .status::<M>()?
+        .path
+        .clone();
+
+    if world.contains_resource::<LateManifestRegistration<M>>() {
+        RegisterManifestCommand::<M>::new(path.clone()).apply(world);
+    } else {
+        world.resource::<AssetServer>().reload(path.clone());
+    }
+
+    Some(path.display().to_string())
+}
+
+fn spawn_item<M: Manifest>(world: &mut World, name: &str) -> Option<Option<Entity>> {
+    let id = Id::<M::Item>::from_name(name);
+    let exists = world.get_resource::<M>()?.get(id).is_some();
+    if !exists {
+        return Some(None);
+    }
+
+    // Record the name, so that inspectors and `manifest` commands can display it.
+    NameRegistry::register::<M::Item>(name);
+    Some(Some(world.spawn(id).id()))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use stdin::StdinConsolePlugin;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod stdin {
+    use std::io::BufRead;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::sync::Mutex;
+
+    use bevy::app::{App, Plugin, Update};
+    use bevy::ecs::prelude::*;
+    use bevy::log::{info, warn};
+
+    use super::{run_console_command, ManifestConsole};
+
+    /// A plugin which reads console commands from standard input, and logs their results.
+    ///
+    /// Lines which do not start with `manifest` are ignored.
+    /// Input is read on a background thread, which runs until standard input is closed.
+    #[derive(Debug, Default)]
+    pub struct StdinConsolePlugin;
+
+    #[derive(Resource)]
+    struct StdinLines(Mutex<Receiver<String>>);
+
+    impl Plugin for StdinConsolePlugin {
+        fn build(&self, app: &mut App) {
+            let (sender, receiver) = channel();
+            std::thread::spawn(move || {
+                for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            app.init_resource::<ManifestConsole>()
+                .insert_resource(StdinLines(Mutex::new(receiver)))
+                .add_systems(Update, run_stdin_commands);
+        }
+    }
+
+    fn run_stdin_commands(world: &mut World) {
+        let lines: Vec<String> = match world.resource::<StdinLines>().0.lock() {
+            Ok(receiver) => receiver.try_iter().collect(),
+            Err(_) => return,
+        };
+
+        for line in lines {
+            if line.split_whitespace().next() != Some("manifest") {
+                continue;
+            }
+
+            match run_console_command(world, &line) {
+                Ok(output) => info!("{output}"),
+                Err(err) => warn!("{err}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Finding manifest entries that nothing uses, so obsolete content can be pruned.
+//!
+//! There are two complementary checks:
+//!
+//! - [`unreferenced_items`] is a static analysis over a [`ReferenceGraph`], reporting items which no other item refers to.
+//!   Top-level content (such as the starting items of a tech tree) will appear here too, so review the results rather than deleting them blindly.
+//! - The [`DeadEntryPlugin`] records which items are actually used during a play session:
+//!   either looked up through a [`TrackedManifest`], or spawned as an [`Id`] component.
+//!   [`ManifestLookups::unused_ids`] then lists every item which was never touched.
+//!
+//! ```
+//! use bevy::ecs::system::{RunSystemOnce, SystemState};
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     dead_entries::{record_spawned_ids, unreferenced_items, ManifestLookups, TrackedManifest},
+//!     identifier::{Id, IdMap},
+//!     manifest::{IterableManifest, Manifest, ManifestFormat},
+//!     name_registry::NameRegistry,
+//!     reference_graph::ReferenceGraph,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item;
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! impl IterableManifest for ItemManifest {
+//!     fn iter(&self) -> impl Iterator<Item = (Id<Item>, &Item)> {
+//!         self.items.iter().map(|(id, item)| (*id, item))
+//!     }
+//! }
+//!
+//! // Nothing refers to the old sword, so it is a candidate for removal.
+//! let mut graph = ReferenceGraph::new();
+//! graph.add_edge(("recipes", "forge"), ("items", "sword"));
+//! graph.add_edge(("recipes", "forge"), ("items", "shield"));
+//! graph.add_node("items", "old_sword");
+//! assert_eq!(unreferenced_items(&graph, "items"), ["old_sword"]);
+//!
+//! // During a session, lookups and spawned `Id` components are recorded instead.
+//! let mut world = World::new();
+//! world.insert_resource(ItemManifest {
+//!     items: ["sword", "shield", "old_sword"]
+//!         .into_iter()
+//!         .map(|name| (NameRegistry::register(name), Item))
+//!         .collect(),
+//! });
+//! // This resource is added by the `DeadEntryPlugin`.
+//! world.init_resource::<ManifestLookups<ItemManifest>>();
+//!
+//! let mut state = SystemState::<TrackedManifest<ItemManifest>>::new(&mut world);
+//! assert!(state.get(&world).get_by_name("sword").is_some());
+//!
+//! world.spawn(Id::<Item>::from_name("shield"));
+//! world.run_system_once(record_spawned_ids::<ItemManifest>);
+//!
+//! let lookups = world.resource::<ManifestLookups<ItemManifest>>();
+//! assert_eq!(lookups.unused_names(world.resource::<ItemManifest>()), ["old_sword"]);
+//! ```
+//!
+//! Recording has a small cost on every lookup, so this is intended for playtest builds.
+
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::SystemParam;
+
+use crate::identifier::{Id, IdSet};
+use crate::manifest::{IterableManifest, Manifest};
+use crate::name_registry::NameRegistry;
+use crate::reference_graph::ReferenceGraph;
+
+/// Returns the names of the items in `manifest` which no other item in the `graph` refers to, in the order they were added.
+///
+/// Items which only refer to themselves are counted as unreferenced.
+#[must_use]
+pub fn unreferenced_items(graph: &ReferenceGraph, manifest: &str) -> Vec<String> {
+    let mut referenced = vec![false; graph.nodes().len()];
+    for edge in graph.edges() {
+        if edge.from != edge.to {
+            referenced[edge.to] = true;
+        }
+    }
+
+    graph
+        .nodes()
+        .iter()
+        .zip(referenced)
+        .filter(|(node, referenced)| node.manifest == manifest && !referenced)
+        .map(|(node, _)| node.name.clone())
+        .collect()
+}
+
+/// The items of the manifest `M` which have been used since recording started.
+///
+/// This resource is added by the [`DeadEntryPlugin`].
+#[derive(Resource)]
+pub struct ManifestLookups<M: Manifest> {
+    // Lookups happen through shared references, so this needs interior mutability.
+    used: Mutex<IdSet<M::Item>>,
+}
+
+impl<M: Manifest> ManifestLookups<M> {
+    /// Records that the item with the given `id` was used.
+    pub fn record(&self, id: Id<M::Item>) {
+        if let Ok(mut used) = self.used.lock() {
+            used.insert(id);
+        }
+    }
+
+    /// Returns true if the item with the given `id` has been used.
+    #[must_use]
+    pub fn was_used(&self, id: Id<M::Item>) -> bool {
+        self.used.lock().is_ok_and(|used| used.contains(&id))
+    }
+
+    /// The number of distinct items that have been used.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.used.lock().map_or(0, |used| used.len())
+    }
+
+    /// Returns true if no items have been used.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets every recorded use, such as at the start of a new playtest.
+    pub fn clear(&self) {
+        if let Ok(mut used) = self.used.lock() {
+            used.clear();
+        }
+    }
+}
+
+impl<M: IterableManifest> ManifestLookups<M> {
+    /// Returns the items in the `manifest` which have never been used, sorted by [`Id`].
+    #[must_use]
+    pub fn unused_ids(&self, manifest: &M) -> Vec<Id<M::Item>> {
+        let mut unused: Vec<Id<M::Item>> =
+            manifest.ids().filter(|id| !self.was_used(*id)).collect();
+        unused.sort();
+        unused
+    }
+
+    /// Returns the names of the items in the `manifest` which have never been used, sorted by name.
+    ///
+    /// Items without a name in the [`NameRegistry`] are listed by their [`Id`].
+    #[must_use]
+    pub fn unused_names(&self, manifest: &M) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .unused_ids(manifest)
+            .into_iter()
+            .map(|id| NameRegistry::name_of(id).unwrap_or_else(|| format!("{id:?}")))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl<M: Manifest> Default for ManifestLookups<M> {
+    fn default() -> Self {
+        Self {
+            used: Mutex::new(IdSet::default()),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestLookups<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestLookups")
+            .field("used", &self.len())
+            .finish()
+    }
+}
+
+/// A [`SystemParam`] for reading the manifest `M`, which records each item looked up in the [`ManifestLookups`].
+///
+/// This dereferences to the manifest, so every other method of `M` continues to work unchanged.
+/// Only lookups made via [`TrackedManifest::get`] (and [`TrackedManifest::get_by_name`]) are recorded.
+/// If the [`DeadEntryPlugin`] has not been added, nothing is recorded.
+#[derive(SystemParam)]
+pub struct TrackedManifest<'w, M: Manifest> {
+    manifest: Res<'w, M>,
+    lookups: Option<Res<'w, ManifestLookups<M>>>,
+}
+
+impl<'w, M: Manifest> TrackedManifest<'w, M> {
+    /// Returns the item with the given `id`, recording that it was used.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        let item = self.manifest.get(id);
+        if let (Some(_), Some(lookups)) = (item, &self.lookups) {
+            lookups.record(id);
+        }
+        item
+    }
+
+    /// Returns the item with the given `name`, recording that it was used.
+    #[must_use]
+    pub fn get_by_name(&self, name: impl Borrow<str>) -> Option<&M::Item> {
+        self.get(Id::from_name(name.borrow()))
+    }
+}
+
+impl<'w, M: Manifest> Deref for TrackedManifest<'w, M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.manifest
+    }
+}
+
+/// A plugin which records which items of the manifest `M` are used, in its [`ManifestLookups`].
+///
+/// Items are recorded when they are looked up through a [`TrackedManifest`],
+/// or when an entity gains an `Id<M::Item>` component.
+pub struct DeadEntryPlugin<M: Manifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for DeadEntryPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> Plugin for DeadEntryPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ManifestLookups<M>>()
+            .add_systems(PostUpdate, record_spawned_ids::<M>);
+    }
+}
+
+/// Records the items of the manifest `M` referred to by newly added `Id<M::Item>` components as used.
+pub fn record_spawned_ids<M: Manifest>(
+    lookups: Res<ManifestLookups<M>>,
+    query: Query<&Id<M::Item>, Added<Id<M::Item>>>,
+) {
+    for id in query.iter() {
+        lookups.record(*id);
+    }
+}
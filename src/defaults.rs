@@ -0,0 +1,111 @@
+//! Default field values shared by every item in a raw manifest.
+//!
+//! Large raw manifests often repeat the same values for most items.
+//! Rather than writing them out each time, wrap the list of raw items in [`Defaults`],
+//! which adds a separate `defaults` section that every item falls back on:
+//!
+//! ```ron
+//! (
+//!     defaults: (weight: Some(1.0), stack_size: Some(64)),
+//!     items: [
+//!         (name: "stone"),
+//!         (name: "anvil", weight: Some(50.0), stack_size: Some(1)),
+//!     ],
+//! )
+//! ```
+//!
+//! Implement [`ApplyDefaults`] for your raw item type, then call [`Defaults::resolve`]
+//! at the start of [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+//! This is simpler than [`inheritance`](crate::inheritance) for flat data, and the two can be combined
+//! with [`Defaults::resolve_inheritance`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::inheritance::{resolve_inheritance, Inheritable, InheritanceError};
+
+/// A raw item that can fall back on default field values.
+///
+/// As with [`Inheritable`], overridable fields are typically stored as [`Option`]s, marked with `#[serde(default)]`
+/// so that they can be left out of the raw manifest.
+/// [`inherit_field`](crate::inheritance::inherit_field) fills in any fields that the item did not specify.
+///
+/// ```rust
+/// use leafwing_manifest::defaults::ApplyDefaults;
+/// use leafwing_manifest::inheritance::inherit_field;
+/// use serde::Deserialize;
+///
+/// #[derive(Default, Deserialize)]
+/// struct RawItem {
+///     #[serde(default)]
+///     name: String,
+///     #[serde(default)]
+///     weight: Option<f32>,
+///     #[serde(default)]
+///     stack_size: Option<u32>,
+/// }
+///
+/// impl ApplyDefaults for RawItem {
+///     fn apply_defaults(&mut self, defaults: &Self) {
+///         inherit_field(&mut self.weight, &defaults.weight);
+///         inherit_field(&mut self.stack_size, &defaults.stack_size);
+///     }
+/// }
+/// ```
+pub trait ApplyDefaults {
+    /// Fills in any values that this item does not override with those from `defaults`.
+    fn apply_defaults(&mut self, defaults: &Self);
+}
+
+/// A list of raw items, along with the [`ApplyDefaults`] values shared by every item.
+///
+/// Use this as (or inside) the [`Manifest::RawManifest`](crate::manifest::Manifest::RawManifest) type.
+/// If the `defaults` section is left out of the raw manifest, [`Default::default`] is used instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Defaults<R> {
+    /// The values used for any fields that an item does not specify.
+    #[serde(default)]
+    pub defaults: R,
+    /// The raw items, each of which may override some of the defaults.
+    pub items: Vec<R>,
+}
+
+impl<R: ApplyDefaults> Defaults<R> {
+    /// Applies the defaults to every item, returning the items in their original order.
+    #[must_use]
+    pub fn resolve(self) -> Vec<R> {
+        let Self {
+            defaults,
+            mut items,
+        } = self;
+
+        for item in &mut items {
+            item.apply_defaults(&defaults);
+        }
+
+        items
+    }
+}
+
+impl<R: ApplyDefaults + Inheritable> Defaults<R> {
+    /// Resolves inheritance between the items with [`resolve_inheritance`], then applies the defaults.
+    ///
+    /// Fields set by an item's parent take priority over the defaults.
+    pub fn resolve_inheritance(self) -> Result<Vec<R>, InheritanceError> {
+        let mut items = resolve_inheritance(self.items)?;
+
+        for item in &mut items {
+            item.apply_defaults(&self.defaults);
+        }
+
+        Ok(items)
+    }
+}
+
+impl<R: Default> Default for Defaults<R> {
+    fn default() -> Self {
+        Self {
+            defaults: R::default(),
+            items: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,418 @@
+//! A contiguous, [`Vec`]-backed storage strategy for manifest items.
+//!
+//! Most manifests are simple hashmaps, mapping `Id<Item>` to `Item`.
+//! This is a great default, but iterating over a hashmap is relatively slow, as the items are scattered throughout memory.
+//! If your game iterates over every item in a manifest every frame (for AI scoring, UI lists and so on),
+//! storing the items in a [`DenseManifest`] instead keeps them tightly packed,
+//! while still allowing for fast lookups by [`Id`].
+
+use std::{fmt::Debug, hash::Hash, marker::PhantomData, ops::Index};
+
+use crate::identifier::{Id, IdMap};
+
+/// A stable index into the items stored in a [`DenseManifest`].
+///
+/// Looking up an item by its [`ItemIndex`] is a pair of array accesses, and is faster than looking it up by its [`Id`].
+/// Cache these in hot loops when you need to repeatedly access the same items.
+///
+/// Indexes are only valid for the [`DenseManifest`] that created them.
+/// Each index refers to a slot with a generation, which is incremented when its item is removed,
+/// so an index to a removed item never refers to a different item, even if its slot is reused.
+pub struct ItemIndex<T> {
+    slot: usize,
+    generation: u32,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> ItemIndex<T> {
+    /// Returns the raw slot of the item.
+    ///
+    /// This is not the position of the item in [`DenseManifest::items`], which changes when other items are removed.
+    #[must_use]
+    pub const fn raw(&self) -> usize {
+        self.slot
+    }
+
+    /// Returns the generation of the slot when this index was created.
+    #[must_use]
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Constructs a new index from a raw slot and generation.
+    ///
+    /// This is an escape hatch: prefer using the indexes returned by [`DenseManifest`] methods.
+    #[must_use]
+    pub const fn from_raw(slot: usize, generation: u32) -> Self {
+        ItemIndex {
+            slot,
+            generation,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for ItemIndex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemIndex")
+            .field("slot", &self.slot)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ItemIndex<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slot == other.slot && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for ItemIndex<T> {}
+
+impl<T> PartialOrd for ItemIndex<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for ItemIndex<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.slot
+            .cmp(&other.slot)
+            .then(self.generation.cmp(&other.generation))
+    }
+}
+
+impl<T> Hash for ItemIndex<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.slot.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> Clone for ItemIndex<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ItemIndex<T> {}
+
+/// A slot which an [`ItemIndex`] refers to.
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: u32,
+    /// The position of the item in the backing [`Vec`], or [`None`] if the slot is free.
+    position: Option<usize>,
+}
+
+/// A dense storage container for manifest items.
+///
+/// Items are stored contiguously in a [`Vec`], with a separate [`IdMap`] from [`Id`] to the item's slot,
+/// which records the item's position.
+/// Store this in your [`Manifest`](crate::manifest::Manifest) type in place of an `IdMap<Item, Item>`
+/// when iteration speed matters more than the (small) cost of the extra indirection during lookups.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{dense_manifest::DenseManifest, identifier::Id};
+///
+/// struct Monster {
+///     life: u32,
+/// }
+///
+/// let mut monsters = DenseManifest::default();
+/// let slime_index = monsters.insert(Id::from_name("slime"), Monster { life: 5 });
+/// monsters.insert(Id::from_name("dragon"), Monster { life: 500 });
+///
+/// assert_eq!(monsters[slime_index].life, 5);
+/// assert_eq!(monsters.get(Id::from_name("dragon")).unwrap().life, 500);
+///
+/// let total_life: u32 = monsters.items().iter().map(|monster| monster.life).sum();
+/// assert_eq!(total_life, 505);
+/// ```
+pub struct DenseManifest<T> {
+    items: Vec<T>,
+    ids: Vec<Id<T>>,
+    /// The slot of each item, in the same order as `items`.
+    item_slots: Vec<usize>,
+    slots: Vec<Slot>,
+    free_slots: Vec<usize>,
+    indexes: IdMap<T, usize>,
+}
+
+impl<T> DenseManifest<T> {
+    /// Creates a new, empty [`DenseManifest`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty [`DenseManifest`] with space for at least `capacity` items.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        DenseManifest {
+            items: Vec::with_capacity(capacity),
+            ids: Vec::with_capacity(capacity),
+            item_slots: Vec::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free_slots: Vec::new(),
+            indexes: IdMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    /// Inserts an item with the given [`Id`], returning its [`ItemIndex`].
+    ///
+    /// If an item with the same [`Id`] is already stored, it is replaced in place,
+    /// and its existing index is returned.
+    pub fn insert(&mut self, id: Id<T>, item: T) -> ItemIndex<T> {
+        if let Some(&slot) = self.indexes.get(&id) {
+            let position = self.slots[slot]
+                .position
+                .expect("Slots of stored items are occupied.");
+            self.items[position] = item;
+            return ItemIndex::from_raw(slot, self.slots[slot].generation);
+        }
+
+        let position = self.items.len();
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot].position = Some(position);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    position: Some(position),
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        self.items.push(item);
+        self.ids.push(id);
+        self.item_slots.push(slot);
+        self.indexes.insert(id, slot);
+        ItemIndex::from_raw(slot, self.slots[slot].generation)
+    }
+
+    /// Removes the item with the given [`Id`], returning it if it was found.
+    ///
+    /// The last item is moved into the gap left by the removed item, changing the order of [`DenseManifest::items`].
+    /// The [`ItemIndex`] of the moved item stays valid, while the [`ItemIndex`] of the removed item no longer refers to any item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use leafwing_manifest::{dense_manifest::DenseManifest, identifier::Id};
+    ///
+    /// let mut manifest = DenseManifest::default();
+    /// let slime = manifest.insert(Id::from_name("slime"), "slime");
+    /// let dragon = manifest.insert(Id::from_name("dragon"), "dragon");
+    ///
+    /// manifest.remove(Id::from_name("slime"));
+    /// assert_eq!(manifest.get_by_index(slime), None);
+    /// assert_eq!(manifest.get_by_index(dragon), Some(&"dragon"));
+    ///
+    /// // The slot of the removed item is reused, but the stale index still refers to nothing.
+    /// let goblin = manifest.insert(Id::from_name("goblin"), "goblin");
+    /// assert_eq!(goblin.raw(), slime.raw());
+    /// assert_eq!(manifest.get_by_index(slime), None);
+    /// assert_eq!(manifest.get_by_index(goblin), Some(&"goblin"));
+    /// ```
+    pub fn remove(&mut self, id: Id<T>) -> Option<T> {
+        let slot = self.indexes.remove(&id)?;
+        let position = self.slots[slot]
+            .position
+            .take()
+            .expect("Slots of stored items are occupied.");
+        self.slots[slot].generation = self.slots[slot].generation.wrapping_add(1);
+        self.free_slots.push(slot);
+
+        let item = self.items.swap_remove(position);
+        self.ids.swap_remove(position);
+        self.item_slots.swap_remove(position);
+
+        if let Some(&moved_slot) = self.item_slots.get(position) {
+            self.slots[moved_slot].position = Some(position);
+        }
+
+        Some(item)
+    }
+
+    /// Returns the position in the backing [`Vec`] of the item that `index` refers to, if it still exists.
+    fn position_of(&self, index: ItemIndex<T>) -> Option<usize> {
+        self.slots
+            .get(index.slot)
+            .filter(|slot| slot.generation == index.generation)
+            .and_then(|slot| slot.position)
+    }
+
+    /// Gets an item by its [`Id`].
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        let position = self.position_of(self.index_of(id)?)?;
+        Some(&self.items[position])
+    }
+
+    /// Gets a mutable reference to an item by its [`Id`].
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn get_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        let position = self.position_of(self.index_of(id)?)?;
+        Some(&mut self.items[position])
+    }
+
+    /// Gets an item by its [`ItemIndex`].
+    ///
+    /// Returns [`None`] if the item has been removed.
+    #[must_use]
+    pub fn get_by_index(&self, index: ItemIndex<T>) -> Option<&T> {
+        self.position_of(index)
+            .map(|position| &self.items[position])
+    }
+
+    /// Gets a mutable reference to an item by its [`ItemIndex`].
+    ///
+    /// Returns [`None`] if the item has been removed.
+    #[must_use]
+    pub fn get_mut_by_index(&mut self, index: ItemIndex<T>) -> Option<&mut T> {
+        self.position_of(index)
+            .map(|position| &mut self.items[position])
+    }
+
+    /// Returns the [`ItemIndex`] of the item with the given [`Id`], if it exists.
+    #[must_use]
+    pub fn index_of(&self, id: Id<T>) -> Option<ItemIndex<T>> {
+        self.indexes
+            .get(&id)
+            .map(|&slot| ItemIndex::from_raw(slot, self.slots[slot].generation))
+    }
+
+    /// Returns the [`Id`] of the item stored at the given [`ItemIndex`], if it exists.
+    #[must_use]
+    pub fn id_of(&self, index: ItemIndex<T>) -> Option<Id<T>> {
+        self.position_of(index).map(|position| self.ids[position])
+    }
+
+    /// Returns true if an item with the given [`Id`] is stored.
+    #[must_use]
+    pub fn contains(&self, id: Id<T>) -> bool {
+        self.indexes.contains_key(&id)
+    }
+
+    /// Returns the number of stored items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns all stored items as a contiguous slice.
+    #[must_use]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns all stored items as a contiguous mutable slice.
+    #[must_use]
+    pub fn items_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+
+    /// Returns the [`Id`]s of all stored items, in the same order as [`DenseManifest::items`].
+    #[must_use]
+    pub fn ids(&self) -> &[Id<T>] {
+        &self.ids
+    }
+
+    /// Iterates over all stored items and their [`Id`]s, in storage order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.ids.iter().copied().zip(self.items.iter())
+    }
+
+    /// Iterates mutably over all stored items and their [`Id`]s, in storage order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<T>, &mut T)> {
+        self.ids.iter().copied().zip(self.items.iter_mut())
+    }
+
+    /// Converts this manifest into a read-only [`FrozenManifest`](crate::frozen_manifest::FrozenManifest).
+    ///
+    /// Any [`ItemIndex`] handles into this manifest are invalidated.
+    #[must_use]
+    pub fn freeze(self) -> crate::frozen_manifest::FrozenManifest<T> {
+        self.ids.into_iter().zip(self.items).collect()
+    }
+}
+
+impl<T> Default for DenseManifest<T> {
+    fn default() -> Self {
+        DenseManifest {
+            items: Vec::new(),
+            ids: Vec::new(),
+            item_slots: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            indexes: IdMap::default(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for DenseManifest<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for DenseManifest<T> {
+    fn clone(&self) -> Self {
+        DenseManifest {
+            items: self.items.clone(),
+            ids: self.ids.clone(),
+            item_slots: self.item_slots.clone(),
+            slots: self.slots.clone(),
+            free_slots: self.free_slots.clone(),
+            indexes: self.indexes.clone(),
+        }
+    }
+}
+
+impl<T> Index<ItemIndex<T>> for DenseManifest<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// Panics if the item has been removed.
+    fn index(&self, index: ItemIndex<T>) -> &Self::Output {
+        self.get_by_index(index)
+            .expect("The item this ItemIndex refers to has been removed.")
+    }
+}
+
+impl<T> FromIterator<(Id<T>, T)> for DenseManifest<T> {
+    fn from_iter<I: IntoIterator<Item = (Id<T>, T)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut manifest = DenseManifest::with_capacity(iter.size_hint().0);
+        for (id, item) in iter {
+            manifest.insert(id, item);
+        }
+        manifest
+    }
+}
+
+impl<T> Extend<(Id<T>, T)> for DenseManifest<T> {
+    fn extend<I: IntoIterator<Item = (Id<T>, T)>>(&mut self, iter: I) {
+        for (id, item) in iter {
+            self.insert(id, item);
+        }
+    }
+}
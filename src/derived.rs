@@ -0,0 +1,95 @@
+//! Resources computed from manifests, which are kept up to date as the manifests change.
+//!
+//! Games often build extra data from their manifests: spatial lookup tables, cumulative stat curves, UI caches and so on.
+//! Implement [`DerivedFromManifest`] for the resource that stores this data,
+//! then register it with [`RegisterDerived::register_derived`]:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     derived::{DerivedFromManifest, RegisterDerived},
+//!     identifier::Id,
+//!     manifest::{IterableManifest, MutableManifest},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//!
+//! #[derive(Resource)]
+//! struct ItemsByCost(Vec<Id<Item>>);
+//!
+//! impl DerivedFromManifest<ItemManifest> for ItemsByCost {
+//!     fn derive(manifest: &ItemManifest, _world: &World) -> Self {
+//!         let mut items: Vec<_> = manifest.iter().collect();
+//!         items.sort_by_key(|(_, item)| item.value);
+//!         ItemsByCost(items.into_iter().map(|(id, _)| id).collect())
+//!     }
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.insert_raw_manifest::<ItemManifest>(ItemManifest::from_items([
+//!     Item::new("sword", 10),
+//!     Item::new("shield", 5),
+//! ]))
+//! .register_derived::<ItemsByCost, ItemManifest>();
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! let by_cost = &app.world.resource::<ItemsByCost>().0;
+//! assert_eq!(by_cost, &[Id::<Item>::from_name("shield"), Id::from_name("sword")]);
+//!
+//! // Changes to the manifest are picked up on the next update.
+//! let mut manifest = app.world.resource_mut::<ItemManifest>();
+//! manifest.insert(Item::new("dagger", 1)).unwrap();
+//! app.update();
+//! assert_eq!(app.world.resource::<ItemsByCost>().0[0], Id::<Item>::from_name("dagger"));
+//! ```
+//!
+//! The resource is inserted once the manifest has been processed,
+//! and recomputed whenever the manifest changes, such as after a hot reload.
+
+use bevy::app::App;
+use bevy::ecs::prelude::*;
+
+use crate::manifest::Manifest;
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+
+/// A resource computed from the manifest `M`.
+///
+/// To derive a resource from several manifests, implement this trait for each of them and register each one:
+/// the resource is recomputed whenever any of them changes, reading the others from the `world`.
+pub trait DerivedFromManifest<M: Manifest>: Resource + Sized {
+    /// Computes the resource from the `manifest`.
+    ///
+    /// Other resources, including other manifests, can be read from the `world`.
+    fn derive(manifest: &M, world: &World) -> Self;
+}
+
+/// An extension trait for registering resources derived from manifests with an [`App`].
+pub trait RegisterDerived {
+    /// Inserts the resource `D` once the manifest `M` has been processed, and recomputes it whenever `M` changes.
+    ///
+    /// The resource is computed in the processing schedule, after the [`ProcessManifestSet`].
+    fn register_derived<D: DerivedFromManifest<M>, M: Manifest>(&mut self) -> &mut Self;
+}
+
+impl RegisterDerived for App {
+    fn register_derived<D: DerivedFromManifest<M>, M: Manifest>(&mut self) -> &mut Self {
+        let schedules = manifest_schedules(self);
+        self.add_systems(
+            schedules.processing,
+            update_derived::<D, M>
+                .run_if(resource_exists::<M>.and_then(resource_changed::<M>))
+                .after(ProcessManifestSet),
+        )
+    }
+}
+
+/// Recomputes the resource `D` from the manifest `M`.
+pub fn update_derived<D: DerivedFromManifest<M>, M: Manifest>(world: &mut World) {
+    let Some(manifest) = world.get_resource::<M>() else {
+        return;
+    };
+
+    let derived = D::derive(manifest, world);
+    world.insert_resource(derived);
+}
@@ -0,0 +1,132 @@
+//! Integration with Bevy's [diagnostics](bevy::diagnostic) system.
+//!
+//! Add the [`ManifestDiagnosticsPlugin`] to publish information about your manifests to the [`DiagnosticsStore`],
+//! alongside built-in diagnostics like frame time.
+//! This is particularly useful for spotting manifests that are slow to process,
+//! and may be worth converting asynchronously or caching.
+//!
+//! This module is only available when the `diagnostics` feature is enabled.
+
+use std::any::TypeId;
+
+use bevy::app::{App, Plugin, Update};
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, Diagnostics, DiagnosticsStore,
+    RegisterDiagnostic,
+};
+use bevy::ecs::prelude::*;
+use bevy::utils::{get_short_name, HashMap, Instant};
+
+use crate::plugin::RawManifestTracker;
+
+/// A plugin which publishes manifest diagnostics to the [`DiagnosticsStore`].
+///
+/// The following diagnostics are recorded:
+/// - [`ManifestDiagnosticsPlugin::MANIFEST_COUNT`]: the number of registered manifests.
+/// - [`ManifestDiagnosticsPlugin::ITEM_COUNT`]: the total number of items across all processed manifests,
+///   as reported by [`Manifest::item_count`](crate::manifest::Manifest::item_count).
+/// - [`ManifestDiagnosticsPlugin::RELOAD_COUNT`]: the total number of times raw manifests have been hot reloaded.
+/// - `manifest/processing_time/<ManifestType>`: the time taken to process each manifest, in milliseconds.
+///
+/// This plugin should be added after the [`ManifestPlugin`](crate::plugin::ManifestPlugin).
+#[derive(Debug, Default)]
+pub struct ManifestDiagnosticsPlugin;
+
+impl ManifestDiagnosticsPlugin {
+    /// The number of registered manifests.
+    pub const MANIFEST_COUNT: DiagnosticPath = DiagnosticPath::const_new("manifest/count");
+    /// The total number of items across all processed manifests.
+    pub const ITEM_COUNT: DiagnosticPath = DiagnosticPath::const_new("manifest/items");
+    /// The total number of times raw manifests have been reloaded after their initial load.
+    pub const RELOAD_COUNT: DiagnosticPath = DiagnosticPath::const_new("manifest/reloads");
+
+    /// Returns the path of the diagnostic which records the processing time of the given manifest type.
+    ///
+    /// `type_name` should be the full type name, as stored in [`RawManifestStatus::type_name`](crate::plugin::RawManifestStatus::type_name).
+    #[must_use]
+    pub fn processing_time_path(type_name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!(
+            "manifest/processing_time/{}",
+            get_short_name(type_name)
+        ))
+    }
+}
+
+impl Plugin for ManifestDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::MANIFEST_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::ITEM_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::RELOAD_COUNT))
+            .add_systems(
+                Update,
+                (
+                    record_manifest_diagnostics,
+                    record_processing_time_diagnostics,
+                )
+                    .run_if(resource_exists::<RawManifestTracker>),
+            );
+    }
+}
+
+/// Records the aggregate manifest diagnostics.
+pub fn record_manifest_diagnostics(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    mut diagnostics: Diagnostics,
+) {
+    diagnostics.add_measurement(&ManifestDiagnosticsPlugin::MANIFEST_COUNT, || {
+        raw_manifest_tracker.iter().count() as f64
+    });
+
+    diagnostics.add_measurement(&ManifestDiagnosticsPlugin::ITEM_COUNT, || {
+        raw_manifest_tracker
+            .iter()
+            .filter_map(|(_, status)| status.item_count)
+            .sum::<usize>() as f64
+    });
+
+    diagnostics.add_measurement(&ManifestDiagnosticsPlugin::RELOAD_COUNT, || {
+        raw_manifest_tracker
+            .iter()
+            .map(|(_, status)| status.load_count.saturating_sub(1))
+            .sum::<u32>() as f64
+    });
+}
+
+/// Records the processing time of each manifest, registering a new diagnostic for each manifest as it is processed.
+///
+/// A measurement is only recorded when a manifest has been (re)processed since the last one,
+/// as reported by [`RawManifestStatus::processed_at`](crate::plugin::RawManifestStatus::processed_at).
+/// Per-manifest diagnostics are created lazily, as manifests may be registered after this plugin is added.
+pub fn record_processing_time_diagnostics(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    mut store: ResMut<DiagnosticsStore>,
+    mut last_processed: Local<HashMap<TypeId, Instant>>,
+) {
+    if !raw_manifest_tracker.is_changed() {
+        return;
+    }
+
+    for (type_id, status) in raw_manifest_tracker.iter() {
+        let (Some(processing_duration), Some(processed_at)) =
+            (status.processing_duration, status.processed_at)
+        else {
+            continue;
+        };
+
+        if last_processed.insert(*type_id, processed_at) == Some(processed_at) {
+            continue;
+        }
+
+        let path = ManifestDiagnosticsPlugin::processing_time_path(status.type_name);
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+        }
+
+        if let Some(diagnostic) = store.get_mut(&path) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value: processing_duration.as_secs_f64() * 1000.0,
+            });
+        }
+    }
+}
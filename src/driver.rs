@@ -0,0 +1,196 @@
+//! Loading manifests without Bevy states.
+//!
+//! The [`ManifestPlugin`](crate::plugin::ManifestPlugin) tracks loading progress using an [`AssetLoadingState`](crate::asset_state::AssetLoadingState).
+//! Apps that manage their flow with another crate (or not at all) can instead use the [`ManifestLoadingDriver`],
+//! which tracks progress in the [`ManifestReadiness`] resource, and sends [`ManifestsReady`] and [`ManifestsFailed`] events.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     driver::{manifests_ready, ManifestLoadingDriver, ManifestReadiness},
+//!     plugin::RegisterManifest,
+//! };
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//!
+//! #[derive(Resource, Default)]
+//! struct Shop(Vec<String>);
+//!
+//! fn spawn_shop(items: Res<ItemManifest>, mut shop: ResMut<Shop>) {
+//!     shop.0 = items.items.values().map(|item| item.name.clone()).collect();
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+//!     .add_plugins(ManifestLoadingDriver::default())
+//!     // Usually loaded from a file, via `.register_manifest::<ItemManifest>("items.ron")`.
+//!     .register_manifest_from_value::<ItemManifest>(ItemManifest::from_items([Item::new("sword", 10)]))
+//!     .init_resource::<Shop>()
+//!     .add_systems(Update, spawn_shop.run_if(manifests_ready));
+//!
+//! for _ in 0..1000 {
+//!     app.update();
+//!     if *app.world.resource::<ManifestReadiness>() == ManifestReadiness::Ready {
+//!         break;
+//!     }
+//!     std::thread::sleep(std::time::Duration::from_millis(1));
+//! }
+//! assert_eq!(*app.world.resource::<ManifestReadiness>(), ManifestReadiness::Ready);
+//!
+//! // The shop is guaranteed to be stocked by the next update.
+//! app.update();
+//! assert_eq!(app.world.resource::<Shop>().0, ["sword"]);
+//! ```
+//!
+//! Only one of the [`ManifestLoadingDriver`] and the [`ManifestPlugin`](crate::plugin::ManifestPlugin) should be added.
+
+use bevy::app::{App, Plugin};
+use bevy::asset::AssetServer;
+use bevy::ecs::prelude::*;
+use bevy::log::{error, info};
+
+use crate::lifecycle::dispatch_lifecycle_events;
+use crate::loading_timeline::record_loading_timeline;
+use crate::plugin::{
+    add_manifest_schedules, manifest_schedules, ManifestSchedules, ProcessManifestSet,
+    ProcessingStatus, RawManifestTracker,
+};
+use crate::summary::{announce_manifests_ready, ManifestsReady};
+
+/// How far the registered manifests have progressed, as tracked by the [`ManifestLoadingDriver`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestReadiness {
+    /// Raw manifests are still being loaded.
+    #[default]
+    Loading,
+    /// Every raw manifest has loaded, and they are being processed.
+    Processing,
+    /// Every manifest has been processed, and is available as a resource.
+    Ready,
+    /// A required manifest failed to load or process.
+    Failed,
+}
+
+/// An event sent if a required manifest fails to load or process.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManifestsFailed;
+
+/// A plugin which loads and processes manifests, tracking progress in the [`ManifestReadiness`] resource rather than a [`State`].
+///
+/// Like the [`ManifestPlugin`](crate::plugin::ManifestPlugin), this plugin **must** be added before manifests are registered.
+#[derive(Debug, Clone)]
+pub struct ManifestLoadingDriver {
+    /// If true, [`Manifest::freeze`](crate::manifest::Manifest::freeze) will be called on each manifest once it has been processed.
+    ///
+    /// Defaults to `true`.
+    pub freeze_manifests: bool,
+    /// The schedules that the manifest loading and processing systems are run from.
+    ///
+    /// Defaults to [`ManifestSchedules::default`].
+    pub schedules: ManifestSchedules,
+}
+
+impl Default for ManifestLoadingDriver {
+    fn default() -> Self {
+        Self {
+            freeze_manifests: true,
+            schedules: ManifestSchedules::default(),
+        }
+    }
+}
+
+impl Plugin for ManifestLoadingDriver {
+    fn build(&self, app: &mut App) {
+        add_manifest_schedules(app, self.schedules);
+        let schedules = manifest_schedules(app);
+
+        app.init_resource::<RawManifestTracker>()
+            .init_resource::<ManifestReadiness>()
+            .insert_resource(self.schedules)
+            .add_event::<ManifestsReady>()
+            .add_event::<ManifestsFailed>()
+            .configure_sets(
+                schedules.processing,
+                ProcessManifestSet.run_if(manifests_processing),
+            )
+            .add_systems(
+                schedules.loading,
+                (
+                    advance_manifest_readiness.run_if(not(manifests_finished)),
+                    (record_loading_timeline, announce_manifests_ready)
+                        .run_if(manifests_ready.and_then(resource_changed::<ManifestReadiness>)),
+                )
+                    .chain(),
+            )
+            .add_systems(schedules.loading, dispatch_lifecycle_events);
+
+        app.world
+            .resource_mut::<RawManifestTracker>()
+            .set_freeze_manifests(self.freeze_manifests);
+    }
+}
+
+/// A run condition which returns true once every manifest has been processed.
+pub fn manifests_ready(readiness: Option<Res<ManifestReadiness>>) -> bool {
+    readiness.is_some_and(|readiness| *readiness == ManifestReadiness::Ready)
+}
+
+/// A run condition which returns true if a required manifest failed to load or process.
+pub fn manifests_failed(readiness: Option<Res<ManifestReadiness>>) -> bool {
+    readiness.is_some_and(|readiness| *readiness == ManifestReadiness::Failed)
+}
+
+/// A run condition which returns true while raw manifests are being processed.
+pub fn manifests_processing(readiness: Option<Res<ManifestReadiness>>) -> bool {
+    readiness.is_some_and(|readiness| *readiness == ManifestReadiness::Processing)
+}
+
+/// A run condition which returns true once loading has either succeeded or failed.
+pub fn manifests_finished(readiness: Option<Res<ManifestReadiness>>) -> bool {
+    readiness.is_some_and(|readiness| {
+        matches!(
+            *readiness,
+            ManifestReadiness::Ready | ManifestReadiness::Failed
+        )
+    })
+}
+
+/// Advances the [`ManifestReadiness`] resource as manifests load and are processed.
+///
+/// The [`ManifestsFailed`] event is sent if loading fails, while the [`ManifestsReady`] event is sent by [`announce_manifests_ready`].
+pub fn advance_manifest_readiness(
+    asset_server: Res<AssetServer>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    mut readiness: ResMut<ManifestReadiness>,
+    mut failed_events: EventWriter<ManifestsFailed>,
+) {
+    let next = match *readiness {
+        ManifestReadiness::Loading => {
+            if raw_manifest_tracker.any_manifests_failed(asset_server.as_ref()) {
+                error!("Some manifests failed to load.");
+                ManifestReadiness::Failed
+            } else if raw_manifest_tracker.all_manifests_loaded(asset_server.as_ref()) {
+                info!("All manifests have been loaded successfully.");
+                ManifestReadiness::Processing
+            } else {
+                return;
+            }
+        }
+        ManifestReadiness::Processing => match raw_manifest_tracker.processing_status() {
+            ProcessingStatus::Failed => {
+                error!("Some manifests failed during processing.");
+                ManifestReadiness::Failed
+            }
+            ProcessingStatus::Ready => {
+                info!("All manifests have been processed successfully.");
+                ManifestReadiness::Ready
+            }
+            _ => return,
+        },
+        ManifestReadiness::Ready | ManifestReadiness::Failed => return,
+    };
+
+    if next == ManifestReadiness::Failed {
+        failed_events.send(ManifestsFailed);
+    }
+    *readiness = next;
+}
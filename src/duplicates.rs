@@ -0,0 +1,208 @@
+//! Finding items with identical contents, which usually indicates a copy-paste mistake in a hand-authored manifest.
+//!
+//! Items usually store their own name, so two copy-pasted items still differ in that one field.
+//! [`find_duplicates_by_key`] therefore groups items by a hashable key, which should cover every field that matters
+//! *except* the name, and [`find_duplicate_raw_items`] does the same for raw items, before they are processed.
+//! [`find_duplicates`] compares whole items with [`PartialEq`] instead,
+//! which only finds duplicates when items do not store their name (or any other unique field).
+//!
+//! Adding a [`DuplicateDetectionPlugin`] for each manifest records the duplicates in the [`ValidationReport`],
+//! and logs a warning for each group:
+//!
+//! ```rust,ignore
+//! app.add_plugins(DuplicateDetectionPlugin::<ItemManifest>::by_key(|item: &Item| (item.damage, item.weight)));
+//!
+//! fn check_content(report: Res<ValidationReport>) {
+//!     assert!(report.is_empty(), "{}", *report);
+//! }
+//! ```
+
+use std::hash::Hash;
+use std::sync::Arc;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::log::warn;
+use bevy::utils::HashMap;
+
+use crate::identifier::Id;
+use crate::manifest::{IterableManifest, Manifest};
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+use crate::validation::ValidationReport;
+
+/// Returns each group of two or more items in the `manifest` which are equal to each other.
+///
+/// The [`Id`]s in each group are sorted, and the groups are sorted by their first [`Id`].
+///
+/// Items which store their name are never equal to each other, so are never reported:
+/// use [`find_duplicates_by_key`] with a key which leaves out the name for those.
+/// This compares every pair of distinct items, so it takes quadratic time in the worst case.
+#[must_use]
+pub fn find_duplicates<M>(manifest: &M) -> Vec<Vec<Id<M::Item>>>
+where
+    M: IterableManifest,
+    M::Item: PartialEq,
+{
+    // Each group starts with its first item, which the remaining items are compared against.
+    let mut groups: Vec<(&M::Item, Vec<Id<M::Item>>)> = Vec::new();
+    for (id, item) in manifest.iter() {
+        match groups.iter_mut().find(|(first, _)| *first == item) {
+            Some((_, ids)) => ids.push(id),
+            None => groups.push((item, vec![id])),
+        }
+    }
+
+    sorted_groups(groups.into_iter().map(|(_, ids)| ids))
+}
+
+/// Returns each group of two or more items in the `manifest` which have the same `key`.
+///
+/// The `key` should cover every field except the item's name, so that copy-pasted items which were only renamed are found.
+/// The [`Id`]s in each group are sorted, and the groups are sorted by their first [`Id`].
+#[must_use]
+pub fn find_duplicates_by_key<M, K>(
+    manifest: &M,
+    key: impl Fn(&M::Item) -> K,
+) -> Vec<Vec<Id<M::Item>>>
+where
+    M: IterableManifest,
+    K: Hash + Eq,
+{
+    let mut groups: HashMap<K, Vec<Id<M::Item>>> = HashMap::default();
+    for (id, item) in manifest.iter() {
+        groups.entry(key(item)).or_default().push(id);
+    }
+
+    sorted_groups(groups.into_values())
+}
+
+/// Returns the names of each group of two or more raw `items` which have the same `key`.
+///
+/// The `key` should cover every field except the item's name, which is returned by `name`.
+/// Groups keep the order of `items`, and are sorted by the position of their first item.
+#[must_use]
+pub fn find_duplicate_raw_items<R, K>(
+    items: &[R],
+    name: impl Fn(&R) -> &str,
+    key: impl Fn(&R) -> K,
+) -> Vec<Vec<String>>
+where
+    K: Hash + Eq,
+{
+    let mut indexes: HashMap<K, usize> = HashMap::default();
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    for item in items {
+        let index = *indexes.entry(key(item)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(name(item).to_string());
+    }
+
+    groups.retain(|names| names.len() > 1);
+    groups
+}
+
+/// Removes groups with a single member, and sorts the remaining groups.
+fn sorted_groups<T>(groups: impl Iterator<Item = Vec<Id<T>>>) -> Vec<Vec<Id<T>>> {
+    let mut groups: Vec<Vec<Id<T>>> = groups
+        .filter(|ids| ids.len() > 1)
+        .map(|mut ids| {
+            ids.sort();
+            ids
+        })
+        .collect();
+    groups.sort();
+    groups
+}
+
+/// A group of duplicated items in the same manifest, as part of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The names of the duplicated items, sorted by [`Id`].
+    ///
+    /// Items without a name in the [`NameRegistry`](crate::name_registry::NameRegistry) are listed by their [`Id`].
+    pub items: Vec<String>,
+}
+
+type FindDuplicates<M> = Arc<dyn Fn(&M) -> Vec<Vec<Id<<M as Manifest>::Item>>> + Send + Sync>;
+
+/// A plugin which records the duplicated items of the manifest `M` in the [`ValidationReport`].
+///
+/// Duplicates are found in the processing schedule whenever the manifest resource changes,
+/// and a warning is logged for each group.
+/// Use [`DuplicateDetectionPlugin::by_key`] for items which store their name,
+/// as the [`Default`] plugin compares whole items with [`find_duplicates`].
+pub struct DuplicateDetectionPlugin<M: IterableManifest> {
+    find: FindDuplicates<M>,
+}
+
+impl<M: IterableManifest> DuplicateDetectionPlugin<M> {
+    /// Creates a plugin which reports items with the same `key`, as found by [`find_duplicates_by_key`].
+    ///
+    /// The `key` should cover every field except the item's name.
+    #[must_use]
+    pub fn by_key<K: Hash + Eq + 'static>(
+        key: impl Fn(&M::Item) -> K + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            find: Arc::new(move |manifest: &M| find_duplicates_by_key(manifest, &key)),
+        }
+    }
+}
+
+impl<M> Default for DuplicateDetectionPlugin<M>
+where
+    M: IterableManifest,
+    M::Item: PartialEq,
+{
+    fn default() -> Self {
+        Self {
+            find: Arc::new(find_duplicates::<M>),
+        }
+    }
+}
+
+impl<M: IterableManifest> Plugin for DuplicateDetectionPlugin<M> {
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        let find = self.find.clone();
+        app.init_resource::<ValidationReport>().add_systems(
+            schedules.processing,
+            (move |manifest: Res<M>, mut report: ResMut<ValidationReport>| {
+                record_duplicate_groups::<M>(&mut report, &find(&*manifest));
+            })
+            .run_if(resource_exists::<M>.and_then(resource_changed::<M>))
+            .after(ProcessManifestSet),
+        );
+    }
+}
+
+/// Records the items of the manifest `M` with identical contents in the [`ValidationReport`], logging a warning for each group.
+///
+/// Items are compared with [`find_duplicates`].
+pub fn record_duplicate_items<M>(manifest: Res<M>, mut report: ResMut<ValidationReport>)
+where
+    M: IterableManifest,
+    M::Item: PartialEq,
+{
+    record_duplicate_groups::<M>(&mut report, &find_duplicates(&*manifest));
+}
+
+/// Replaces the duplicate groups of the manifest `M` in the `report`, logging a warning for each group.
+fn record_duplicate_groups<M: Manifest>(
+    report: &mut ValidationReport,
+    groups: &[Vec<Id<M::Item>>],
+) {
+    report.record_duplicates::<M>(groups);
+
+    for group in report.duplicates_of::<M>() {
+        warn!(
+            "The items {} in {} are duplicates: this may be a copy-paste mistake.",
+            group.items.join(", "),
+            group.type_name
+        );
+    }
+}
@@ -0,0 +1,534 @@
+//! An in-game editor for the items of a manifest, drawn with egui.
+//!
+//! Add a [`ManifestEditorPlugin`] for each manifest that should be editable.
+//! Each editor is an egui window, which can float freely or be docked to either side of the screen.
+//! From it, items can be created, edited field by field, checked against the manifest's [`LintRules`],
+//! and finally saved back to the raw manifest file they were loaded from:
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestPlugin::<SimpleAssetState>::default())
+//!     .register_manifest::<ItemManifest>("items.ron")
+//!     .add_plugins(ManifestEditorPlugin::<ItemManifest>::default());
+//! ```
+//!
+//! Editing relies on reflection, so the item type must implement [`Reflect`] and [`FromReflect`].
+//! New items are created from the [`ReflectDefault`] registered for the item type in the [`AppTypeRegistry`]:
+//! derive [`Default`], add `#[reflect(Default)]`, and register the type with [`App::register_type`].
+//! If the item has a `name: String` field, it is set to the name entered in the editor.
+//!
+//! Saving requires the manifest to implement [`WriteBackManifest`]: see the [`write_back`](crate::write_back) module.
+//! Frozen manifests reject modification, so the `editor` feature enables the `mutable` feature,
+//! which stops manifests from being frozen by default: see [`ManifestPlugin::freeze_manifests`](crate::plugin::ManifestPlugin::freeze_manifests).
+//!
+//! This module is only available when the `editor` feature is enabled, and is not available on the web.
+
+use std::any::{type_name, TypeId};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use bevy::app::{App, Plugin, Update};
+use bevy::asset::io::file::FileAssetReader;
+use bevy::ecs::prelude::*;
+use bevy::reflect::std_traits::ReflectDefault;
+use bevy::reflect::{FromReflect, Reflect, ReflectMut, TypeRegistry};
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use serde::Serialize;
+
+use crate::identifier::Id;
+use crate::lints::{LintConfig, LintRules};
+use crate::manifest::{IterableManifest, Manifest, MutableManifest};
+use crate::manifest_lock::ManifestLock;
+use crate::name_registry::NameRegistry;
+use crate::plugin::RawManifestTracker;
+use crate::write_back::{prepare_write_back, write_back_manifest, WriteBackManifest};
+
+/// Where a [`ManifestEditor`] window is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EditorDock {
+    /// A window which can be moved and resized freely.
+    #[default]
+    Floating,
+    /// A panel docked to the left edge of the screen.
+    Left,
+    /// A panel docked to the right edge of the screen.
+    Right,
+}
+
+/// The state of the editor window for the manifest `M`.
+///
+/// This resource is added by the [`ManifestEditorPlugin`].
+#[derive(Resource)]
+pub struct ManifestEditor<M: Manifest> {
+    /// If false, the editor is not drawn.
+    pub open: bool,
+    /// Where the editor is drawn.
+    pub dock: EditorDock,
+    /// The folder that raw manifest paths are relative to, used when saving.
+    ///
+    /// This should match the file path used by the [`AssetPlugin`](bevy::asset::AssetPlugin).
+    /// Defaults to the `assets` folder.
+    pub asset_dir: PathBuf,
+    selected: Option<Id<M::Item>>,
+    filter: String,
+    new_item_name: String,
+    messages: Vec<String>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestEditor<M> {
+    fn default() -> Self {
+        Self {
+            open: true,
+            dock: EditorDock::default(),
+            asset_dir: FileAssetReader::get_base_path().join("assets"),
+            selected: None,
+            filter: String::new(),
+            new_item_name: String::new(),
+            messages: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestEditor<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestEditor")
+            .field("open", &self.open)
+            .field("dock", &self.dock)
+            .field("asset_dir", &self.asset_dir)
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestEditor<M> {
+    /// The item currently being edited, if any.
+    #[must_use]
+    pub fn selected(&self) -> Option<Id<M::Item>> {
+        self.selected
+    }
+
+    /// Selects the item to edit.
+    pub fn select(&mut self, id: Option<Id<M::Item>>) {
+        self.selected = id;
+    }
+
+    /// The results of the last validation, save or modification, one line each.
+    #[must_use]
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
+
+/// A plugin which adds an egui editor window for the manifest `M`.
+///
+/// The [`EguiPlugin`] is added if it has not been already.
+/// See the [module docs](crate::editor) for the requirements on `M`.
+pub struct ManifestEditorPlugin<M: Manifest> {
+    open: bool,
+    dock: EditorDock,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestEditorPlugin<M> {
+    fn default() -> Self {
+        Self {
+            open: true,
+            dock: EditorDock::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> ManifestEditorPlugin<M> {
+    /// Sets whether the editor starts open.
+    ///
+    /// Defaults to `true`.
+    #[must_use]
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    /// Sets where the editor is initially drawn.
+    ///
+    /// Defaults to [`EditorDock::Floating`].
+    #[must_use]
+    pub fn dock(mut self, dock: EditorDock) -> Self {
+        self.dock = dock;
+        self
+    }
+}
+
+impl<M> Plugin for ManifestEditorPlugin<M>
+where
+    M: WriteBackManifest + MutableManifest + IterableManifest,
+    M::Item: FromReflect,
+    M::RawManifest: Serialize,
+{
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.insert_resource(ManifestEditor::<M> {
+            open: self.open,
+            dock: self.dock,
+            ..Default::default()
+        })
+        .add_systems(Update, manifest_editor_ui::<M>.run_if(resource_exists::<M>));
+    }
+}
+
+/// Draws the editor window for the manifest `M`, applying any changes made in it.
+///
+/// While the [`ManifestLock`] is locked, the items are shown but cannot be edited.
+#[allow(clippy::too_many_arguments)]
+pub fn manifest_editor_ui<M>(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<ManifestEditor<M>>,
+    mut manifest: ResMut<M>,
+    lock: Option<Res<ManifestLock>>,
+    type_registry: Res<AppTypeRegistry>,
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    lint_rules: Option<Res<LintRules<M>>>,
+    lint_config: Option<Res<LintConfig<M::Item>>>,
+) where
+    M: WriteBackManifest + MutableManifest + IterableManifest,
+    M::Item: FromReflect,
+    M::RawManifest: Serialize,
+{
+    if !editor.open {
+        return;
+    }
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let editor = &mut *editor;
+    let dock = editor.dock;
+    let locked = lock.as_deref().is_some_and(ManifestLock::is_locked);
+    let title = format!("{} editor", bevy::utils::get_short_name(type_name::<M>()));
+    let mut draw = |ui: &mut egui::Ui| {
+        // Drawing the manifest must not mark it as changed: only real edits should trigger change detection.
+        let changed = editor_contents(
+            ui,
+            editor,
+            manifest.bypass_change_detection(),
+            &type_registry.read(),
+            locked,
+        );
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Validate").clicked() {
+                let mut messages: Vec<String> =
+                    lint_rules.as_ref().map_or_else(Vec::new, |rules| {
+                        rules
+                            .check(&*manifest, lint_config.as_deref())
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect()
+                    });
+                if let Err(err) =
+                    prepare_write_back(&*manifest, &raw_manifest_tracker, &editor.asset_dir)
+                {
+                    messages.push(format!("The items cannot be saved: {err}"));
+                }
+                if messages.is_empty() {
+                    messages.push("No problems found.".to_string());
+                }
+                editor.messages = messages;
+            }
+
+            if ui.button("Save").clicked() {
+                editor.messages = vec![match write_back_manifest(
+                    &*manifest,
+                    &raw_manifest_tracker,
+                    &editor.asset_dir,
+                ) {
+                    Ok(path) => format!("Saved to {}.", path.display()),
+                    Err(err) => format!("Could not save: {err}"),
+                }];
+            }
+
+            egui::ComboBox::from_id_source("dock")
+                .selected_text(format!("{:?}", editor.dock))
+                .show_ui(ui, |ui| {
+                    for dock in [EditorDock::Floating, EditorDock::Left, EditorDock::Right] {
+                        ui.selectable_value(&mut editor.dock, dock, format!("{dock:?}"));
+                    }
+                });
+        });
+
+        for message in &editor.messages {
+            ui.label(message);
+        }
+
+        if changed {
+            manifest.set_changed();
+        }
+    };
+
+    match dock {
+        EditorDock::Floating => {
+            let mut open = true;
+            egui::Window::new(&title)
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| draw(ui));
+            editor.open = open;
+        }
+        EditorDock::Left => {
+            egui::SidePanel::left(title.clone()).show(ctx, |ui| draw(ui));
+        }
+        EditorDock::Right => {
+            egui::SidePanel::right(title.clone()).show(ctx, |ui| draw(ui));
+        }
+    }
+}
+
+/// Draws the item list, the fields of the selected item, and the controls for creating and removing items.
+///
+/// While `locked`, the items can be browsed but the editing controls are disabled.
+///
+/// Returns true if the manifest was modified.
+fn editor_contents<M>(
+    ui: &mut egui::Ui,
+    editor: &mut ManifestEditor<M>,
+    manifest: &mut M,
+    type_registry: &TypeRegistry,
+    locked: bool,
+) -> bool
+where
+    M: MutableManifest + IterableManifest,
+    M::Item: FromReflect,
+{
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Filter");
+        ui.text_edit_singleline(&mut editor.filter);
+    });
+
+    let mut items: Vec<(String, Id<M::Item>)> = manifest
+        .ids()
+        .map(|id| {
+            (
+                NameRegistry::name_of(id).unwrap_or_else(|| format!("{id:?}")),
+                id,
+            )
+        })
+        .filter(|(name, _)| name.contains(editor.filter.as_str()))
+        .collect();
+    items.sort();
+
+    egui::ScrollArea::vertical()
+        .id_source("items")
+        .max_height(160.0)
+        .show(ui, |ui| {
+            for (name, id) in items {
+                if ui
+                    .selectable_label(editor.selected == Some(id), name)
+                    .clicked()
+                {
+                    editor.selected = Some(id);
+                }
+            }
+        });
+
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut editor.new_item_name);
+        if ui
+            .add_enabled(!locked, egui::Button::new("Create"))
+            .clicked()
+        {
+            match create_item::<M>(type_registry, &editor.new_item_name) {
+                Ok(item) => {
+                    let id = NameRegistry::register(&editor.new_item_name);
+                    match manifest.insert_with_id(id, item) {
+                        Ok(()) => {
+                            editor.selected = Some(id);
+                            editor.new_item_name.clear();
+                            changed = true;
+                        }
+                        Err(err) => editor.messages = vec![format!("Could not create item: {err}")],
+                    }
+                }
+                Err(message) => editor.messages = vec![message],
+            }
+        }
+    });
+
+    ui.separator();
+
+    if locked {
+        ui.label("Manifests are locked and cannot be modified.");
+    }
+    let Some(id) = editor.selected else {
+        ui.label("Select an item to edit it.");
+        return changed;
+    };
+    let name = NameRegistry::name_of(id).unwrap_or_else(|| format!("{id:?}"));
+    ui.horizontal(|ui| {
+        ui.heading(&name);
+        if ui
+            .add_enabled(!locked, egui::Button::new("Remove"))
+            .clicked()
+        {
+            match manifest.remove(&id) {
+                Ok(_) => {
+                    editor.selected = None;
+                    changed = true;
+                }
+                Err(err) => editor.messages = vec![format!("Could not remove {name}: {err}")],
+            }
+        }
+    });
+
+    let Some(item) = editor.selected.and_then(|id| manifest.get_mut(id)) else {
+        ui.label("This item cannot be edited.");
+        return changed;
+    };
+    egui::ScrollArea::vertical()
+        .id_source("fields")
+        .show(ui, |ui| {
+            ui.add_enabled_ui(!locked, |ui| {
+                changed |= reflect_ui(ui, item.as_reflect_mut());
+            });
+        });
+
+    changed
+}
+
+/// Creates a new item from the [`ReflectDefault`] registered for the item type, setting its `name` field if it has one.
+fn create_item<M>(type_registry: &TypeRegistry, name: &str) -> Result<M::Item, String>
+where
+    M: Manifest,
+    M::Item: FromReflect,
+{
+    if name.is_empty() {
+        return Err("Enter a name for the new item.".to_string());
+    }
+
+    let default = type_registry
+        .get_type_data::<ReflectDefault>(TypeId::of::<M::Item>())
+        .ok_or_else(|| {
+            format!(
+                "{} must be registered with `#[reflect(Default)]` to create new items.",
+                type_name::<M::Item>()
+            )
+        })?
+        .default();
+    let mut item = M::Item::from_reflect(&*default)
+        .ok_or_else(|| format!("Could not create a default {}.", type_name::<M::Item>()))?;
+
+    if let ReflectMut::Struct(fields) = item.reflect_mut() {
+        if let Some(field) = fields
+            .field_mut("name")
+            .and_then(|field| field.downcast_mut::<String>())
+        {
+            *field = name.to_string();
+        }
+    }
+
+    Ok(item)
+}
+
+/// Draws widgets to edit the reflected `value`, returning true if it was changed.
+///
+/// Numbers, booleans and strings are edited directly.
+/// Structs, tuples, lists and enum variants are drawn as a collapsible list of their fields.
+/// Other values are shown, but cannot be edited.
+pub fn reflect_ui(ui: &mut egui::Ui, value: &mut dyn Reflect) -> bool {
+    macro_rules! drag_value {
+        ($($number:ty),*) => {
+            $(
+                if let Some(number) = value.downcast_mut::<$number>() {
+                    return ui.add(egui::DragValue::new(number)).changed();
+                }
+            )*
+        };
+    }
+    drag_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+    if let Some(boolean) = value.downcast_mut::<bool>() {
+        return ui.checkbox(boolean, "").changed();
+    }
+    if let Some(string) = value.downcast_mut::<String>() {
+        return ui.text_edit_singleline(string).changed();
+    }
+
+    let mut changed = false;
+    match value.reflect_mut() {
+        ReflectMut::Struct(fields) => {
+            for index in 0..fields.field_len() {
+                let name = fields.name_at(index).unwrap_or_default().to_string();
+                if let Some(field) = fields.field_at_mut(index) {
+                    changed |= field_ui(ui, index, &name, field);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(fields) => {
+            for index in 0..fields.field_len() {
+                if let Some(field) = fields.field_mut(index) {
+                    changed |= field_ui(ui, index, &index.to_string(), field);
+                }
+            }
+        }
+        ReflectMut::Tuple(fields) => {
+            for index in 0..fields.field_len() {
+                if let Some(field) = fields.field_mut(index) {
+                    changed |= field_ui(ui, index, &index.to_string(), field);
+                }
+            }
+        }
+        ReflectMut::List(elements) => {
+            for index in 0..elements.len() {
+                if let Some(element) = elements.get_mut(index) {
+                    changed |= field_ui(ui, index, &index.to_string(), element);
+                }
+            }
+        }
+        ReflectMut::Array(elements) => {
+            for index in 0..elements.len() {
+                if let Some(element) = elements.get_mut(index) {
+                    changed |= field_ui(ui, index, &index.to_string(), element);
+                }
+            }
+        }
+        ReflectMut::Enum(variant) => {
+            ui.label(variant.variant_name().to_string());
+            for index in 0..variant.field_len() {
+                let name = variant
+                    .name_at(index)
+                    .map_or_else(|| index.to_string(), ToString::to_string);
+                if let Some(field) = variant.field_at_mut(index) {
+                    changed |= field_ui(ui, index, &name, field);
+                }
+            }
+        }
+        ReflectMut::Map(_) | ReflectMut::Value(_) => {
+            ui.weak(value.reflect_short_type_path());
+        }
+    }
+    changed
+}
+
+/// Draws a single labelled field, collapsing fields which contain further fields.
+fn field_ui(ui: &mut egui::Ui, index: usize, label: &str, field: &mut dyn Reflect) -> bool {
+    let is_leaf = matches!(field.reflect_mut(), ReflectMut::Value(_));
+    if is_leaf {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            reflect_ui(ui, field)
+        })
+        .inner
+    } else {
+        egui::CollapsingHeader::new(label)
+            .id_source(index)
+            .show(ui, |ui| reflect_ui(ui, field))
+            .body_returned
+            .unwrap_or(false)
+    }
+}
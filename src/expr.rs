@@ -0,0 +1,521 @@
+//! Numeric fields that can be written as small arithmetic expressions.
+//!
+//! Game data is full of values derived from each other: a heavy sword does twice the base damage,
+//! and a boss has ten times the health of a regular monster.
+//! Rather than precomputing these by hand (and forgetting to update them), an [`Expr`] field can be either
+//! a plain number or a formula referring to named constants:
+//!
+//! ```ron
+//! (
+//!     name: "heavy_sword",
+//!     damage: "BASE_DAMAGE * 2",
+//!     weight: 6.0,
+//! )
+//! ```
+//!
+//! Expressions are parsed when the raw manifest is deserialized, so syntax errors are reported as loading errors.
+//! They are evaluated during processing, using the constants stored in the [`ExprConstants`] resource.
+//!
+//! The supported syntax is deliberately small: numbers, constants, `+`, `-`, `*`, `/`, `%` and parentheses.
+//!
+//! # Example
+//!
+//! ```
+//! use leafwing_manifest::expr::{Expr, ExprConstants, ExprError};
+//!
+//! let constants = ExprConstants::new().with("BASE_DAMAGE", 10.0);
+//!
+//! // Literals are used as-is.
+//! assert_eq!(Expr::literal(7_u32).evaluate(&constants), Ok(7));
+//!
+//! // Formulas can refer to constants, and follow the usual precedence rules.
+//! let damage = Expr::<u32>::parse("BASE_DAMAGE * 2 + 1").unwrap();
+//! assert_eq!(damage.evaluate(&constants), Ok(21));
+//! let damage = Expr::<u32>::parse("BASE_DAMAGE * (2 + 1)").unwrap();
+//! assert_eq!(damage.evaluate(&constants), Ok(30));
+//! assert_eq!(Expr::<f32>::parse("-7 % 4").unwrap().evaluate(&constants), Ok(-3.0));
+//!
+//! // Syntax errors are caught when parsing.
+//! assert!(matches!(Expr::<u32>::parse("2 * (3 + 4"), Err(ExprError::Syntax { .. })));
+//! assert!(matches!(Expr::<u32>::parse(&"-".repeat(1000)), Err(ExprError::Syntax { .. })));
+//!
+//! // Unknown constants and unrepresentable results are caught when evaluating.
+//! let health = Expr::<u32>::parse("BASE_HEALTH * 10").unwrap();
+//! assert_eq!(health.evaluate(&constants), Err(ExprError::UnknownConstant("BASE_HEALTH".to_string())));
+//! assert!(matches!(
+//!     Expr::<u8>::parse("BASE_DAMAGE / 3").unwrap().evaluate(&constants),
+//!     Err(ExprError::OutOfRange { .. })
+//! ));
+//! assert!(matches!(
+//!     Expr::<u64>::parse("18446744073709551616").unwrap().evaluate(&constants),
+//!     Err(ExprError::OutOfRange { .. })
+//! ));
+//! ```
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Named constants that can be referred to by [`Expr`] fields.
+///
+/// Insert this resource, and add your constants, before manifests are processed.
+/// Constants can be loaded from their own manifest, as long as it is processed first
+/// (see [`RegisterManifest::register_manifest_dependency`](crate::plugin::RegisterManifest::register_manifest_dependency)).
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ExprConstants {
+    constants: HashMap<String, f64>,
+}
+
+impl ExprConstants {
+    /// Creates an empty set of constants.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the constant `name`, returning the previous value if it existed.
+    pub fn insert(&mut self, name: impl Into<String>, value: f64) -> Option<f64> {
+        self.constants.insert(name.into(), value)
+    }
+
+    /// Sets the value of the constant `name`, returning `self` for chaining.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Returns the value of the constant `name`, if it exists.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.constants.get(name).copied()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, f64)> for ExprConstants {
+    fn from_iter<I: IntoIterator<Item = (S, f64)>>(iter: I) -> Self {
+        Self {
+            constants: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+        }
+    }
+}
+
+/// A numeric type that an [`Expr`] can evaluate to.
+///
+/// Expressions are evaluated using `f64` arithmetic, and then converted to this type.
+pub trait ExprValue: Copy {
+    /// Converts the result of evaluating an expression into this type.
+    ///
+    /// Returns [`None`] if the value cannot be represented, such as a fractional value for an integer type.
+    fn from_f64(value: f64) -> Option<Self>;
+
+    /// Converts a literal value of this type into an `f64`.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_expr_value_float {
+    ($($ty:ty),*) => {
+        $(
+            impl ExprValue for $ty {
+                fn from_f64(value: f64) -> Option<Self> {
+                    value.is_finite().then_some(value as $ty)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_expr_value_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ExprValue for $ty {
+                fn from_f64(value: f64) -> Option<Self> {
+                    // `MAX as f64` rounds up to the next power of two for 64-bit types, so the upper bound is exclusive.
+                    let in_range = value >= <$ty>::MIN as f64 && value < <$ty>::MAX as f64;
+                    (value.fract() == 0.0 && in_range).then_some(value as $ty)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_expr_value_float!(f32, f64);
+impl_expr_value_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Errors that can occur when parsing or evaluating an [`Expr`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ExprError {
+    /// The expression could not be parsed.
+    #[error("Could not parse the expression {expression:?} at position {position}: {message}")]
+    Syntax {
+        /// The text of the expression.
+        expression: String,
+        /// The byte offset at which parsing failed.
+        position: usize,
+        /// A description of the problem.
+        message: &'static str,
+    },
+    /// The expression refers to a constant that does not exist.
+    #[error("The constant {0} is not defined")]
+    UnknownConstant(String),
+    /// The result of the expression cannot be represented by the target type.
+    #[error("The expression {expression:?} evaluated to {value}, which is not a valid {target}")]
+    OutOfRange {
+        /// The text of the expression.
+        expression: String,
+        /// The computed value.
+        value: f64,
+        /// The name of the target type.
+        target: &'static str,
+    },
+}
+
+/// A numeric field which is either a literal value, or an arithmetic expression evaluated during processing.
+///
+/// This type deserializes from either a number (`10`) or a string containing an expression (`"BASE_DAMAGE * 2"`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr<T: ExprValue> {
+    kind: ExprKind<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprKind<T> {
+    Literal(T),
+    Formula {
+        source: Arc<str>,
+        ast: Arc<Node>,
+        _phantom: PhantomData<T>,
+    },
+}
+
+impl<T: ExprValue> Expr<T> {
+    /// Creates an expression with a fixed value.
+    #[must_use]
+    pub fn literal(value: T) -> Self {
+        Self {
+            kind: ExprKind::Literal(value),
+        }
+    }
+
+    /// Parses an arithmetic expression.
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let ast = Parser::new(source).parse()?;
+
+        Ok(Self {
+            kind: ExprKind::Formula {
+                source: source.into(),
+                ast: Arc::new(ast),
+                _phantom: PhantomData,
+            },
+        })
+    }
+
+    /// Returns the value of a literal expression, or [`None`] if this expression must be evaluated.
+    #[must_use]
+    pub fn as_literal(&self) -> Option<T> {
+        match &self.kind {
+            ExprKind::Literal(value) => Some(*value),
+            ExprKind::Formula { .. } => None,
+        }
+    }
+
+    /// Evaluates the expression using the supplied constants.
+    pub fn evaluate(&self, constants: &ExprConstants) -> Result<T, ExprError> {
+        match &self.kind {
+            ExprKind::Literal(value) => Ok(*value),
+            ExprKind::Formula { source, ast, .. } => {
+                let value = ast.evaluate(constants)?;
+                T::from_f64(value).ok_or_else(|| ExprError::OutOfRange {
+                    expression: source.to_string(),
+                    value,
+                    target: std::any::type_name::<T>(),
+                })
+            }
+        }
+    }
+
+    /// Evaluates the expression using the [`ExprConstants`] stored in the [`World`].
+    ///
+    /// If the resource does not exist, only expressions without constants can be evaluated.
+    pub fn evaluate_in(&self, world: &World) -> Result<T, ExprError> {
+        match world.get_resource::<ExprConstants>() {
+            Some(constants) => self.evaluate(constants),
+            None => self.evaluate(&ExprConstants::default()),
+        }
+    }
+}
+
+impl<T: ExprValue> From<T> for Expr<T> {
+    fn from(value: T) -> Self {
+        Expr::literal(value)
+    }
+}
+
+impl<T: ExprValue + Display> Display for Expr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ExprKind::Literal(value) => write!(f, "{value}"),
+            ExprKind::Formula { source, .. } => f.write_str(source),
+        }
+    }
+}
+
+impl<T: ExprValue + Serialize> Serialize for Expr<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.kind {
+            ExprKind::Literal(value) => value.serialize(serializer),
+            ExprKind::Formula { source, .. } => serializer.serialize_str(source),
+        }
+    }
+}
+
+impl<'de, T: ExprValue> Deserialize<'de> for Expr<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ExprVisitor<T>(PhantomData<T>);
+
+        impl<T: ExprValue> ExprVisitor<T> {
+            fn literal<E: serde::de::Error>(value: f64) -> Result<Expr<T>, E> {
+                T::from_f64(value).map(Expr::literal).ok_or_else(|| {
+                    E::custom(format!(
+                        "{value} is not a valid {}",
+                        std::any::type_name::<T>()
+                    ))
+                })
+            }
+        }
+
+        impl<'de, T: ExprValue> serde::de::Visitor<'de> for ExprVisitor<T> {
+            type Value = Expr<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a number, or a string containing an arithmetic expression")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Expr<T>, E> {
+                Self::literal(value as f64)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Expr<T>, E> {
+                Self::literal(value as f64)
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Expr<T>, E> {
+                Self::literal(value)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Expr<T>, E> {
+                Expr::parse(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(ExprVisitor(PhantomData))
+    }
+}
+
+/// A node in the syntax tree of a parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Constant(String),
+    Negate(Box<Node>),
+    Binary(Box<Node>, Operator, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Node {
+    fn evaluate(&self, constants: &ExprConstants) -> Result<f64, ExprError> {
+        Ok(match self {
+            Node::Number(value) => *value,
+            Node::Constant(name) => constants
+                .get(name)
+                .ok_or_else(|| ExprError::UnknownConstant(name.clone()))?,
+            Node::Negate(node) => -node.evaluate(constants)?,
+            Node::Binary(left, operator, right) => {
+                let left = left.evaluate(constants)?;
+                let right = right.evaluate(constants)?;
+                match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                    Operator::Remainder => left % right,
+                }
+            }
+        })
+    }
+}
+
+/// The maximum depth of the syntax tree of an expression.
+///
+/// Parsing and evaluation are both recursive, so this keeps malicious or malformed data from overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A recursive descent parser for arithmetic expressions, following the usual precedence rules.
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Node, ExprError> {
+        let node = self.parse_sum()?;
+        self.skip_whitespace();
+        if self.position < self.source.len() {
+            return Err(self.error("unexpected trailing characters"));
+        }
+        Ok(node)
+    }
+
+    fn error(&self, message: &'static str) -> ExprError {
+        ExprError::Syntax {
+            expression: self.source.to_string(),
+            position: self.position,
+            message,
+        }
+    }
+
+    /// Records that the syntax tree is one level deeper, failing if it is nested too deeply.
+    fn descend(&mut self) -> Result<(), ExprError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("the expression is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.source[self.position..].chars().next()
+    }
+
+    fn parse_sum(&mut self) -> Result<Node, ExprError> {
+        let depth = self.depth;
+        let mut node = self.parse_product()?;
+        loop {
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => break,
+            };
+            self.position += 1;
+            // Each operator nests the expression so far one level deeper.
+            self.descend()?;
+            node = Node::Binary(Box::new(node), operator, Box::new(self.parse_product()?));
+        }
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn parse_product(&mut self) -> Result<Node, ExprError> {
+        let depth = self.depth;
+        let mut node = self.parse_unary()?;
+        loop {
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                Some('%') => Operator::Remainder,
+                _ => break,
+            };
+            self.position += 1;
+            self.descend()?;
+            node = Node::Binary(Box::new(node), operator, Box::new(self.parse_unary()?));
+        }
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, ExprError> {
+        let depth = self.depth;
+        let node = match self.peek() {
+            Some('-') => {
+                self.position += 1;
+                self.descend()?;
+                Node::Negate(Box::new(self.parse_unary()?))
+            }
+            Some('+') => {
+                self.position += 1;
+                self.descend()?;
+                self.parse_unary()?
+            }
+            _ => self.parse_atom()?,
+        };
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ExprError> {
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                self.descend()?;
+                let node = self.parse_sum()?;
+                self.depth -= 1;
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected a closing parenthesis"));
+                }
+                self.position += 1;
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let text = self.take_while(|c| c.is_ascii_digit() || c == '.' || c == '_');
+                text.replace('_', "")
+                    .parse()
+                    .map(Node::Number)
+                    .map_err(|_| self.error("invalid number"))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Ok(Node::Constant(name.to_string()))
+            }
+            Some(_) => Err(self.error("expected a number, constant or parenthesis")),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.source[self.position..];
+        let length = rest
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(rest.len(), |(index, _)| index);
+        self.position += length;
+        &rest[..length]
+    }
+}
@@ -0,0 +1,109 @@
+//! Watching raw manifest files for changes, without running Bevy's asset server.
+//!
+//! Editor tools and other standalone programs built on the [`blocking`](crate::blocking) loader
+//! can use these functions to react when a manifest file is edited externally:
+//!
+//! ```rust,ignore
+//! let _watcher = watch_manifest::<ItemManifest>("assets/items.ron", |result| match result {
+//!     Ok(manifest) => println!("Reloaded {} items", manifest.item_count().unwrap_or_default()),
+//!     Err(err) => eprintln!("Could not reload items: {err}"),
+//! })?;
+//! ```
+//!
+//! Watching stops when the returned [`ManifestFileWatcher`] is dropped.
+//!
+//! Text editors often write a file in several steps, so a single save may be reported more than once.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::blocking::{load_manifest_blocking, BlockingLoadError};
+use crate::manifest::Manifest;
+
+/// A handle to a file being watched by [`watch_manifest_file`] or one of its variants.
+///
+/// The file is watched for as long as this handle is kept alive.
+pub struct ManifestFileWatcher {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+impl ManifestFileWatcher {
+    /// The canonical path of the watched file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl std::fmt::Debug for ManifestFileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestFileWatcher")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Calls `callback` with the canonical path of the file at `path` whenever it is created or modified.
+///
+/// The file must already exist.
+/// Its parent directory is watched rather than the file itself,
+/// so editors which save by replacing the file are handled correctly.
+/// The `callback` is called from a background thread.
+pub fn watch_manifest_file(
+    path: impl AsRef<Path>,
+    mut callback: impl FnMut(&Path) + Send + 'static,
+) -> Result<ManifestFileWatcher, notify::Error> {
+    let path = std::fs::canonicalize(path.as_ref())?;
+    let directory = path.parent().unwrap_or(&path).to_path_buf();
+
+    let watched = path.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        // Errors from the underlying platform watcher aren't actionable here, so they are skipped.
+        let Ok(event) = event else {
+            return;
+        };
+
+        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event.paths.contains(&watched)
+        {
+            callback(&watched);
+        }
+    })?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+    Ok(ManifestFileWatcher {
+        path,
+        _watcher: watcher,
+    })
+}
+
+/// Watches the file at `path` as in [`watch_manifest_file`], sending its canonical path on the returned channel whenever it changes.
+///
+/// This is convenient for tools with their own event loop, which can poll the channel with [`Receiver::try_recv`].
+pub fn watch_manifest_file_channel(
+    path: impl AsRef<Path>,
+) -> Result<(ManifestFileWatcher, Receiver<PathBuf>), notify::Error> {
+    let (sender, receiver) = channel();
+    let watcher = watch_manifest_file(path, move |changed| {
+        // The receiver may have been dropped, in which case nobody is listening.
+        let _ = sender.send(changed.to_path_buf());
+    })?;
+
+    Ok((watcher, receiver))
+}
+
+/// Watches the file at `path` as in [`watch_manifest_file`], reloading and processing the manifest `M`
+/// with [`load_manifest_blocking`] whenever it changes.
+///
+/// The result of each reload is passed to `callback`, from a background thread.
+pub fn watch_manifest<M: Manifest>(
+    path: impl AsRef<Path>,
+    mut callback: impl FnMut(Result<M, BlockingLoadError<M::ConversionError>>) + Send + 'static,
+) -> Result<ManifestFileWatcher, notify::Error> {
+    watch_manifest_file(path, move |changed| {
+        callback(load_manifest_blocking::<M>(changed));
+    })
+}
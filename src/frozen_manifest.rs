@@ -0,0 +1,153 @@
+//! An immutable, compact storage strategy for manifest items.
+//!
+//! Once a manifest has been processed, most games never modify it again.
+//! Hashmaps reserve extra capacity and scatter their entries through memory in order to support cheap insertions:
+//! if no further insertions are coming, a [`FrozenManifest`] stores the same data in two tightly packed sorted slices instead.
+
+use std::{fmt::Debug, hash::BuildHasher};
+
+use bevy::utils::hashbrown::HashMap;
+
+use crate::{dense_manifest::DenseManifest, identifier::Id};
+
+/// A read-only storage container for manifest items.
+///
+/// Items are sorted by their [`Id`], and looked up via binary search.
+/// No spare capacity is kept around, and the [`Id`]s are stored separately from the items,
+/// so lookups only need to touch a small, contiguous block of memory.
+///
+/// Once frozen, items cannot be added or removed.
+/// [`MutableManifest`](crate::manifest::MutableManifest) implementations backed by a [`FrozenManifest`]
+/// should return [`ManifestModificationError::Frozen`](crate::manifest::ManifestModificationError::Frozen) when asked to do so.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{
+///     frozen_manifest::FrozenManifest,
+///     identifier::{Id, IdMap},
+/// };
+///
+/// let mut names = IdMap::default();
+/// names.insert(Id::<String>::from_name("grass"), "Grass".to_string());
+/// names.insert(Id::from_name("water"), "Water".to_string());
+///
+/// let frozen = FrozenManifest::from(names);
+/// assert_eq!(frozen.get(Id::from_name("water")), Some(&"Water".to_string()));
+/// assert_eq!(frozen.get(Id::from_name("lava")), None);
+/// ```
+pub struct FrozenManifest<T> {
+    ids: Box<[Id<T>]>,
+    items: Box<[T]>,
+}
+
+impl<T> FrozenManifest<T> {
+    /// Gets an item by its [`Id`].
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.ids
+            .binary_search(&id)
+            .ok()
+            .map(|index| &self.items[index])
+    }
+
+    /// Returns true if an item with the given [`Id`] is stored.
+    #[must_use]
+    pub fn contains(&self, id: Id<T>) -> bool {
+        self.ids.binary_search(&id).is_ok()
+    }
+
+    /// Returns the number of stored items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns all stored items, sorted by their [`Id`].
+    #[must_use]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns the [`Id`]s of all stored items, in sorted order.
+    #[must_use]
+    pub fn ids(&self) -> &[Id<T>] {
+        &self.ids
+    }
+
+    /// Iterates over all stored items and their [`Id`]s, sorted by [`Id`].
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.ids.iter().copied().zip(self.items.iter())
+    }
+}
+
+impl<T> Default for FrozenManifest<T> {
+    fn default() -> Self {
+        FrozenManifest {
+            ids: Box::default(),
+            items: Box::default(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for FrozenManifest<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for FrozenManifest<T> {
+    fn clone(&self) -> Self {
+        FrozenManifest {
+            ids: self.ids.clone(),
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Id<T>, T)> for FrozenManifest<T> {
+    /// Collects and sorts the items.
+    ///
+    /// If the same [`Id`] appears multiple times, the last entry wins.
+    fn from_iter<I: IntoIterator<Item = (Id<T>, T)>>(iter: I) -> Self {
+        let mut entries: Vec<(Id<T>, T)> = iter.into_iter().collect();
+        // A stable sort keeps duplicates in insertion order, so we can keep the last of each run.
+        entries.sort_by_key(|(id, _)| *id);
+
+        let mut ids = Vec::with_capacity(entries.len());
+        let mut items = Vec::with_capacity(entries.len());
+        for (id, item) in entries {
+            if ids.last() == Some(&id) {
+                items.pop();
+                ids.pop();
+            }
+            ids.push(id);
+            items.push(item);
+        }
+
+        FrozenManifest {
+            ids: ids.into_boxed_slice(),
+            items: items.into_boxed_slice(),
+        }
+    }
+}
+
+impl<T, S: BuildHasher> From<HashMap<Id<T>, T, S>> for FrozenManifest<T> {
+    fn from(map: HashMap<Id<T>, T, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<T> From<DenseManifest<T>> for FrozenManifest<T> {
+    fn from(dense: DenseManifest<T>) -> Self {
+        dense.freeze()
+    }
+}
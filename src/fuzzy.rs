@@ -0,0 +1,128 @@
+//! Approximate lookups of items by name, for console commands, search boxes and error messages.
+//!
+//! [`Manifest::get_by_name`] only finds exact matches.
+//! The [`FuzzyLookup`] trait, implemented for every manifest, finds the closest names instead,
+//! scoring each name in the [`NameRegistry`] by its [Jaro-Winkler similarity](strsim::jaro_winkler) to the query:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     fuzzy::FuzzyLookup,
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     name_registry::NameRegistry,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item {
+//!     name: String,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! let items = ItemManifest {
+//!     items: ["sword", "shield", "potion", "short sword"]
+//!         .into_iter()
+//!         .map(|name| (NameRegistry::register(name), Item { name: name.to_string() }))
+//!         .collect(),
+//! };
+//! // Registered names of items which are not in the manifest are never suggested.
+//! NameRegistry::register::<Item>("swords");
+//!
+//! let suggestions: Vec<String> = items.suggest("swrd", 2).into_iter().map(|(name, _)| name).collect();
+//! assert_eq!(suggestions, ["sword", "shield"]);
+//!
+//! let (item, score) = items.get_by_name_fuzzy("Swrod").unwrap();
+//! assert_eq!(item.name, "sword");
+//! assert!(score > 0.9);
+//!
+//! assert_eq!(items.get_by_name_fuzzy("potion").unwrap().1, 1.0);
+//! assert!(items.get_by_name_fuzzy("xyz").is_none());
+//! ```
+//!
+//! Only names registered with the [`NameRegistry`] can be found, and only those of items which exist in the manifest are returned.
+//! Names are compared case-insensitively.
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+
+/// The minimum score for [`FuzzyLookup::get_by_name_fuzzy`] to return a match.
+///
+/// Scores range from 0.0 (nothing in common) to 1.0 (identical, ignoring case).
+pub const FUZZY_MATCH_THRESHOLD: f32 = 0.8;
+
+/// Scores how similar `candidate` is to the `query`, from 0.0 to 1.0.
+///
+/// The Jaro-Winkler similarity favors shared prefixes, which matches how partially-typed names look.
+#[must_use]
+pub fn similarity(query: &str, candidate: &str) -> f32 {
+    strsim::jaro_winkler(&query.to_lowercase(), &candidate.to_lowercase()) as f32
+}
+
+/// Approximate name lookups, implemented for all [`Manifest`]s.
+pub trait FuzzyLookup: Manifest {
+    /// Returns the item whose name is most similar to `name`, along with its [`similarity`] score.
+    ///
+    /// Exact matches are returned with a score of 1.0.
+    /// Returns [`None`] if no item scores at least [`FUZZY_MATCH_THRESHOLD`].
+    #[must_use]
+    fn get_by_name_fuzzy(&self, name: &str) -> Option<(&Self::Item, f32)> {
+        if let Some(item) = self.get(Id::from_name(name)) {
+            return Some((item, 1.0));
+        }
+
+        let (best, score) = self.suggest(name, 1).into_iter().next()?;
+        if score < FUZZY_MATCH_THRESHOLD {
+            return None;
+        }
+
+        self.get(Id::from_name(&best)).map(|item| (item, score))
+    }
+
+    /// Returns up to `n` names of items in this manifest which are most similar to `name`, with their [`similarity`] scores.
+    ///
+    /// Suggestions are sorted from most to least similar, with ties broken alphabetically.
+    #[must_use]
+    fn suggest(&self, name: &str, n: usize) -> Vec<(String, f32)> {
+        let mut suggestions: Vec<(String, f32)> = NameRegistry::names()
+            .into_iter()
+            .filter(|candidate| self.get(Id::from_name(candidate)).is_some())
+            .map(|candidate| {
+                let score = similarity(name, &candidate);
+                (candidate, score)
+            })
+            .collect();
+
+        suggestions
+            .sort_by(|(a_name, a), (b_name, b)| b.total_cmp(a).then_with(|| a_name.cmp(b_name)));
+        suggestions.truncate(n);
+        suggestions
+    }
+}
+
+impl<M: Manifest> FuzzyLookup for M {}
@@ -0,0 +1,314 @@
+//! Undo and redo for runtime changes to a [`MutableManifest`].
+//!
+//! A [`ManifestHistory`] stores each committed [`ManifestTransaction`] as a single step,
+//! recording the state of every item it touched before and after the change.
+//! Undoing a step restores the earlier states; redoing it re-applies the later ones.
+//!
+//! ```rust,ignore
+//! app.init_resource::<ManifestHistory<ItemManifest>>();
+//!
+//! // Transactions committed in the world are recorded automatically.
+//! ManifestTransaction::<ItemManifest>::new()
+//!     .update(SWORD, |sword| sword.damage += 1)
+//!     .commit_in_world(world)?;
+//!
+//! world.resource_scope(|world, mut history: Mut<ManifestHistory<ItemManifest>>| {
+//!     history.undo(&mut world.resource_mut::<ItemManifest>())
+//! })?;
+//! ```
+//!
+//! Outside of the world, use [`ManifestHistory::commit`], which commits a transaction and records it in one call.
+//! Single changes can be recorded by wrapping them in a transaction with one operation.
+//! Changes made directly through [`MutableManifest`] are not recorded: if they touch the same items,
+//! undoing an earlier step will overwrite them.
+
+use bevy::ecs::prelude::*;
+
+use crate::identifier::Id;
+use crate::manifest::{Manifest, ManifestModificationError, MutableManifest};
+use crate::transaction::{ManifestTransaction, TransactionError, TransactionSummary};
+
+/// A single undoable change, as recorded by a [`ManifestHistory`].
+///
+/// Each touched item is stored once, with its state before and after the change.
+/// A state of [`None`] means that the item did not exist.
+pub struct HistoryStep<M: Manifest> {
+    changes: Vec<(Id<M::Item>, Option<M::Item>, Option<M::Item>)>,
+}
+
+impl<M: Manifest> HistoryStep<M> {
+    /// Creates a step from the state of each touched item before and after the change.
+    #[must_use]
+    pub fn new(changes: Vec<(Id<M::Item>, Option<M::Item>, Option<M::Item>)>) -> Self {
+        Self { changes }
+    }
+
+    /// The identifiers of the items that this step changed.
+    pub fn ids(&self) -> impl Iterator<Item = Id<M::Item>> + '_ {
+        self.changes.iter().map(|(id, _, _)| *id)
+    }
+
+    /// The number of items that this step changed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns true if this step did not change any items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<M: Manifest> Clone for HistoryStep<M>
+where
+    M::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            changes: self.changes.clone(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for HistoryStep<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryStep")
+            .field("ids", &self.ids().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The undo and redo stacks for the manifest `M`.
+///
+/// See the [module docs](crate::history) for more information.
+///
+/// # Example
+///
+/// ```
+/// # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+/// use leafwing_manifest::{
+///     history::ManifestHistory, identifier::Id, manifest::Manifest,
+///     transaction::ManifestTransaction,
+/// };
+///
+/// let sword = Id::<Item>::from_name("sword");
+/// let axe = Id::<Item>::from_name("axe");
+///
+/// let mut manifest = ItemManifest::from_items([Item::new("sword", 10)]);
+///
+/// let mut history = ManifestHistory::<ItemManifest>::new();
+/// history
+///     .commit(
+///         &mut manifest,
+///         ManifestTransaction::new()
+///             .update(sword, |sword: &mut Item| sword.value = 15)
+///             .insert(Item::new("axe", 12)),
+///     )
+///     .unwrap();
+/// assert_eq!(manifest.get(sword).unwrap().value, 15);
+/// assert!(manifest.get(axe).is_some());
+///
+/// // Undoing reverts every change made by the transaction at once.
+/// history.undo(&mut manifest).unwrap();
+/// assert_eq!(manifest.get(sword).unwrap().value, 10);
+/// assert!(manifest.get(axe).is_none());
+/// assert!(!history.can_undo());
+/// assert_eq!(history.redo_len(), 1);
+///
+/// history.redo(&mut manifest).unwrap();
+/// assert_eq!(manifest.get(sword).unwrap().value, 15);
+/// assert!(manifest.get(axe).is_some());
+///
+/// // Recording a new step discards the steps that could be redone.
+/// history.undo(&mut manifest).unwrap();
+/// history
+///     .commit(&mut manifest, ManifestTransaction::new().remove(sword))
+///     .unwrap();
+/// assert!(!history.can_redo());
+/// assert!(history.redo(&mut manifest).unwrap().is_none());
+/// ```
+#[derive(Resource)]
+pub struct ManifestHistory<M: Manifest> {
+    undo: Vec<HistoryStep<M>>,
+    redo: Vec<HistoryStep<M>>,
+    limit: Option<usize>,
+}
+
+impl<M: Manifest> Default for ManifestHistory<M> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: None,
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestHistory<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestHistory")
+            .field("undo", &self.undo.len())
+            .field("redo", &self.redo.len())
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestHistory<M> {
+    /// Creates an empty history, which stores an unlimited number of steps.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty history, which discards the oldest steps once more than `limit` can be undone.
+    #[must_use]
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Records a step that has already been applied to the manifest.
+    ///
+    /// This clears the redo stack. Empty steps are ignored.
+    pub fn record(&mut self, step: HistoryStep<M>) {
+        if step.is_empty() {
+            return;
+        }
+
+        self.redo.clear();
+        self.undo.push(step);
+        if let Some(limit) = self.limit {
+            let excess = self.undo.len().saturating_sub(limit);
+            self.undo.drain(..excess);
+        }
+    }
+
+    /// Returns true if there is a step that can be undone.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns true if there is a step that can be redone.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// The number of steps that can be undone.
+    #[must_use]
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// The number of steps that can be redone.
+    #[must_use]
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Discards every recorded step.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<M: MutableManifest> ManifestHistory<M>
+where
+    M::Item: Clone,
+{
+    /// Commits the `transaction` to the `manifest`, and records it as a single step if it succeeds.
+    pub fn commit(
+        &mut self,
+        manifest: &mut M,
+        transaction: ManifestTransaction<M>,
+    ) -> Result<TransactionSummary<M>, TransactionError<M>> {
+        let (summary, step) = transaction.commit_with_step(manifest)?;
+        self.record(step);
+        Ok(summary)
+    }
+
+    /// Reverts the most recent step, moving it onto the redo stack.
+    ///
+    /// Returns the identifiers of the items that were changed, or [`None`] if there was nothing to undo.
+    /// If the manifest cannot be modified, the step is kept and the error is returned.
+    pub fn undo(
+        &mut self,
+        manifest: &mut M,
+    ) -> Result<Option<Vec<Id<M::Item>>>, ManifestModificationError<M>> {
+        let Some(step) = self.undo.pop() else {
+            return Ok(None);
+        };
+
+        let result = restore(manifest, &step, |(_, before, _)| before);
+        let ids = step.ids().collect();
+        match result {
+            Ok(()) => {
+                self.redo.push(step);
+                Ok(Some(ids))
+            }
+            Err(error) => {
+                self.undo.push(step);
+                Err(error)
+            }
+        }
+    }
+
+    /// Re-applies the most recently undone step, moving it back onto the undo stack.
+    ///
+    /// Returns the identifiers of the items that were changed, or [`None`] if there was nothing to redo.
+    /// If the manifest cannot be modified, the step is kept and the error is returned.
+    pub fn redo(
+        &mut self,
+        manifest: &mut M,
+    ) -> Result<Option<Vec<Id<M::Item>>>, ManifestModificationError<M>> {
+        let Some(step) = self.redo.pop() else {
+            return Ok(None);
+        };
+
+        let result = restore(manifest, &step, |(_, _, after)| after);
+        let ids = step.ids().collect();
+        match result {
+            Ok(()) => {
+                self.undo.push(step);
+                Ok(Some(ids))
+            }
+            Err(error) => {
+                self.redo.push(step);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Sets each item touched by the `step` to the state chosen by `state`.
+fn restore<M: MutableManifest>(
+    manifest: &mut M,
+    step: &HistoryStep<M>,
+    state: impl Fn(&(Id<M::Item>, Option<M::Item>, Option<M::Item>)) -> &Option<M::Item>,
+) -> Result<(), ManifestModificationError<M>>
+where
+    M::Item: Clone,
+{
+    for change in &step.changes {
+        let id = change.0;
+        match state(change) {
+            Some(item) => {
+                manifest.upsert(id, item.clone())?;
+            }
+            None => {
+                if manifest.get(id).is_some() {
+                    manifest.remove(&id)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
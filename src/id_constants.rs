@@ -0,0 +1,304 @@
+//! Tools for keeping compile-time [`Id`] constants in sync with the data on disk.
+//!
+//! Constants like `const SWORD: Id<Item> = Id::from_name("sword")` are fast and convenient,
+//! but nothing checks that `"sword"` is still in the manifest after the data files are edited.
+//! The [`manifest_ids!`](crate::manifest_ids) macro declares a set of constants together with a list of their names,
+//! which can then be checked against the loaded manifest with [`assert_ids_exist`]:
+//!
+//! ```rust
+//! use leafwing_manifest::identifier::Id;
+//! use leafwing_manifest::manifest_ids;
+//!
+//! struct Item;
+//!
+//! manifest_ids! {
+//!     /// Every item referred to directly in code.
+//!     pub ITEM_IDS: Item {
+//!         SWORD = "sword",
+//!         SHIELD = "shield",
+//!     }
+//! }
+//!
+//! assert_eq!(SWORD, Id::from_name("sword"));
+//! assert_eq!(ITEM_IDS, &[("sword", SWORD), ("shield", SHIELD)]);
+//! ```
+//!
+//! Checking the constants once manifests are ready catches any drift on the first run:
+//!
+//! ```rust,ignore
+//! app.add_systems(OnEnter(SimpleAssetState::Ready), |items: Res<ItemManifest>| {
+//!     assert_ids_exist(&*items, ITEM_IDS);
+//! });
+//! ```
+//!
+//! Alternatively, the constants can be generated from the data files themselves with an [`IdConstantsGenerator`],
+//! typically from a `build.rs` script or a test:
+//!
+//! ```rust,ignore
+//! IdConstantsGenerator::from_raw_manifest_file::<ItemManifest>("assets/items.ron", "Item", |raw| {
+//!     raw.items.iter().map(|item| item.name.clone()).collect()
+//! })?
+//! .write_to("src/item_ids.rs")?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+
+/// Declares a set of [`Id`] constants, along with a constant list of their names and values.
+///
+/// See the [module docs](crate::id_constants) for more information.
+#[macro_export]
+macro_rules! manifest_ids {
+    (
+        $(#[$list_meta:meta])*
+        $list_vis:vis $list:ident : $item:ty {
+            $($(#[$meta:meta])* $name:ident = $value:literal),* $(,)?
+        }
+    ) => {
+        $(
+            $(#[$meta])*
+            $list_vis const $name: $crate::identifier::Id<$item> = $crate::identifier::Id::from_name($value);
+        )*
+
+        $(#[$list_meta])*
+        $list_vis const $list: &[(&str, $crate::identifier::Id<$item>)] = &[$(($value, $name)),*];
+    };
+}
+
+/// Returns the names of any of the `ids` which are not found in the `manifest`.
+#[must_use]
+pub fn missing_ids<M: Manifest>(
+    manifest: &M,
+    ids: &[(&'static str, Id<M::Item>)],
+) -> Vec<&'static str> {
+    ids.iter()
+        .filter(|(_, id)| manifest.get(*id).is_none())
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Panics if any of the `ids` are not found in the `manifest`, listing every missing name.
+///
+/// This is intended to be run once manifests are ready, typically upon entering [`AssetLoadingState::READY`](crate::asset_state::AssetLoadingState::READY).
+pub fn assert_ids_exist<M: Manifest>(manifest: &M, ids: &[(&'static str, Id<M::Item>)]) {
+    let missing = missing_ids(manifest, ids);
+    assert!(
+        missing.is_empty(),
+        "The following names are used as Id constants, but were not found in the {} manifest: {}",
+        std::any::type_name::<M>(),
+        missing.join(", ")
+    );
+}
+
+/// An error that prevents an [`IdConstantsGenerator`] from generating valid source code.
+#[derive(Debug, Error)]
+pub enum IdConstantsError {
+    /// Several distinct names would generate the same constant.
+    #[error("The names {names:?} would all generate the constant {constant}.")]
+    Collision {
+        /// The constant name shared by the `names`.
+        constant: String,
+        /// The clashing names from the manifest.
+        names: Vec<String>,
+    },
+    /// A name would generate the same constant as the list of constants.
+    #[error("The name {name:?} would generate the constant {list_name}, which is also the name of the list of constants.")]
+    ListNameCollision {
+        /// The clashing name from the manifest.
+        name: String,
+        /// The name of the generated list of constants.
+        list_name: String,
+    },
+    /// A name does not contain any ASCII letters or digits, so no valid constant can be generated for it.
+    #[error("The name {name:?} cannot be converted into a valid constant name.")]
+    InvalidName {
+        /// The offending name from the manifest.
+        name: String,
+    },
+    /// The generated module could not be written.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Generates the source code of a Rust module containing an [`Id`] constant for each name in a manifest.
+///
+/// The generated module uses the [`manifest_ids!`](crate::manifest_ids) macro,
+/// so the list of constants can still be checked with [`assert_ids_exist`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdConstantsGenerator {
+    item_type: String,
+    list_name: String,
+    names: Vec<String>,
+}
+
+impl IdConstantsGenerator {
+    /// Creates a new generator for constants of type `Id<item_type>`.
+    ///
+    /// The `item_type` must be a path to the item type that is valid in the generated module, such as `crate::items::Item`.
+    #[must_use]
+    pub fn new(item_type: impl Into<String>) -> Self {
+        let item_type = item_type.into();
+        let short_name = item_type.rsplit("::").next().unwrap_or(&item_type);
+        let list_name = format!("{}_IDS", constant_name(short_name));
+
+        Self {
+            item_type,
+            list_name,
+            names: Vec::new(),
+        }
+    }
+
+    /// Reads the raw manifest for `M` from the file at `path`, and adds the names returned by `names`.
+    ///
+    /// Like [`load_raw_manifest_blocking`](crate::blocking::load_raw_manifest_blocking), the path is relative to the current working directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_raw_manifest_file<M: Manifest>(
+        path: impl AsRef<Path>,
+        item_type: impl Into<String>,
+        names: impl FnOnce(&M::RawManifest) -> Vec<String>,
+    ) -> Result<Self, crate::loader::ManifestLoaderError> {
+        let raw_manifest = crate::blocking::load_raw_manifest_blocking::<M>(path)?;
+        Ok(Self::new(item_type).with_names(names(&raw_manifest)))
+    }
+
+    /// Sets the name of the generated list of constants.
+    ///
+    /// Defaults to the item type's name in `SCREAMING_SNAKE_CASE`, followed by `_IDS`.
+    #[must_use]
+    pub fn with_list_name(mut self, list_name: impl Into<String>) -> Self {
+        self.list_name = list_name.into();
+        self
+    }
+
+    /// Adds the supplied names, each of which becomes a constant.
+    #[must_use]
+    pub fn with_names(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Generates the source code of the module.
+    ///
+    /// Names are sorted and deduplicated, so the output is stable across runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if distinct names would generate the same constant,
+    /// or if a name has no letters or digits to build a constant from.
+    ///
+    /// ```rust
+    /// use leafwing_manifest::id_constants::{IdConstantsError, IdConstantsGenerator};
+    ///
+    /// let source = IdConstantsGenerator::new("Item").with_names(["sword", "shield"]).generate().unwrap();
+    /// assert!(source.contains(r#"SWORD = "sword","#));
+    ///
+    /// let clashing = IdConstantsGenerator::new("Item").with_names(["iron sword", "iron-sword", "IronSword"]);
+    /// let Err(IdConstantsError::Collision { constant, names }) = clashing.generate() else {
+    ///     panic!("The names should clash.");
+    /// };
+    /// assert_eq!(constant, "IRON_SWORD");
+    /// assert_eq!(names, ["IronSword", "iron sword", "iron-sword"]);
+    ///
+    /// let invalid = IdConstantsGenerator::new("Item").with_names(["_"]);
+    /// assert!(matches!(invalid.generate(), Err(IdConstantsError::InvalidName { .. })));
+    /// ```
+    pub fn generate(&self) -> Result<String, IdConstantsError> {
+        let mut names = self.names.clone();
+        names.sort();
+        names.dedup();
+
+        let mut constants: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for name in &names {
+            let constant = constant_name(name);
+            if constant.chars().all(|c| c == '_') {
+                return Err(IdConstantsError::InvalidName { name: name.clone() });
+            }
+            if constant == self.list_name {
+                return Err(IdConstantsError::ListNameCollision {
+                    name: name.clone(),
+                    list_name: self.list_name.clone(),
+                });
+            }
+            constants.entry(constant).or_default().push(name.clone());
+        }
+
+        if let Some((constant, names)) = constants.into_iter().find(|(_, names)| names.len() > 1) {
+            return Err(IdConstantsError::Collision { constant, names });
+        }
+
+        let mut source = String::new();
+        source.push_str(
+            "// This file was generated by leafwing_manifest. Do not edit it by hand.\n\n",
+        );
+        source.push_str("leafwing_manifest::manifest_ids! {\n");
+        let _ = writeln!(source, "    pub {}: {} {{", self.list_name, self.item_type);
+        for name in &names {
+            let _ = writeln!(source, "        {} = {:?},", constant_name(name), name);
+        }
+        source.push_str("    }\n}\n");
+        Ok(source)
+    }
+
+    /// Generates the module, and writes it to the file at `path`.
+    ///
+    /// The file is only written if its contents have changed, to avoid triggering unnecessary rebuilds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module could not be [generated](Self::generate) or written.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), IdConstantsError> {
+        let path = path.as_ref();
+        let source = self.generate()?;
+        if std::fs::read_to_string(path).is_ok_and(|existing| existing == source) {
+            return Ok(());
+        }
+
+        std::fs::write(path, source)?;
+        Ok(())
+    }
+}
+
+/// Converts a name from a manifest into a valid `SCREAMING_SNAKE_CASE` constant name.
+///
+/// Any characters which are not ASCII letters or digits become underscores.
+/// As a result, distinct names can share a constant name, and names without any letters or digits become `_`:
+/// [`IdConstantsGenerator::generate`] rejects both.
+///
+/// ```rust
+/// use leafwing_manifest::id_constants::constant_name;
+///
+/// assert_eq!(constant_name("iron sword"), "IRON_SWORD");
+/// assert_eq!(constant_name("FireBall"), "FIRE_BALL");
+/// assert_eq!(constant_name("2h-axe"), "_2H_AXE");
+/// ```
+#[must_use]
+pub fn constant_name(name: &str) -> String {
+    let mut constant = String::with_capacity(name.len());
+    let mut previous_lowercase = false;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lowercase {
+                constant.push('_');
+            }
+            previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+            constant.push(c.to_ascii_uppercase());
+        } else {
+            if !constant.ends_with('_') {
+                constant.push('_');
+            }
+            previous_lowercase = false;
+        }
+    }
+
+    if constant.is_empty() || constant.starts_with(|c: char| c.is_ascii_digit()) {
+        constant.insert(0, '_');
+    }
+    constant
+}
@@ -3,9 +3,20 @@
 //! This can be constructed from a string-based identifier, stored in the human-readable files,
 //! that marks entries as e.g. "grass" or "hammer".
 
-use bevy::{prelude::Component, reflect::Reflect};
+use bevy::{
+    prelude::Component,
+    reflect::Reflect,
+    utils::{get_short_name, hashbrown},
+};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{
+    any::type_name,
+    fmt::{Debug, Display},
+    hash::{BuildHasherDefault, Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::name_registry::{NameRegistry, RAW_ID_PREFIX};
 
 /// The unique identifier of type `T`.
 ///
@@ -25,9 +36,11 @@ pub struct Id<T> {
     value: u64,
 
     /// Marker to make the compiler happy
+    ///
+    /// Using `fn() -> T` keeps this type [`Send`] and [`Sync`], regardless of `T`.
     #[reflect(ignore)]
     #[serde(skip)]
-    _phantom: PhantomData<T>,
+    _phantom: PhantomData<fn() -> T>,
 }
 
 /// A constant used in the hashing algorithm of the IDs.
@@ -113,11 +126,109 @@ impl<T> Id<T> {
             _phantom: PhantomData,
         }
     }
+
+    /// Returns the underlying [`u64`] value of the ID.
+    ///
+    /// This is the same as [`Id::raw`], and is the value that should be stored when persisting IDs,
+    /// such as in save files or network messages.
+    /// Use [`Id::from_raw`] to reconstruct the ID.
+    #[must_use]
+    pub const fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Reinterprets this ID as an ID for a different type of object, preserving the underlying value.
+    ///
+    /// This is useful when the same name is used to identify related objects across manifests,
+    /// such as an item and the recipe that produces it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use leafwing_manifest::identifier::Id;
+    ///
+    /// struct Item;
+    /// struct Recipe;
+    ///
+    /// let sword: Id<Item> = Id::from_name("sword");
+    /// let sword_recipe: Id<Recipe> = sword.cast();
+    ///
+    /// assert_eq!(sword_recipe, Id::from_name("sword"));
+    /// ```
+    #[must_use]
+    pub const fn cast<U>(self) -> Id<U> {
+        Id::from_raw(self.value)
+    }
+
+    /// Deterministically derives a new ID from two existing IDs.
+    ///
+    /// This is useful for identifying composite objects, such as an item with a particular enchantment,
+    /// without needing to construct and hash a combined name.
+    ///
+    /// The result depends on the order of the arguments: `combine(a, b)` is generally not equal to `combine(b, a)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use leafwing_manifest::identifier::Id;
+    ///
+    /// struct Item;
+    /// struct Enchantment;
+    /// struct EnchantedItem;
+    ///
+    /// const SWORD: Id<Item> = Id::from_name("sword");
+    /// const FIRE: Id<Enchantment> = Id::from_name("fire");
+    /// const FIRE_SWORD: Id<EnchantedItem> = Id::combine(SWORD, FIRE);
+    ///
+    /// assert_eq!(FIRE_SWORD, Id::combine(SWORD, FIRE));
+    /// assert!(FIRE_SWORD != Id::combine(FIRE, SWORD));
+    /// ```
+    #[must_use]
+    pub const fn combine<A, B>(a: Id<A>, b: Id<B>) -> Self {
+        // Adapted from the 64-bit variant of Boost's `hash_combine`.
+        let seed = a.value;
+        let value = seed
+            ^ (b.value
+                .wrapping_add(0x9E37_79B9_7F4A_7C15)
+                .wrapping_add(seed << 12)
+                .wrapping_add(seed >> 4));
+
+        Id::from_raw(value)
+    }
 }
 
 impl<T> Debug for Id<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Id").field("value", &self.value).finish()
+        f.debug_struct(&format!("Id<{}>", get_short_name(type_name::<T>())))
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// Displays the [`Id`] in a compact form, including the name it was created from if it is known.
+///
+/// Names are looked up in the [`NameRegistry`](crate::name_registry::NameRegistry).
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{identifier::Id, name_registry::NameRegistry};
+///
+/// struct Item;
+///
+/// let sword: Id<Item> = NameRegistry::register("sword");
+/// assert_eq!(sword.to_string(), format!("Id<Item>(sword #0x{:x})", sword.raw()));
+///
+/// let shield: Id<Item> = Id::from_name("shield");
+/// assert_eq!(shield.to_string(), format!("Id<Item>(#0x{:x})", shield.raw()));
+/// ```
+impl<T> Display for Id<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Id<{}>(", get_short_name(type_name::<T>()))?;
+        if let Some(name) = NameRegistry::name_of(*self) {
+            write!(f, "{name} ")?;
+        }
+        write!(f, "{RAW_ID_PREFIX}{:x})", self.value)
     }
 }
 
@@ -154,3 +265,49 @@ impl<T> Clone for Id<T> {
 }
 
 impl<T> Copy for Id<T> {}
+
+/// A [`Hasher`] specialized for [`Id`] keys.
+///
+/// [`Id`]s are already hashes, so running them through a general-purpose hash function like `ahash` is wasted work.
+/// However, [`Id::from_name`] produces values smaller than 2^30, and `hashbrown` relies on the high bits of a hash
+/// to quickly filter candidate entries.
+/// As a result, this hasher spreads the bits of the value with a single multiplication, rather than using it directly.
+///
+/// This hasher is only intended for use with [`Id`] keys: other types will fall back to a slow byte-wise hash.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdHasher {
+    hash: u64,
+}
+
+/// A large odd constant derived from the golden ratio, used for Fibonacci hashing.
+const ID_HASHER_MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl Hasher for IdHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash = (self.hash.rotate_left(8) ^ byte as u64).wrapping_mul(ID_HASHER_MULTIPLIER);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.hash = (self.hash ^ value).wrapping_mul(ID_HASHER_MULTIPLIER);
+    }
+}
+
+/// A [`HashMap`](hashbrown::HashMap) keyed by [`Id<T>`], using the fast [`IdHasher`].
+///
+/// This is the recommended storage for most manifests.
+///
+/// To write the keys as human-readable names when serializing,
+/// use the [`id_map_by_name`](crate::name_registry::id_map_by_name) serde helpers.
+pub type IdMap<T, V> = hashbrown::HashMap<Id<T>, V, BuildHasherDefault<IdHasher>>;
+
+/// A [`HashSet`](hashbrown::HashSet) of [`Id<T>`], using the fast [`IdHasher`].
+///
+/// To write the elements as human-readable names when serializing,
+/// use the [`id_set_by_name`](crate::name_registry::id_set_by_name) serde helpers.
+pub type IdSet<T> = hashbrown::HashSet<Id<T>, BuildHasherDefault<IdHasher>>;
@@ -0,0 +1,454 @@
+//! Hot reloading which only converts the raw items that changed.
+//!
+//! Registering a manifest with [`ManifestRegistration::hot_reload`](crate::registration::ManifestRegistration::hot_reload)
+//! reprocesses the whole raw manifest whenever its file is saved.
+//! For huge manifests, this slows down iteration, even when only a single item was edited.
+//!
+//! Instead, implement [`IncrementalManifest`] to describe how each raw item is converted on its own,
+//! and add an [`IncrementalReloadPlugin`] (without enabling `hot_reload` for the manifest).
+//! When the raw manifest file changes, its raw items are compared to those from the previous load:
+//! only added or changed items are converted, removed items are removed,
+//! and the existing manifest resource is patched in place.
+//! A [`ManifestItemsChanged`] event then reports exactly which items changed.
+//!
+//! ```rust,ignore
+//! app.register_manifest_with(ManifestRegistration::<ItemManifest>::new().path("items.ron"))
+//!     .add_plugins(IncrementalReloadPlugin::<ItemManifest>::default());
+//!
+//! fn refresh_sprites(mut events: EventReader<ManifestItemsChanged<ItemManifest>>) {
+//!     for event in events.read() {
+//!         for id in event.added.iter().chain(&event.changed) {
+//!             // Update any entities using this item.
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::any::type_name;
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::asset::Assets;
+use bevy::ecs::prelude::*;
+use bevy::log::{error, info};
+use bevy::utils::Instant;
+
+use crate::identifier::{Id, IdMap};
+use crate::lifecycle::ManifestLifecycleStage;
+use crate::manifest::MutableManifest;
+use crate::plugin::{
+    manifest_schedules, raw_manifest_modified, take_raw_manifest, ManifestProcessingSet,
+    RawManifestTracker,
+};
+use crate::registration::raw_manifest_available;
+
+/// A [`MutableManifest`] whose raw items can be converted one at a time.
+///
+/// Each raw item must have a stable [`Id`], typically computed from its name via [`Id::from_name`],
+/// so that the raw items of successive loads can be matched up.
+/// Added items are inserted under that [`Id`] via [`MutableManifest::upsert`],
+/// so the manifest must support [`MutableManifest::insert_with_id`].
+pub trait IncrementalManifest: MutableManifest {
+    /// Splits the raw manifest into its raw items, along with the [`Id`] of the item each is converted into.
+    fn raw_items(raw_manifest: &Self::RawManifest) -> Vec<(Id<Self::Item>, Self::RawItem)>;
+
+    /// Converts a single raw item into an item.
+    ///
+    /// This should match the conversion performed for each item by [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+    fn convert_item(
+        raw_item: &Self::RawItem,
+        world: &mut World,
+    ) -> Result<Self::Item, Self::ConversionError>;
+}
+
+/// The raw items of the manifest `M` as of the last time it was processed, used to find the items that changed.
+///
+/// This resource is added by the [`IncrementalReloadPlugin`].
+#[derive(Resource)]
+pub struct RawItemCache<M: IncrementalManifest> {
+    raw_items: IdMap<M::Item, M::RawItem>,
+}
+
+impl<M: IncrementalManifest> RawItemCache<M> {
+    /// Returns the raw item that the item with the given `id` was last converted from.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::RawItem> {
+        self.raw_items.get(&id)
+    }
+
+    /// The number of cached raw items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.raw_items.len()
+    }
+
+    /// Returns true if no raw items are cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.raw_items.is_empty()
+    }
+}
+
+impl<M: IncrementalManifest> Default for RawItemCache<M> {
+    fn default() -> Self {
+        Self {
+            raw_items: IdMap::default(),
+        }
+    }
+}
+
+impl<M: IncrementalManifest> std::fmt::Debug for RawItemCache<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawItemCache")
+            .field("raw_items", &self.raw_items.len())
+            .finish()
+    }
+}
+
+/// An event sent when the manifest `M` is patched by the [`IncrementalReloadPlugin`].
+///
+/// Items which failed to convert are left unchanged, and are not listed here.
+#[derive(Event)]
+pub struct ManifestItemsChanged<M: IncrementalManifest> {
+    /// The items that were added to the manifest.
+    pub added: Vec<Id<M::Item>>,
+    /// The items whose raw items changed, which were converted again and replaced.
+    pub changed: Vec<Id<M::Item>>,
+    /// The items that were removed from the manifest.
+    pub removed: Vec<Id<M::Item>>,
+}
+
+impl<M: IncrementalManifest> ManifestItemsChanged<M> {
+    /// Returns true if no items changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<M: IncrementalManifest> Clone for ManifestItemsChanged<M> {
+    fn clone(&self) -> Self {
+        Self {
+            added: self.added.clone(),
+            changed: self.changed.clone(),
+            removed: self.removed.clone(),
+        }
+    }
+}
+
+impl<M: IncrementalManifest> std::fmt::Debug for ManifestItemsChanged<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestItemsChanged")
+            .field("added", &self.added)
+            .field("changed", &self.changed)
+            .field("removed", &self.removed)
+            .finish()
+    }
+}
+
+/// A plugin which hot reloads the manifest `M` by only converting the raw items that changed.
+///
+/// Do not also enable [`ManifestRegistration::hot_reload`](crate::registration::ManifestRegistration::hot_reload) for `M`:
+/// whichever system runs first would consume the reloaded raw manifest.
+///
+/// # Example
+///
+/// Only the raw item that changed is converted again:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     asset_state::SimpleAssetState,
+///     identifier::{Id, IdMap},
+///     incremental::{IncrementalManifest, IncrementalReloadPlugin, ManifestItemsChanged},
+///     manifest::{Manifest, ManifestFormat, ManifestModificationError, MutableManifest},
+///     plugin::{ManifestPlugin, RawManifestTracker, RegisterManifest},
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Clone, PartialEq, Deserialize)]
+/// struct RawItem {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// #[derive(Asset, TypePath, Deserialize)]
+/// struct RawItemManifest {
+///     items: Vec<RawItem>,
+/// }
+///
+/// /// Counts the calls to `convert_item`.
+/// #[derive(Resource, Default)]
+/// struct Conversions(usize);
+///
+/// #[derive(Resource, Default)]
+/// struct ItemManifest {
+///     items: IdMap<i32, i32>,
+/// }
+///
+/// impl Manifest for ItemManifest {
+///     type Item = i32;
+///     type RawItem = RawItem;
+///     type RawManifest = RawItemManifest;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+///
+///     fn get(&self, id: Id<i32>) -> Option<&i32> {
+///         self.items.get(&id)
+///     }
+///
+///     fn from_raw_manifest(
+///         raw_manifest: RawItemManifest,
+///         world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         let mut manifest = ItemManifest::default();
+///         for (id, raw_item) in Self::raw_items(&raw_manifest) {
+///             manifest.items.insert(id, Self::convert_item(&raw_item, world)?);
+///         }
+///         Ok(manifest)
+///     }
+/// }
+///
+/// impl MutableManifest for ItemManifest {
+///     fn insert(&mut self, _item: i32) -> Result<Id<i32>, ManifestModificationError<Self>> {
+///         unimplemented!("items are inserted with `insert_with_id`")
+///     }
+///
+///     // The id of an `i32` cannot be derived from the item, so added raw items are inserted under their own id.
+///     fn insert_with_id(&mut self, id: Id<i32>, item: i32) -> Result<(), ManifestModificationError<Self>> {
+///         self.items.insert(id, item);
+///         Ok(())
+///     }
+///
+///     fn remove(&mut self, id: &Id<i32>) -> Result<Id<i32>, ManifestModificationError<Self>> {
+///         self.items.remove(id);
+///         Ok(*id)
+///     }
+///
+///     fn get_mut(&mut self, id: Id<i32>) -> Option<&mut i32> {
+///         self.items.get_mut(&id)
+///     }
+/// }
+///
+/// impl IncrementalManifest for ItemManifest {
+///     fn raw_items(raw_manifest: &RawItemManifest) -> Vec<(Id<i32>, RawItem)> {
+///         raw_manifest
+///             .items
+///             .iter()
+///             .map(|raw_item| (Id::from_name(&raw_item.name), raw_item.clone()))
+///             .collect()
+///     }
+///
+///     fn convert_item(raw_item: &RawItem, world: &mut World) -> Result<i32, Self::ConversionError> {
+///         world.resource_mut::<Conversions>().0 += 1;
+///         Ok(raw_item.value)
+///     }
+/// }
+///
+/// let raw_manifest = |sword: i32| RawItemManifest {
+///     items: vec![
+///         RawItem { name: "sword".to_string(), value: sword },
+///         RawItem { name: "shield".to_string(), value: 5 },
+///     ],
+/// };
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     AssetPlugin::default(),
+///     ManifestPlugin::<SimpleAssetState>::default(),
+///     IncrementalReloadPlugin::<ItemManifest>::default(),
+/// ))
+/// .init_resource::<Conversions>()
+/// .register_manifest_from_value::<ItemManifest>(raw_manifest(10));
+///
+/// while *app.world.resource::<State<SimpleAssetState>>() != SimpleAssetState::Ready {
+///     app.update();
+/// }
+/// assert_eq!(app.world.resource::<Conversions>().0, 2);
+///
+/// // Replace the raw manifest, as the `AssetServer` does when the file changes.
+/// let id = app
+///     .world
+///     .resource::<RawManifestTracker>()
+///     .status::<ItemManifest>()
+///     .unwrap()
+///     .handle
+///     .id()
+///     .typed::<RawItemManifest>();
+/// app.world
+///     .resource_mut::<Assets<RawItemManifest>>()
+///     .insert(id, raw_manifest(20));
+/// // Asset events are sent at the end of each frame, so the manifest is patched in the next one.
+/// app.update();
+/// app.update();
+///
+/// // Only the sword was converted again.
+/// assert_eq!(app.world.resource::<Conversions>().0, 3);
+/// assert_eq!(app.world.resource::<ItemManifest>().get_by_name("sword"), Some(&20));
+///
+/// let events = app.world.resource::<Events<ManifestItemsChanged<ItemManifest>>>();
+/// let mut reader = events.get_reader();
+/// let changes = reader.read(events).next().unwrap();
+/// assert_eq!(changes.changed, vec![Id::from_name("sword")]);
+/// assert!(changes.added.is_empty() && changes.removed.is_empty());
+/// ```
+pub struct IncrementalReloadPlugin<M: IncrementalManifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: IncrementalManifest> Default for IncrementalReloadPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M> Plugin for IncrementalReloadPlugin<M>
+where
+    M: IncrementalManifest,
+    M::RawItem: Clone + PartialEq + Send + Sync,
+{
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        app.init_resource::<RawItemCache<M>>()
+            .add_event::<ManifestItemsChanged<M>>()
+            .add_systems(
+                schedules.processing,
+                cache_raw_items::<M>
+                    .before(ManifestProcessingSet::<M>::default())
+                    .run_if(not(resource_exists::<M>).and_then(raw_manifest_available::<M>)),
+            )
+            // Like full reloads, incremental reloads respect the order set by any dependencies.
+            .add_systems(
+                schedules.processing,
+                reprocess_changed_items::<M>
+                    .in_set(ManifestProcessingSet::<M>::default())
+                    .run_if(resource_exists::<M>)
+                    .run_if(raw_manifest_modified::<M>),
+            );
+    }
+}
+
+/// Records the raw items of the manifest `M` before it is first processed, so later reloads can be compared against them.
+pub fn cache_raw_items<M>(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    raw_manifests: Res<Assets<M::RawManifest>>,
+    mut cache: ResMut<RawItemCache<M>>,
+) where
+    M: IncrementalManifest,
+    M::RawItem: Clone + PartialEq + Send + Sync,
+{
+    let Some(raw_manifest) = raw_manifest_tracker
+        .status::<M>()
+        .and_then(|status| raw_manifests.get(status.handle.id().typed::<M::RawManifest>()))
+    else {
+        return;
+    };
+
+    cache.raw_items = M::raw_items(raw_manifest).into_iter().collect();
+}
+
+/// Patches the manifest `M` with the raw items that changed in its reloaded raw manifest,
+/// then sends a [`ManifestItemsChanged`] event.
+///
+/// Items which fail to convert are logged, and left unchanged.
+pub fn reprocess_changed_items<M>(world: &mut World)
+where
+    M: IncrementalManifest,
+    M::RawItem: Clone + PartialEq + Send + Sync,
+{
+    let raw_manifest = world.resource_scope(|world, mut assets: Mut<Assets<M::RawManifest>>| {
+        take_raw_manifest::<M>(world.resource::<RawManifestTracker>(), &mut assets)
+    });
+    let raw_manifest = match raw_manifest {
+        Ok(raw_manifest) => raw_manifest,
+        Err(err) => {
+            error!("{err}");
+            return;
+        }
+    };
+
+    info!(
+        "Reprocessing changed items of manifest {}.",
+        type_name::<M>()
+    );
+    world
+        .resource_mut::<RawManifestTracker>()
+        .record_lifecycle::<M>(ManifestLifecycleStage::ProcessingStarted, None);
+    let start = Instant::now();
+
+    let raw_items: IdMap<M::Item, M::RawItem> = M::raw_items(&raw_manifest).into_iter().collect();
+    let mut cache = world
+        .remove_resource::<RawItemCache<M>>()
+        .unwrap_or_default();
+
+    let mut converted = Vec::new();
+    let mut removed = Vec::new();
+    for (id, raw_item) in &raw_items {
+        if cache.raw_items.get(id) == Some(raw_item) {
+            continue;
+        }
+
+        match M::convert_item(raw_item, world) {
+            Ok(item) => converted.push((*id, item)),
+            Err(err) => error!(
+                "Failed to convert item {id:?} of manifest {}: {err:?}",
+                type_name::<M>()
+            ),
+        }
+    }
+    for id in cache.raw_items.keys() {
+        if !raw_items.contains_key(id) {
+            removed.push(*id);
+        }
+    }
+
+    let mut changes = ManifestItemsChanged::<M> {
+        added: Vec::new(),
+        changed: Vec::new(),
+        removed: Vec::new(),
+    };
+    let mut manifest = world.resource_mut::<M>();
+    for (id, item) in converted {
+        match manifest.upsert(id, item) {
+            Ok(Some(_)) => changes.changed.push(id),
+            Ok(None) => changes.added.push(id),
+            Err(err) => {
+                error!(
+                    "Failed to update item {id:?} of manifest {}: {err}",
+                    type_name::<M>()
+                );
+                continue;
+            }
+        }
+        cache.raw_items.insert(id, raw_items[&id].clone());
+    }
+    for id in removed {
+        if let Err(err) = manifest.remove(&id) {
+            error!(
+                "Failed to remove item {id:?} of manifest {}: {err}",
+                type_name::<M>()
+            );
+            continue;
+        }
+        cache.raw_items.remove(&id);
+        changes.removed.push(id);
+    }
+
+    let item_count = manifest.item_count();
+    let end = Instant::now();
+    world.insert_resource(cache);
+    world
+        .resource_mut::<RawManifestTracker>()
+        .record_processed::<M>(item_count, start, end);
+
+    changes.added.sort();
+    changes.changed.sort();
+    changes.removed.sort();
+    if !changes.is_empty() {
+        world.send_event(changes);
+    }
+}
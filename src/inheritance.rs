@@ -0,0 +1,155 @@
+//! Prototype-style inheritance between raw items.
+//!
+//! Data-heavy games often contain many items that differ from each other in only a field or two.
+//! Rather than duplicating every field, raw items can name a parent to inherit from,
+//! and only specify the fields that they override:
+//!
+//! ```ron
+//! [
+//!     (name: "base_sword", damage: Some(10), weight: Some(3.0), sprite: Some("sword.png")),
+//!     (name: "heavy_sword", inherits: Some("base_sword"), weight: Some(6.0)),
+//! ]
+//! ```
+//!
+//! Implement [`Inheritable`] for your raw item type, and call [`resolve_inheritance`]
+//! at the start of [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+//! Parents are always resolved before their children, so inheritance chains of any depth work as expected.
+
+use bevy::utils::HashMap;
+
+/// A raw item that can inherit field values from another raw item in the same manifest.
+///
+/// Overridable fields are typically stored as [`Option`]s,
+/// with [`inherit_field`] filling in any fields that the child did not specify.
+///
+/// ```rust
+/// use leafwing_manifest::inheritance::{inherit_field, Inheritable};
+///
+/// #[derive(Clone)]
+/// struct RawSword {
+///     name: String,
+///     inherits: Option<String>,
+///     damage: Option<i32>,
+///     weight: Option<f32>,
+/// }
+///
+/// impl Inheritable for RawSword {
+///     fn key(&self) -> &str {
+///         &self.name
+///     }
+///
+///     fn parent(&self) -> Option<&str> {
+///         self.inherits.as_deref()
+///     }
+///
+///     fn inherit_from(&mut self, parent: &Self) {
+///         inherit_field(&mut self.damage, &parent.damage);
+///         inherit_field(&mut self.weight, &parent.weight);
+///     }
+/// }
+/// ```
+pub trait Inheritable: Clone {
+    /// The unique name of this item, which children use to refer to it.
+    fn key(&self) -> &str;
+
+    /// The name of the item that this item inherits from, if any.
+    fn parent(&self) -> Option<&str>;
+
+    /// Fills in any values that this item does not override with those from its (fully-resolved) parent.
+    fn inherit_from(&mut self, parent: &Self);
+}
+
+/// Sets `child` to a copy of `parent` if it has not been specified.
+pub fn inherit_field<T: Clone>(child: &mut Option<T>, parent: &Option<T>) {
+    if child.is_none() {
+        child.clone_from(parent);
+    }
+}
+
+/// Errors that can occur when resolving inheritance between raw items.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InheritanceError {
+    /// An item inherits from an item which does not exist.
+    #[error("The item {child} inherits from {parent}, which does not exist.")]
+    MissingParent {
+        /// The key of the item with the missing parent.
+        child: String,
+        /// The key of the missing parent.
+        parent: String,
+    },
+    /// Two items share the same key.
+    #[error("More than one item has the key {0}.")]
+    DuplicateKey(String),
+    /// A chain of items inherit from each other in a loop.
+    #[error("The items {} inherit from each other in a cycle.", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// The progress of resolving a single item.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    Unresolved,
+    InProgress,
+    Resolved,
+}
+
+/// Applies inheritance to every item, returning the resolved items in their original order.
+///
+/// Items without a parent are returned unchanged.
+pub fn resolve_inheritance<T: Inheritable>(mut items: Vec<T>) -> Result<Vec<T>, InheritanceError> {
+    let mut indexes: HashMap<String, usize> =
+        HashMap::with_capacity_and_hasher(items.len(), Default::default());
+    for (index, item) in items.iter().enumerate() {
+        if indexes.insert(item.key().to_string(), index).is_some() {
+            return Err(InheritanceError::DuplicateKey(item.key().to_string()));
+        }
+    }
+
+    let mut resolution = vec![Resolution::Unresolved; items.len()];
+
+    for start in 0..items.len() {
+        // Walk up the chain of parents until we find one that is already resolved (or has no parent),
+        // then resolve the chain from the top down.
+        let mut chain = Vec::new();
+        let mut current = start;
+
+        while resolution[current] == Resolution::Unresolved {
+            resolution[current] = Resolution::InProgress;
+            chain.push(current);
+
+            let Some(parent) = items[current].parent() else {
+                break;
+            };
+            let Some(&parent_index) = indexes.get(parent) else {
+                return Err(InheritanceError::MissingParent {
+                    child: items[current].key().to_string(),
+                    parent: parent.to_string(),
+                });
+            };
+            current = parent_index;
+        }
+
+        if resolution[current] == Resolution::InProgress && items[current].parent().is_some() {
+            let cycle_start = chain
+                .iter()
+                .position(|&index| index == current)
+                .unwrap_or_default();
+            let mut cycle: Vec<String> = chain[cycle_start..]
+                .iter()
+                .map(|&index| items[index].key().to_string())
+                .collect();
+            cycle.push(items[current].key().to_string());
+            return Err(InheritanceError::Cycle(cycle));
+        }
+
+        for &index in chain.iter().rev() {
+            if let Some(parent) = items[index].parent() {
+                let parent = items[indexes[parent]].clone();
+                items[index].inherit_from(&parent);
+            }
+            resolution[index] = Resolution::Resolved;
+        }
+    }
+
+    Ok(items)
+}
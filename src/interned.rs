@@ -0,0 +1,293 @@
+//! An alternative identifier backend, where names are interned into a global table rather than hashed.
+//!
+//! [`Id::from_name`] is fast, `const` and needs no shared state, but as with any hash, distinct names can collide.
+//! [`InternedId`] instead stores the index of the name in a runtime table, guaranteeing that every distinct name
+//! receives a distinct identifier.
+//!
+//! The price is that interned identifiers can only be created at runtime,
+//! and that their values depend on the order in which names were first interned.
+//! As a result, [`InternedId`] always serializes as its name, never as its index:
+//! this keeps save files and network messages stable across runs and builds.
+//!
+//! Interned names are leaked, and live for the rest of the program.
+//! This is rarely a problem, as the set of names used by manifests is small and fixed.
+//!
+//! To use interned identifiers for a manifest, set [`InternedId`] as its [`GenericManifest::Key`](crate::manifest::GenericManifest::Key):
+//! the key-based lookup, mutation and validation helpers then work with interned identifiers instead of hashed ones.
+//! Like [`Id`], an [`InternedId`] can be stored as a component and reflected.
+//!
+//! This module requires the `interned` feature.
+
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    sync::{OnceLock, RwLock},
+};
+
+use bevy::prelude::Component;
+use bevy::reflect::Reflect;
+use bevy::utils::HashMap;
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::identifier::Id;
+
+/// The global table of interned names.
+#[derive(Default)]
+struct Interner {
+    names: Vec<&'static str>,
+    indexes: HashMap<&'static str, u32>,
+}
+
+static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+
+fn interner() -> &'static RwLock<Interner> {
+    INTERNER.get_or_init(Default::default)
+}
+
+/// A collision-free identifier of type `T`, backed by an index into a global table of interned names.
+///
+/// This is a drop-in alternative to [`Id`] for projects that would rather pay for a global lookup table
+/// than risk hash collisions.
+/// Like [`Id`], it is a tiny [`Copy`] type, representing a *kind* of object rather than a unique instance.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::interned::InternedId;
+///
+/// struct Item;
+///
+/// let sword = InternedId::<Item>::intern("sword");
+/// let shield = InternedId::<Item>::intern("shield");
+///
+/// assert_eq!(sword, InternedId::intern("sword"));
+/// assert!(sword != shield);
+/// assert_eq!(sword.name(), "sword");
+/// assert_eq!(InternedId::<Item>::get("axe"), None);
+/// ```
+///
+/// Manifests select this backend by using it as their [`GenericManifest::Key`](crate::manifest::GenericManifest::Key):
+///
+/// ```
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     identifier::Id,
+///     interned::{InternedId, InternedIdMap},
+///     manifest::{GenericManifest, Manifest, ManifestFormat},
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Item {
+///     name: String,
+/// }
+///
+/// #[derive(Resource, Asset, TypePath, Deserialize)]
+/// struct ItemManifest {
+///     items: InternedIdMap<Item, Item>,
+/// }
+///
+/// impl Manifest for ItemManifest {
+///     type Item = Item;
+///     type RawItem = Item;
+///     type RawManifest = ItemManifest;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+///
+///     fn get(&self, id: Id<Item>) -> Option<&Item> {
+///         self.items.values().find(|item| Id::from_name(&item.name) == id)
+///     }
+///
+///     fn from_raw_manifest(
+///         raw_manifest: Self::RawManifest,
+///         _world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         Ok(raw_manifest)
+///     }
+/// }
+///
+/// impl GenericManifest for ItemManifest {
+///     type Key = InternedId<Item>;
+///
+///     fn get_by_key(&self, key: InternedId<Item>) -> Option<&Item> {
+///         self.items.get(&key)
+///     }
+/// }
+///
+/// let sword = InternedId::intern("sword");
+/// let mut items = InternedIdMap::default();
+/// items.insert(sword, Item { name: "sword".to_string() });
+/// let manifest = ItemManifest { items };
+///
+/// assert!(manifest.contains_key(sword));
+/// assert_eq!(manifest.missing_keys(&[sword, InternedId::intern("shield")]).len(), 1);
+///
+/// // Interned identifiers can be stored as components, just like hashed ones.
+/// let mut world = World::new();
+/// let entity = world.spawn(sword).id();
+/// assert_eq!(world.get::<InternedId<Item>>(entity), Some(&sword));
+/// ```
+#[derive(Component, Reflect)]
+pub struct InternedId<T> {
+    /// The index of the name in the global table of interned names.
+    index: u32,
+
+    /// Marker to make the compiler happy
+    ///
+    /// Using `fn() -> T` keeps this type [`Send`] and [`Sync`], regardless of `T`.
+    #[reflect(ignore)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> InternedId<T> {
+    /// Returns the identifier for the given name, interning it if it has not been seen before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`u32::MAX`] distinct names are interned.
+    pub fn intern(name: &str) -> Self {
+        if let Some(id) = Self::get(name) {
+            return id;
+        }
+
+        let mut interner = interner().write().expect("The interner lock was poisoned.");
+
+        // Another thread may have interned the same name while we were waiting for the lock.
+        if let Some(&index) = interner.indexes.get(name) {
+            return Self::from_index(index);
+        }
+
+        let index = u32::try_from(interner.names.len()).expect("Too many interned names.");
+        let name: &'static str = Box::leak(name.into());
+        interner.names.push(name);
+        interner.indexes.insert(name, index);
+        Self::from_index(index)
+    }
+
+    /// Returns the identifier for the given name, if it has already been interned.
+    #[must_use]
+    pub fn get(name: &str) -> Option<Self> {
+        interner()
+            .read()
+            .expect("The interner lock was poisoned.")
+            .indexes
+            .get(name)
+            .map(|&index| Self::from_index(index))
+    }
+
+    /// Returns the name that this identifier was interned from.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        interner()
+            .read()
+            .expect("The interner lock was poisoned.")
+            .names[self.index as usize]
+    }
+
+    /// Returns the index of this identifier in the global table of interned names.
+    ///
+    /// This value depends on the order in which names were interned,
+    /// and should never be persisted: use [`InternedId::name`] instead.
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Converts this identifier into the equivalent hashed [`Id`], via [`Id::from_name`].
+    #[must_use]
+    pub fn to_hashed(&self) -> Id<T> {
+        Id::from_name(self.name())
+    }
+
+    const fn from_index(index: u32) -> Self {
+        InternedId {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for InternedId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InternedId")
+            .field("index", &self.index)
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl<T> Display for InternedId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl<T> PartialEq for InternedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for InternedId<T> {}
+
+impl<T> PartialOrd for InternedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for InternedId<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for InternedId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Hashing as a u64 lets `IdHasher` take its fast path.
+        u64::from(self.index).hash(state);
+    }
+}
+
+impl<T> Clone for InternedId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for InternedId<T> {}
+
+impl<T> Serialize for InternedId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for InternedId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InternedIdVisitor<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T> Visitor<'de> for InternedIdVisitor<T> {
+            type Value = InternedId<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(InternedId::intern(value))
+            }
+        }
+
+        deserializer.deserialize_str(InternedIdVisitor(PhantomData))
+    }
+}
+
+/// A [`HashMap`](bevy::utils::hashbrown::HashMap) keyed by [`InternedId<T>`], using the fast [`IdHasher`](crate::identifier::IdHasher).
+pub type InternedIdMap<T, V> = bevy::utils::hashbrown::HashMap<
+    InternedId<T>,
+    V,
+    std::hash::BuildHasherDefault<crate::identifier::IdHasher>,
+>;
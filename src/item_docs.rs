@@ -0,0 +1,130 @@
+//! Designer-written documentation for manifest items, kept out of the item type itself.
+//!
+//! Raw items can carry a free-form `docs` field explaining why a value was chosen, or what an item is for.
+//! This is useful to inspectors, editors and schema tools, but is dead weight at runtime.
+//! Instead of copying it into the [`Item`](crate::manifest::Manifest::Item),
+//! store it in the [`ItemDocs`] side table while processing:
+//!
+//! ```rust,ignore
+//! #[derive(Deserialize)]
+//! struct RawItem {
+//!     name: String,
+//!     // Accept `comment` too, for designers used to other tools.
+//!     #[serde(default, alias = "comment")]
+//!     docs: Option<String>,
+//!     weight: f32,
+//! }
+//!
+//! impl DocumentedRawItem for RawItem {
+//!     fn docs(&self) -> Option<&str> {
+//!         self.docs.as_deref()
+//!     }
+//! }
+//!
+//! fn from_raw_manifest(raw_manifest: RawItemManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     record_item_docs::<Self, _>(world, raw_manifest.items.iter().map(|raw| (Id::from_name(&raw.name), raw)));
+//!     // ...
+//! }
+//! ```
+
+use bevy::ecs::prelude::*;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::Manifest;
+
+/// A raw item with an optional documentation string.
+pub trait DocumentedRawItem {
+    /// The documentation written for this item, if any.
+    fn docs(&self) -> Option<&str>;
+}
+
+/// The documentation strings of the items in the manifest `M`.
+///
+/// This resource is replaced each time the manifest is processed by [`record_item_docs`],
+/// so documentation for removed items does not linger.
+#[derive(Resource)]
+pub struct ItemDocs<M: Manifest> {
+    docs: IdMap<M::Item, String>,
+}
+
+impl<M: Manifest> ItemDocs<M> {
+    /// Records the documentation for the item with the given `id`, replacing any existing documentation.
+    pub fn record(&mut self, id: Id<M::Item>, docs: impl Into<String>) {
+        self.docs.insert(id, docs.into());
+    }
+
+    /// Removes the documentation for the item with the given `id`, returning it if there was any.
+    pub fn remove(&mut self, id: Id<M::Item>) -> Option<String> {
+        self.docs.remove(&id)
+    }
+
+    /// Returns the documentation for the item with the given `id`.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&str> {
+        self.docs.get(&id).map(String::as_str)
+    }
+
+    /// Returns the documentation for the item with the given `name`.
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&str> {
+        self.get(Id::from_name(name))
+    }
+
+    /// Iterates over the documented items and their documentation, in any order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<M::Item>, &str)> {
+        self.docs.iter().map(|(id, docs)| (*id, docs.as_str()))
+    }
+
+    /// The number of documented items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Returns true if no items are documented.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+}
+
+impl<M: Manifest> Default for ItemDocs<M> {
+    fn default() -> Self {
+        Self {
+            docs: IdMap::default(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ItemDocs<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemDocs")
+            .field("docs", &self.docs.len())
+            .finish()
+    }
+}
+
+impl<'a, M: Manifest, R: DocumentedRawItem + 'a> FromIterator<(Id<M::Item>, &'a R)>
+    for ItemDocs<M>
+{
+    fn from_iter<I: IntoIterator<Item = (Id<M::Item>, &'a R)>>(raw_items: I) -> Self {
+        let mut docs = Self::default();
+        for (id, raw_item) in raw_items {
+            if let Some(text) = raw_item.docs() {
+                docs.record(id, text);
+            }
+        }
+        docs
+    }
+}
+
+/// Replaces the [`ItemDocs`] of the manifest `M` with the documentation of the supplied raw items.
+///
+/// Call this from [`Manifest::from_raw_manifest`], pairing each raw item with the [`Id`] of the item it becomes.
+/// Raw items without documentation are skipped.
+pub fn record_item_docs<'a, M: Manifest, R: DocumentedRawItem + 'a>(
+    world: &mut World,
+    raw_items: impl IntoIterator<Item = (Id<M::Item>, &'a R)>,
+) {
+    world.insert_resource(raw_items.into_iter().collect::<ItemDocs<M>>());
+}
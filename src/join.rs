@@ -0,0 +1,280 @@
+//! Joined views over two manifests, where the items of one refer to items of the other.
+//!
+//! Manifests frequently depend on each other: each monster lists the items it can drop, each recipe lists its ingredients.
+//! Implement [`LinksTo`] to describe these links, typically by storing a [`Ref`](crate::references::Ref) or [`Id`] in the item.
+//! Then, either iterate over the links directly with [`join`]:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     join::{join, LinksTo, ManifestJoin},
+//!     manifest::{IterableManifest, Manifest, ManifestFormat},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item {
+//!     name: String,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Monster {
+//!     loot: Vec<Id<Item>>,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct MonsterManifest {
+//!     monsters: IdMap<Monster, Monster>,
+//! }
+//!
+//! impl Manifest for MonsterManifest {
+//!     type Item = Monster;
+//!     type RawItem = Monster;
+//!     type RawManifest = MonsterManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Monster>) -> Option<&Monster> {
+//!         self.monsters.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! impl IterableManifest for MonsterManifest {
+//!     fn iter(&self) -> impl Iterator<Item = (Id<Monster>, &Monster)> {
+//!         self.monsters.iter().map(|(id, monster)| (*id, monster))
+//!     }
+//! }
+//!
+//! impl LinksTo<ItemManifest> for MonsterManifest {
+//!     fn links(monster: &Monster) -> Vec<Id<Item>> {
+//!         monster.loot.clone()
+//!     }
+//! }
+//!
+//! let items = ItemManifest {
+//!     items: ["dagger", "gold"]
+//!         .into_iter()
+//!         .map(|name| (Id::from_name(name), Item { name: name.to_string() }))
+//!         .collect(),
+//! };
+//! let goblin = Id::from_name("goblin");
+//! let mut monsters = IdMap::default();
+//! let loot = ["dagger", "gold", "cursed amulet"].map(Id::from_name).to_vec();
+//! monsters.insert(goblin, Monster { loot });
+//! let monsters = MonsterManifest { monsters };
+//!
+//! for joined in join(&monsters, &items) {
+//!     let loot: Vec<&str> = joined.linked.iter().map(|(_, item)| item.name.as_str()).collect();
+//!     assert_eq!(loot, ["dagger", "gold"]);
+//!     // The amulet is not in the item manifest.
+//!     assert_eq!(joined.missing, [Id::from_name("cursed amulet")]);
+//! }
+//!
+//! let cached = ManifestJoin::new(&monsters, &items);
+//! assert_eq!(cached.resolve(goblin, &items).count(), 2);
+//! assert_eq!(cached.missing(), [(goblin, Id::from_name("cursed amulet"))]);
+//! ```
+//!
+//! or cache the links in a [`ManifestJoin`] resource with [`RegisterJoin::register_join`],
+//! which is rebuilt whenever either manifest changes.
+
+use std::marker::PhantomData;
+
+use bevy::app::App;
+use bevy::ecs::prelude::*;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::{IterableManifest, Manifest};
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+
+/// A manifest whose items refer to items in the manifest `B`.
+pub trait LinksTo<B: Manifest>: IterableManifest {
+    /// The [`Id`]s of the items in `B` that `item` refers to, in order.
+    fn links(item: &Self::Item) -> Vec<Id<B::Item>>;
+}
+
+/// An item of the manifest `A`, along with the items of `B` it refers to, as returned by [`join`].
+pub struct Joined<'a, A: LinksTo<B>, B: Manifest> {
+    /// The [`Id`] of the item in `A`.
+    pub id: Id<A::Item>,
+    /// The item in `A`.
+    pub item: &'a A::Item,
+    /// The items in `B` which the item refers to, in the order returned by [`LinksTo::links`].
+    pub linked: Vec<(Id<B::Item>, &'a B::Item)>,
+    /// The [`Id`]s of any referenced items which are not in `B`.
+    pub missing: Vec<Id<B::Item>>,
+}
+
+impl<'a, A: LinksTo<B>, B: Manifest> std::fmt::Debug for Joined<'a, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Joined")
+            .field("id", &self.id)
+            .field(
+                "linked",
+                &self.linked.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            )
+            .field("missing", &self.missing)
+            .finish()
+    }
+}
+
+/// Iterates over every item in `left`, resolving the items in `right` that it refers to.
+///
+/// Items are visited in the order of [`IterableManifest::iter`].
+pub fn join<'a, A: LinksTo<B>, B: Manifest>(
+    left: &'a A,
+    right: &'a B,
+) -> impl Iterator<Item = Joined<'a, A, B>> + 'a {
+    left.iter().map(move |(id, item)| {
+        let mut linked = Vec::new();
+        let mut missing = Vec::new();
+        for link in A::links(item) {
+            match right.get(link) {
+                Some(other) => linked.push((link, other)),
+                None => missing.push(link),
+            }
+        }
+
+        Joined {
+            id,
+            item,
+            linked,
+            missing,
+        }
+    })
+}
+
+/// The cached links from each item in the manifest `A` to the items of `B` it refers to.
+///
+/// This resource is added by [`RegisterJoin::register_join`], and rebuilt whenever either manifest changes.
+/// Only [`Id`]s are stored, so look up the items themselves with [`ManifestJoin::resolve`].
+#[derive(Resource)]
+pub struct ManifestJoin<A: LinksTo<B>, B: Manifest> {
+    links: IdMap<A::Item, Vec<Id<B::Item>>>,
+    missing: Vec<(Id<A::Item>, Id<B::Item>)>,
+    _phantom: PhantomData<fn() -> (A, B)>,
+}
+
+impl<A: LinksTo<B>, B: Manifest> ManifestJoin<A, B> {
+    /// Builds the links between the items of `left` and `right`.
+    #[must_use]
+    pub fn new(left: &A, right: &B) -> Self {
+        let mut links = IdMap::default();
+        let mut missing = Vec::new();
+        for joined in join(left, right) {
+            missing.extend(joined.missing.into_iter().map(|link| (joined.id, link)));
+            links.insert(
+                joined.id,
+                joined.linked.into_iter().map(|(link, _)| link).collect(),
+            );
+        }
+        missing.sort();
+
+        Self {
+            links,
+            missing,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The [`Id`]s of the items in `B` that the item with the given `id` refers to.
+    ///
+    /// Referenced items which were missing from `B` are not included.
+    #[must_use]
+    pub fn linked(&self, id: Id<A::Item>) -> &[Id<B::Item>] {
+        self.links.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the items in `right` that the item with the given `id` refers to.
+    pub fn resolve<'a>(
+        &'a self,
+        id: Id<A::Item>,
+        right: &'a B,
+    ) -> impl Iterator<Item = &'a B::Item> + 'a {
+        self.linked(id)
+            .iter()
+            .filter_map(move |link| right.get(*link))
+    }
+
+    /// Every item in `A` which refers to an item that is missing from `B`, along with the missing [`Id`].
+    ///
+    /// These pairs are sorted by the [`Id`] of the item in `A`.
+    #[must_use]
+    pub fn missing(&self) -> &[(Id<A::Item>, Id<B::Item>)] {
+        &self.missing
+    }
+}
+
+impl<A: LinksTo<B>, B: Manifest> std::fmt::Debug for ManifestJoin<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestJoin")
+            .field("links", &self.links.len())
+            .field("missing", &self.missing)
+            .finish()
+    }
+}
+
+/// An extension trait for registering [`ManifestJoin`]s with an [`App`].
+pub trait RegisterJoin {
+    /// Inserts a [`ManifestJoin`] once both `A` and `B` have been processed, and rebuilds it whenever either changes.
+    fn register_join<A: LinksTo<B>, B: Manifest>(&mut self) -> &mut Self;
+}
+
+impl RegisterJoin for App {
+    fn register_join<A: LinksTo<B>, B: Manifest>(&mut self) -> &mut Self {
+        let schedules = manifest_schedules(self);
+        self.add_systems(
+            schedules.processing,
+            update_join::<A, B>
+                .run_if(
+                    resource_exists::<A>
+                        .and_then(resource_exists::<B>)
+                        .and_then(resource_changed::<A>.or_else(resource_changed::<B>)),
+                )
+                .after(ProcessManifestSet),
+        )
+    }
+}
+
+/// Rebuilds the [`ManifestJoin`] between the manifests `A` and `B`.
+pub fn update_join<A: LinksTo<B>, B: Manifest>(
+    left: Res<A>,
+    right: Res<B>,
+    mut commands: Commands,
+) {
+    commands.insert_resource(ManifestJoin::new(&*left, &*right));
+}
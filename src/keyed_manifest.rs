@@ -0,0 +1,226 @@
+//! A storage strategy for manifest items keyed by an enum or small struct, rather than an open-ended string.
+//!
+//! Hashed [`Id`]s can refer to any name, which is ideal for content that designers and modders add freely.
+//! But some manifests have a fixed set of entries, which code matches on exhaustively:
+//! every [`TileKind`](#example) needs its data, and a typo should be a compile error, not a missing item.
+//!
+//! A [`KeyedManifest`] stores items by their [`ManifestKey`], while still supporting lookups by [`Id`],
+//! so it can back an ordinary [`Manifest`](crate::manifest::Manifest) implementation and use the same plugin pipeline.
+//! Raw manifests can use the key type directly, as long as it implements [`Deserialize`](serde::Deserialize):
+//!
+//! ```rust,ignore
+//! fn from_raw_manifest(raw_manifest: RawTileManifest, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     let tiles: KeyedManifest<TileKind, Tile> = raw_manifest.tiles.into_iter().map(|raw| (raw.kind, Tile::from(raw))).collect();
+//!     tiles.ensure_all(TileKind::ALL)?;
+//!     Ok(TileManifest { tiles })
+//! }
+//!
+//! fn get(&self, id: Id<Tile>) -> Option<&Tile> {
+//!     self.tiles.get(id)
+//! }
+//! ```
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use bevy::utils::HashMap;
+
+use crate::identifier::Id;
+
+/// A key which uniquely identifies an item in a [`KeyedManifest`].
+///
+/// Each key also corresponds to an [`Id`], created from its [`key_name`](ManifestKey::key_name).
+/// By default, the name is the [`Debug`] representation of the key, so `TileKind::Water` has the same [`Id`] as `"Water"`.
+pub trait ManifestKey: Eq + Hash + Clone + Debug {
+    /// The name of this key, used to compute its [`Id`].
+    fn key_name(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// The [`Id`] corresponding to this key.
+    fn id<T>(&self) -> Id<T> {
+        Id::from_name(&self.key_name())
+    }
+}
+
+/// Some keys were expected in a [`KeyedManifest`], but were not found.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("The manifest is missing entries for the keys {missing:?}.")]
+pub struct MissingKeysError<K: ManifestKey> {
+    /// The keys which were not found.
+    pub missing: Vec<K>,
+}
+
+/// A storage container for manifest items, keyed by a [`ManifestKey`].
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{
+///     identifier::Id,
+///     keyed_manifest::{KeyedManifest, ManifestKey},
+/// };
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum TileKind {
+///     City,
+///     Water,
+/// }
+///
+/// impl ManifestKey for TileKind {}
+///
+/// let mut tiles = KeyedManifest::default();
+/// tiles.insert(TileKind::City, "A bustling city");
+///
+/// assert_eq!(tiles.get_by_key(&TileKind::City), Some(&"A bustling city"));
+/// assert_eq!(tiles.get(Id::from_name("City")), Some(&"A bustling city"));
+/// assert_eq!(tiles.missing_keys(&[TileKind::City, TileKind::Water]), vec![TileKind::Water]);
+/// ```
+pub struct KeyedManifest<K: ManifestKey, T> {
+    items: HashMap<K, T>,
+    keys_by_id: HashMap<Id<T>, K>,
+}
+
+impl<K: ManifestKey, T> KeyedManifest<K, T> {
+    /// Creates an empty [`KeyedManifest`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an item with the given `key`, returning the previous item with that key, if any.
+    pub fn insert(&mut self, key: K, item: T) -> Option<T> {
+        self.keys_by_id.insert(key.id(), key.clone());
+        self.items.insert(key, item)
+    }
+
+    /// Removes the item with the given `key`, returning it.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        self.keys_by_id.remove(&key.id());
+        self.items.remove(key)
+    }
+
+    /// Gets an item by its key.
+    #[must_use]
+    pub fn get_by_key(&self, key: &K) -> Option<&T> {
+        self.items.get(key)
+    }
+
+    /// Gets a mutable reference to an item by its key.
+    #[must_use]
+    pub fn get_mut_by_key(&mut self, key: &K) -> Option<&mut T> {
+        self.items.get_mut(key)
+    }
+
+    /// Gets an item by its [`Id`].
+    #[must_use]
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.items.get(self.key_of(id)?)
+    }
+
+    /// Gets a mutable reference to an item by its [`Id`].
+    #[must_use]
+    pub fn get_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        let key = self.keys_by_id.get(&id)?;
+        self.items.get_mut(key)
+    }
+
+    /// Returns the key corresponding to the given [`Id`], if an item with that key is stored.
+    #[must_use]
+    pub fn key_of(&self, id: Id<T>) -> Option<&K> {
+        self.keys_by_id.get(&id)
+    }
+
+    /// Returns true if an item with the given `key` is stored.
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.items.contains_key(key)
+    }
+
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there are no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over the keys and items, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &T)> {
+        self.items.iter()
+    }
+
+    /// Iterates over the keys, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.items.keys()
+    }
+
+    /// Returns the keys in `expected` which do not have an item, in the same order.
+    #[must_use]
+    pub fn missing_keys(&self, expected: &[K]) -> Vec<K> {
+        expected
+            .iter()
+            .filter(|key| !self.items.contains_key(*key))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns an error if any of the keys in `expected` do not have an item.
+    ///
+    /// For enum keys, pass every variant to check that the manifest is exhaustive.
+    pub fn ensure_all(&self, expected: &[K]) -> Result<(), MissingKeysError<K>> {
+        let missing = self.missing_keys(expected);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingKeysError { missing })
+        }
+    }
+}
+
+impl<K: ManifestKey, T> Default for KeyedManifest<K, T> {
+    fn default() -> Self {
+        Self {
+            items: HashMap::default(),
+            keys_by_id: HashMap::default(),
+        }
+    }
+}
+
+impl<K: ManifestKey, T: Debug> Debug for KeyedManifest<K, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.items.iter()).finish()
+    }
+}
+
+impl<K: ManifestKey, T: Clone> Clone for KeyedManifest<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            keys_by_id: self.keys_by_id.clone(),
+        }
+    }
+}
+
+impl<K: ManifestKey, T> FromIterator<(K, T)> for KeyedManifest<K, T> {
+    /// Collects the items.
+    ///
+    /// If the same key appears multiple times, the last entry wins.
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
+        let mut manifest = Self::default();
+        manifest.extend(iter);
+        manifest
+    }
+}
+
+impl<K: ManifestKey, T> Extend<(K, T)> for KeyedManifest<K, T> {
+    fn extend<I: IntoIterator<Item = (K, T)>>(&mut self, iter: I) {
+        for (key, item) in iter {
+            self.insert(key, item);
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Registering manifests after the app has started, such as when a mod is enabled from the main menu.
+//!
+//! Manifests registered with [`RegisterManifest`](crate::plugin::RegisterManifest) are loaded together during [`AssetLoadingState::LOADING`](crate::asset_state::AssetLoadingState::LOADING).
+//! Manifests registered later are loaded and processed on their own, regardless of the current loading state.
+//!
+//! Registering the raw manifest asset type and its loader requires access to the [`App`],
+//! so each manifest that may be registered late must first be prepared while the app is being built:
+//!
+//! ```rust
+//! # #[cfg(feature = "ron")]
+//! # fn main() {
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     late_registration::{LateManifestRegistration, RegisterManifestAtRuntime},
+//!     manifest::{Manifest, ManifestFormat},
+//!     plugin::RegisterManifest,
+//!     test_utils::manifest_test_app,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Item {
+//!     name: String,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ModItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ModItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ModItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! fn pending(app: &App) -> bool {
+//!     app.world
+//!         .resource::<LateManifestRegistration<ModItemManifest>>()
+//!         .pending_path()
+//!         .is_some()
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.allow_late_registration::<ModItemManifest>();
+//! app.update();
+//!
+//! // Later on, such as when a mod is enabled from the main menu...
+//! app.world.register_manifest::<ModItemManifest>("items.ron");
+//! for _ in 0..10_000 {
+//!     if app.world.contains_resource::<ModItemManifest>() {
+//!         break;
+//!     }
+//!     app.update();
+//!     std::thread::sleep(std::time::Duration::from_millis(1));
+//! }
+//! assert!(app.world.resource::<ModItemManifest>().get_by_name("sword").is_some());
+//!
+//! // Registering the manifest again keeps the existing manifest until the replacement is ready,
+//! // so a replacement that fails to load leaves the existing manifest in place.
+//! app.world.register_manifest::<ModItemManifest>("missing_mod/items.ron");
+//! for _ in 0..10_000 {
+//!     if !pending(&app) {
+//!         break;
+//!     }
+//!     app.update();
+//!     assert!(app.world.contains_resource::<ModItemManifest>());
+//!     std::thread::sleep(std::time::Duration::from_millis(1));
+//! }
+//! assert!(!pending(&app));
+//! assert!(app.world.resource::<ModItemManifest>().get_by_name("sword").is_some());
+//!
+//! // Compressed raw manifests are rejected.
+//! # #[cfg(feature = "gzip")]
+//! # {
+//! app.world.register_manifest::<ModItemManifest>("items.ron.gz");
+//! assert!(!pending(&app));
+//! # }
+//! # }
+//! # #[cfg(not(feature = "ron"))]
+//! # fn main() {}
+//! ```
+//!
+//! The [`RawManifestOptions`] used to load the raw manifest can be supplied with
+//! [`RegisterManifest::allow_late_registration_with_options`](crate::plugin::RegisterManifest::allow_late_registration_with_options),
+//! and apply to every late registration of that manifest.
+//!
+//! Late manifests report their progress through the usual [`ManifestLifecycleEvent`](crate::lifecycle::ManifestLifecycleEvent)s.
+//! Compressed raw manifests cannot be registered late, as their loader depends on the path, and are rejected with an error.
+
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use bevy::app::App;
+use bevy::asset::{AssetServer, LoadState};
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::Command;
+use bevy::log::error;
+
+use crate::loader::Compression;
+use crate::manifest::{Manifest, ManifestFormat};
+use crate::plugin::{
+    manifest_schedules, prepare_raw_manifest, process_manifest, ManifestProcessingSet,
+    RawManifestOptions, RawManifestTracker,
+};
+use crate::provenance::{ItemSource, ManifestProvenance};
+
+/// Records that the manifest `M` may be registered after the app has started, and the path it was registered with.
+///
+/// This resource is inserted by [`RegisterManifest::allow_late_registration`](crate::plugin::RegisterManifest::allow_late_registration).
+#[derive(Resource)]
+pub struct LateManifestRegistration<M: Manifest> {
+    path: Option<PathBuf>,
+    format: ManifestFormat,
+    transformed: bool,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> LateManifestRegistration<M> {
+    /// Returns the path that the manifest is being loaded from, if it has been registered and has not finished loading.
+    #[must_use]
+    pub fn pending_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for LateManifestRegistration<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LateManifestRegistration")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Prepares the raw manifest asset type of `M`, and the systems which load and process it once it is registered late.
+///
+/// The `options` are used for every late registration of `M`, as the asset loader can only be registered while the app is being built.
+pub(crate) fn prepare_late_registration<M: Manifest>(app: &mut App, options: RawManifestOptions) {
+    let format = options.format.unwrap_or(M::FORMAT);
+    let transformed = options.transform.is_some();
+    prepare_raw_manifest::<M>(app, Compression::None, options);
+    app.insert_resource(LateManifestRegistration::<M> {
+        path: None,
+        format,
+        transformed,
+        _phantom: PhantomData,
+    });
+
+    // These systems are not part of the `ProcessManifestSet`, as that only runs while the app is in the processing state.
+    let schedules = manifest_schedules(app);
+    app.add_systems(
+        schedules.loading,
+        update_late_manifest_load_state::<M>.run_if(late_manifest_pending::<M>),
+    )
+    .add_systems(
+        schedules.processing,
+        (
+            begin_late_processing::<M>,
+            process_manifest::<M>,
+            finish_late_registration::<M>,
+        )
+            .chain()
+            .in_set(ManifestProcessingSet::<M>::default())
+            .run_if(late_manifest_loaded::<M>),
+    );
+}
+
+/// A [`Command`] which registers the manifest `M` after the app has started.
+///
+/// Any existing manifest resource of type `M` is kept until the new raw manifest has been processed, and is then replaced.
+/// If the new raw manifest fails to load or process, the existing manifest remains in place.
+/// [`RegisterManifest::allow_late_registration`](crate::plugin::RegisterManifest::allow_late_registration) must have been called for `M`.
+///
+/// Compressed raw manifests are rejected with an error, as their loader depends on the path.
+pub struct RegisterManifestCommand<M: Manifest> {
+    path: PathBuf,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> RegisterManifestCommand<M> {
+    /// Creates a command which registers the manifest `M`, loading it from `path`.
+    ///
+    /// Like [`RegisterManifest::register_manifest`](crate::plugin::RegisterManifest::register_manifest), the path root is the `assets` folder.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> Command for RegisterManifestCommand<M> {
+    fn apply(self, world: &mut World) {
+        if Compression::from_path(&self.path) != Compression::None {
+            error!(
+                "Could not register the manifest {} after startup from {}: \
+                compressed raw manifests cannot be registered late.",
+                std::any::type_name::<M>(),
+                self.path.display()
+            );
+            return;
+        }
+
+        let Some(mut registration) = world.get_resource_mut::<LateManifestRegistration<M>>() else {
+            error!(
+                "Could not register the manifest {0} after startup. \
+                Call `app.allow_late_registration::<{0}>()` while building the app.",
+                std::any::type_name::<M>()
+            );
+            return;
+        };
+        registration.path = Some(self.path.clone());
+        let (format, transformed) = (registration.format, registration.transformed);
+
+        // The existing manifest stays in place until the new one has been processed.
+        world.resource_scope(|world, mut asset_server: Mut<AssetServer>| {
+            let mut raw_manifest_tracker = world.resource_mut::<RawManifestTracker>();
+            raw_manifest_tracker.register::<M>(self.path, asset_server.as_mut());
+            if let Some(status) = raw_manifest_tracker.status_mut::<M>() {
+                status.format = format;
+                status.transformed = transformed;
+                // Late manifests must never hold up or fail the manifests registered at startup.
+                status.required = false;
+            }
+        });
+    }
+}
+
+/// An extension trait for registering manifests after the app has started.
+pub trait RegisterManifestAtRuntime {
+    /// Registers the manifest `M`, loading it from `path`.
+    ///
+    /// See [`RegisterManifestCommand`] for more information.
+    fn register_manifest<M: Manifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+}
+
+impl RegisterManifestAtRuntime for Commands<'_, '_> {
+    fn register_manifest<M: Manifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.add(RegisterManifestCommand::<M>::new(path));
+        self
+    }
+}
+
+impl RegisterManifestAtRuntime for World {
+    fn register_manifest<M: Manifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        RegisterManifestCommand::<M>::new(path).apply(self);
+        self
+    }
+}
+
+/// A run condition which returns true if the manifest `M` was registered late, and has not finished loading.
+pub fn late_manifest_pending<M: Manifest>(
+    registration: Option<Res<LateManifestRegistration<M>>>,
+) -> bool {
+    registration.is_some_and(|registration| registration.path.is_some())
+}
+
+/// A run condition which returns true if the raw manifest of a late-registered manifest `M` has loaded, and is ready to be processed.
+pub fn late_manifest_loaded<M: Manifest>(
+    registration: Option<Res<LateManifestRegistration<M>>>,
+    raw_manifest_tracker: Res<RawManifestTracker>,
+) -> bool {
+    late_manifest_pending(registration)
+        && raw_manifest_tracker
+            .status::<M>()
+            .is_some_and(|status| status.load_state == LoadState::Loaded)
+}
+
+/// Updates the load state of a late-registered manifest `M`.
+///
+/// If the raw manifest fails to load, the registration is abandoned.
+pub fn update_late_manifest_load_state<M: Manifest>(
+    asset_server: Res<AssetServer>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    mut registration: ResMut<LateManifestRegistration<M>>,
+) {
+    let Some(status) = raw_manifest_tracker.status_mut::<M>() else {
+        return;
+    };
+
+    match asset_server.get_load_state(status.handle.clone_weak()) {
+        Some(LoadState::Loaded) => status.load_state = LoadState::Loaded,
+        Some(LoadState::Failed) => {
+            status.load_state = LoadState::Failed;
+            error!(
+                "The manifest {} registered after startup failed to load from {}.",
+                std::any::type_name::<M>(),
+                status.path.display()
+            );
+            registration.path = None;
+        }
+        _ => (),
+    }
+}
+
+/// Resets the [`ManifestProvenance`] of a late-registered manifest `M`, just before its new raw manifest is processed.
+///
+/// This is deferred until now so that the provenance keeps describing the existing manifest while the new one loads.
+pub fn begin_late_processing<M: Manifest>(
+    registration: Res<LateManifestRegistration<M>>,
+    mut commands: Commands,
+) {
+    if let Some(path) = &registration.path {
+        commands.insert_resource(ManifestProvenance::<M>::from_file(ItemSource::new(path)));
+    }
+}
+
+/// Marks the late registration of the manifest `M` as finished, once it has been processed.
+///
+/// Processing failures are reported by [`process_manifest`], so are not retried.
+pub fn finish_late_registration<M: Manifest>(
+    mut registration: ResMut<LateManifestRegistration<M>>,
+) {
+    registration.path = None;
+}
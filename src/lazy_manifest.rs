@@ -0,0 +1,761 @@
+//! Storage for huge manifests, whose items are only read from disk when they are first needed.
+//!
+//! Ordinary manifests deserialize every item up front, which is impractical for datasets like world chunks or dialogue,
+//! where the full data is much larger than what is needed at any one time.
+//! A [`LazyManifest`] instead loads an index, which maps each item's name to the [`ItemLocation`] of its data,
+//! and reads each item on demand: either synchronously with [`LazyManifest::fetch`],
+//! or in the background with [`LazyManifest::request`].
+//! Once read, items are available through the usual [`Manifest::get`] API,
+//! and can optionally be evicted again once too many are resident.
+//!
+//! The raw manifest is the index, and the manifest wraps a [`LazyManifest`]:
+//!
+//! ```rust,ignore
+//! #[derive(Asset, TypePath, Deserialize)]
+//! struct RawChunkIndex {
+//!     chunks: Vec<LazyIndexEntry>,
+//! }
+//!
+//! #[derive(Resource)]
+//! struct ChunkManifest {
+//!     chunks: LazyManifest<Chunk>,
+//! }
+//!
+//! impl Manifest for ChunkManifest {
+//!     type RawManifest = RawChunkIndex;
+//!     type RawItem = LazyIndexEntry;
+//!     type Item = Chunk;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//!
+//!     fn from_raw_manifest(raw_manifest: RawChunkIndex, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//!         let chunks = LazyManifest::new("assets/chunks", ManifestFormat::Bincode, raw_manifest.chunks).with_capacity(256);
+//!         Ok(ChunkManifest { chunks })
+//!     }
+//!
+//!     fn get(&self, id: Id<Chunk>) -> Option<&Chunk> {
+//!         self.chunks.get(id)
+//!     }
+//! }
+//!
+//! impl AsLazyManifest for ChunkManifest {
+//!     fn lazy(&self) -> &LazyManifest<Chunk> {
+//!         &self.chunks
+//!     }
+//!
+//!     fn lazy_mut(&mut self) -> &mut LazyManifest<Chunk> {
+//!         &mut self.chunks
+//!     }
+//! }
+//!
+//! app.add_plugins(LazyManifestPlugin::<ChunkManifest>::default());
+//! ```
+//!
+//! To avoid hitches when items are first used, they can be prefetched in the background with the [`PrefetchManifestItems`] command.
+//! A [`PrefetchComplete`] event is sent once every item in the batch has been read:
+//!
+//! ```rust,ignore
+//! fn start_level(level: Res<NextLevel>, mut commands: Commands) {
+//!     commands.add(PrefetchManifestItems::<ChunkManifest>::new(level.chunks.iter().copied()));
+//! }
+//!
+//! fn level_ready(mut events: EventReader<PrefetchComplete<ChunkManifest>>) {
+//!     for event in events.read() {
+//!         info!("Prefetched {} chunks, {} failed", event.loaded.len(), event.failed.len());
+//!     }
+//! }
+//! ```
+//!
+//! This module requires the `lazy` feature, which enables Bevy's `multi-threaded` feature so that background reads return their results.
+//! It is not available on `wasm32`, as items are read directly from the filesystem.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::Command;
+use bevy::log::error;
+use bevy::tasks::{block_on, IoTaskPool, Task};
+use bevy::utils::{HashMap, HashSet};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::identifier::Id;
+use crate::loader::{deserialize_raw_manifest, ManifestLoaderError};
+use crate::manifest::{Manifest, ManifestFormat};
+
+/// Where the serialized data for a single item is stored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ItemLocation {
+    /// The file containing the item, relative to the root directory of the [`LazyManifest`].
+    pub path: PathBuf,
+    /// The offset of the item's data within the file, in bytes.
+    #[serde(default)]
+    pub offset: u64,
+    /// The length of the item's data, in bytes.
+    ///
+    /// If [`None`], the item extends to the end of the file.
+    #[serde(default)]
+    pub len: Option<u64>,
+}
+
+impl ItemLocation {
+    /// An item stored on its own, as the entire contents of the file at `path`.
+    #[must_use]
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+            len: None,
+        }
+    }
+}
+
+/// An entry in the index of a [`LazyManifest`], as stored in the raw manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LazyIndexEntry {
+    /// The name of the item, used to compute its [`Id`].
+    pub name: String,
+    /// Where the item's data is stored.
+    pub location: ItemLocation,
+}
+
+/// An item which has been read from disk, along with when it was last used.
+struct ResidentItem<T> {
+    item: T,
+    last_used: AtomicU64,
+}
+
+/// A manifest storage strategy which reads items from disk on demand.
+///
+/// See the [module docs](crate::lazy_manifest) for more information.
+pub struct LazyManifest<T> {
+    root: PathBuf,
+    format: ManifestFormat,
+    index: HashMap<Id<T>, ItemLocation>,
+    resident: HashMap<Id<T>, ResidentItem<T>>,
+    pending: HashMap<Id<T>, Task<Result<T, ManifestLoaderError>>>,
+    capacity: Option<usize>,
+    clock: AtomicU64,
+}
+
+impl<T> LazyManifest<T> {
+    /// Creates a new lazy manifest from its index.
+    ///
+    /// Each item is read from the file at its [`ItemLocation`], relative to `root`, and deserialized using `format`.
+    /// Like [`load_raw_manifest_blocking`](crate::blocking::load_raw_manifest_blocking), `root` is relative to the current working directory,
+    /// not the asset folder.
+    #[must_use]
+    pub fn new(
+        root: impl Into<PathBuf>,
+        format: ManifestFormat,
+        entries: impl IntoIterator<Item = LazyIndexEntry>,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            format,
+            index: entries
+                .into_iter()
+                .map(|entry| (Id::from_name(&entry.name), entry.location))
+                .collect(),
+            resident: HashMap::default(),
+            pending: HashMap::default(),
+            capacity: None,
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    /// Limits the number of resident items.
+    ///
+    /// When an item is read and the limit is exceeded, the least recently used item is evicted.
+    /// Items are used when they are read, and each time they are returned by [`get`](Self::get).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "ron")]
+    /// # fn main() {
+    /// use leafwing_manifest::identifier::Id;
+    /// use leafwing_manifest::lazy_manifest::{ItemLocation, LazyIndexEntry, LazyManifest};
+    /// use leafwing_manifest::manifest::ManifestFormat;
+    ///
+    /// // Each item is stored in its own file.
+    /// let root = std::env::temp_dir().join("leafwing_manifest_lazy_capacity");
+    /// std::fs::create_dir_all(&root).unwrap();
+    /// let entries = [("a", 1), ("b", 2), ("c", 3)].map(|(name, value)| {
+    ///     let path = format!("{name}.ron");
+    ///     std::fs::write(root.join(&path), value.to_string()).unwrap();
+    ///     LazyIndexEntry {
+    ///         name: name.to_string(),
+    ///         location: ItemLocation::file(path),
+    ///     }
+    /// });
+    ///
+    /// let mut items = LazyManifest::<u32>::new(&root, ManifestFormat::Ron, entries).with_capacity(2);
+    /// let [a, b, c] = ["a", "b", "c"].map(Id::<u32>::from_name);
+    ///
+    /// assert_eq!(items.fetch(a).unwrap(), Some(&1));
+    /// assert_eq!(items.fetch(b).unwrap(), Some(&2));
+    /// // Using `a` again makes `b` the least recently used item, so it is evicted to make room for `c`.
+    /// assert_eq!(items.get(a), Some(&1));
+    /// assert_eq!(items.fetch(c).unwrap(), Some(&3));
+    /// assert_eq!(items.resident_len(), 2);
+    /// assert_eq!(items.get(b), None);
+    ///
+    /// // Evicted items stay in the index, and are read again on demand, evicting `a` in turn.
+    /// assert!(items.contains(b));
+    /// assert_eq!(items.fetch(b).unwrap(), Some(&2));
+    /// assert!(items.is_resident(c) && !items.is_resident(a));
+    /// # }
+    /// # #[cfg(not(feature = "ron"))]
+    /// # fn main() {}
+    /// ```
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        // A capacity of zero would evict every item as soon as it was read.
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Gets an item by its [`Id`], if it is resident.
+    ///
+    /// This never reads from disk: use [`fetch`](Self::fetch) or [`request`](Self::request) to load items first.
+    #[must_use]
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        let resident = self.resident.get(&id)?;
+        resident.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(&resident.item)
+    }
+
+    /// Evicts the item with the given [`Id`], returning it if it was resident.
+    ///
+    /// The item remains in the index, and can be read again later.
+    pub fn evict(&mut self, id: Id<T>) -> Option<T> {
+        self.resident.remove(&id).map(|resident| resident.item)
+    }
+
+    /// Evicts every resident item, and cancels any pending requests.
+    pub fn evict_all(&mut self) {
+        self.resident.clear();
+        self.pending.clear();
+    }
+
+    /// Returns true if the item with the given [`Id`] is in the index, whether or not it is resident.
+    #[must_use]
+    pub fn contains(&self, id: Id<T>) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    /// Returns true if the item with the given [`Id`] has been read, and can be returned by [`get`](Self::get).
+    #[must_use]
+    pub fn is_resident(&self, id: Id<T>) -> bool {
+        self.resident.contains_key(&id)
+    }
+
+    /// Returns true if the item with the given [`Id`] is being read in the background.
+    #[must_use]
+    pub fn is_pending(&self, id: Id<T>) -> bool {
+        self.pending.contains_key(&id)
+    }
+
+    /// Returns where the item with the given [`Id`] is stored, if it is in the index.
+    #[must_use]
+    pub fn location(&self, id: Id<T>) -> Option<&ItemLocation> {
+        self.index.get(&id)
+    }
+
+    /// Iterates over the [`Id`] of every item in the index, in arbitrary order.
+    pub fn ids(&self) -> impl Iterator<Item = Id<T>> + '_ {
+        self.index.keys().copied()
+    }
+
+    /// Returns the number of items in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns true if the index is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the number of resident items.
+    #[must_use]
+    pub fn resident_len(&self) -> usize {
+        self.resident.len()
+    }
+
+    /// Returns the number of items being read in the background.
+    #[must_use]
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn insert_resident(&mut self, id: Id<T>, item: T) {
+        let last_used = AtomicU64::new(self.tick());
+        self.resident.insert(id, ResidentItem { item, last_used });
+
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while self.resident.len() > capacity {
+            let least_recently_used = self
+                .resident
+                .iter()
+                .filter(|(resident_id, _)| **resident_id != id)
+                .min_by_key(|(_, resident)| resident.last_used.load(Ordering::Relaxed))
+                .map(|(resident_id, _)| *resident_id);
+
+            match least_recently_used {
+                Some(evicted) => self.resident.remove(&evicted),
+                None => break,
+            };
+        }
+    }
+}
+
+impl<T: DeserializeOwned + Send + 'static> LazyManifest<T> {
+    /// Gets an item by its [`Id`], reading it from disk first if it is not resident.
+    ///
+    /// This blocks until the item has been read. Returns `Ok(None)` if the item is not in the index.
+    pub fn fetch(&mut self, id: Id<T>) -> Result<Option<&T>, ManifestLoaderError> {
+        if !self.resident.contains_key(&id) {
+            let Some(location) = self.index.get(&id) else {
+                return Ok(None);
+            };
+
+            // Reading the item now makes any background request redundant.
+            self.pending.remove(&id);
+            let item = read_item(&self.root.join(&location.path), location, self.format)?;
+            self.insert_resident(id, item);
+        }
+
+        Ok(self.get(id))
+    }
+
+    /// Starts reading the item with the given [`Id`] in the background, unless it is already resident or pending.
+    ///
+    /// The item becomes available once [`poll_pending`](Self::poll_pending) sees that it has finished loading,
+    /// which the [`LazyManifestPlugin`] does every frame.
+    /// Returns false if the item is not in the index.
+    pub fn request(&mut self, id: Id<T>) -> bool {
+        let Some(location) = self.index.get(&id) else {
+            return false;
+        };
+
+        if !self.resident.contains_key(&id) && !self.pending.contains_key(&id) {
+            let path = self.root.join(&location.path);
+            let location = location.clone();
+            let format = self.format;
+            let task = IoTaskPool::get().spawn(async move { read_item(&path, &location, format) });
+            self.pending.insert(id, task);
+        }
+
+        true
+    }
+
+    /// Starts reading each of the items with the given `ids` in the background, as with [`request`](Self::request).
+    ///
+    /// This is useful to warm items before they are needed, such as every item used by the next level.
+    /// Note that if more items are prefetched than the capacity allows, the earliest ones will be evicted again.
+    /// Returns the number of `ids` that were found in the index.
+    pub fn prefetch(&mut self, ids: impl IntoIterator<Item = Id<T>>) -> usize {
+        ids.into_iter().filter(|id| self.request(*id)).count()
+    }
+
+    /// Makes any items which have finished loading in the background resident.
+    ///
+    /// Returns the result of each request that finished.
+    pub fn poll_pending(&mut self) -> Vec<(Id<T>, Result<(), ManifestLoaderError>)> {
+        let finished: Vec<Id<T>> = self
+            .pending
+            .iter()
+            .filter(|(_, task)| task.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+
+        finished
+            .into_iter()
+            .filter_map(|id| {
+                let task = self.pending.remove(&id)?;
+                let result = block_on(task).map(|item| self.insert_resident(id, item));
+                Some((id, result))
+            })
+            .collect()
+    }
+}
+
+impl<T> std::fmt::Debug for LazyManifest<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyManifest")
+            .field("root", &self.root)
+            .field("format", &self.format)
+            .field("len", &self.index.len())
+            .field("resident", &self.resident.len())
+            .field("pending", &self.pending.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// Reads and deserializes a single item.
+fn read_item<T: DeserializeOwned>(
+    path: &Path,
+    location: &ItemLocation,
+    format: ManifestFormat,
+) -> Result<T, ManifestLoaderError> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(location.offset))?;
+
+    let mut bytes = Vec::new();
+    file.take(location.len.unwrap_or(u64::MAX))
+        .read_to_end(&mut bytes)?;
+    if location.len.is_some_and(|len| bytes.len() as u64 != len) {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+
+    deserialize_raw_manifest(format, &bytes)
+}
+
+/// A [`Manifest`] which stores its items in a [`LazyManifest`].
+///
+/// This allows the [`LazyManifestPlugin`] to finish background requests.
+pub trait AsLazyManifest: Manifest {
+    /// Returns the lazy storage of this manifest.
+    fn lazy(&self) -> &LazyManifest<Self::Item>;
+
+    /// Returns the lazy storage of this manifest, mutably.
+    fn lazy_mut(&mut self) -> &mut LazyManifest<Self::Item>;
+}
+
+/// An event sent when an item of the manifest `M` has finished loading in the background.
+#[derive(Event)]
+pub struct LazyItemLoaded<M: Manifest> {
+    /// The item that was loaded.
+    pub id: Id<M::Item>,
+}
+
+/// An event sent when an item of the manifest `M` could not be loaded in the background.
+#[derive(Event)]
+pub struct LazyItemLoadFailed<M: Manifest> {
+    /// The item that could not be loaded.
+    pub id: Id<M::Item>,
+    /// Why the item could not be loaded.
+    pub error: ManifestLoaderError,
+}
+
+/// An event sent when every item requested by a [`PrefetchManifestItems`] command has finished loading.
+#[derive(Event)]
+pub struct PrefetchComplete<M: Manifest> {
+    /// The items that are now resident.
+    pub loaded: Vec<Id<M::Item>>,
+    /// The items that could not be loaded, including any that were not in the index.
+    pub failed: Vec<Id<M::Item>>,
+}
+
+/// A [`Command`] which prefetches the items with the given [`Id`]s from the manifest `M` in the background.
+///
+/// A [`PrefetchComplete`] event is sent once they have all finished loading.
+/// The [`LazyManifestPlugin`] for `M` must be added.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "ron")]
+/// # fn main() {
+/// use bevy::ecs::system::Command;
+/// use bevy::prelude::*;
+/// use leafwing_manifest::identifier::Id;
+/// use leafwing_manifest::lazy_manifest::*;
+/// use leafwing_manifest::manifest::{Manifest, ManifestFormat};
+/// use serde::Deserialize;
+///
+/// #[derive(Asset, TypePath, Deserialize)]
+/// struct RawChunkIndex {
+///     chunks: Vec<LazyIndexEntry>,
+/// }
+///
+/// #[derive(Resource)]
+/// struct ChunkManifest {
+///     chunks: LazyManifest<u32>,
+/// }
+///
+/// impl Manifest for ChunkManifest {
+///     type RawManifest = RawChunkIndex;
+///     type RawItem = LazyIndexEntry;
+///     type Item = u32;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+///
+///     fn from_raw_manifest(raw: RawChunkIndex, _world: &mut World) -> Result<Self, Self::ConversionError> {
+///         let chunks = LazyManifest::new("assets/chunks", ManifestFormat::Ron, raw.chunks);
+///         Ok(ChunkManifest { chunks })
+///     }
+///
+///     fn get(&self, id: Id<u32>) -> Option<&u32> {
+///         self.chunks.get(id)
+///     }
+/// }
+///
+/// impl AsLazyManifest for ChunkManifest {
+///     fn lazy(&self) -> &LazyManifest<u32> {
+///         &self.chunks
+///     }
+///
+///     fn lazy_mut(&mut self) -> &mut LazyManifest<u32> {
+///         &mut self.chunks
+///     }
+/// }
+///
+/// let root = std::env::temp_dir().join("leafwing_manifest_prefetch");
+/// std::fs::create_dir_all(&root).unwrap();
+/// let entries = [("hills", 1), ("lake", 2)].map(|(name, value)| {
+///     let path = format!("{name}.ron");
+///     std::fs::write(root.join(&path), value.to_string()).unwrap();
+///     LazyIndexEntry {
+///         name: name.to_string(),
+///         location: ItemLocation::file(path),
+///     }
+/// });
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, LazyManifestPlugin::<ChunkManifest>::default()))
+///     .insert_resource(ChunkManifest {
+///         chunks: LazyManifest::new(&root, ManifestFormat::Ron, entries),
+///     });
+///
+/// let [hills, lake, desert] = ["hills", "lake", "desert"].map(Id::<u32>::from_name);
+/// PrefetchManifestItems::<ChunkManifest>::new([hills, lake, desert]).apply(&mut app.world);
+///
+/// // The items are read in the background, so wait for the batch to complete.
+/// let mut complete = None;
+/// for _ in 0..1000 {
+///     app.update();
+///     let events = app.world.resource::<Events<PrefetchComplete<ChunkManifest>>>();
+///     let mut reader = events.get_reader();
+///     if let Some(event) = reader.read(events).next() {
+///         complete = Some((event.loaded.clone(), event.failed.clone()));
+///         break;
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+///
+/// let (loaded, failed) = complete.expect("The prefetch never completed.");
+/// assert_eq!(loaded.len(), 2);
+/// assert!(loaded.contains(&hills) && loaded.contains(&lake));
+/// // Items that are not in the index fail immediately.
+/// assert_eq!(failed, vec![desert]);
+///
+/// let manifest = app.world.resource::<ChunkManifest>();
+/// assert_eq!(manifest.get(hills), Some(&1));
+/// assert_eq!(manifest.get(lake), Some(&2));
+/// assert!(app.world.resource::<PrefetchBatches<ChunkManifest>>().is_empty());
+/// # }
+/// # #[cfg(not(feature = "ron"))]
+/// # fn main() {}
+/// ```
+pub struct PrefetchManifestItems<M: Manifest> {
+    ids: Vec<Id<M::Item>>,
+}
+
+impl<M: Manifest> PrefetchManifestItems<M> {
+    /// Creates a command which prefetches the items with the given `ids`.
+    #[must_use]
+    pub fn new(ids: impl IntoIterator<Item = Id<M::Item>>) -> Self {
+        Self {
+            ids: ids.into_iter().collect(),
+        }
+    }
+}
+
+impl<M: AsLazyManifest> Command for PrefetchManifestItems<M>
+where
+    M::Item: DeserializeOwned + Send + 'static,
+{
+    fn apply(self, world: &mut World) {
+        let Some(mut manifest) = world.get_resource_mut::<M>() else {
+            error!(
+                "Could not prefetch items: the {} manifest does not exist.",
+                std::any::type_name::<M>()
+            );
+            return;
+        };
+
+        let lazy = manifest.lazy_mut();
+        let mut batch = PrefetchBatch::<M> {
+            remaining: HashSet::default(),
+            loaded: Vec::new(),
+            failed: Vec::new(),
+        };
+        for id in self.ids {
+            if !lazy.request(id) {
+                batch.failed.push(id);
+            } else if lazy.is_pending(id) {
+                batch.remaining.insert(id);
+            } else {
+                batch.loaded.push(id);
+            }
+        }
+
+        world
+            .resource_mut::<PrefetchBatches<M>>()
+            .batches
+            .push(batch);
+    }
+}
+
+/// A group of items prefetched by a single [`PrefetchManifestItems`] command.
+struct PrefetchBatch<M: Manifest> {
+    remaining: HashSet<Id<M::Item>>,
+    loaded: Vec<Id<M::Item>>,
+    failed: Vec<Id<M::Item>>,
+}
+
+/// The batches of items being prefetched for the manifest `M`.
+#[derive(Resource)]
+pub struct PrefetchBatches<M: Manifest> {
+    batches: Vec<PrefetchBatch<M>>,
+}
+
+impl<M: Manifest> PrefetchBatches<M> {
+    /// Returns the number of batches which are still loading.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Returns true if no batches are loading.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+impl<M: Manifest> Default for PrefetchBatches<M> {
+    fn default() -> Self {
+        Self {
+            batches: Vec::new(),
+        }
+    }
+}
+
+/// A plugin which finishes background requests made with [`LazyManifest::request`] for the manifest `M`.
+pub struct LazyManifestPlugin<M: AsLazyManifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: AsLazyManifest> Default for LazyManifestPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: AsLazyManifest> Plugin for LazyManifestPlugin<M>
+where
+    M::Item: DeserializeOwned + Send + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrefetchBatches<M>>()
+            .add_event::<LazyItemLoaded<M>>()
+            .add_event::<LazyItemLoadFailed<M>>()
+            .add_event::<PrefetchComplete<M>>()
+            .add_systems(
+                PreUpdate,
+                poll_lazy_manifest::<M>.run_if(resource_exists::<M>),
+            );
+    }
+}
+
+/// Makes any items of the manifest `M` which have finished loading in the background resident,
+/// and sends the corresponding events.
+///
+/// Errors are also logged.
+pub fn poll_lazy_manifest<M: AsLazyManifest>(
+    mut manifest: ResMut<M>,
+    mut batches: ResMut<PrefetchBatches<M>>,
+    mut loaded_events: EventWriter<LazyItemLoaded<M>>,
+    mut failed_events: EventWriter<LazyItemLoadFailed<M>>,
+    mut complete_events: EventWriter<PrefetchComplete<M>>,
+) where
+    M::Item: DeserializeOwned + Send + 'static,
+{
+    // Checking first avoids triggering change detection every frame.
+    if manifest.lazy().pending_len() > 0 {
+        for (id, result) in manifest.lazy_mut().poll_pending() {
+            let succeeded = result.is_ok();
+            for batch in &mut batches.batches {
+                if batch.remaining.remove(&id) {
+                    if succeeded {
+                        batch.loaded.push(id);
+                    } else {
+                        batch.failed.push(id);
+                    }
+                }
+            }
+
+            match result {
+                Ok(()) => {
+                    loaded_events.send(LazyItemLoaded { id });
+                }
+                Err(error) => {
+                    error!(
+                        "Could not load {id:?} for the {} manifest: {error}",
+                        std::any::type_name::<M>()
+                    );
+                    failed_events.send(LazyItemLoadFailed { id, error });
+                }
+            }
+        }
+    }
+
+    // Items may also stop being pending without finishing, such as when they are fetched synchronously or evicted.
+    let lazy = manifest.lazy();
+    for batch in &mut batches.batches {
+        let (resident, cancelled): (Vec<_>, Vec<_>) = batch
+            .remaining
+            .iter()
+            .copied()
+            .filter(|id| !lazy.is_pending(*id))
+            .partition(|id| lazy.is_resident(*id));
+        for id in resident {
+            batch.remaining.remove(&id);
+            batch.loaded.push(id);
+        }
+        for id in cancelled {
+            batch.remaining.remove(&id);
+            batch.failed.push(id);
+        }
+    }
+
+    if batches
+        .batches
+        .iter()
+        .any(|batch| batch.remaining.is_empty())
+    {
+        let (complete, incomplete): (Vec<PrefetchBatch<M>>, _) =
+            std::mem::take(&mut batches.batches)
+                .into_iter()
+                .partition(|batch| batch.remaining.is_empty());
+        batches.batches = incomplete;
+        for batch in complete {
+            complete_events.send(PrefetchComplete {
+                loaded: batch.loaded,
+                failed: batch.failed,
+            });
+        }
+    }
+}
@@ -1,6 +1,105 @@
 #![doc = include_str!("../README.md")]
 
+pub mod aliases;
 pub mod asset_state;
+pub mod asset_store;
+#[cfg(feature = "atlas")]
+pub mod atlas;
+#[cfg(feature = "audio")]
+pub mod audio;
+#[cfg(all(
+    feature = "baked_manifests",
+    not(any(target_arch = "wasm32", target_os = "android"))
+))]
+pub mod baked_manifest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod blocking;
+pub mod bundles;
+pub mod conditions;
+#[cfg(feature = "console")]
+pub mod console;
+pub mod dead_entries;
+pub mod defaults;
+pub mod dense_manifest;
+pub mod derived;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod driver;
+pub mod duplicates;
+#[cfg(all(feature = "editor", not(target_arch = "wasm32")))]
+pub mod editor;
+pub mod expr;
+#[cfg(all(feature = "file_watch", not(target_arch = "wasm32")))]
+pub mod file_watch;
+pub mod frozen_manifest;
+#[cfg(feature = "fuzzy")]
+pub mod fuzzy;
+pub mod history;
+pub mod id_constants;
 pub mod identifier;
+pub mod incremental;
+pub mod inheritance;
+#[cfg(feature = "interned")]
+pub mod interned;
+pub mod item_docs;
+pub mod join;
+pub mod keyed_manifest;
+pub mod late_registration;
+#[cfg(all(feature = "lazy", not(target_arch = "wasm32")))]
+pub mod lazy_manifest;
+pub mod lifecycle;
+pub mod lints;
+pub mod loader;
+pub mod loading_timeline;
 pub mod manifest;
+pub mod manifest_asset;
+pub mod manifest_folder;
+pub mod manifest_lock;
+pub mod merge;
+pub mod migrations;
+pub mod missing_lookups;
+pub mod multi_world;
+pub mod name_registry;
+pub mod network_index;
+pub mod overlay;
 pub mod plugin;
+pub mod prelude;
+pub mod procedural_assets;
+pub mod processing_hooks;
+pub mod processing_report;
+#[cfg(feature = "progress_tracking")]
+pub mod progress;
+pub mod prototype;
+pub mod provenance;
+#[cfg(any(feature = "csv", feature = "toml"))]
+pub mod raw_manifests;
+pub mod reference_graph;
+pub mod references;
+pub mod registration;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod sanitized;
+#[cfg(feature = "scenes")]
+pub mod scene;
+#[cfg(feature = "search")]
+pub mod search;
+pub mod shared_manifest;
+pub mod snapshot;
+pub mod sorted_index;
+pub mod split_manifest;
+pub mod stall_detection;
+pub mod stats;
+pub mod summary;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+pub mod transaction;
+pub mod ui_list;
+pub mod usage;
+#[cfg(feature = "uuid")]
+pub mod uuid_id;
+pub mod validation;
+#[cfg(feature = "value_types")]
+pub mod value_types;
+pub mod view;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod write_back;
@@ -0,0 +1,123 @@
+//! Hooks for forwarding manifest lifecycle events to your own logging or analytics.
+//!
+//! Every manifest passes through the same stages: its raw manifest starts loading, finishes loading,
+//! and is then processed, which either succeeds or fails.
+//! Each of these transitions is recorded as a structured [`ManifestLifecycleEvent`],
+//! which is passed to every hook in the [`ManifestLifecycleHooks`] resource.
+//!
+//! ```rust,ignore
+//! app.insert_resource(ManifestLifecycleHooks::default().with_hook(|event: &ManifestLifecycleEvent| {
+//!     analytics::record("manifest", event.type_name, format!("{:?}", event.stage));
+//! }));
+//! ```
+//!
+//! Events are collected by the [`RawManifestTracker`] as they happen,
+//! and dispatched to the hooks once per update by [`dispatch_lifecycle_events`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::Instant;
+
+use crate::plugin::RawManifestTracker;
+
+/// A stage in the lifecycle of a single manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestLifecycleStage {
+    /// The manifest was registered, and its raw manifest started loading.
+    LoadStarted,
+    /// The raw manifest finished loading.
+    LoadCompleted,
+    /// The raw manifest could not be loaded.
+    LoadFailed,
+    /// The raw manifest started being processed into the manifest.
+    ProcessingStarted,
+    /// The manifest was processed successfully.
+    ProcessingSucceeded,
+    /// The manifest could not be processed.
+    ProcessingFailed,
+}
+
+/// Structured information about a single stage in the lifecycle of a manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestLifecycleEvent {
+    /// The stage that was reached.
+    pub stage: ManifestLifecycleStage,
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The path to the raw manifest.
+    pub path: PathBuf,
+    /// When the stage was reached.
+    pub at: Instant,
+    /// The number of items in the manifest, once it has been processed.
+    pub item_count: Option<usize>,
+    /// How long processing took, once it has finished.
+    pub duration: Option<Duration>,
+    /// A description of the error, for the failure stages.
+    pub error: Option<String>,
+}
+
+/// A function which is called with each [`ManifestLifecycleEvent`].
+pub type ManifestLifecycleHook = Arc<dyn Fn(&ManifestLifecycleEvent) + Send + Sync>;
+
+/// The hooks that are called for each [`ManifestLifecycleEvent`].
+///
+/// Insert this resource to start receiving events.
+#[derive(Resource, Default, Clone)]
+pub struct ManifestLifecycleHooks {
+    hooks: Vec<ManifestLifecycleHook>,
+}
+
+impl std::fmt::Debug for ManifestLifecycleHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestLifecycleHooks")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+impl ManifestLifecycleHooks {
+    /// Adds a hook, which will be called with every future [`ManifestLifecycleEvent`].
+    pub fn add_hook(&mut self, hook: impl Fn(&ManifestLifecycleEvent) + Send + Sync + 'static) {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    /// Adds a hook, returning `self` for chaining.
+    #[must_use]
+    pub fn with_hook(
+        mut self,
+        hook: impl Fn(&ManifestLifecycleEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.add_hook(hook);
+        self
+    }
+
+    /// Calls every hook with the supplied `event`.
+    pub fn notify(&self, event: &ManifestLifecycleEvent) {
+        for hook in &self.hooks {
+            hook(event);
+        }
+    }
+}
+
+/// Passes the lifecycle events collected by the [`RawManifestTracker`] to the [`ManifestLifecycleHooks`].
+///
+/// Events are discarded if there are no hooks.
+pub fn dispatch_lifecycle_events(
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    hooks: Option<Res<ManifestLifecycleHooks>>,
+) {
+    // Draining the queue is bookkeeping, and should not mark the tracker as changed every frame.
+    let events = raw_manifest_tracker
+        .bypass_change_detection()
+        .drain_lifecycle_events();
+    let Some(hooks) = hooks else {
+        return;
+    };
+
+    for event in &events {
+        hooks.notify(event);
+    }
+}
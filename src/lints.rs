@@ -0,0 +1,525 @@
+//! Style checks for the content of manifests, which report warnings rather than failing to load.
+//!
+//! Hard validation belongs in [`Manifest::from_raw_manifest`]: if an item cannot be used, the manifest should not load.
+//! Lints are for everything else, such as a missing description, a price that is suspiciously high,
+//! or a name that does not follow the project's conventions.
+//! Each manifest declares its rules with a [`LintRules`] builder:
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestLintPlugin::new(
+//!     LintRules::<ItemManifest>::new()
+//!         .non_empty("description", |item: &Item| &item.description)
+//!         .within("price", |item: &Item| item.price, 0..=10_000)
+//!         .kebab_case_names(),
+//! ));
+//!
+//! fn check_content(report: Res<ValidationReport>) {
+//!     for lint in &report.lints {
+//!         println!("{lint}");
+//!     }
+//! }
+//! ```
+//!
+//! Whenever the manifest changes, every rule is checked against every item,
+//! and the warnings are logged and recorded in the [`ValidationReport`].
+//!
+//! Rules are declared in code, but can be turned off, or allowed for individual items, by a [`LintConfig`] file.
+//! [`LintConfig`] is itself a [`Manifest`], so content authors can silence a warning without recompiling:
+//!
+//! ```ron
+//! (
+//!     levels: { "kebab-case-names": Allow },
+//!     exceptions: [
+//!         // This item is deliberately free.
+//!         (item: "starter-sword", allow: ["price"]),
+//!     ],
+//! )
+//! ```
+//!
+//! ```rust,ignore
+//! app.register_manifest::<LintConfig<Item>>("item_lints.ron");
+//! ```
+//!
+//! By default, lint configuration files are read as RON.
+//! Other formats can be used with [`RawManifestOptions::with_format`](crate::plugin::RawManifestOptions::with_format).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use bevy::app::{App, Plugin};
+use bevy::asset::{Asset, UntypedAssetId, VisitAssetDependencies};
+use bevy::ecs::prelude::*;
+use bevy::log::warn;
+use bevy::reflect::TypePath;
+use serde::Deserialize;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::{IterableManifest, Manifest, ManifestFormat};
+use crate::name_registry::NameRegistry;
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+use crate::validation::ValidationReport;
+
+type Check<M> = Arc<
+    dyn Fn(Id<<M as Manifest>::Item>, &<M as Manifest>::Item) -> Result<(), String> + Send + Sync,
+>;
+
+/// Returns true if `name` is made of lowercase ASCII letters and digits, separated by single hyphens.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::lints::is_kebab_case;
+///
+/// assert!(is_kebab_case("iron-sword-2"));
+/// assert!(!is_kebab_case("Iron Sword"));
+/// assert!(!is_kebab_case("iron_sword"));
+/// assert!(!is_kebab_case("iron--sword"));
+/// assert!(!is_kebab_case(""));
+/// ```
+#[must_use]
+pub fn is_kebab_case(name: &str) -> bool {
+    name.split('-').all(|word| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    })
+}
+
+/// Whether a lint rule is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum LintLevel {
+    /// The rule is not checked.
+    Allow,
+    /// Items which break the rule are recorded in the [`ValidationReport`], and a warning is logged.
+    #[default]
+    Warn,
+}
+
+/// An item which broke a lint rule, as part of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The name of the rule that was broken.
+    pub rule: String,
+    /// The name of the item that broke the rule.
+    ///
+    /// Items without a name in the [`NameRegistry`] are listed by their [`Id`].
+    pub item: String,
+    /// Why the item broke the rule.
+    pub message: String,
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} [{}]: {}",
+            self.type_name, self.item, self.rule, self.message
+        )
+    }
+}
+
+/// A single rule in a [`LintRules`].
+struct LintRule<M: Manifest> {
+    name: String,
+    level: LintLevel,
+    check: Check<M>,
+}
+
+impl<M: Manifest> Clone for LintRule<M> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            level: self.level,
+            check: self.check.clone(),
+        }
+    }
+}
+
+/// The lint rules checked against each item of the manifest `M`.
+///
+/// Each rule has a name, which identifies it in warnings and in a [`LintConfig`].
+/// Rule names should be unique within a manifest.
+///
+/// See the [module docs](crate::lints) for more information.
+#[derive(Resource)]
+pub struct LintRules<M: Manifest> {
+    rules: Vec<LintRule<M>>,
+}
+
+impl<M: Manifest> LintRules<M> {
+    /// Creates an empty set of rules.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule named `name`, which passes if `check` returns `Ok`.
+    ///
+    /// The error message explains why the item broke the rule.
+    #[must_use]
+    pub fn rule(
+        self,
+        name: impl Into<String>,
+        check: impl Fn(&M::Item) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.rule_with_id(name, move |_id, item| check(item))
+    }
+
+    /// Adds a rule named `name`, which passes if `check` returns `Ok`.
+    ///
+    /// Unlike [`LintRules::rule`], `check` is also passed the [`Id`] of the item.
+    #[must_use]
+    pub fn rule_with_id(
+        mut self,
+        name: impl Into<String>,
+        check: impl Fn(Id<M::Item>, &M::Item) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.rules.push(LintRule {
+            name: name.into(),
+            level: LintLevel::Warn,
+            check: Arc::new(check),
+        });
+        self
+    }
+
+    /// Adds a rule named `name`, which passes if the text returned by `field` is not empty or whitespace.
+    #[must_use]
+    pub fn non_empty(
+        self,
+        name: impl Into<String>,
+        field: impl Fn(&M::Item) -> &str + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.into();
+        let message = format!("{name} must not be empty");
+        self.rule(name, move |item| {
+            if field(item).trim().is_empty() {
+                Err(message.clone())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Adds a rule named `name`, which passes if the value returned by `field` is within `range`.
+    #[must_use]
+    pub fn within<T>(
+        self,
+        name: impl Into<String>,
+        field: impl Fn(&M::Item) -> T + Send + Sync + 'static,
+        range: RangeInclusive<T>,
+    ) -> Self
+    where
+        T: PartialOrd + Display + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let label = name.clone();
+        self.rule(name, move |item| {
+            let value = field(item);
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{label} must be from {} to {}, but is {value}",
+                    range.start(),
+                    range.end()
+                ))
+            }
+        })
+    }
+
+    /// Adds a rule named `kebab-case-names`, which passes if the item's name is [kebab case](is_kebab_case).
+    ///
+    /// Items without a name in the [`NameRegistry`] always pass.
+    #[must_use]
+    pub fn kebab_case_names(self) -> Self {
+        self.rule_with_id(
+            "kebab-case-names",
+            |id, _item| match NameRegistry::name_of(id) {
+                Some(name) if !is_kebab_case(&name) => {
+                    Err(format!("the name {name:?} is not kebab case"))
+                }
+                _ => Ok(()),
+            },
+        )
+    }
+
+    /// Sets the level of the rule named `rule`.
+    ///
+    /// The levels set by a [`LintConfig`] take precedence over this.
+    #[must_use]
+    pub fn with_level(mut self, rule: &str, level: LintLevel) -> Self {
+        for lint_rule in self
+            .rules
+            .iter_mut()
+            .filter(|lint_rule| lint_rule.name == rule)
+        {
+            lint_rule.level = level;
+        }
+        self
+    }
+
+    /// The names of each rule, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.name.as_str())
+    }
+
+    /// The number of rules.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns true if there are no rules.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl<M: IterableManifest> LintRules<M> {
+    /// Checks each rule against each item in the `manifest`, returning a warning for each rule that was broken.
+    ///
+    /// The levels and exceptions in the `config` are respected, if one is supplied.
+    /// Warnings are sorted by item, then by the order the rules were added.
+    #[must_use]
+    pub fn check(&self, manifest: &M, config: Option<&LintConfig<M::Item>>) -> Vec<LintWarning> {
+        let type_name = std::any::type_name::<M>();
+        let mut warnings: Vec<(String, usize, LintWarning)> = Vec::new();
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let level = config
+                .and_then(|config| config.levels.get(&rule.name).copied())
+                .unwrap_or(rule.level);
+            if level == LintLevel::Allow {
+                continue;
+            }
+
+            for (id, item) in manifest.iter() {
+                if config.is_some_and(|config| config.is_allowed(id, &rule.name)) {
+                    continue;
+                }
+
+                if let Err(message) = (rule.check)(id, item) {
+                    let item = NameRegistry::name_of(id).unwrap_or_else(|| format!("{id:?}"));
+                    warnings.push((
+                        item.clone(),
+                        index,
+                        LintWarning {
+                            type_name,
+                            rule: rule.name.clone(),
+                            item,
+                            message,
+                        },
+                    ));
+                }
+            }
+        }
+
+        warnings.sort_by(|(a_item, a_index, _), (b_item, b_index, _)| {
+            a_item.cmp(b_item).then(a_index.cmp(b_index))
+        });
+        warnings
+            .into_iter()
+            .map(|(_, _, warning)| warning)
+            .collect()
+    }
+}
+
+impl<M: Manifest> Default for LintRules<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Manifest> Clone for LintRules<M> {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for LintRules<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// A single entry in the exceptions of a [`RawLintConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RawLintException {
+    /// The name of the item.
+    pub item: String,
+    /// The names of the rules that this item is allowed to break.
+    pub allow: Vec<String>,
+}
+
+/// The raw manifest of a [`LintConfig`] for items of type `T`.
+#[derive(Debug, Clone, PartialEq, Eq, TypePath, Deserialize)]
+pub struct RawLintConfig<T: TypePath> {
+    /// The level of each rule, overriding the level set in the [`LintRules`].
+    #[serde(default)]
+    pub levels: BTreeMap<String, LintLevel>,
+    /// The rules that individual items are allowed to break.
+    #[serde(default)]
+    pub exceptions: Vec<RawLintException>,
+    #[serde(skip)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: TypePath> RawLintConfig<T> {
+    /// Creates a raw manifest from the supplied levels and exceptions.
+    #[must_use]
+    pub fn new(levels: BTreeMap<String, LintLevel>, exceptions: Vec<RawLintException>) -> Self {
+        Self {
+            levels,
+            exceptions,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: TypePath + Send + Sync> Asset for RawLintConfig<T> {}
+
+impl<T: TypePath + Send + Sync> VisitAssetDependencies for RawLintConfig<T> {
+    fn visit_dependencies(&self, _visit: &mut impl FnMut(UntypedAssetId)) {}
+}
+
+/// The lint levels and per-item exceptions for items of type `T`, loaded from a file.
+///
+/// Each item of this manifest is the list of rules that the item with the same name is allowed to break.
+#[derive(Resource)]
+pub struct LintConfig<T: 'static> {
+    levels: BTreeMap<String, LintLevel>,
+    exceptions: IdMap<T, Vec<String>>,
+}
+
+impl<T: 'static> LintConfig<T> {
+    /// The level of the rule named `rule`, if the configuration sets one.
+    #[must_use]
+    pub fn level(&self, rule: &str) -> Option<LintLevel> {
+        self.levels.get(rule).copied()
+    }
+
+    /// Returns true if the item with the given `id` is allowed to break the rule named `rule`.
+    #[must_use]
+    pub fn is_allowed(&self, id: Id<T>, rule: &str) -> bool {
+        self.exceptions
+            .get(&id)
+            .is_some_and(|allowed| allowed.iter().any(|allowed| allowed == rule))
+    }
+}
+
+impl<T: 'static> Default for LintConfig<T> {
+    fn default() -> Self {
+        Self {
+            levels: BTreeMap::new(),
+            exceptions: IdMap::default(),
+        }
+    }
+}
+
+impl<T: 'static> std::fmt::Debug for LintConfig<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LintConfig")
+            .field("levels", &self.levels)
+            .field("exceptions", &self.exceptions.len())
+            .finish()
+    }
+}
+
+impl<T: TypePath + Send + Sync> Manifest for LintConfig<T> {
+    type RawManifest = RawLintConfig<T>;
+    type RawItem = RawLintException;
+    type Item = Vec<String>;
+    type ConversionError = std::convert::Infallible;
+
+    #[cfg(feature = "ron")]
+    const FORMAT: ManifestFormat = ManifestFormat::Ron;
+    #[cfg(not(feature = "ron"))]
+    const FORMAT: ManifestFormat = ManifestFormat::Custom;
+
+    fn from_raw_manifest(
+        raw_manifest: RawLintConfig<T>,
+        _world: &mut World,
+    ) -> Result<Self, Self::ConversionError> {
+        let mut exceptions: IdMap<T, Vec<String>> = IdMap::default();
+        // Repeated entries for the same item are combined, rather than replacing each other.
+        for exception in raw_manifest.exceptions {
+            exceptions
+                .entry(Id::from_name(&exception.item))
+                .or_default()
+                .extend(exception.allow);
+        }
+
+        Ok(Self {
+            levels: raw_manifest.levels,
+            exceptions,
+        })
+    }
+
+    fn get(&self, id: Id<Vec<String>>) -> Option<&Vec<String>> {
+        self.exceptions.get(&Id::from_raw(id.raw()))
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.exceptions.len())
+    }
+}
+
+/// A plugin which checks the [`LintRules`] of the manifest `M` whenever it changes.
+///
+/// Warnings are recorded in the [`ValidationReport`], and logged.
+/// If a [`LintConfig`] for the item type has been registered, its levels and exceptions are respected,
+/// and the rules are checked again whenever it changes.
+pub struct ManifestLintPlugin<M: IterableManifest> {
+    rules: LintRules<M>,
+}
+
+impl<M: IterableManifest> ManifestLintPlugin<M> {
+    /// Creates a plugin which checks the supplied `rules`.
+    #[must_use]
+    pub fn new(rules: LintRules<M>) -> Self {
+        Self { rules }
+    }
+}
+
+impl<M: IterableManifest> Plugin for ManifestLintPlugin<M> {
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        app.init_resource::<ValidationReport>()
+            .insert_resource(self.rules.clone())
+            .add_systems(
+                schedules.processing,
+                lint_manifest::<M>
+                    .run_if(
+                        resource_exists::<M>.and_then(
+                            resource_changed::<M>
+                                .or_else(resource_changed::<LintRules<M>>)
+                                .or_else(resource_exists_and_changed::<LintConfig<M::Item>>),
+                        ),
+                    )
+                    .after(ProcessManifestSet),
+            );
+    }
+}
+
+/// Records the lint warnings of the manifest `M` in the [`ValidationReport`], logging each of them.
+pub fn lint_manifest<M: IterableManifest>(
+    manifest: Res<M>,
+    rules: Res<LintRules<M>>,
+    config: Option<Res<LintConfig<M::Item>>>,
+    mut report: ResMut<ValidationReport>,
+) {
+    let warnings = rules.check(&*manifest, config.as_deref());
+    for warning in &warnings {
+        warn!("{warning}");
+    }
+    report.record_lints::<M>(warnings);
+}
@@ -0,0 +1,1071 @@
+//! A built-in asset loader for raw manifests, used when the loaders provided by [`bevy_common_assets`] aren't enough.
+//!
+//! The [`ManifestLoader`] reads the raw bytes of a manifest file, applies any [`RawBytesTransform`],
+//! decompresses them if needed, and then deserializes them according to the [`ManifestFormat`] of the manifest.
+//!
+//! Manifests in the self-describing text formats (RON, JSON, YAML and TOML) can opt into this loader
+//! via [`RawManifestOptions::with_error_paths`](crate::plugin::RawManifestOptions::with_error_paths),
+//! so that errors point to the invalid value, as in `items[42].weight (found "heavy")`.
+//!
+//! You won't typically need to interact with this module directly:
+//! the appropriate loader is chosen automatically when registering a manifest via [`RegisterManifest`](crate::plugin::RegisterManifest).
+
+use std::error::Error;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Arc;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::utils::BoxedFuture;
+use serde::de::DeserializeOwned;
+
+use crate::manifest::ManifestFormat;
+
+/// The compression applied to a raw manifest file.
+///
+/// Compressed manifests are identified by their file extension:
+/// `items.ron.gz` is a gzip-compressed RON file, while `items.json.zst` is a zstd-compressed JSON file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// The file is not compressed.
+    #[default]
+    None,
+    #[cfg(feature = "gzip")]
+    /// The file is compressed with gzip, and has the `.gz` extension.
+    Gzip,
+    #[cfg(feature = "zstd")]
+    /// The file is compressed with zstd, and has the `.zst` extension.
+    Zstd,
+}
+
+impl Compression {
+    /// Determines the compression of a file from its final extension.
+    ///
+    /// Extensions for compression formats whose feature is disabled are treated as uncompressed.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "gzip")]
+            Some("gz") => Compression::Gzip,
+            #[cfg(feature = "zstd")]
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Decompresses the supplied bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "gzip")]
+    /// # fn main() {
+    /// use std::io::Write;
+    /// use std::path::Path;
+    ///
+    /// use leafwing_manifest::loader::Compression;
+    ///
+    /// let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    /// encoder.write_all(b"(items: [])").unwrap();
+    /// let compressed = encoder.finish().unwrap();
+    ///
+    /// assert_eq!(Compression::from_path(Path::new("items.ron.gz")), Compression::Gzip);
+    /// assert_eq!(Compression::Gzip.decompress(compressed).unwrap(), b"(items: [])");
+    /// # }
+    /// # #[cfg(not(feature = "gzip"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// ```
+    /// # #[cfg(feature = "zstd")]
+    /// # fn main() {
+    /// use std::path::Path;
+    ///
+    /// use leafwing_manifest::loader::Compression;
+    ///
+    /// // A zstd frame containing a single uncompressed block.
+    /// let content = b"{\"items\": []}";
+    /// let mut compressed = vec![0x28, 0xB5, 0x2F, 0xFD, 0x20, content.len() as u8];
+    /// let block_header = 1 | (content.len() as u32) << 3;
+    /// compressed.extend_from_slice(&block_header.to_le_bytes()[..3]);
+    /// compressed.extend_from_slice(content);
+    ///
+    /// assert_eq!(Compression::from_path(Path::new("items.json.zst")), Compression::Zstd);
+    /// assert_eq!(Compression::Zstd.decompress(compressed).unwrap(), content);
+    /// # }
+    /// # #[cfg(not(feature = "zstd"))]
+    /// # fn main() {}
+    /// ```
+    pub fn decompress(self, bytes: Vec<u8>) -> Result<Vec<u8>, std::io::Error> {
+        match self {
+            Compression::None => Ok(bytes),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                use std::io::Read;
+
+                let mut decompressed = Vec::new();
+                flate2::read::GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                use std::io::Read;
+
+                let mut source = bytes.as_slice();
+                let mut decoder = ruzstd::StreamingDecoder::new(&mut source).map_err(|err| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+                })?;
+                let mut decompressed = Vec::new();
+                decoder.read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+        }
+    }
+}
+
+/// A reversible transformation applied to the raw bytes of a manifest file.
+///
+/// This hook allows manifests to be stored on disk in an encrypted or obfuscated form,
+/// while still being parsed by the standard format loaders.
+/// When loading, [`decode`](RawBytesTransform::decode) is applied to the file contents before decompression and deserialization.
+/// When writing manifests back to disk, [`encode`](RawBytesTransform::encode) should be applied after serialization and compression.
+///
+/// Note that any key shipped with your game can be extracted by a determined user:
+/// this discourages casual tampering, but is not a security boundary.
+pub trait RawBytesTransform: Send + Sync + 'static {
+    /// Reverses the transformation, recovering the original bytes.
+    fn decode(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+
+    /// Applies the transformation to the original bytes.
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
+
+/// A simple [`RawBytesTransform`] which XORs each byte with a repeating key.
+///
+/// As XOR is its own inverse, encoding and decoding are the same operation.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::loader::{RawBytesTransform, XorTransform};
+///
+/// let transform = XorTransform::new(b"key".to_vec());
+/// let encoded = transform.encode(b"(items: [])".to_vec()).unwrap();
+/// assert_ne!(encoded, b"(items: [])");
+/// assert_eq!(transform.decode(encoded).unwrap(), b"(items: [])");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorTransform {
+    key: Vec<u8>,
+}
+
+impl XorTransform {
+    /// Creates a new transform using the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is empty.
+    #[must_use]
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        let key = key.into();
+        assert!(
+            !key.is_empty(),
+            "The key for an XorTransform cannot be empty."
+        );
+
+        Self { key }
+    }
+
+    fn apply(&self, mut bytes: Vec<u8>) -> Vec<u8> {
+        for (byte, key) in bytes.iter_mut().zip(self.key.iter().cycle()) {
+            *byte ^= key;
+        }
+        bytes
+    }
+}
+
+impl RawBytesTransform for XorTransform {
+    fn decode(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(self.apply(bytes))
+    }
+
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(self.apply(bytes))
+    }
+}
+
+/// Errors that can occur when loading a raw manifest via the [`ManifestLoader`].
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestLoaderError {
+    /// The file could not be read, or could not be decompressed.
+    #[error("Could not read the raw manifest: {0}")]
+    Io(#[from] std::io::Error),
+    /// The [`RawBytesTransform`] could not decode the file.
+    #[error("Could not decode the raw manifest: {0}")]
+    Transform(Box<dyn Error + Send + Sync>),
+    /// The file could not be deserialized into the raw manifest type.
+    #[error("Could not deserialize the raw manifest: {0}")]
+    Deserialization(Box<dyn std::error::Error + Send + Sync>),
+    /// A value in the file could not be deserialized into the raw manifest type.
+    ///
+    /// This is only reported for the self-describing text formats: RON, JSON, YAML and TOML.
+    #[error(
+        "Could not deserialize the raw manifest at {path}{}: {error}",
+        .value.as_ref().map(|value| format!(" (found {value})")).unwrap_or_default()
+    )]
+    InvalidValue {
+        /// The path to the invalid value, such as `items[42].weight`.
+        path: String,
+        /// A description of the invalid value, such as `"heavy"`, if it could be found.
+        value: Option<String>,
+        /// The underlying error, which describes the invalid value.
+        error: Box<dyn Error + Send + Sync>,
+    },
+    /// The raw manifest contains fields which the raw manifest type does not have,
+    /// and the [`Strictness`] is [`Strictness::Deny`].
+    #[error("The raw manifest contains unknown fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+    /// The [`ManifestLoader`] does not know how to deserialize this format.
+    #[error("The {0:?} format is not supported by the built-in manifest loader")]
+    UnsupportedFormat(ManifestFormat),
+}
+
+/// How fields in the raw manifest file which the raw manifest type does not have are handled.
+///
+/// By default, serde silently skips unknown fields.
+/// This means that a typo in the name of an optional field (such as `desciption`) leaves the field at its default value,
+/// with no indication that anything went wrong.
+///
+/// Unknown fields can only be detected in the self-describing text formats: RON, JSON, YAML and TOML.
+/// In other formats, every setting behaves like [`Strictness::Permissive`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "ron")]
+/// # fn main() {
+/// use leafwing_manifest::loader::{
+///     deserialize_raw_manifest_with_strictness, ManifestLoaderError, Strictness,
+/// };
+/// use leafwing_manifest::manifest::ManifestFormat;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct RawItem {
+///     name: String,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct RawItemManifest {
+///     items: Vec<RawItem>,
+/// }
+///
+/// let ron = br#"(items: [(name: "sword", desciption: "A sharp sword")])"#;
+///
+/// let permissive = deserialize_raw_manifest_with_strictness::<RawItemManifest>(
+///     ManifestFormat::Ron,
+///     ron,
+///     Strictness::Permissive,
+/// );
+/// assert!(permissive.is_ok());
+///
+/// let denied = deserialize_raw_manifest_with_strictness::<RawItemManifest>(
+///     ManifestFormat::Ron,
+///     ron,
+///     Strictness::Deny,
+/// );
+/// match denied {
+///     Err(ManifestLoaderError::UnknownFields(fields)) => {
+///         assert_eq!(fields, ["items.0.desciption"]);
+///     }
+///     other => panic!("Expected unknown fields, got {other:?}"),
+/// }
+/// # }
+/// # #[cfg(not(feature = "ron"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// Unknown fields are ignored.
+    #[default]
+    Permissive,
+    /// Unknown fields are ignored, but a warning listing them is logged.
+    Warn,
+    /// Unknown fields cause loading to fail with [`ManifestLoaderError::UnknownFields`].
+    Deny,
+}
+
+impl Strictness {
+    /// Reports the `unknown` fields found in a raw manifest, according to this setting.
+    ///
+    /// Each field is described by its path, such as `items.3.desciption`.
+    pub fn check(self, unknown: Vec<String>) -> Result<(), ManifestLoaderError> {
+        if unknown.is_empty() {
+            return Ok(());
+        }
+
+        match self {
+            Strictness::Permissive => Ok(()),
+            Strictness::Warn => {
+                bevy::log::warn!(
+                    "The raw manifest contains unknown fields, which were ignored: {}",
+                    unknown.join(", ")
+                );
+                Ok(())
+            }
+            Strictness::Deny => Err(ManifestLoaderError::UnknownFields(unknown)),
+        }
+    }
+}
+
+/// Deserializes a raw manifest of type `A` from bytes in the supplied `format`.
+///
+/// CSV files are read using the default [`CsvSettings`]: use [`deserialize_csv`] to customize this.
+/// [`ManifestFormat::Custom`] is not supported.
+pub fn deserialize_raw_manifest<A: DeserializeOwned>(
+    format: ManifestFormat,
+    bytes: &[u8],
+) -> Result<A, ManifestLoaderError> {
+    deserialize_raw_manifest_with_strictness(format, bytes, Strictness::Permissive)
+}
+
+/// Deserializes a raw manifest of type `A` from bytes in the supplied `format`,
+/// handling unknown fields according to the supplied [`Strictness`].
+///
+/// As with [`deserialize_raw_manifest`], [`ManifestFormat::Custom`] is not supported.
+///
+/// For RON, JSON, YAML and TOML files, deserialization errors report the path to the invalid value
+/// and the value itself as a [`ManifestLoaderError::InvalidValue`], such as `items[42].weight`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "ron")]
+/// # fn main() {
+/// use leafwing_manifest::loader::{
+///     deserialize_raw_manifest_with_strictness, ManifestLoaderError, Strictness,
+/// };
+/// use leafwing_manifest::manifest::ManifestFormat;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct RawItem {
+///     name: String,
+///     weight: f32,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct RawItemManifest {
+///     items: Vec<RawItem>,
+/// }
+///
+/// let ron = br#"(items: [(name: "sword", weight: 2.0), (name: "shield", weight: "heavy")])"#;
+/// let result = deserialize_raw_manifest_with_strictness::<RawItemManifest>(
+///     ManifestFormat::Ron,
+///     ron,
+///     Strictness::Permissive,
+/// );
+///
+/// match result {
+///     Err(ManifestLoaderError::InvalidValue { path, value, .. }) => {
+///         assert_eq!(path, "items[1].weight");
+///         assert_eq!(value.as_deref(), Some("\"heavy\""));
+///     }
+///     other => panic!("Expected an invalid value, got {other:?}"),
+/// }
+/// # }
+/// # #[cfg(not(feature = "ron"))]
+/// # fn main() {}
+/// ```
+// When no self-describing formats are enabled, the strictness has no effect.
+#[cfg_attr(
+    not(any(feature = "ron", feature = "json", feature = "yaml", feature = "toml")),
+    allow(unused_variables)
+)]
+pub fn deserialize_raw_manifest_with_strictness<A: DeserializeOwned>(
+    format: ManifestFormat,
+    bytes: &[u8],
+    strictness: Strictness,
+) -> Result<A, ManifestLoaderError> {
+    match format {
+        #[cfg(feature = "ron")]
+        ManifestFormat::Ron => self_describing::from_ron(bytes, strictness),
+        #[cfg(feature = "json")]
+        ManifestFormat::Json => self_describing::from_json(bytes, strictness),
+        #[cfg(feature = "yaml")]
+        ManifestFormat::Yaml => self_describing::from_yaml(bytes, strictness),
+        #[cfg(feature = "toml")]
+        ManifestFormat::Toml => self_describing::from_toml(bytes, strictness),
+        // Other formats are not self-describing, so they cannot report where an error occurred or which fields were unknown.
+        _ => deserialize_untracked(format, bytes),
+    }
+}
+
+/// Deserialization of the self-describing text formats, which can report where errors occurred and which fields were unknown.
+#[cfg(any(feature = "ron", feature = "json", feature = "yaml", feature = "toml"))]
+mod self_describing {
+    use serde::de::{
+        DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    };
+    use serde::Deserialize;
+    use serde_path_to_error::{Path, Segment};
+
+    use super::{ManifestLoaderError, Strictness};
+
+    fn boxed(err: impl std::error::Error + Send + Sync + 'static) -> ManifestLoaderError {
+        ManifestLoaderError::Deserialization(Box::new(err))
+    }
+
+    /// Reports an error at the path where it occurred, unless it occurred at the top level of the file.
+    ///
+    /// The underlying error is passed through `describe`, which can add more context,
+    /// while `find_value` looks up the invalid value by re-reading the file.
+    fn located<E, F>(
+        err: serde_path_to_error::Error<E>,
+        describe: impl FnOnce(E) -> F,
+        find_value: impl FnOnce(&Path) -> Option<String>,
+    ) -> ManifestLoaderError
+    where
+        F: std::error::Error + Send + Sync + 'static,
+    {
+        if err.path().iter().next().is_none() {
+            return boxed(describe(err.into_inner()));
+        }
+
+        let path = err.path().to_string();
+        let value = find_value(err.path());
+        ManifestLoaderError::InvalidValue {
+            path,
+            value,
+            error: Box::new(describe(err.into_inner())),
+        }
+    }
+
+    /// Deserializes `A`, tracking the path to the value being deserialized,
+    /// and recording the path of each field that `A` does not have in `unknown`.
+    fn tracked<'de, A, D>(
+        deserializer: D,
+        unknown: &mut Vec<String>,
+    ) -> Result<A, serde_path_to_error::Error<D::Error>>
+    where
+        A: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let mut record_unknown = |path: serde_ignored::Path<'_>| {
+            unknown.push(path.to_string());
+        };
+        serde_path_to_error::deserialize(serde_ignored::Deserializer::new(
+            deserializer,
+            &mut record_unknown,
+        ))
+    }
+
+    #[cfg(feature = "ron")]
+    pub(super) fn from_ron<A: DeserializeOwned>(
+        bytes: &[u8],
+        strictness: Strictness,
+    ) -> Result<A, ManifestLoaderError> {
+        let mut unknown = Vec::new();
+        let mut deserializer = ron::Deserializer::from_bytes(bytes).map_err(boxed)?;
+        // Include the line and column of any error, as `ron::de::from_bytes` would.
+        let value = tracked(&mut deserializer, &mut unknown).map_err(|err| {
+            located(
+                err,
+                |err| deserializer.span_error(err),
+                |path| {
+                    let mut deserializer = ron::Deserializer::from_bytes(bytes).ok()?;
+                    value_at(&mut deserializer, path)
+                },
+            )
+        })?;
+        deserializer
+            .end()
+            .map_err(|err| boxed(deserializer.span_error(err)))?;
+
+        strictness.check(unknown)?;
+        Ok(value)
+    }
+
+    #[cfg(feature = "json")]
+    pub(super) fn from_json<A: DeserializeOwned>(
+        bytes: &[u8],
+        strictness: Strictness,
+    ) -> Result<A, ManifestLoaderError> {
+        let mut unknown = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        let value = tracked(&mut deserializer, &mut unknown).map_err(|err| {
+            located(err, std::convert::identity, |path| {
+                value_at(&mut serde_json::Deserializer::from_slice(bytes), path)
+            })
+        })?;
+        deserializer.end().map_err(boxed)?;
+
+        strictness.check(unknown)?;
+        Ok(value)
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(super) fn from_yaml<A: DeserializeOwned>(
+        bytes: &[u8],
+        strictness: Strictness,
+    ) -> Result<A, ManifestLoaderError> {
+        let mut unknown = Vec::new();
+        let value =
+            tracked(serde_yaml::Deserializer::from_slice(bytes), &mut unknown).map_err(|err| {
+                located(err, std::convert::identity, |path| {
+                    value_at(serde_yaml::Deserializer::from_slice(bytes), path)
+                })
+            })?;
+
+        strictness.check(unknown)?;
+        Ok(value)
+    }
+
+    #[cfg(feature = "toml")]
+    pub(super) fn from_toml<A: DeserializeOwned>(
+        bytes: &[u8],
+        strictness: Strictness,
+    ) -> Result<A, ManifestLoaderError> {
+        let mut unknown = Vec::new();
+        let text = std::str::from_utf8(bytes).map_err(boxed)?;
+        let value = tracked(toml::Deserializer::new(text), &mut unknown).map_err(|err| {
+            located(err, std::convert::identity, |path| {
+                value_at(toml::Deserializer::new(text), path)
+            })
+        })?;
+
+        strictness.check(unknown)?;
+        Ok(value)
+    }
+
+    /// Reads the file again, and describes the value found at `path`, such as `"heavy"` or `a map`.
+    ///
+    /// Returns [`None`] if the value cannot be found,
+    /// such as when the path passes through an enum variant or a map key which is not a string or number.
+    fn value_at<'de, D: Deserializer<'de>>(deserializer: D, path: &Path) -> Option<String> {
+        let segments: Vec<&Segment> = path.iter().collect();
+        ValueAt(&segments).deserialize(deserializer).ok().flatten()
+    }
+
+    /// Follows the remaining path segments through the file, ignoring every other value along the way.
+    struct ValueAt<'a>(&'a [&'a Segment]);
+
+    impl ValueAt<'_> {
+        /// Describes a value which can't be followed any further.
+        fn leaf(&self, description: impl ToString) -> Option<String> {
+            self.0.is_empty().then(|| description.to_string())
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for ValueAt<'_> {
+        type Value = Option<String>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for ValueAt<'_> {
+        type Value = Option<String>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("any value")
+        }
+
+        fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+            Ok(self.leaf(value))
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+            Ok(self.leaf(value))
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+            Ok(self.leaf(value))
+        }
+
+        fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+            Ok(self.leaf(value))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(self.leaf(format!("{value:?}")))
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(self.leaf("()"))
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E> {
+            Ok(self.leaf("None"))
+        }
+
+        // Paths pass straight through optional values and newtypes.
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            self.deserialize(deserializer)
+        }
+
+        fn visit_newtype_struct<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            self.deserialize(deserializer)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let (target, rest) = match self.0.split_first() {
+                Some((Segment::Seq { index }, rest)) => (Some(*index), rest),
+                _ => (None, &[][..]),
+            };
+
+            let mut found = None;
+            let mut index = 0;
+            loop {
+                // Every element must be read, or the deserializer will report the rest of the sequence as an error.
+                if target == Some(index) {
+                    match seq.next_element_seed(ValueAt(rest))? {
+                        Some(value) => found = value,
+                        None => break,
+                    }
+                } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                    break;
+                }
+                index += 1;
+            }
+
+            Ok(self.leaf("a sequence").or(found))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let (target, rest) = match self.0.split_first() {
+                Some((Segment::Map { key }, rest)) => (Some(key.as_str()), rest),
+                _ => (None, &[][..]),
+            };
+
+            let mut found = None;
+            while let Some(KeyName(key)) = map.next_key()? {
+                if target.is_some() && key.as_deref() == target {
+                    found = map.next_value_seed(ValueAt(rest))?;
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+
+            Ok(self.leaf("a map").or(found))
+        }
+    }
+
+    /// A map key, recorded in the same way as the keys in a [`Path`].
+    ///
+    /// Keys which are not strings or numbers are ignored.
+    struct KeyName(Option<String>);
+
+    impl<'de> Deserialize<'de> for KeyName {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(KeyNameVisitor)
+        }
+    }
+
+    struct KeyNameVisitor;
+
+    impl<'de> Visitor<'de> for KeyNameVisitor {
+        type Value = KeyName;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a map key")
+        }
+
+        fn visit_bool<E>(self, value: bool) -> Result<KeyName, E> {
+            Ok(KeyName(Some(value.to_string())))
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<KeyName, E> {
+            Ok(KeyName(Some(value.to_string())))
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<KeyName, E> {
+            Ok(KeyName(Some(value.to_string())))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<KeyName, E> {
+            Ok(KeyName(Some(value.to_string())))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KeyName, A::Error> {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(KeyName(None))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<KeyName, A::Error> {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            Ok(KeyName(None))
+        }
+    }
+}
+
+/// Deserializes formats which are not self-describing, for [`deserialize_raw_manifest_with_strictness`].
+// Without any of these formats enabled, every format is unsupported and the bytes are never read.
+#[cfg_attr(
+    not(any(
+        feature = "csv",
+        feature = "xml",
+        feature = "msgpack",
+        feature = "bincode",
+        feature = "postcard"
+    )),
+    allow(unused_variables)
+)]
+fn deserialize_untracked<A: DeserializeOwned>(
+    format: ManifestFormat,
+    bytes: &[u8],
+) -> Result<A, ManifestLoaderError> {
+    #[allow(unused)]
+    fn boxed(err: impl std::error::Error + Send + Sync + 'static) -> ManifestLoaderError {
+        ManifestLoaderError::Deserialization(Box::new(err))
+    }
+
+    match format {
+        #[cfg(feature = "csv")]
+        ManifestFormat::Csv => deserialize_csv(bytes, CsvSettings::default()),
+        #[cfg(feature = "xml")]
+        ManifestFormat::Xml => quick_xml::de::from_reader(bytes).map_err(boxed),
+        #[cfg(feature = "msgpack")]
+        ManifestFormat::MsgPack => rmp_serde::from_slice(bytes).map_err(boxed),
+        #[cfg(feature = "bincode")]
+        ManifestFormat::Bincode => bincode::deserialize(bytes).map_err(boxed),
+        #[cfg(feature = "postcard")]
+        ManifestFormat::Postcard => postcard::from_bytes(bytes).map_err(boxed),
+        #[allow(unreachable_patterns)]
+        format => Err(ManifestLoaderError::UnsupportedFormat(format)),
+    }
+}
+
+/// Configures how CSV files are read.
+#[cfg(feature = "csv")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvSettings {
+    /// The character that separates each field.
+    ///
+    /// Defaults to `b','`.
+    pub delimiter: u8,
+    /// If true, the first row of the file names each column.
+    ///
+    /// Rows are deserialized as maps from column names to values when this is true,
+    /// and as sequences of values (like a tuple struct) when this is false.
+    ///
+    /// Defaults to `true`.
+    pub has_headers: bool,
+}
+
+#[cfg(feature = "csv")]
+impl Default for CsvSettings {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+        }
+    }
+}
+
+/// Deserializes a raw manifest of type `A` from a CSV file.
+///
+/// The file is presented to `A` as a sequence of rows, so `A` will typically be a `Vec` of your raw item type,
+/// or a [`CsvRows`](crate::raw_manifests::CsvRows).
+/// Each cell is parsed as a boolean or number when the target type expects one.
+/// Empty cells are treated as [`None`] for optional fields.
+///
+/// Without headers, the cells of each row are matched against the fields of the raw item in order.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "csv")]
+/// # fn main() {
+/// use leafwing_manifest::loader::{deserialize_csv, CsvSettings};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct RawItem {
+///     name: String,
+///     value: i32,
+///     weight: Option<f32>,
+/// }
+///
+/// let with_headers = b"name,value,weight\nsword,10,2.5\nshield,5,\n";
+/// let items: Vec<RawItem> = deserialize_csv(with_headers, CsvSettings::default()).unwrap();
+/// assert_eq!(
+///     items[0],
+///     RawItem { name: "sword".to_string(), value: 10, weight: Some(2.5) }
+/// );
+/// assert_eq!(items[1].weight, None);
+///
+/// let without_headers = b"sword;10;2.5\nshield;5;\n";
+/// let settings = CsvSettings { delimiter: b';', has_headers: false };
+/// let rows: Vec<RawItem> = deserialize_csv(without_headers, settings).unwrap();
+/// assert_eq!(rows, items);
+/// # }
+/// # #[cfg(not(feature = "csv"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "csv")]
+pub fn deserialize_csv<A: DeserializeOwned>(
+    bytes: &[u8],
+    settings: CsvSettings,
+) -> Result<A, ManifestLoaderError> {
+    use serde::de::value::{MapDeserializer, SeqDeserializer};
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(settings.delimiter)
+        .has_headers(settings.has_headers)
+        .from_reader(bytes);
+
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| ManifestLoaderError::Deserialization(Box::new(err)))?;
+
+    let result = if settings.has_headers {
+        let headers = reader
+            .headers()
+            .map_err(|err| ManifestLoaderError::Deserialization(Box::new(err)))?
+            .clone();
+
+        let rows = records.iter().map(|record| {
+            MapDeserializer::new(
+                headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(header, cell)| (header, csv_cell::CellDeserializer(cell))),
+            )
+        });
+        A::deserialize(SeqDeserializer::new(rows))
+    } else {
+        let rows = records
+            .iter()
+            .map(|record| SeqDeserializer::new(record.iter().map(csv_cell::CellDeserializer)));
+        A::deserialize(SeqDeserializer::new(rows))
+    };
+
+    result
+        .map_err(|err: serde::de::value::Error| ManifestLoaderError::Deserialization(Box::new(err)))
+}
+
+/// A deserializer for a single CSV cell, which parses the text according to the type that is expected.
+#[cfg(feature = "csv")]
+mod csv_cell {
+    use serde::de::value::Error;
+    use serde::de::{Deserializer, IntoDeserializer, Visitor};
+
+    pub(super) struct CellDeserializer<'a>(pub(super) &'a str);
+
+    impl<'de, 'a> IntoDeserializer<'de, Error> for CellDeserializer<'a> {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self {
+            self
+        }
+    }
+
+    macro_rules! parse_cell {
+        ($de:lifetime; $($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+            $(
+                fn $method<V: Visitor<$de>>(self, visitor: V) -> Result<V::Value, Error> {
+                    match self.0.trim().parse::<$ty>() {
+                        Ok(value) => visitor.$visit(value),
+                        Err(err) => Err(serde::de::Error::custom(format!(
+                            "could not parse {:?} as {}: {err}",
+                            self.0,
+                            stringify!($ty)
+                        ))),
+                    }
+                }
+            )*
+        };
+    }
+
+    impl<'de, 'a> Deserializer<'de> for CellDeserializer<'a> {
+        type Error = Error;
+
+        /// Infers the type of the cell from its contents, for self-describing targets such as untagged enums.
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let trimmed = self.0.trim();
+
+            if let Ok(value) = trimmed.parse::<bool>() {
+                visitor.visit_bool(value)
+            } else if let Ok(value) = trimmed.parse::<i64>() {
+                visitor.visit_i64(value)
+            } else if let Ok(value) = trimmed.parse::<f64>() {
+                visitor.visit_f64(value)
+            } else {
+                visitor.visit_str(self.0)
+            }
+        }
+
+        parse_cell! {
+            'de;
+            deserialize_bool => visit_bool: bool,
+            deserialize_i8 => visit_i8: i8,
+            deserialize_i16 => visit_i16: i16,
+            deserialize_i32 => visit_i32: i32,
+            deserialize_i64 => visit_i64: i64,
+            deserialize_u8 => visit_u8: u8,
+            deserialize_u16 => visit_u16: u16,
+            deserialize_u32 => visit_u32: u32,
+            deserialize_u64 => visit_u64: u64,
+            deserialize_f32 => visit_f32: f32,
+            deserialize_f64 => visit_f64: f64,
+            deserialize_char => visit_char: char,
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_str(self.0)
+        }
+
+        fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_str(self.0)
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            if self.0.trim().is_empty() {
+                visitor.visit_none()
+            } else {
+                visitor.visit_some(self)
+            }
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            // Unit variants are written as their name.
+            visitor.visit_enum(self.0.trim().into_deserializer())
+        }
+
+        serde::forward_to_deserialize_any! {
+            i128 u128 bytes byte_buf unit unit_struct seq tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+}
+
+/// An [`AssetLoader`] for raw manifests of type `A`.
+///
+/// Unlike the loaders in [`bevy_common_assets`], this loader supports compressed manifest files,
+/// and manifest files which have been encoded using a [`RawBytesTransform`].
+/// In the self-describing text formats, it also reports the path to any invalid value, along with the value itself.
+/// It is also the only loader for formats which `bevy_common_assets` does not support,
+/// such as bincode and postcard.
+pub struct ManifestLoader<A> {
+    format: ManifestFormat,
+    compression: Compression,
+    transform: Option<Arc<dyn RawBytesTransform>>,
+    #[cfg(feature = "csv")]
+    csv_settings: CsvSettings,
+    extensions: &'static [&'static str],
+    strictness: Strictness,
+    _phantom: PhantomData<fn() -> A>,
+}
+
+impl<A> ManifestLoader<A> {
+    /// Creates a new loader for files in the supplied format and compression.
+    #[must_use]
+    pub fn new(format: ManifestFormat, compression: Compression) -> Self {
+        Self {
+            format,
+            compression,
+            transform: None,
+            #[cfg(feature = "csv")]
+            csv_settings: CsvSettings::default(),
+            extensions: &[],
+            strictness: Strictness::Permissive,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Decodes the raw bytes using the supplied transform before they are decompressed and deserialized.
+    #[must_use]
+    pub fn with_transform(mut self, transform: Option<Arc<dyn RawBytesTransform>>) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Registers this loader for the supplied file extensions, such as `["items.ron"]`.
+    ///
+    /// By default, no extensions are registered, and the loader is selected based on the asset type alone.
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: &'static [&'static str]) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Handles fields that the raw manifest type does not have according to the supplied [`Strictness`].
+    #[must_use]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Reads CSV files using the supplied settings.
+    #[cfg(feature = "csv")]
+    #[must_use]
+    pub fn with_csv_settings(mut self, csv_settings: CsvSettings) -> Self {
+        self.csv_settings = csv_settings;
+        self
+    }
+}
+
+impl<A: Asset + DeserializeOwned> AssetLoader for ManifestLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = ManifestLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<A, ManifestLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            if let Some(transform) = &self.transform {
+                bytes = transform
+                    .decode(bytes)
+                    .map_err(ManifestLoaderError::Transform)?;
+            }
+
+            let bytes = self.compression.decompress(bytes)?;
+
+            #[cfg(feature = "csv")]
+            if self.format == ManifestFormat::Csv {
+                return deserialize_csv(&bytes, self.csv_settings);
+            }
+
+            deserialize_raw_manifest_with_strictness(self.format, &bytes, self.strictness)
+        })
+    }
+
+    // As with the `bevy_common_assets` loaders, we always know the asset type when loading,
+    // so extensions are not needed unless explicitly requested.
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
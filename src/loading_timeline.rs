@@ -0,0 +1,108 @@
+//! A summary of how long each phase of manifest loading took.
+//!
+//! Loading a manifest happens in three phases:
+//! 1. The raw manifest is read from disk and deserialized by the [`AssetServer`](bevy::asset::AssetServer).
+//! 2. The loaded raw manifest waits until all other raw manifests have loaded, and processing begins.
+//! 3. The raw manifest is converted into the final manifest via [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+//!
+//! When optimizing startup times, the [`LoadingTimeline`] resource tells you which of these phases is the bottleneck.
+
+use std::time::Duration;
+
+use bevy::ecs::prelude::*;
+use bevy::log::info;
+
+use crate::plugin::{RawManifestStatus, RawManifestTracker};
+
+/// A breakdown of the time spent loading each manifest.
+///
+/// This resource is inserted by the [`ManifestPlugin`](crate::plugin::ManifestPlugin)
+/// upon entering [`AssetLoadingState::READY`](crate::asset_state::AssetLoadingState::READY).
+/// The raw timestamps can be found in each [`RawManifestStatus`].
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LoadingTimeline {
+    /// The timings for each registered manifest, sorted by the time they finished processing.
+    pub manifests: Vec<ManifestTimeline>,
+    /// The time between the first manifest being registered and the last manifest finishing processing.
+    pub total: Duration,
+}
+
+/// The time spent in each phase of loading a single manifest.
+///
+/// Phases which never completed are recorded as [`None`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestTimeline {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// Time spent reading and deserializing the raw manifest.
+    pub loading: Option<Duration>,
+    /// Time spent waiting between the raw manifest loading and processing starting.
+    pub waiting: Option<Duration>,
+    /// Time spent converting the raw manifest into the final manifest.
+    pub processing: Option<Duration>,
+}
+
+impl ManifestTimeline {
+    /// Computes the timeline for a single manifest from its recorded timestamps.
+    #[must_use]
+    pub fn from_status(status: &RawManifestStatus) -> Self {
+        let loading = status
+            .load_finished_at
+            .map(|finished| finished.saturating_duration_since(status.registered_at));
+        let waiting = status
+            .load_finished_at
+            .zip(status.processing_started_at)
+            .map(|(finished, started)| started.saturating_duration_since(finished));
+        let processing = status
+            .processing_started_at
+            .zip(status.processed_at)
+            .map(|(started, processed)| processed.saturating_duration_since(started));
+
+        ManifestTimeline {
+            type_name: status.type_name,
+            loading,
+            waiting,
+            processing,
+        }
+    }
+}
+
+impl LoadingTimeline {
+    /// Summarizes the timestamps recorded by the [`RawManifestTracker`].
+    #[must_use]
+    pub fn from_tracker(raw_manifest_tracker: &RawManifestTracker) -> Self {
+        let mut statuses: Vec<&RawManifestStatus> = raw_manifest_tracker
+            .iter()
+            .map(|(_, status)| status)
+            .collect();
+        statuses.sort_by_key(|status| status.processed_at);
+
+        let first_registered = statuses.iter().map(|status| status.registered_at).min();
+        let last_processed = statuses
+            .iter()
+            .filter_map(|status| status.processed_at)
+            .max();
+        let total = first_registered
+            .zip(last_processed)
+            .map(|(first, last)| last.saturating_duration_since(first))
+            .unwrap_or_default();
+
+        LoadingTimeline {
+            manifests: statuses
+                .into_iter()
+                .map(ManifestTimeline::from_status)
+                .collect(),
+            total,
+        }
+    }
+}
+
+/// Inserts the [`LoadingTimeline`] resource, and logs the total loading time.
+pub fn record_loading_timeline(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    mut commands: Commands,
+) {
+    let timeline = LoadingTimeline::from_tracker(&raw_manifest_tracker);
+    info!("All manifests were ready after {:?}.", timeline.total);
+    commands.insert_resource(timeline);
+}
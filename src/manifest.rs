@@ -1,8 +1,11 @@
 use std::{borrow::Borrow, error::Error};
 
 use bevy::{
-    asset::Asset,
-    ecs::{system::Resource, world::World},
+    asset::{Asset, AssetPath},
+    ecs::{
+        system::{Resource, SystemParam, SystemParamItem, SystemState},
+        world::World,
+    },
 };
 use serde::Deserialize;
 use thiserror::Error;
@@ -48,6 +51,8 @@ pub trait Manifest: Sized + Resource {
     /// These are commonly [`Bundle`](bevy::ecs::bundle::Bundle) types, allowing you to directly spawn them into the [`World`].
     /// If you wish to store [`Handles`](bevy::asset::Handle) to other assets (such as textures, sprites or sounds),
     /// starting the asset loading process for those assets in [`from_raw_manifest`](Manifest::from_raw_manifest) works very well!
+    /// Assets created during processing can be kept alive by the [`ManifestAssetStore`](crate::asset_store::ManifestAssetStore),
+    /// allowing items to store weak handles instead.
     type Item;
 
     /// The error type that can occur when converting raw manifests into a manifest.
@@ -77,7 +82,7 @@ pub trait Manifest: Sized + Resource {
     /// If you need access to data from *other* manifests, you can use the [`World`] to look them up as resources.
     /// This is useful for cross-referencing data between manifests.
     /// Use ordinary system ordering to ensure that the required manifests are loaded first:
-    /// the system that calls this method is [`process_manifest::<M>`](crate::plugin::process_manifest), run in the [`PreUpdate`](bevy::prelude::PreUpdate) schedule.
+    /// the system that calls this method is [`process_manifest::<M>`](crate::plugin::process_manifest), run in the [`PreUpdate`](bevy::prelude::PreUpdate) schedule by default.
     ///
     /// This method is commonly implemented using the [`TryFrom`] trait between [`Self::RawItem`](Manifest::RawItem) and [`Self::Item`](Manifest::Item).
     /// By iterating over the items in the raw manifest, you can convert them into the final item type one at a time.
@@ -95,16 +100,94 @@ pub trait Manifest: Sized + Resource {
     /// Gets an item from the manifest by its name.
     ///
     /// Returns [`None`] if no item with the given name is found.
+    /// To report missing items according to their [`MissingLookupPolicy`](crate::missing_lookups::MissingLookupPolicy),
+    /// look them up through a [`CheckedManifest`](crate::missing_lookups::CheckedManifest) instead.
     #[must_use]
     fn get_by_name(&self, name: impl Borrow<str>) -> Option<&Self::Item> {
         self.get(Id::from_name(name.borrow()))
     }
+
+    /// Converts the manifest's storage into a read-only, lookup-optimized form.
+    ///
+    /// This is called by [`process_manifest::<M>`](crate::plugin::process_manifest) once the manifest has been created,
+    /// as long as [`ManifestPlugin::freeze_manifests`](crate::plugin::ManifestPlugin::freeze_manifests) is enabled.
+    /// A typical implementation moves the items into a [`FrozenManifest`](crate::frozen_manifest::FrozenManifest).
+    ///
+    /// By default, this does nothing.
+    /// If your manifest also implements [`MutableManifest`], any modifications made after freezing
+    /// should be rejected with [`ManifestModificationError::Frozen`].
+    fn freeze(&mut self) {}
+
+    /// Returns the number of items stored in the manifest, if known.
+    ///
+    /// This is used for reporting and diagnostics only.
+    /// By default, this returns [`None`].
+    #[must_use]
+    fn item_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the paths of any assets referenced by the raw manifest, such as textures, scenes or audio.
+    ///
+    /// These are loaded as soon as the raw manifest itself has loaded, during [`AssetLoadingState::LOADING`](crate::asset_state::AssetLoadingState::LOADING),
+    /// rather than only once [`from_raw_manifest`](Manifest::from_raw_manifest) is called during processing.
+    /// Loading the same paths again in [`from_raw_manifest`](Manifest::from_raw_manifest) then returns the already-loading handles,
+    /// which reduces startup time for manifests that reference many assets.
+    ///
+    /// By default, this returns an empty list.
+    #[must_use]
+    fn asset_dependencies(_raw_manifest: &Self::RawManifest) -> Vec<AssetPath<'static>> {
+        Vec::new()
+    }
+}
+
+/// A manifest which is processed using only the data it asks for, rather than exclusive access to the [`World`].
+///
+/// [`Manifest::from_raw_manifest`] takes `&mut World`, which forces manifest processing to run in an exclusive system,
+/// and makes it hard to test conversions without setting up a full [`World`].
+/// Instead, implementors of this trait declare a [`SystemParam`] as their [`Context`](ManifestWithContext::Context),
+/// such as `(Res<AssetServer>, ResMut<Assets<ColorMaterial>>)`, which is fetched for them when processing.
+///
+/// Register these manifests using [`register_manifest_with_context`](crate::plugin::RegisterManifest::register_manifest_with_context).
+/// As [`Manifest`] is a supertrait, [`Manifest::from_raw_manifest`] must still be implemented:
+/// simply forward it to [`from_raw_manifest_via_context`].
+pub trait ManifestWithContext: Manifest {
+    /// The data needed to convert a raw manifest into this manifest.
+    ///
+    /// This can be any [`SystemParam`], including tuples of system params.
+    type Context: SystemParam + 'static;
+
+    /// Converts a raw manifest into the corresponding manifest, using the requested [`Context`](ManifestWithContext::Context).
+    ///
+    /// See [`Manifest::from_raw_manifest`] for more information.
+    fn from_raw_manifest_with_context(
+        raw_manifest: Self::RawManifest,
+        context: &mut SystemParamItem<Self::Context>,
+    ) -> Result<Self, Self::ConversionError>;
+}
+
+/// Converts a raw manifest into a [`ManifestWithContext`], fetching its context from the [`World`].
+///
+/// This is intended to be used as the implementation of [`Manifest::from_raw_manifest`] for [`ManifestWithContext`] types.
+/// Any [`Commands`](bevy::ecs::system::Commands) issued by the conversion are applied before returning.
+pub fn from_raw_manifest_via_context<M: ManifestWithContext>(
+    raw_manifest: M::RawManifest,
+    world: &mut World,
+) -> Result<M, M::ConversionError> {
+    let mut system_state = SystemState::<M::Context>::new(world);
+    let result = {
+        let mut context = system_state.get_mut(world);
+        M::from_raw_manifest_with_context(raw_manifest, &mut context)
+    };
+    system_state.apply(world);
+    result
 }
 
 /// The file format of the raw manifest on disk.
 ///
 /// All of the corresponding features are off by default, and must be enabled with feature flags.
 /// Check the `Cargo.toml` file for the list of available features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ManifestFormat {
     #[cfg(feature = "ron")]
     /// A Rust-specific configuration format that is easy for both humans and machines to read and write.
@@ -127,18 +210,67 @@ pub enum ManifestFormat {
     #[cfg(feature = "msgpack")]
     /// A JSON-derived binary format.
     MsgPack,
+    #[cfg(feature = "bincode")]
+    /// A compact, fast Rust-specific binary format.
+    Bincode,
+    #[cfg(feature = "postcard")]
+    /// A compact Rust-specific binary format, designed for constrained environments.
+    Postcard,
     /// Your own custom format.
     ///
     /// If this is selected, you will need to create and register your own [`bevy::asset::AssetLoader`] trait for the [`Manifest::RawManifest`] asset type.
     Custom,
 }
 
+impl ManifestFormat {
+    /// Returns the standard file extensions for this format, without a leading `.`, as expected by asset loaders.
+    ///
+    /// Returns an empty slice for [`ManifestFormat::Custom`].
+    #[must_use]
+    pub const fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "ron")]
+            ManifestFormat::Ron => &["ron"],
+            #[cfg(feature = "json")]
+            ManifestFormat::Json => &["json"],
+            #[cfg(feature = "yaml")]
+            ManifestFormat::Yaml => &["yaml"],
+            #[cfg(feature = "toml")]
+            ManifestFormat::Toml => &["toml"],
+            #[cfg(feature = "xml")]
+            ManifestFormat::Xml => &["xml"],
+            #[cfg(feature = "csv")]
+            ManifestFormat::Csv => &["csv"],
+            #[cfg(feature = "msgpack")]
+            ManifestFormat::MsgPack => &["msgpack"],
+            #[cfg(feature = "bincode")]
+            ManifestFormat::Bincode => &["bincode"],
+            #[cfg(feature = "postcard")]
+            ManifestFormat::Postcard => &["postcard"],
+            ManifestFormat::Custom => &[],
+        }
+    }
+
+    /// Returns the standard file extension for this format, without a leading `.`.
+    ///
+    /// Returns [`None`] for [`ManifestFormat::Custom`].
+    #[must_use]
+    pub const fn extension(&self) -> Option<&'static str> {
+        match self.extensions() {
+            [extension, ..] => Some(*extension),
+            [] => None,
+        }
+    }
+}
+
 /// A trait for manifests that can be modified.
 ///
 /// In many cases, manifests are read-only, and are loaded from disk at the start of the game.
 /// Mutating the data in a manifest is generally not recommended, as it can lead to inconsistencies and bugs.
 /// For example, you may accidentally remove an item that is referenced elsewhere in the game,
 /// or change the properties of an item that is already in use without updating all corresponding instances.
+/// The [`ManifestUsagePlugin`](crate::usage::ManifestUsagePlugin) can count how many entities refer to each item,
+/// and warns when an item that is still in use is removed.
 ///
 /// However, there are some cases where mutable manifests are useful:
 /// - User-generated content, where players can create new items or modify existing ones.
@@ -146,9 +278,10 @@ pub enum ManifestFormat {
 /// - Debugging, where you want to quickly add or remove items to test new features.
 /// - Procedural generation, where you want to create new items on the fly.
 /// - Temporary changes, such as changing the properties of an item for a single level.
-/// - Huge datasets, where you want to load only a subset of the data into memory at a time.
+/// - Huge datasets, where you want to load only a subset of the data into memory at a time: see `LazyManifest`, behind the `lazy` feature.
 ///
 /// In many of these cases, only implementing this trait when a feature flag is enabled is a good way to prevent accidental modification.
+/// To switch modification on and off at runtime instead, such as when entering a modding mode, see [`ManifestLock`](crate::manifest_lock::ManifestLock).
 pub trait MutableManifest: Manifest {
     /// Inserts a new item into the manifest.
     ///
@@ -182,6 +315,69 @@ pub trait MutableManifest: Manifest {
         }
     }
 
+    /// Returns the [`Id`] that [`insert`](MutableManifest::insert) would assign to `item`, without inserting it.
+    ///
+    /// This is used by the default implementation of [`insert_with_id`](MutableManifest::insert_with_id).
+    /// By default, this returns [`None`], meaning the [`Id`] cannot be known in advance.
+    #[must_use]
+    fn id_of(&self, item: &Self::Item) -> Option<Id<Self::Item>> {
+        let _ = item;
+        None
+    }
+
+    /// Inserts a new item into the manifest, using the supplied [`Id`] rather than deriving one from the item.
+    ///
+    /// If an item with this [`Id`] already exists, [`ManifestModificationError::DuplicateId`] is returned.
+    ///
+    /// By default, the [`Id`] that the item would be given is checked via [`id_of`](MutableManifest::id_of) before anything is inserted:
+    /// [`ManifestModificationError::IdMismatch`] is returned if it does not match,
+    /// and [`ManifestModificationError::IdNotDerivable`] if it cannot be known in advance.
+    /// In both cases, the manifest is left unchanged.
+    /// Manifests that can store items under arbitrary identifiers should override this method.
+    fn insert_with_id(
+        &mut self,
+        id: Id<Self::Item>,
+        item: Self::Item,
+    ) -> Result<(), ManifestModificationError<Self>> {
+        if self.get(id).is_some() {
+            return Err(ManifestModificationError::DuplicateId(id));
+        }
+
+        match self.id_of(&item) {
+            Some(actual) if actual == id => self.insert(item).map(|_| ()),
+            Some(actual) => Err(ManifestModificationError::IdMismatch {
+                expected: id,
+                actual,
+            }),
+            None => Err(ManifestModificationError::IdNotDerivable),
+        }
+    }
+
+    /// Inserts an item with the supplied [`Id`], replacing any existing item.
+    ///
+    /// The replaced item is returned, if there was one.
+    fn upsert(
+        &mut self,
+        id: Id<Self::Item>,
+        item: Self::Item,
+    ) -> Result<Option<Self::Item>, ManifestModificationError<Self>> {
+        match self.get_mut(id) {
+            Some(existing) => Ok(Some(std::mem::replace(existing, item))),
+            None => self.insert_with_id(id, item).map(|()| None),
+        }
+    }
+
+    /// Inserts every item from the iterator via [`insert`](MutableManifest::insert), returning their identifiers in order.
+    ///
+    /// Insertion stops at the first error, which is returned.
+    /// Any items inserted before the error remain in the manifest.
+    fn extend(
+        &mut self,
+        items: impl IntoIterator<Item = Self::Item>,
+    ) -> Result<Vec<Id<Self::Item>>, ManifestModificationError<Self>> {
+        items.into_iter().map(|item| self.insert(item)).collect()
+    }
+
     /// Removes an item from the manifest.
     ///
     /// The item removed is returned, if it was found.
@@ -200,6 +396,23 @@ pub trait MutableManifest: Manifest {
         self.remove(&Id::from_name(name.borrow()))
     }
 
+    /// Removes an item from the manifest, unless any entities still refer to it.
+    ///
+    /// Entities are counted by the [`ManifestUsage`](crate::usage::ManifestUsage) resource, which is only updated once per frame.
+    /// For an exact check, or to clean up the entities as part of the removal, use [`try_remove`](crate::usage::try_remove).
+    ///
+    /// If the item is in use, [`ManifestModificationError::StillInUse`] is returned.
+    fn remove_if_unused(
+        &mut self,
+        id: &Id<Self::Item>,
+        usage: &crate::usage::ManifestUsage<Self>,
+    ) -> Result<Id<Self::Item>, ManifestModificationError<Self>> {
+        match usage.usage_count(*id) {
+            0 => self.remove(id),
+            count => Err(ManifestModificationError::StillInUse { count }),
+        }
+    }
+
     /// Gets a mutable reference to an item from the manifest by its unique identifier.
     ///
     /// Returns [`None`] if no item with the given ID is found.
@@ -215,6 +428,117 @@ pub trait MutableManifest: Manifest {
     }
 }
 
+/// A [`Manifest`] whose items can be iterated over.
+///
+/// This is required for tools that need to visit every item, such as [`ManifestView`](crate::view::ManifestView)
+/// and the [`ManifestNetworkIndex`](crate::network_index::ManifestNetworkIndex).
+/// Most manifests store their items in a map, and can simply forward to its iterator.
+pub trait IterableManifest: Manifest {
+    /// Iterates over the [`Id`] and value of every item in the manifest, in any order.
+    fn iter(&self) -> impl Iterator<Item = (Id<Self::Item>, &Self::Item)>;
+
+    /// Iterates over the [`Id`] of every item in the manifest, in any order.
+    fn ids(&self) -> impl Iterator<Item = Id<Self::Item>> {
+        self.iter().map(|(id, _)| id)
+    }
+
+    /// Returns the first item found for which `predicate` returns true.
+    ///
+    /// As iteration order is arbitrary, this should only be used when at most one item can match.
+    #[must_use]
+    fn find(
+        &self,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<(Id<Self::Item>, &Self::Item)> {
+        self.iter().find(|(_, item)| predicate(item))
+    }
+
+    /// Iterates over every item for which `predicate` returns true, in any order.
+    fn filter(
+        &self,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> impl Iterator<Item = (Id<Self::Item>, &Self::Item)> {
+        self.iter().filter(move |(_, item)| predicate(item))
+    }
+
+    /// Returns the item with the largest value of `key`.
+    ///
+    /// Ties are broken by the smallest [`Id`], so the result does not depend on iteration order.
+    #[must_use]
+    fn best_by_key<K: Ord>(
+        &self,
+        mut key: impl FnMut(&Self::Item) -> K,
+    ) -> Option<(Id<Self::Item>, &Self::Item)> {
+        self.iter()
+            .map(|(id, item)| (key(item), std::cmp::Reverse(id), item))
+            .max_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)))
+            .map(|(_, std::cmp::Reverse(id), item)| (id, item))
+    }
+}
+
+/// A [`Manifest`] whose items can also be looked up by a key other than their [`Id`].
+///
+/// Hashed [`Id`]s are a good default, but some content pipelines already have their own identifiers:
+/// UUIDs from a content database, integer keys from a spreadsheet, or composite keys like `(biome, tier)`.
+/// Implementing this trait lets those keys be used for lookup, mutation (via [`MutableGenericManifest`]) and validation,
+/// while the manifest still works with the rest of the plugin pipeline through [`Manifest`].
+///
+/// Manifests stored in a [`KeyedManifest`](crate::keyed_manifest::KeyedManifest) can forward these methods directly.
+pub trait GenericManifest: Manifest {
+    /// The key used to identify items.
+    type Key: Eq + std::hash::Hash + Copy + std::fmt::Debug + Send + Sync + 'static;
+
+    /// Gets an item from the manifest by its key.
+    ///
+    /// Returns [`None`] if no item with the given key is found.
+    #[must_use]
+    fn get_by_key(&self, key: Self::Key) -> Option<&Self::Item>;
+
+    /// Returns true if an item with the given key exists.
+    #[must_use]
+    fn contains_key(&self, key: Self::Key) -> bool {
+        self.get_by_key(key).is_some()
+    }
+
+    /// Returns the keys in `expected` which do not have an item, in the same order.
+    #[must_use]
+    fn missing_keys(&self, expected: &[Self::Key]) -> Vec<Self::Key> {
+        expected
+            .iter()
+            .copied()
+            .filter(|key| !self.contains_key(*key))
+            .collect()
+    }
+}
+
+/// A [`GenericManifest`] which can be modified using its keys.
+///
+/// See [`MutableManifest`] for when modifying manifests is appropriate.
+pub trait MutableGenericManifest: GenericManifest {
+    /// Inserts an item with the given key.
+    ///
+    /// If an item with this key already exists, [`ManifestModificationError::DuplicateKey`] should be returned.
+    fn insert_with_key(
+        &mut self,
+        key: Self::Key,
+        item: Self::Item,
+    ) -> Result<(), ManifestModificationError<Self>>;
+
+    /// Removes the item with the given key, returning it.
+    ///
+    /// If no item with this key exists, [`ManifestModificationError::KeyNotFound`] should be returned.
+    fn remove_by_key(
+        &mut self,
+        key: Self::Key,
+    ) -> Result<Self::Item, ManifestModificationError<Self>>;
+
+    /// Gets a mutable reference to an item by its key.
+    ///
+    /// Returns [`None`] if no item with the given key is found.
+    #[must_use]
+    fn get_mut_by_key(&mut self, key: Self::Key) -> Option<&mut Self::Item>;
+}
+
 /// An error that can occur when modifying a manifest.
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum ManifestModificationError<M: Manifest> {
@@ -229,7 +553,55 @@ pub enum ManifestModificationError<M: Manifest> {
     /// The item with the given ID was not found.
     #[error("The item with ID {:?} was not found.", _0)]
     NotFound(Id<M::Item>),
+    /// An item with the given ID already exists.
+    #[error("An item with ID {:?} already exists.", _0)]
+    DuplicateId(Id<M::Item>),
+    /// The item was inserted with an explicit ID, but the manifest derived a different ID for it.
+    ///
+    /// See [`MutableManifest::insert_with_id`] for more information.
+    #[error(
+        "Expected the item to have ID {:?}, but it was given ID {:?}.",
+        expected,
+        actual
+    )]
+    IdMismatch {
+        /// The ID that was requested.
+        expected: Id<M::Item>,
+        /// The ID that the manifest derived for the item.
+        actual: Id<M::Item>,
+    },
+    /// The item was inserted with an explicit ID, but the manifest cannot tell which ID it would derive for the item.
+    ///
+    /// See [`MutableManifest::id_of`] for more information.
+    #[error("The ID of the item cannot be derived without inserting it.")]
+    IdNotDerivable,
     /// The item with the given name was not found.
     #[error("No item with the name {} was found.", _0)]
     NameNotFound(String),
+    /// An item with the given key already exists.
+    ///
+    /// The key is stored in its [`Debug`](std::fmt::Debug) form, as used by [`MutableGenericManifest`].
+    #[error("An item with key {} already exists.", _0)]
+    DuplicateKey(String),
+    /// The item with the given key was not found.
+    ///
+    /// The key is stored in its [`Debug`](std::fmt::Debug) form, as used by [`MutableGenericManifest`].
+    #[error("No item with key {} was found.", _0)]
+    KeyNotFound(String),
+    /// The manifest has been frozen, and can no longer be modified.
+    ///
+    /// See [`Manifest::freeze`] for more information.
+    #[error("The manifest is frozen and cannot be modified.")]
+    Frozen,
+    /// Manifests cannot currently be modified, as the [`ManifestLock`](crate::manifest_lock::ManifestLock) is locked.
+    #[error("Manifests are locked and cannot be modified.")]
+    Locked,
+    /// The item could not be removed, as it is still referenced by live entities.
+    ///
+    /// See [`MutableManifest::remove_if_unused`] for more information.
+    #[error("The item is still used by {count} entities.")]
+    StillInUse {
+        /// The number of entities that refer to the item.
+        count: usize,
+    },
 }
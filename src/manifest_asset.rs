@@ -0,0 +1,224 @@
+//! A typed field for raw items that refer to an asset by its path.
+//!
+//! Most raw items refer to other assets by path: a sprite, a sound, a scene.
+//! Storing these as [`ManifestAsset<A>`] rather than a [`String`] records which kind of asset is expected,
+//! and turns the path into a [`Handle<A>`] in a single call during processing:
+//!
+//! ```rust,ignore
+//! #[derive(Deserialize)]
+//! struct RawMonster {
+//!     name: String,
+//!     sprite: ManifestAsset<Image>,
+//! }
+//!
+//! fn from_raw_manifest(raw_manifest: Self::RawManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     let asset_server = world.resource::<AssetServer>();
+//!     for raw_monster in raw_manifest.monsters {
+//!         let sprite: Handle<Image> = raw_monster.sprite.load(asset_server);
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! In raw manifest files, the field is written as a plain path string, such as `"sprites/goblin.png"`.
+//!
+//! Typos in these paths usually only show up much later, as missing textures or silent sounds.
+//! Insert the [`AssetPathValidation`] resource and use [`ManifestAsset::load_validated`] to catch them during processing instead,
+//! with an error that names the item and the missing path.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use bevy::asset::io::AssetReaderError;
+use bevy::asset::{Asset, AssetPath, AssetServer, Handle};
+use bevy::ecs::prelude::*;
+use bevy::log::warn;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The path to an asset of type `A`, which can be loaded into a [`Handle<A>`] while processing a manifest.
+///
+/// See the [module docs](crate::manifest_asset) for more information.
+pub struct ManifestAsset<A: Asset> {
+    path: AssetPath<'static>,
+    _phantom: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> ManifestAsset<A> {
+    /// Creates a new [`ManifestAsset`] from the supplied path.
+    #[must_use]
+    pub fn new(path: impl Into<AssetPath<'static>>) -> Self {
+        Self {
+            path: path.into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The path to the asset.
+    #[must_use]
+    pub fn path(&self) -> &AssetPath<'static> {
+        &self.path
+    }
+
+    /// Starts loading the asset, returning a strong handle to it.
+    ///
+    /// If the asset is already loading or loaded, the existing handle is returned.
+    #[must_use]
+    pub fn load(&self, asset_server: &AssetServer) -> Handle<A> {
+        asset_server.load(self.path.clone())
+    }
+
+    /// Checks that the asset exists according to the [`AssetPathValidation`] setting, then starts loading it.
+    ///
+    /// The `item_name` is only used for error reporting.
+    /// If the [`AssetPathValidation`] resource does not exist, no check is performed.
+    pub fn load_validated(
+        &self,
+        world: &World,
+        item_name: &str,
+    ) -> Result<Handle<A>, MissingAssetError> {
+        validate_asset_path(world, item_name, &self.path)?;
+        Ok(self.load(world.resource::<AssetServer>()))
+    }
+
+    /// Checks whether the asset exists in its asset source.
+    ///
+    /// Returns [`None`] if this cannot be determined,
+    /// such as when the asset source is missing or cannot be read synchronously (as on the web).
+    #[must_use]
+    pub fn exists(&self, asset_server: &AssetServer) -> Option<bool> {
+        asset_path_exists(&self.path, asset_server)
+    }
+}
+
+/// Checks whether the asset at `path` exists in its asset source.
+///
+/// Returns [`None`] if this cannot be determined,
+/// such as when the asset source is missing or cannot be read synchronously (as on the web).
+#[must_use]
+pub fn asset_path_exists(path: &AssetPath, asset_server: &AssetServer) -> Option<bool> {
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+
+    let source = asset_server.get_source(path.source().clone()).ok()?;
+    match bevy::tasks::block_on(source.reader().read(path.path())) {
+        Ok(_) => Some(true),
+        Err(AssetReaderError::NotFound(_)) => Some(false),
+        Err(_) => None,
+    }
+}
+
+/// Controls whether the paths of assets referenced by raw items are checked to exist during processing.
+///
+/// Paths are only checked when this resource exists, and only when the asset source can be read synchronously.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AssetPathValidation {
+    /// Paths are not checked.
+    #[default]
+    Off,
+    /// A warning is logged for each missing asset, but processing continues.
+    Warn,
+    /// Processing fails if any asset is missing.
+    Error,
+}
+
+/// An asset referenced by a raw item could not be found.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("The asset {path} referenced by the item {item_name} does not exist.")]
+pub struct MissingAssetError {
+    /// The name of the item that referenced the asset.
+    pub item_name: String,
+    /// The path to the missing asset.
+    pub path: AssetPath<'static>,
+}
+
+/// Checks that the asset at `path`, referenced by the item named `item_name`, exists,
+/// according to the [`AssetPathValidation`] resource.
+///
+/// This can be used for raw item fields that store paths as plain strings, rather than as a [`ManifestAsset`].
+pub fn validate_asset_path(
+    world: &World,
+    item_name: &str,
+    path: &AssetPath,
+) -> Result<(), MissingAssetError> {
+    let validation = world
+        .get_resource::<AssetPathValidation>()
+        .copied()
+        .unwrap_or_default();
+    if validation == AssetPathValidation::Off {
+        return Ok(());
+    }
+
+    if asset_path_exists(path, world.resource::<AssetServer>()) != Some(false) {
+        return Ok(());
+    }
+
+    let error = MissingAssetError {
+        item_name: item_name.to_string(),
+        path: path.clone_owned(),
+    };
+    match validation {
+        AssetPathValidation::Off => Ok(()),
+        AssetPathValidation::Warn => {
+            warn!("{error}");
+            Ok(())
+        }
+        AssetPathValidation::Error => Err(error),
+    }
+}
+
+impl<A: Asset> Clone for ManifestAsset<A> {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone())
+    }
+}
+
+impl<A: Asset> PartialEq for ManifestAsset<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<A: Asset> Eq for ManifestAsset<A> {}
+
+impl<A: Asset> std::hash::Hash for ManifestAsset<A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+    }
+}
+
+impl<A: Asset> std::fmt::Debug for ManifestAsset<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ManifestAsset").field(&self.path).finish()
+    }
+}
+
+impl<A: Asset> Display for ManifestAsset<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+impl<A: Asset> From<&str> for ManifestAsset<A> {
+    fn from(path: &str) -> Self {
+        Self::new(AssetPath::from(path.to_string()))
+    }
+}
+
+impl<A: Asset> From<String> for ManifestAsset<A> {
+    fn from(path: String) -> Self {
+        Self::new(AssetPath::from(path))
+    }
+}
+
+impl<A: Asset> Serialize for ManifestAsset<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.path)
+    }
+}
+
+impl<'de, A: Asset> Deserialize<'de> for ManifestAsset<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -0,0 +1,192 @@
+//! Support for manifests whose items are each stored in their own file.
+//!
+//! By default, a raw manifest is a single file containing every item.
+//! On larger teams, this quickly becomes a source of merge conflicts.
+//! Instead, each item can be stored in its own file inside a folder:
+//!
+//! ```text
+//! assets/
+//!     items/
+//!         sword.ron
+//!         shield.ron
+//! ```
+//!
+//! Register these manifests with [`RegisterManifest::register_manifest_dir`](crate::plugin::RegisterManifest::register_manifest_dir).
+//! Every file in the folder is loaded as a raw item, and the items are collected into the raw manifest
+//! (via its [`FromIterator`] implementation), in order of their paths.
+//! Processing then continues as normal.
+//!
+//! Note that folders cannot be loaded on platforms which can't list the contents of a directory, such as the web.
+
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use bevy::app::App;
+use bevy::asset::{
+    Asset, AssetApp, AssetServer, Assets, Handle, LoadState, LoadedFolder,
+    RecursiveDependencyLoadState,
+};
+use bevy::ecs::prelude::*;
+use bevy::log::{error, warn};
+use bevy::utils::Instant;
+use serde::de::DeserializeOwned;
+
+use crate::lifecycle::ManifestLifecycleStage;
+use crate::loader::{Compression, ManifestLoader};
+use crate::manifest::Manifest;
+use crate::plugin::{
+    manifest_schedules, prepare_raw_manifest, process_manifest, raw_manifest_unprocessed,
+    ManifestProcessingSet, ProcessManifestSet, RawManifestOptions, RawManifestTracker,
+};
+use crate::provenance::{ItemSource, ManifestProvenance};
+
+/// Keeps the folder of raw items for the manifest `M` alive until the raw manifest has been assembled.
+#[derive(Resource)]
+pub struct ManifestFolder<M: Manifest> {
+    /// A strong handle to the folder containing the raw items.
+    pub handle: Handle<LoadedFolder>,
+    _phantom: PhantomData<M>,
+}
+
+/// Registers the manifest `M`, assembling its raw manifest from a folder of `RawItem` files.
+///
+/// `RawItem` files are loaded using the built-in [`ManifestLoader`], based on their file extension.
+/// If no extensions are supplied in the `options`, the standard extension for [`Manifest::FORMAT`] is used.
+pub(crate) fn register_manifest_folder<M, RawItem>(
+    app: &mut App,
+    path: impl Into<PathBuf>,
+    options: RawManifestOptions,
+) where
+    M: Manifest,
+    M::RawManifest: FromIterator<RawItem>,
+    RawItem: Asset + DeserializeOwned,
+{
+    let path: PathBuf = path.into();
+    let format = options.format.unwrap_or(M::FORMAT);
+    let extensions = if options.extensions.is_empty() {
+        format.extensions()
+    } else {
+        options.extensions
+    };
+
+    let item_loader = ManifestLoader::<RawItem>::new(format, Compression::None)
+        .with_transform(options.transform)
+        .with_extensions(extensions);
+
+    #[cfg(feature = "csv")]
+    let item_loader = item_loader.with_csv_settings(options.csv.unwrap_or_default());
+
+    prepare_raw_manifest::<M>(app, Compression::None, RawManifestOptions::default());
+    let schedules = manifest_schedules(app);
+    app.init_asset::<RawItem>()
+        .register_asset_loader(item_loader)
+        .add_systems(
+            schedules.loading,
+            assemble_raw_manifest::<M, RawItem>.run_if(resource_exists::<ManifestFolder<M>>),
+        )
+        .add_systems(
+            schedules.processing,
+            process_manifest::<M>
+                .in_set(ProcessManifestSet)
+                .in_set(ManifestProcessingSet::<M>::default())
+                .run_if(not(resource_exists::<M>).and_then(raw_manifest_unprocessed::<M>)),
+        );
+
+    let folder_handle = app
+        .world
+        .resource::<AssetServer>()
+        .load_folder(path.to_string_lossy().into_owned());
+    let raw_manifest_handle = app
+        .world
+        .resource::<Assets<M::RawManifest>>()
+        .reserve_handle();
+
+    app.world.insert_resource(ManifestFolder::<M> {
+        handle: folder_handle,
+        _phantom: PhantomData,
+    });
+    app.world
+        .resource_mut::<RawManifestTracker>()
+        .register_pending::<M>(path, raw_manifest_handle);
+}
+
+/// Once every file in the folder has loaded, collects the raw items into the raw manifest of `M`.
+///
+/// The path of each raw item is recorded in the [`ManifestProvenance`] of `M`.
+///
+/// If any file fails to load, the raw manifest is marked as [`LoadState::Failed`].
+pub fn assemble_raw_manifest<M, RawItem>(
+    folder: Res<ManifestFolder<M>>,
+    asset_server: Res<AssetServer>,
+    loaded_folders: Res<Assets<LoadedFolder>>,
+    mut raw_items: ResMut<Assets<RawItem>>,
+    mut raw_manifests: ResMut<Assets<M::RawManifest>>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    mut commands: Commands,
+) where
+    M: Manifest,
+    M::RawManifest: FromIterator<RawItem>,
+    RawItem: Asset,
+{
+    let Some(status) = raw_manifest_tracker.status_mut::<M>() else {
+        return;
+    };
+
+    match asset_server.get_recursive_dependency_load_state(folder.handle.id()) {
+        Some(RecursiveDependencyLoadState::Loaded) => (),
+        Some(RecursiveDependencyLoadState::Failed) => {
+            error!(
+                "Failed to load the folder of raw items for the manifest {}.",
+                type_name::<M>()
+            );
+            status.load_state = LoadState::Failed;
+            raw_manifest_tracker.record_lifecycle::<M>(
+                ManifestLifecycleStage::LoadFailed,
+                Some("A file in the folder failed to load.".to_string()),
+            );
+            commands.remove_resource::<ManifestFolder<M>>();
+            return;
+        }
+        _ => return,
+    }
+
+    let Some(loaded_folder) = loaded_folders.get(&folder.handle) else {
+        return;
+    };
+
+    let mut handles: Vec<Handle<RawItem>> = Vec::with_capacity(loaded_folder.handles.len());
+    for handle in &loaded_folder.handles {
+        match handle.clone().try_typed::<RawItem>() {
+            Ok(handle) => handles.push(handle),
+            Err(_) => warn!(
+                "Ignoring {:?} in the folder for the manifest {}, as it is not a {}.",
+                handle.path(),
+                type_name::<M>(),
+                type_name::<RawItem>()
+            ),
+        }
+    }
+    // Keep the order of items stable, regardless of the order the files were loaded in.
+    handles.sort_by_cached_key(|handle| handle.path().map(ToString::to_string));
+
+    let mut raw_sources = Vec::with_capacity(handles.len());
+    let raw_manifest: M::RawManifest = handles
+        .iter()
+        .filter_map(|handle| {
+            let raw_item = raw_items.remove(handle)?;
+            let path = handle.path().map(|path| path.path().to_path_buf());
+            raw_sources.push(ItemSource::new(path.unwrap_or_default()));
+            Some(raw_item)
+        })
+        .collect();
+
+    raw_manifests.insert(status.handle.id().typed::<M::RawManifest>(), raw_manifest);
+    status.load_state = LoadState::Loaded;
+    status.load_count += 1;
+    status.load_finished_at = Some(Instant::now());
+    raw_manifest_tracker.record_lifecycle::<M>(ManifestLifecycleStage::LoadCompleted, None);
+
+    commands.insert_resource(ManifestProvenance::<M>::from_raw_sources(raw_sources));
+    commands.remove_resource::<ManifestFolder<M>>();
+}
@@ -0,0 +1,163 @@
+//! A global switch that prevents manifests from being modified at runtime.
+//!
+//! [`MutableManifest`](crate::manifest::MutableManifest) is convenient in editors, debug tools and mod loaders,
+//! but shipping builds often want a guarantee that game data cannot change once loaded.
+//! Inserting a locked [`ManifestLock`] resource makes every modification made through [`ManifestMut`]
+//! (or [`try_remove`](crate::usage::try_remove)) fail with [`ManifestModificationError::Locked`]:
+//!
+//! ```rust,ignore
+//! // Locked in release builds, unlocked in debug builds.
+//! app.insert_resource(ManifestLock::release_builds());
+//!
+//! fn enter_modding_mode(mut lock: ResMut<ManifestLock>) {
+//!     lock.unlock();
+//! }
+//!
+//! fn buff_swords(mut items: ManifestMut<ItemManifest>) {
+//!     if let Ok(items) = items.get_mut() {
+//!         items.get_mut(SWORD).unwrap().damage += 1;
+//!     }
+//! }
+//! ```
+//!
+//! Every modification routed through this crate respects the lock:
+//! [`ManifestMut`], [`try_remove`](crate::usage::try_remove),
+//! [`ManifestTransaction::commit_in_world`](crate::transaction::ManifestTransaction::commit_in_world),
+//! [`restore_all_manifests`](crate::snapshot::restore_all_manifests), [`PATCH_METHOD`](crate::remote::PATCH_METHOD) requests and the manifest editor.
+//!
+//! Bevy offers no way to forbid access to a resource, so code with direct access to `ResMut<M>` can still modify the manifest.
+//! Systems which should respect the lock must use [`ManifestMut`] instead.
+//!
+//! [`ManifestMut`] is only available when the `mutable` feature is enabled.
+//! Without it, shipping builds have no way to modify manifests through this module.
+//! Manifests which implement [`Manifest::freeze`](crate::manifest::Manifest::freeze) are also frozen once processed,
+//! so they can't be modified at all: see [`ManifestPlugin::freeze_manifests`](crate::plugin::ManifestPlugin::freeze_manifests).
+//! Manifests which keep the default, no-op implementation are left as they are.
+
+#[cfg(feature = "mutable")]
+use std::ops::Deref;
+
+use bevy::ecs::prelude::*;
+#[cfg(feature = "mutable")]
+use bevy::ecs::system::SystemParam;
+
+#[cfg(feature = "mutable")]
+use crate::identifier::Id;
+#[cfg(feature = "mutable")]
+use crate::manifest::MutableManifest;
+use crate::manifest::{Manifest, ManifestModificationError};
+
+/// Controls whether manifests may be modified through [`ManifestMut`].
+///
+/// If this resource does not exist, manifests are unlocked.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ManifestLock {
+    locked: bool,
+}
+
+impl ManifestLock {
+    /// A lock which allows manifests to be modified.
+    #[must_use]
+    pub const fn unlocked() -> Self {
+        Self { locked: false }
+    }
+
+    /// A lock which prevents manifests from being modified.
+    #[must_use]
+    pub const fn locked() -> Self {
+        Self { locked: true }
+    }
+
+    /// A lock which prevents manifests from being modified in release builds only,
+    /// based on whether `debug_assertions` are enabled.
+    #[must_use]
+    pub const fn release_builds() -> Self {
+        Self {
+            locked: !cfg!(debug_assertions),
+        }
+    }
+
+    /// Returns true if manifests cannot currently be modified.
+    #[must_use]
+    pub const fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Prevents manifests from being modified.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Allows manifests to be modified, such as when entering an editor or modding mode.
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    /// Returns [`ManifestModificationError::Locked`] if manifests cannot currently be modified.
+    pub fn check<M: Manifest>(&self) -> Result<(), ManifestModificationError<M>> {
+        if self.locked {
+            Err(ManifestModificationError::Locked)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A [`SystemParam`] for modifying the manifest `M`, which respects the [`ManifestLock`].
+///
+/// This dereferences to the manifest, so reading from it is always allowed.
+///
+/// This is only available when the `mutable` feature is enabled.
+#[cfg(feature = "mutable")]
+#[derive(SystemParam)]
+pub struct ManifestMut<'w, M: Manifest> {
+    manifest: ResMut<'w, M>,
+    lock: Option<Res<'w, ManifestLock>>,
+}
+
+#[cfg(feature = "mutable")]
+impl<'w, M: Manifest> ManifestMut<'w, M> {
+    /// Returns true if the manifest cannot currently be modified.
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.lock.as_ref().is_some_and(|lock| lock.is_locked())
+    }
+
+    /// Returns mutable access to the manifest, or [`ManifestModificationError::Locked`] if the [`ManifestLock`] is locked.
+    pub fn get_mut(&mut self) -> Result<&mut M, ManifestModificationError<M>> {
+        if self.is_locked() {
+            return Err(ManifestModificationError::Locked);
+        }
+
+        Ok(self.manifest.as_mut())
+    }
+}
+
+#[cfg(feature = "mutable")]
+impl<'w, M: MutableManifest> ManifestMut<'w, M> {
+    /// Inserts a new item into the manifest, if it is not locked.
+    ///
+    /// See [`MutableManifest::insert`].
+    pub fn insert(&mut self, item: M::Item) -> Result<Id<M::Item>, ManifestModificationError<M>> {
+        self.get_mut()?.insert(item)
+    }
+
+    /// Removes an item from the manifest, if it is not locked.
+    ///
+    /// See [`MutableManifest::remove`].
+    pub fn remove(
+        &mut self,
+        id: &Id<M::Item>,
+    ) -> Result<Id<M::Item>, ManifestModificationError<M>> {
+        self.get_mut()?.remove(id)
+    }
+}
+
+#[cfg(feature = "mutable")]
+impl<'w, M: Manifest> Deref for ManifestMut<'w, M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.manifest
+    }
+}
@@ -0,0 +1,294 @@
+//! Explicit handling of conflicts when raw items from several sources are merged into one manifest.
+//!
+//! When a manifest is assembled from several files (see [`manifest_folder`](crate::manifest_folder)),
+//! two files can easily define an item with the same key.
+//! Silently keeping the last definition hides mistakes, particularly when mods override base game data.
+//! Instead, each manifest has a [`ConflictPolicy`], set via [`ManifestRegistration::conflict_policy`](crate::registration::ManifestRegistration::conflict_policy),
+//! which decides what happens.
+//!
+//! Call [`merge_raw_items`] at the start of [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+//! Every conflict and its resolution is listed in the [`MergeReport`] resource for that manifest.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::manifest::Manifest;
+use crate::provenance::{ItemSource, ManifestProvenance};
+
+/// Decides what happens when two raw items share the same key.
+#[derive(Default)]
+pub enum ConflictPolicy<T> {
+    /// Conflicts are an error, and the manifest fails to process.
+    #[default]
+    Error,
+    /// The first definition is kept, and later definitions are discarded.
+    FirstWins,
+    /// Later definitions replace earlier ones.
+    LastWins,
+    /// The two definitions are combined by the supplied function, which is passed the existing and the new definition.
+    Custom(Arc<dyn Fn(&T, &T) -> T + Send + Sync>),
+}
+
+impl<T> ConflictPolicy<T> {
+    /// Creates a [`ConflictPolicy::Custom`] policy from the supplied function.
+    #[must_use]
+    pub fn custom(resolve: impl Fn(&T, &T) -> T + Send + Sync + 'static) -> Self {
+        ConflictPolicy::Custom(Arc::new(resolve))
+    }
+}
+
+impl<T> Clone for ConflictPolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            ConflictPolicy::Error => ConflictPolicy::Error,
+            ConflictPolicy::FirstWins => ConflictPolicy::FirstWins,
+            ConflictPolicy::LastWins => ConflictPolicy::LastWins,
+            ConflictPolicy::Custom(resolve) => ConflictPolicy::Custom(resolve.clone()),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ConflictPolicy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictPolicy::Error => f.write_str("Error"),
+            ConflictPolicy::FirstWins => f.write_str("FirstWins"),
+            ConflictPolicy::LastWins => f.write_str("LastWins"),
+            ConflictPolicy::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// The [`ConflictPolicy`] used when merging the `T` raw items of the manifest `M`.
+///
+/// This is inserted by [`ManifestRegistration::conflict_policy`](crate::registration::ManifestRegistration::conflict_policy).
+/// If it does not exist, [`ConflictPolicy::Error`] is used.
+#[derive(Resource)]
+pub struct ManifestConflictPolicy<M: Manifest, T: 'static> {
+    /// The policy to apply.
+    pub policy: ConflictPolicy<T>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest, T: 'static> ManifestConflictPolicy<M, T> {
+    /// Creates a new resource wrapping `policy`.
+    #[must_use]
+    pub fn new(policy: ConflictPolicy<T>) -> Self {
+        Self {
+            policy,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// How a single conflict was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictResolution {
+    /// The existing definition was kept.
+    KeptFirst,
+    /// The new definition replaced the existing one.
+    KeptLast,
+    /// The definitions were combined by a [`ConflictPolicy::Custom`] function.
+    Combined,
+}
+
+/// A single conflict found while merging raw items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestConflict {
+    /// The key shared by the conflicting items.
+    pub key: String,
+    /// Where the existing definition came from, if known.
+    pub first: Option<ItemSource>,
+    /// Where the new definition came from, if known.
+    pub second: Option<ItemSource>,
+    /// How the conflict was resolved.
+    pub resolution: ConflictResolution,
+}
+
+/// Every conflict found the last time the raw items of the manifest `M` were merged.
+#[derive(Resource)]
+pub struct MergeReport<M: Manifest> {
+    /// The conflicts, in the order they were found.
+    pub conflicts: Vec<ManifestConflict>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> std::fmt::Debug for MergeReport<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MergeReport")
+            .field("conflicts", &self.conflicts)
+            .finish()
+    }
+}
+
+/// An error returned when raw items conflict under [`ConflictPolicy::Error`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("The key {key} is defined more than once (in {} and {}).", display_source(.first), display_source(.second))]
+pub struct MergeError {
+    /// The key shared by the conflicting items.
+    pub key: String,
+    /// Where the existing definition came from, if known.
+    pub first: Option<ItemSource>,
+    /// Where the new definition came from, if known.
+    pub second: Option<ItemSource>,
+}
+
+fn display_source(source: &Option<ItemSource>) -> String {
+    match source {
+        Some(source) => source.to_string(),
+        None => "an unknown source".to_string(),
+    }
+}
+
+impl Display for ConflictResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictResolution::KeptFirst => f.write_str("kept the first definition"),
+            ConflictResolution::KeptLast => f.write_str("kept the last definition"),
+            ConflictResolution::Combined => f.write_str("combined the definitions"),
+        }
+    }
+}
+
+/// Merges raw items which share the same key, according to `policy`.
+///
+/// Each item is paired with its source, if known.
+/// Merged items stay in the position where their key was first defined.
+/// Returns the merged items with their sources, along with every conflict that was found.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::merge::{merge_items, ConflictPolicy, ConflictResolution};
+/// use leafwing_manifest::provenance::ItemSource;
+///
+/// // Raw items are (name, damage) pairs, and the base game and a mod both define a sword.
+/// type RawItem = (&'static str, u32);
+///
+/// fn key(item: &RawItem) -> &str {
+///     item.0
+/// }
+///
+/// fn sword(merged: &[(RawItem, Option<ItemSource>)]) -> (RawItem, Option<ItemSource>) {
+///     merged[0].clone()
+/// }
+///
+/// let items = || {
+///     [
+///         (("sword", 10), Some(ItemSource::new("base/items.ron"))),
+///         (("shield", 0), Some(ItemSource::new("base/items.ron"))),
+///         (("sword", 15), Some(ItemSource::new("mods/items.ron"))),
+///     ]
+/// };
+///
+/// // By default, conflicts are an error which names both sources.
+/// let error = merge_items(items(), key, &ConflictPolicy::Error).unwrap_err();
+/// assert_eq!(error.key, "sword");
+/// assert_eq!(error.second, Some(ItemSource::new("mods/items.ron")));
+///
+/// let (merged, conflicts) = merge_items(items(), key, &ConflictPolicy::FirstWins).unwrap();
+/// assert_eq!(merged.len(), 2);
+/// assert_eq!(sword(&merged), (("sword", 10), Some(ItemSource::new("base/items.ron"))));
+/// assert_eq!(conflicts[0].resolution, ConflictResolution::KeptFirst);
+///
+/// // Merged items keep the position where their key was first defined.
+/// let (merged, conflicts) = merge_items(items(), key, &ConflictPolicy::LastWins).unwrap();
+/// assert_eq!(sword(&merged), (("sword", 15), Some(ItemSource::new("mods/items.ron"))));
+/// assert_eq!(conflicts[0].resolution, ConflictResolution::KeptLast);
+///
+/// let strongest = ConflictPolicy::custom(|a: &RawItem, b: &RawItem| (a.0, a.1.max(b.1) + 1));
+/// let (merged, conflicts) = merge_items(items(), key, &strongest).unwrap();
+/// assert_eq!(sword(&merged).0, ("sword", 16));
+/// assert_eq!(conflicts.len(), 1);
+/// assert_eq!(conflicts[0].resolution, ConflictResolution::Combined);
+/// ```
+pub fn merge_items<T>(
+    items: impl IntoIterator<Item = (T, Option<ItemSource>)>,
+    key: impl Fn(&T) -> &str,
+    policy: &ConflictPolicy<T>,
+) -> Result<(Vec<(T, Option<ItemSource>)>, Vec<ManifestConflict>), MergeError> {
+    let mut merged: Vec<(T, Option<ItemSource>)> = Vec::new();
+    let mut indexes: HashMap<String, usize> = HashMap::default();
+    let mut conflicts = Vec::new();
+
+    for (item, source) in items {
+        let item_key = key(&item).to_string();
+        let Some(&index) = indexes.get(&item_key) else {
+            indexes.insert(item_key, merged.len());
+            merged.push((item, source));
+            continue;
+        };
+
+        let existing = &mut merged[index];
+        let resolution = match policy {
+            ConflictPolicy::Error => {
+                return Err(MergeError {
+                    key: item_key,
+                    first: existing.1.clone(),
+                    second: source,
+                });
+            }
+            ConflictPolicy::FirstWins => ConflictResolution::KeptFirst,
+            ConflictPolicy::LastWins => ConflictResolution::KeptLast,
+            ConflictPolicy::Custom(_) => ConflictResolution::Combined,
+        };
+
+        conflicts.push(ManifestConflict {
+            key: item_key,
+            first: existing.1.clone(),
+            second: source.clone(),
+            resolution,
+        });
+
+        match policy {
+            ConflictPolicy::Error | ConflictPolicy::FirstWins => (),
+            ConflictPolicy::LastWins => *existing = (item, source),
+            ConflictPolicy::Custom(resolve) => {
+                existing.0 = resolve(&existing.0, &item);
+                existing.1 = source;
+            }
+        }
+    }
+
+    Ok((merged, conflicts))
+}
+
+/// Merges the raw items of the manifest `M`, using its [`ManifestConflictPolicy`].
+///
+/// The sources of the raw items are read from the [`ManifestProvenance`] of `M`, and updated to match the merged items.
+/// The conflicts are stored in the [`MergeReport`] resource for `M`.
+pub fn merge_raw_items<M: Manifest, T: 'static>(
+    items: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> &str,
+    world: &mut World,
+) -> Result<Vec<T>, MergeError> {
+    let policy = world
+        .get_resource::<ManifestConflictPolicy<M, T>>()
+        .map(|resource| resource.policy.clone())
+        .unwrap_or_default();
+
+    let provenance = world.get_resource::<ManifestProvenance<M>>();
+    let items = items.into_iter().enumerate().map(|(index, item)| {
+        let source = provenance.and_then(|provenance| provenance.raw_source(index).cloned());
+        (item, source)
+    });
+
+    let (merged, conflicts) = merge_items(items, key, &policy)?;
+    let (merged, sources): (Vec<T>, Vec<Option<ItemSource>>) = merged.into_iter().unzip();
+
+    if let Some(mut provenance) = world.get_resource_mut::<ManifestProvenance<M>>() {
+        if sources.iter().all(Option::is_some) {
+            provenance.set_raw_sources(sources.into_iter().flatten().collect());
+        }
+    }
+    world.insert_resource(MergeReport::<M> {
+        conflicts,
+        _phantom: PhantomData,
+    });
+
+    Ok(merged)
+}
@@ -0,0 +1,242 @@
+//! Keeping old [`Id`]s working after content is renamed or merged, such as those stored in player saves.
+//!
+//! [`Id`]s are hashes of item names, so renaming an item, or merging two items into one, changes the [`Id`] it is stored under.
+//! Unlike [aliases](crate::aliases), which live alongside the items themselves,
+//! [`IdMigrations`] are loaded from their own manifest file, which maps each old name (or raw [`Id`]) to its replacement:
+//!
+//! ```ron
+//! (
+//!     migrations: [
+//!         (from: "rusty_sword", to: "iron_sword"),
+//!         // Items whose names were never recorded can be migrated by their raw id.
+//!         (from: "#0x3c1f0a5e7d2b9481", to: "iron_sword"),
+//!     ],
+//! )
+//! ```
+//!
+//! [`IdMigrations`] is itself a [`Manifest`], so it is registered like any other:
+//!
+//! ```rust
+//! use bevy::ecs::system::SystemState;
+//! use leafwing_manifest::{
+//!     aliases::AliasedManifest,
+//!     asset_state::SimpleAssetState,
+//!     identifier::Id,
+//!     migrations::{IdMigrations, RawIdMigration, RawIdMigrations},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//!
+//! let mut app = manifest_test_app();
+//! app.insert_raw_manifest::<ItemManifest>(ItemManifest::from_items([Item::new("iron_sword", 10)]))
+//!     // Usually loaded from a file, via `app.register_manifest::<IdMigrations<Item>>("item_migrations.ron")`.
+//!     .insert_raw_manifest::<IdMigrations<Item>>(RawIdMigrations::new(vec![
+//!         RawIdMigration {
+//!             from: "rusty_sword".to_string(),
+//!             to: "iron_sword".to_string(),
+//!         },
+//!         RawIdMigration {
+//!             from: "#0x3c1f0a5e7d2b9481".to_string(),
+//!             to: "iron_sword".to_string(),
+//!         },
+//!     ]));
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! // The ids stored in an old save.
+//! let inventory = [Id::<Item>::from_name("rusty_sword"), Id::from_raw(0x3c1f0a5e7d2b9481)];
+//!
+//! let mut system_state = SystemState::<AliasedManifest<ItemManifest>>::new(&mut app.world);
+//! let items = system_state.get(&app.world);
+//! for id in inventory {
+//!     // Old ids are migrated to their replacements before the lookup.
+//!     assert_eq!(items.get(id).unwrap().name, "iron_sword");
+//! }
+//!
+//! let migrations = app.world.resource::<IdMigrations<Item>>();
+//! assert_eq!(migrations.resolve_migrated(inventory[0]), Id::from_name("iron_sword"));
+//! ```
+//!
+//! Lookups through an [`AliasedManifest`](crate::aliases::AliasedManifest) only consult the migrations
+//! if the [`IdMigrations`] resource exists: registering the migration manifest is the opt-in.
+//! Saved ids can also be rewritten directly with [`IdMigrations::resolve_migrated`].
+//!
+//! By default, migrations are read as RON.
+//! Other formats can be used with [`RawManifestOptions::with_format`](crate::plugin::RawManifestOptions::with_format).
+
+use std::marker::PhantomData;
+
+use bevy::asset::{Asset, UntypedAssetId, VisitAssetDependencies};
+use bevy::ecs::prelude::*;
+use bevy::reflect::TypePath;
+use serde::Deserialize;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::{Manifest, ManifestFormat};
+use crate::name_registry::RAW_ID_PREFIX;
+
+/// A single entry in a [`RawIdMigrations`] file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RawIdMigration {
+    /// The old name of the item, or its raw [`Id`] prefixed with [`RAW_ID_PREFIX`].
+    pub from: String,
+    /// The name of the item that replaces it.
+    pub to: String,
+}
+
+/// The raw manifest of [`IdMigrations`] for items of type `T`.
+#[derive(Debug, Clone, PartialEq, Eq, TypePath, Deserialize)]
+pub struct RawIdMigrations<T: TypePath> {
+    /// The migrations, in any order.
+    pub migrations: Vec<RawIdMigration>,
+    #[serde(skip)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: TypePath> RawIdMigrations<T> {
+    /// Creates a raw manifest from the supplied migrations.
+    #[must_use]
+    pub fn new(migrations: Vec<RawIdMigration>) -> Self {
+        Self {
+            migrations,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: TypePath + Send + Sync> Asset for RawIdMigrations<T> {}
+
+impl<T: TypePath + Send + Sync> VisitAssetDependencies for RawIdMigrations<T> {
+    fn visit_dependencies(&self, _visit: &mut impl FnMut(UntypedAssetId)) {}
+}
+
+/// An error that occurred while processing [`RawIdMigrations`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MigrationError {
+    /// The `from` field of a migration started with [`RAW_ID_PREFIX`], but was not followed by a hexadecimal number.
+    #[error("The migration from {0} does not contain a valid raw id")]
+    InvalidRawId(String),
+    /// The same old name was migrated more than once.
+    #[error("The name {0} is migrated more than once")]
+    Duplicate(String),
+    /// Following the migrations from this name leads back to itself.
+    #[error("The migration from {0} is part of a cycle")]
+    Cycle(String),
+}
+
+/// A map from the old [`Id`]s of items of type `T` to the [`Id`]s that replaced them.
+///
+/// Chains of migrations (such as `a -> b` followed by a later `b -> c`) are resolved when the manifest is processed,
+/// so each old [`Id`] maps directly to its final replacement.
+#[derive(Resource)]
+pub struct IdMigrations<T: 'static> {
+    migrations: IdMap<T, Id<T>>,
+}
+
+impl<T: 'static> IdMigrations<T> {
+    /// Returns the [`Id`] that replaced `id`, or `id` itself if it was never migrated.
+    #[must_use]
+    pub fn resolve_migrated(&self, id: Id<T>) -> Id<T> {
+        self.migrations.get(&id).copied().unwrap_or(id)
+    }
+
+    /// Returns true if `id` has been migrated to a different [`Id`].
+    #[must_use]
+    pub fn is_migrated(&self, id: Id<T>) -> bool {
+        self.migrations.contains_key(&id)
+    }
+
+    /// Iterates over each old [`Id`] and its final replacement, in any order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, Id<T>)> + '_ {
+        self.migrations.iter().map(|(from, to)| (*from, *to))
+    }
+
+    /// The number of migrated [`Id`]s.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.migrations.len()
+    }
+
+    /// Returns true if no [`Id`]s have been migrated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+}
+
+impl<T: 'static> Default for IdMigrations<T> {
+    fn default() -> Self {
+        Self {
+            migrations: IdMap::default(),
+        }
+    }
+}
+
+impl<T: 'static> std::fmt::Debug for IdMigrations<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdMigrations")
+            .field("migrations", &self.migrations.len())
+            .finish()
+    }
+}
+
+/// Reads the `from` field of a migration, which is either a name or a raw [`Id`].
+fn parse_old_id<T>(from: &str) -> Result<Id<T>, MigrationError> {
+    match from.strip_prefix(RAW_ID_PREFIX) {
+        Some(hex) => u64::from_str_radix(hex, 16)
+            .map(Id::from_raw)
+            .map_err(|_| MigrationError::InvalidRawId(from.to_string())),
+        None => Ok(Id::from_name(from)),
+    }
+}
+
+impl<T: TypePath + Send + Sync> Manifest for IdMigrations<T> {
+    type RawManifest = RawIdMigrations<T>;
+    type RawItem = RawIdMigration;
+    type Item = Id<T>;
+    type ConversionError = MigrationError;
+
+    #[cfg(feature = "ron")]
+    const FORMAT: ManifestFormat = ManifestFormat::Ron;
+    #[cfg(not(feature = "ron"))]
+    const FORMAT: ManifestFormat = ManifestFormat::Custom;
+
+    fn from_raw_manifest(
+        raw_manifest: RawIdMigrations<T>,
+        _world: &mut World,
+    ) -> Result<Self, MigrationError> {
+        let mut direct: IdMap<T, (String, Id<T>)> = IdMap::default();
+        for migration in raw_manifest.migrations {
+            let from = parse_old_id(&migration.from)?;
+            let to = Id::from_name(&migration.to);
+            if direct.insert(from, (migration.from.clone(), to)).is_some() {
+                return Err(MigrationError::Duplicate(migration.from));
+            }
+        }
+
+        let mut migrations = IdMap::default();
+        for (from, (name, to)) in &direct {
+            // Each step visits a new id, so a chain longer than the number of migrations must loop.
+            let mut target = *to;
+            let mut steps = 0;
+            while let Some((_, next)) = direct.get(&target) {
+                if target == *from || steps > direct.len() {
+                    return Err(MigrationError::Cycle(name.clone()));
+                }
+                target = *next;
+                steps += 1;
+            }
+
+            migrations.insert(*from, target);
+        }
+
+        Ok(Self { migrations })
+    }
+
+    fn get(&self, id: Id<Id<T>>) -> Option<&Id<T>> {
+        self.migrations.get(&Id::from_raw(id.raw()))
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.migrations.len())
+    }
+}
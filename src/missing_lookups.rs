@@ -0,0 +1,246 @@
+//! Reporting lookups of items which are not in their manifest.
+//!
+//! [`Manifest::get`] and [`Manifest::get_by_name`] return [`None`] for missing items, which is easy to ignore.
+//! When code and data drift apart (an item is renamed in the manifest, but not in the code that refers to it),
+//! these silent misses can hide the problem until much later.
+//!
+//! Setting a [`MissingLookupPolicy`] in the [`MissingLookups`] resource makes misses loud instead, suggesting the closest registered name:
+//!
+//! ```rust,ignore
+//! // For every manifest:
+//! app.insert_resource(MissingLookups::new(MissingLookupPolicy::Log));
+//! // Or for a single manifest, overriding the default:
+//! app.register_manifest_with(
+//!     ManifestRegistration::<ItemManifest>::new()
+//!         .path("items.ron")
+//!         .missing_lookups(MissingLookupPolicy::Panic),
+//! );
+//!
+//! fn use_items(items: CheckedManifest<ItemManifest>) {
+//!     // ERROR: No item named swrod was found in ItemManifest: did you mean sword?
+//!     items.get_by_name("swrod");
+//! }
+//! ```
+//!
+//! [`Manifest::get`] and [`Manifest::get_by_name`] have no access to the [`World`](bevy::ecs::world::World),
+//! so misses are only reported by lookups made through the [`CheckedManifest`] and [`AliasedManifest`](crate::aliases::AliasedManifest) system parameters,
+//! which read the policy from the [`MissingLookups`] resource.
+//! To report misses from elsewhere, call [`report_missing_lookup`] or [`report_missing_name`] with the policy of the manifest.
+//!
+//! Suggestions are taken from the [`NameRegistry`], so are only available if names were registered while processing.
+
+use std::any::TypeId;
+use std::borrow::Borrow;
+use std::ops::Deref;
+
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::log::error;
+use bevy::utils::HashMap;
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+
+/// How lookups of items which are missing from a manifest are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MissingLookupPolicy {
+    /// Missing items are silently returned as [`None`].
+    ///
+    /// This is the default.
+    #[default]
+    Ignore,
+    /// An error is logged for each missing item, suggesting the closest registered name.
+    Log,
+    /// The app panics on missing items in debug builds.
+    ///
+    /// In release builds, this behaves like [`MissingLookupPolicy::Log`].
+    Panic,
+}
+
+/// The settings for how missing lookups are reported, for each manifest.
+///
+/// This resource is initialized by the [`ManifestPlugin`](crate::plugin::ManifestPlugin),
+/// and updated by [`ManifestRegistration::missing_lookups`](crate::registration::ManifestRegistration::missing_lookups).
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissingLookups {
+    default: MissingLookupPolicy,
+    per_manifest: HashMap<TypeId, MissingLookupPolicy>,
+}
+
+impl MissingLookups {
+    /// Creates settings which use the supplied policy for every manifest.
+    #[must_use]
+    pub fn new(default: MissingLookupPolicy) -> Self {
+        Self {
+            default,
+            per_manifest: HashMap::default(),
+        }
+    }
+
+    /// Sets the policy used for manifests which do not have their own policy.
+    pub fn set_default(&mut self, policy: MissingLookupPolicy) {
+        self.default = policy;
+    }
+
+    /// Sets the policy used for the manifest `M`, overriding the default.
+    pub fn set<M: Manifest>(&mut self, policy: MissingLookupPolicy) {
+        self.per_manifest.insert(TypeId::of::<M>(), policy);
+    }
+
+    /// Returns the policy used for the manifest `M`.
+    #[must_use]
+    pub fn policy<M: Manifest>(&self) -> MissingLookupPolicy {
+        self.per_manifest
+            .get(&TypeId::of::<M>())
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// A [`SystemParam`] for reading the manifest `M`, which reports lookups of missing items according to the [`MissingLookups`].
+///
+/// This dereferences to the manifest, so every other method of `M` continues to work unchanged.
+///
+/// # Example
+///
+/// Policies are stored per [`World`], so each app can report misses differently:
+///
+/// ```
+/// use bevy::ecs::system::SystemState;
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     identifier::{Id, IdMap},
+///     manifest::{Manifest, ManifestFormat},
+///     missing_lookups::{CheckedManifest, MissingLookupPolicy, MissingLookups},
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Resource, Asset, TypePath, Deserialize, Default)]
+/// struct ItemManifest {
+///     items: IdMap<i32, i32>,
+/// }
+///
+/// impl Manifest for ItemManifest {
+///     type Item = i32;
+///     type RawItem = i32;
+///     type RawManifest = ItemManifest;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+///
+///     fn get(&self, id: Id<i32>) -> Option<&i32> {
+///         self.items.get(&id)
+///     }
+///
+///     fn from_raw_manifest(
+///         raw_manifest: ItemManifest,
+///         _world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         Ok(raw_manifest)
+///     }
+/// }
+///
+/// let mut lenient = World::new();
+/// lenient.init_resource::<ItemManifest>();
+/// let mut state = SystemState::<CheckedManifest<ItemManifest>>::new(&mut lenient);
+/// assert_eq!(state.get(&lenient).get_by_name("swrod"), None);
+///
+/// let mut strict = World::new();
+/// strict.init_resource::<ItemManifest>();
+/// strict.insert_resource(MissingLookups::new(MissingLookupPolicy::Panic));
+/// let mut state = SystemState::<CheckedManifest<ItemManifest>>::new(&mut strict);
+/// assert_eq!(state.get(&strict).policy(), MissingLookupPolicy::Panic);
+/// ```
+#[derive(SystemParam)]
+pub struct CheckedManifest<'w, M: Manifest> {
+    manifest: Res<'w, M>,
+    lookups: Option<Res<'w, MissingLookups>>,
+}
+
+impl<'w, M: Manifest> CheckedManifest<'w, M> {
+    /// The policy used for lookups of items missing from `M`.
+    #[must_use]
+    pub fn policy(&self) -> MissingLookupPolicy {
+        self.lookups
+            .as_ref()
+            .map_or(MissingLookupPolicy::Ignore, |lookups| lookups.policy::<M>())
+    }
+
+    /// Returns the item with the given `id`, reporting it if it is missing.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        let item = self.manifest.get(id);
+        if item.is_none() {
+            report_missing_lookup(self.policy(), &*self.manifest, id);
+        }
+        item
+    }
+
+    /// Returns the item with the given `name`, reporting it if it is missing.
+    #[must_use]
+    pub fn get_by_name(&self, name: impl Borrow<str>) -> Option<&M::Item> {
+        let item = self.manifest.get(Id::from_name(name.borrow()));
+        if item.is_none() {
+            report_missing_name(self.policy(), &*self.manifest, name.borrow());
+        }
+        item
+    }
+}
+
+impl<'w, M: Manifest> Deref for CheckedManifest<'w, M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.manifest
+    }
+}
+
+/// Reports that no item with the given `id` was found in the `manifest`, according to the `policy`.
+///
+/// The policy for each manifest is stored in the [`MissingLookups`] resource.
+pub fn report_missing_lookup<M: Manifest>(
+    policy: MissingLookupPolicy,
+    manifest: &M,
+    id: Id<M::Item>,
+) {
+    match NameRegistry::name_of(id) {
+        Some(name) => report_missing_name(policy, manifest, &name),
+        None => {
+            let type_name = std::any::type_name::<M>();
+            report(
+                policy,
+                format!("No item with {id:?} was found in {type_name}."),
+            );
+        }
+    }
+}
+
+/// Reports that no item with the given `name` was found in the `manifest`, according to the `policy`.
+///
+/// The policy for each manifest is stored in the [`MissingLookups`] resource.
+pub fn report_missing_name<M: Manifest>(policy: MissingLookupPolicy, manifest: &M, name: &str) {
+    if policy == MissingLookupPolicy::Ignore {
+        return;
+    }
+
+    let suggestion = NameRegistry::closest_match(name, |candidate| {
+        manifest.get(Id::from_name(candidate)).is_some()
+    });
+    let type_name = std::any::type_name::<M>();
+    let message = match suggestion {
+        Some(suggestion) => {
+            format!("No item named {name} was found in {type_name}: did you mean {suggestion}?")
+        }
+        None => format!("No item named {name} was found in {type_name}."),
+    };
+    report(policy, message);
+}
+
+fn report(policy: MissingLookupPolicy, message: String) {
+    match policy {
+        MissingLookupPolicy::Ignore => {}
+        MissingLookupPolicy::Panic if cfg!(debug_assertions) => panic!("{message}"),
+        MissingLookupPolicy::Log | MissingLookupPolicy::Panic => error!("{message}"),
+    }
+}
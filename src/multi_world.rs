@@ -0,0 +1,188 @@
+//! Copying processed manifests into other [`World`]s, such as a render world, a server simulation world or a background generation world.
+//!
+//! Manifests are only loaded and processed in the main world.
+//! The [`ManifestExportPlugin`] keeps an [`ExportedManifest`] of `M` in the main world,
+//! which is updated whenever the manifest changes (including after hot reloads).
+//! Other worlds then pull the latest version with [`sync_manifest_into`], typically from a sub-app's extract function:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     manifest::Manifest,
+//!     multi_world::{sync_manifest_into, ExportMode, ManifestExportPlugin},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//!
+//! let mut app = manifest_test_app();
+//! app.add_plugins(ManifestExportPlugin::<ItemManifest>::default())
+//!     .insert_raw_manifest::<ItemManifest>(ItemManifest::from_items([Item::new("sword", 10)]));
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//! // The export is refreshed in `PostUpdate`.
+//! app.update();
+//!
+//! fn extract(main_world: &mut World, simulation: &mut World) -> bool {
+//!     sync_manifest_into::<ItemManifest>(main_world, simulation, ExportMode::Clone)
+//! }
+//!
+//! let mut simulation = World::new();
+//! assert!(extract(&mut app.world, &mut simulation));
+//! assert_eq!(simulation.resource::<ItemManifest>().get_by_name("sword").unwrap().value, 10);
+//! // Nothing is copied until the main world's manifest changes again.
+//! assert!(!extract(&mut app.world, &mut simulation));
+//! ```
+//!
+//! With [`ExportMode::Share`], every world shares a single copy of the manifest, read through [`ExportedManifest`].
+//! With [`ExportMode::Clone`], each world also receives its own `M` resource, so existing systems using `Res<M>` work unchanged.
+//! Storing the items in a [`SharedManifest`](crate::shared_manifest::SharedManifest) makes these clones cheap.
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+
+use crate::manifest::Manifest;
+
+/// How [`sync_manifest_into`] makes a manifest available in another world.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportMode {
+    /// Only insert the [`ExportedManifest`], sharing the same allocation as every other world.
+    #[default]
+    Share,
+    /// Also insert a clone of the manifest as an ordinary `M` resource.
+    Clone,
+}
+
+/// A shared, read-only copy of the manifest `M`, for use across worlds.
+///
+/// In the main world, this is kept up to date by the [`ManifestExportPlugin`].
+/// In other worlds, it is inserted by [`sync_manifest_into`].
+#[derive(Resource)]
+pub struct ExportedManifest<M: Manifest> {
+    manifest: Arc<M>,
+    generation: u64,
+}
+
+impl<M: Manifest> ExportedManifest<M> {
+    /// Returns a handle to the shared manifest, which can be sent to other threads or worlds.
+    #[must_use]
+    pub fn shared(&self) -> Arc<M> {
+        self.manifest.clone()
+    }
+
+    /// How many times the manifest has been exported.
+    ///
+    /// This increases by one each time the main world's manifest changes.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<M: Manifest> Deref for ExportedManifest<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.manifest
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ExportedManifest<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportedManifest")
+            .field("type_name", &std::any::type_name::<M>())
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// A plugin which keeps an [`ExportedManifest`] of `M` in sync with the manifest resource, for use by [`sync_manifest_into`].
+///
+/// The export is refreshed in [`PostUpdate`] whenever the manifest changes.
+pub struct ManifestExportPlugin<M: Manifest + Clone> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest + Clone> Default for ManifestExportPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest + Clone> Plugin for ManifestExportPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            export_manifest::<M>.run_if(resource_exists::<M>.and_then(resource_changed::<M>)),
+        );
+    }
+}
+
+/// Updates the [`ExportedManifest`] of `M` to match the manifest resource.
+pub fn export_manifest<M: Manifest + Clone>(
+    manifest: Res<M>,
+    exported: Option<ResMut<ExportedManifest<M>>>,
+    mut commands: Commands,
+) {
+    let manifest = Arc::new(manifest.clone());
+    match exported {
+        Some(mut exported) => {
+            exported.manifest = manifest;
+            exported.generation += 1;
+        }
+        None => commands.insert_resource(ExportedManifest {
+            manifest,
+            generation: 0,
+        }),
+    }
+}
+
+/// Copies the latest [`ExportedManifest`] of `M` from the `source` world into the `target` world.
+///
+/// Nothing is copied if the `target` already has the latest version, so this is cheap to call every frame.
+/// Returns true if the `target` was updated.
+///
+/// The `source` world must have a [`ManifestExportPlugin`] for `M`: see [`clone_manifest_into`] for one-off copies.
+pub fn sync_manifest_into<M: Manifest + Clone>(
+    source: &World,
+    target: &mut World,
+    mode: ExportMode,
+) -> bool {
+    let Some(exported) = source.get_resource::<ExportedManifest<M>>() else {
+        return false;
+    };
+
+    let up_to_date = target
+        .get_resource::<ExportedManifest<M>>()
+        .is_some_and(|existing| existing.generation == exported.generation);
+    if up_to_date {
+        return false;
+    }
+
+    if mode == ExportMode::Clone {
+        target.insert_resource(M::clone(&exported.manifest));
+    }
+    target.insert_resource(ExportedManifest {
+        manifest: exported.manifest.clone(),
+        generation: exported.generation,
+    });
+
+    true
+}
+
+/// Inserts a clone of the manifest resource `M` from the `source` world into the `target` world.
+///
+/// Returns false if the `source` world does not have the manifest.
+pub fn clone_manifest_into<M: Manifest + Clone>(source: &World, target: &mut World) -> bool {
+    let Some(manifest) = source.get_resource::<M>() else {
+        return false;
+    };
+
+    target.insert_resource(manifest.clone());
+    true
+}
@@ -0,0 +1,511 @@
+//! A global registry of the human-readable names that [`Id`]s were created from.
+//!
+//! [`Id::from_name`] is a one-way hash: once an [`Id`] has been created, the original name is lost.
+//! This is great for performance, but makes serialized data and log output hard to read.
+//! By registering names as they are read from disk, tools like [`id_map_by_name`] can turn [`Id`]s back into names.
+//!
+//! Names are stored by their hash alone: the same name registered for two different item types is only stored once.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::Deref,
+    sync::{OnceLock, RwLock},
+};
+
+use bevy::log::warn;
+use bevy::utils::HashMap;
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::identifier::Id;
+
+/// The backing storage of the [`NameRegistry`], mapping raw [`Id`] values to names.
+static NAMES: OnceLock<RwLock<HashMap<u64, Box<str>>>> = OnceLock::new();
+
+fn names() -> &'static RwLock<HashMap<u64, Box<str>>> {
+    NAMES.get_or_init(Default::default)
+}
+
+/// The prefix used when writing [`Id`]s without a registered name as text.
+///
+/// The raw value of the [`Id`] follows in hexadecimal.
+pub const RAW_ID_PREFIX: &str = "#0x";
+
+/// A global lookup table from [`Id`]s back to the names they were created from.
+///
+/// Names are only known if they have been explicitly registered, typically while processing a raw manifest.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{identifier::Id, name_registry::NameRegistry};
+///
+/// struct Item;
+///
+/// let sword: Id<Item> = NameRegistry::register("sword");
+/// assert_eq!(sword, Id::from_name("sword"));
+/// assert_eq!(NameRegistry::name_of(sword).as_deref(), Some("sword"));
+///
+/// let unregistered: Id<Item> = Id::from_name("shield");
+/// assert_eq!(NameRegistry::name_of(unregistered), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NameRegistry;
+
+impl NameRegistry {
+    /// Hashes the name into an [`Id`] via [`Id::from_name`], and records the name for later lookup.
+    ///
+    /// If a different name with the same hash was already registered, the two names collide:
+    /// the first name is kept, and a warning is logged, as both names now refer to the same [`Id`].
+    pub fn register<T>(name: &str) -> Id<T> {
+        let id = Id::from_name(name);
+        let mut registry = names()
+            .write()
+            .expect("The name registry lock was poisoned.");
+        let registered = registry.entry(id.raw()).or_insert_with(|| name.into());
+        if &**registered != name {
+            warn!(
+                "The names {registered:?} and {name:?} both hash to the Id {:#x}: rename one of them, as they cannot be told apart.",
+                id.raw()
+            );
+        }
+        id
+    }
+
+    /// Returns the name that the given [`Id`] was registered with, if any.
+    #[must_use]
+    pub fn name_of<T>(id: Id<T>) -> Option<String> {
+        names()
+            .read()
+            .expect("The name registry lock was poisoned.")
+            .get(&id.raw())
+            .map(|name| name.to_string())
+    }
+
+    /// Returns true if a name has been registered for the given [`Id`].
+    #[must_use]
+    pub fn contains<T>(id: Id<T>) -> bool {
+        names()
+            .read()
+            .expect("The name registry lock was poisoned.")
+            .contains_key(&id.raw())
+    }
+
+    /// Returns the number of registered names.
+    #[must_use]
+    pub fn len() -> usize {
+        names()
+            .read()
+            .expect("The name registry lock was poisoned.")
+            .len()
+    }
+
+    /// Returns true if no names have been registered.
+    #[must_use]
+    pub fn is_empty() -> bool {
+        Self::len() == 0
+    }
+
+    /// Returns every registered name, in no particular order.
+    #[must_use]
+    pub fn names() -> Vec<String> {
+        names()
+            .read()
+            .expect("The name registry lock was poisoned.")
+            .values()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Removes all registered names.
+    pub fn clear() {
+        names()
+            .write()
+            .expect("The name registry lock was poisoned.")
+            .clear();
+    }
+
+    /// Returns the registered name that is most similar to `name`, as measured by [`edit_distance`].
+    ///
+    /// Only names for which `filter` returns true are considered, such as those of items that actually exist.
+    /// Names which differ from `name` in more than half of their characters are never returned.
+    /// This is intended for "did you mean ...?" suggestions in error messages.
+    #[must_use]
+    pub fn closest_match(name: &str, filter: impl Fn(&str) -> bool) -> Option<String> {
+        let max_distance = (name.chars().count() / 2).max(1);
+        names()
+            .read()
+            .expect("The name registry lock was poisoned.")
+            .values()
+            .filter(|candidate| filter(candidate))
+            .map(|candidate| (edit_distance(name, candidate), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            // Break ties by name, so that suggestions are deterministic.
+            .min_by(|(a, a_name), (b, b_name)| a.cmp(b).then_with(|| a_name.cmp(b_name)))
+            .map(|(_, candidate)| candidate.to_string())
+    }
+}
+
+/// The number of single-character insertions, deletions and substitutions needed to turn `a` into `b`.
+///
+/// This is the Levenshtein distance, compared case-insensitively.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::name_registry::edit_distance;
+///
+/// assert_eq!(edit_distance("sword", "Sword"), 0);
+/// assert_eq!(edit_distance("sword", "swords"), 1);
+/// assert_eq!(edit_distance("sword", "swrod"), 2);
+/// ```
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+
+    // Only the previous row of the distance matrix is needed to compute the next one.
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Writes an [`Id`] in its most readable form for the given serializer.
+///
+/// Human-readable formats get the registered name, falling back to [`RAW_ID_PREFIX`] followed by the raw value.
+/// Binary formats always get the raw [`u64`].
+pub(crate) fn serialize_id<T, S: Serializer>(id: Id<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        match NameRegistry::name_of(id) {
+            Some(name) => serializer.serialize_str(&name),
+            None => serializer.serialize_str(&format!("{RAW_ID_PREFIX}{:x}", id.raw())),
+        }
+    } else {
+        serializer.serialize_u64(id.raw())
+    }
+}
+
+/// Reads an [`Id`] written by [`serialize_id`].
+///
+/// Names read from human-readable formats are added to the [`NameRegistry`].
+pub(crate) fn deserialize_id<'de, T, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Id<T>, D::Error> {
+    if deserializer.is_human_readable() {
+        // Hand-written files may contain raw integers as well as names, so let the format decide.
+        deserializer.deserialize_any(IdVisitor(std::marker::PhantomData))
+    } else {
+        deserializer.deserialize_u64(IdVisitor(std::marker::PhantomData))
+    }
+}
+
+/// The most elements that are preallocated for when deserializing a collection of ids.
+///
+/// Size hints come from the input, so they can't be trusted to be reasonable.
+const MAX_PREALLOCATION: usize = 4096;
+
+/// Returns the capacity to preallocate for a collection with the given size hint,
+/// capped at [`MAX_PREALLOCATION`] so that malformed input can't exhaust memory up front.
+fn cautious_capacity(size_hint: Option<usize>) -> usize {
+    size_hint.unwrap_or_default().min(MAX_PREALLOCATION)
+}
+
+struct IdVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for IdVisitor<T> {
+    type Value = Id<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a name, a raw id prefixed with {RAW_ID_PREFIX}, or an unsigned integer"
+        )
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        match value.strip_prefix(RAW_ID_PREFIX) {
+            Some(hex) => u64::from_str_radix(hex, 16)
+                .map(Id::from_raw)
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+            None => Ok(NameRegistry::register(value)),
+        }
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(Id::from_raw(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        u64::try_from(value)
+            .map(Id::from_raw)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+    }
+}
+
+/// An [`Id`] that is serialized as its name in human-readable formats.
+///
+/// The default serialization of [`Id`] writes the raw hash, which is compact and stable but impossible to edit by hand.
+/// Wrapping an [`Id`] in [`NamedId`] instead writes the name it was registered with in the [`NameRegistry`],
+/// falling back to the raw value (prefixed with [`RAW_ID_PREFIX`]) if no name is known.
+/// Binary formats (as reported by [`Serializer::is_human_readable`]) always use the raw [`u64`].
+///
+/// If you would rather keep plain [`Id`] fields, use `#[serde(with = "leafwing_manifest::name_registry::id_by_name")]` instead.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{identifier::Id, name_registry::NamedId};
+///
+/// struct Item;
+///
+/// let sword: NamedId<Item> = ron::from_str("\"sword\"").unwrap();
+/// assert_eq!(*sword, Id::from_name("sword"));
+/// assert_eq!(ron::to_string(&sword).unwrap(), "\"sword\"");
+///
+/// // Raw values are accepted too, either as plain integers or prefixed with `RAW_ID_PREFIX`.
+/// let raw: NamedId<Item> = ron::from_str("12345").unwrap();
+/// assert_eq!(*raw, Id::from_raw(12345));
+/// let prefixed: NamedId<Item> = ron::from_str("\"#0x3039\"").unwrap();
+/// assert_eq!(*prefixed, Id::from_raw(12345));
+/// ```
+pub struct NamedId<T>(pub Id<T>);
+
+impl<T> NamedId<T> {
+    /// Returns the wrapped [`Id`].
+    #[must_use]
+    pub const fn id(&self) -> Id<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for NamedId<T> {
+    type Target = Id<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Id<T>> for NamedId<T> {
+    fn from(id: Id<T>) -> Self {
+        NamedId(id)
+    }
+}
+
+impl<T> From<NamedId<T>> for Id<T> {
+    fn from(named_id: NamedId<T>) -> Self {
+        named_id.0
+    }
+}
+
+impl<T> Serialize for NamedId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_id(self.0, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NamedId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_id(deserializer).map(NamedId)
+    }
+}
+
+impl<T> Debug for NamedId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NamedId").field(&self.0).finish()
+    }
+}
+
+impl<T> PartialEq for NamedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for NamedId<T> {}
+
+impl<T> PartialOrd for NamedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for NamedId<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T> Hash for NamedId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T> Clone for NamedId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NamedId<T> {}
+
+/// Serializes a plain [`Id`] field as a name, in the same way as [`NamedId`].
+///
+/// Use this via `#[serde(with = "leafwing_manifest::name_registry::id_by_name")]`.
+pub mod id_by_name {
+    use serde::{Deserializer, Serializer};
+
+    use crate::identifier::Id;
+
+    /// Serializes the [`Id`], writing it as a name where possible.
+    pub fn serialize<T, S: Serializer>(id: &Id<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_id(*id, serializer)
+    }
+
+    /// Deserializes the [`Id`], reading it as either a name or a raw value.
+    pub fn deserialize<'de, T, D: Deserializer<'de>>(deserializer: D) -> Result<Id<T>, D::Error> {
+        super::deserialize_id(deserializer)
+    }
+}
+
+/// Serializes an [`IdMap`](crate::identifier::IdMap) with its keys written as names.
+///
+/// Use this via `#[serde(with = "leafwing_manifest::name_registry::id_map_by_name")]`.
+///
+/// In human-readable formats, keys are written as their registered names,
+/// falling back to the raw value (prefixed with [`RAW_ID_PREFIX`]) if no name is known.
+/// Names read back in are registered with the [`NameRegistry`].
+/// Binary formats use the raw [`u64`] value of each key.
+pub mod id_map_by_name {
+    use std::marker::PhantomData;
+
+    use serde::{
+        de::{MapAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::identifier::{Id, IdMap};
+
+    struct Key<T>(Id<T>);
+
+    impl<T> Serialize for Key<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize_id(self.0, serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for Key<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize_id(deserializer).map(Key)
+        }
+    }
+
+    /// Serializes the map, writing each key as a name where possible.
+    pub fn serialize<T, V: Serialize, S: Serializer>(
+        map: &IdMap<T, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(id, value)| (Key(*id), value)))
+    }
+
+    /// Deserializes the map, reading each key as either a name or a raw value.
+    pub fn deserialize<'de, T, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<IdMap<T, V>, D::Error> {
+        struct MapVisitor<T, V>(PhantomData<(T, V)>);
+
+        impl<'de, T, V: Deserialize<'de>> Visitor<'de> for MapVisitor<T, V> {
+            type Value = IdMap<T, V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map keyed by item names")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut map = IdMap::with_capacity_and_hasher(
+                    super::cautious_capacity(access.size_hint()),
+                    Default::default(),
+                );
+                while let Some((Key(id), value)) = access.next_entry::<Key<T>, V>()? {
+                    map.insert(id, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+/// Serializes an [`IdSet`](crate::identifier::IdSet) with its elements written as names.
+///
+/// Use this via `#[serde(with = "leafwing_manifest::name_registry::id_set_by_name")]`.
+///
+/// Elements are written in the same way as the keys in [`id_map_by_name`].
+pub mod id_set_by_name {
+    use std::marker::PhantomData;
+
+    use serde::{
+        de::{SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::identifier::{Id, IdSet};
+
+    struct Element<T>(Id<T>);
+
+    impl<T> Serialize for Element<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize_id(self.0, serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for Element<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize_id(deserializer).map(Element)
+        }
+    }
+
+    /// Serializes the set, writing each element as a name where possible.
+    pub fn serialize<T, S: Serializer>(set: &IdSet<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(set.iter().map(|id| Element(*id)))
+    }
+
+    /// Deserializes the set, reading each element as either a name or a raw value.
+    pub fn deserialize<'de, T, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<IdSet<T>, D::Error> {
+        struct SetVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for SetVisitor<T> {
+            type Value = IdSet<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a list of item names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut set = IdSet::with_capacity_and_hasher(
+                    super::cautious_capacity(access.size_hint()),
+                    Default::default(),
+                );
+                while let Some(Element(id)) = access.next_element::<Element<T>>()? {
+                    set.insert(id);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,277 @@
+//! Compact, deterministic indices for referring to manifest items over the network.
+//!
+//! [`Id`]s are 8 bytes each, which adds up quickly when replicating many item references every tick.
+//! Once a manifest has been processed, a [`ManifestNetworkIndex`] assigns each item a dense [`NetworkIndex`],
+//! in order of their [`Id`]s.
+//! As long as every peer has loaded the same content, every peer assigns the same indices:
+//! compare [`ManifestNetworkIndex::checksum`] during the handshake to be sure.
+//!
+//! Add a [`NetworkIndexPlugin`] to keep the [`ManifestNetworkIndex`] resource up to date,
+//! then convert between [`Id`]s and [`NetworkIndex`]es when sending and receiving item references:
+//!
+//! ```
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//! use leafwing_manifest::{
+//!     identifier::Id,
+//!     network_index::{ManifestNetworkIndex, NetworkIndex},
+//! };
+//!
+//! let build = |names: &[&str]| {
+//!     ItemManifest::from_items(names.iter().map(|name| Item::new(name, 0)))
+//! };
+//!
+//! // Two peers load the same content, but insert the items in a different order.
+//! let server = ManifestNetworkIndex::from_manifest(&build(&["sword", "shield", "potion"]));
+//! let client = ManifestNetworkIndex::from_manifest(&build(&["potion", "sword", "shield"]));
+//! assert_eq!(server.checksum(), client.checksum());
+//!
+//! // The server sends a compact index, which the client resolves back into the same item.
+//! let shield = Id::<Item>::from_name("shield");
+//! let index: NetworkIndex<Item> = server.index_of(shield).unwrap();
+//! assert_eq!(client.index_of(shield), Some(index));
+//! assert_eq!(client.id_of(index), Some(shield));
+//! assert_eq!(client.id_of(NetworkIndex::from_raw(3)), None);
+//! ```
+
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::{IterableManifest, Manifest};
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+
+/// A dense index identifying an item of type `T`, assigned by a [`ManifestNetworkIndex`].
+pub struct NetworkIndex<T> {
+    index: u32,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> NetworkIndex<T> {
+    /// Creates a network index from its raw value, such as one received over the network.
+    #[must_use]
+    pub const fn from_raw(index: u32) -> Self {
+        Self {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the raw value of the index.
+    #[must_use]
+    pub const fn raw(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the index as a [`u16`], if it fits.
+    ///
+    /// Manifests with at most 65536 items always fit.
+    #[must_use]
+    pub fn to_u16(&self) -> Option<u16> {
+        u16::try_from(self.index).ok()
+    }
+}
+
+impl<T> std::fmt::Debug for NetworkIndex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NetworkIndex").field(&self.index).finish()
+    }
+}
+
+impl<T> PartialEq for NetworkIndex<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for NetworkIndex<T> {}
+
+impl<T> Hash for NetworkIndex<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> Clone for NetworkIndex<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NetworkIndex<T> {}
+
+impl<T> From<u16> for NetworkIndex<T> {
+    fn from(index: u16) -> Self {
+        Self::from_raw(index.into())
+    }
+}
+
+/// Serializes as the bare [`u32`] value of the index.
+///
+/// ```
+/// use leafwing_manifest::network_index::NetworkIndex;
+///
+/// struct Item;
+///
+/// let index = NetworkIndex::<Item>::from_raw(7);
+/// assert_eq!(ron::to_string(&index).unwrap(), "7");
+/// assert_eq!(ron::from_str::<NetworkIndex<Item>>("7").unwrap(), index);
+/// ```
+impl<T> Serialize for NetworkIndex<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.index)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NetworkIndex<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(Self::from_raw)
+    }
+}
+
+/// A bidirectional mapping between the [`Id`]s of the items in the manifest `M` and their [`NetworkIndex`].
+///
+/// This resource is rebuilt by the [`NetworkIndexPlugin`] whenever the manifest changes.
+#[derive(Resource)]
+pub struct ManifestNetworkIndex<M: Manifest> {
+    ids: Vec<Id<M::Item>>,
+    indices: IdMap<M::Item, u32>,
+}
+
+impl<M: Manifest> ManifestNetworkIndex<M> {
+    /// Assigns an index to each of the `ids`, in ascending order of their raw values.
+    ///
+    /// Duplicate [`Id`]s are only assigned a single index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`u32::MAX`] items.
+    #[must_use]
+    pub fn from_ids(ids: impl IntoIterator<Item = Id<M::Item>>) -> Self {
+        let mut ids: Vec<Id<M::Item>> = ids.into_iter().collect();
+        ids.sort();
+        ids.dedup();
+
+        let indices = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let index =
+                    u32::try_from(index).expect("Too many items to assign network indices.");
+                (*id, index)
+            })
+            .collect();
+
+        Self { ids, indices }
+    }
+
+    /// Builds the index for every item in the `manifest`.
+    #[must_use]
+    pub fn from_manifest(manifest: &M) -> Self
+    where
+        M: IterableManifest,
+    {
+        Self::from_ids(manifest.ids())
+    }
+
+    /// Returns the [`NetworkIndex`] of the item with the given `id`, if it is in the manifest.
+    #[must_use]
+    pub fn index_of(&self, id: Id<M::Item>) -> Option<NetworkIndex<M::Item>> {
+        self.indices.get(&id).copied().map(NetworkIndex::from_raw)
+    }
+
+    /// Returns the [`Id`] of the item with the given `index`, if it is valid.
+    #[must_use]
+    pub fn id_of(&self, index: NetworkIndex<M::Item>) -> Option<Id<M::Item>> {
+        self.ids.get(index.raw() as usize).copied()
+    }
+
+    /// Returns the number of indexed items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns true if no items are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns true if every index fits in a [`u16`].
+    #[must_use]
+    pub fn fits_in_u16(&self) -> bool {
+        self.ids.len() <= usize::from(u16::MAX) + 1
+    }
+
+    /// A checksum of the indexed [`Id`]s, which peers can compare to ensure that they agree on every index.
+    ///
+    /// This uses the FNV-1a hash, which is stable across platforms and compiler versions.
+    #[must_use]
+    pub fn checksum(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut hash = OFFSET_BASIS;
+        for id in &self.ids {
+            for byte in id.raw().to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(PRIME);
+            }
+        }
+        hash
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestNetworkIndex<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestNetworkIndex")
+            .field("len", &self.ids.len())
+            .field("checksum", &self.checksum())
+            .finish()
+    }
+}
+
+impl<M: Manifest> Clone for ManifestNetworkIndex<M> {
+    fn clone(&self) -> Self {
+        Self {
+            ids: self.ids.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+/// A plugin which keeps the [`ManifestNetworkIndex`] for the manifest `M` up to date.
+///
+/// The index is rebuilt in the processing schedule whenever the manifest resource changes.
+pub struct NetworkIndexPlugin<M: IterableManifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: IterableManifest> Default for NetworkIndexPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: IterableManifest> Plugin for NetworkIndexPlugin<M> {
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        app.add_systems(
+            schedules.processing,
+            build_network_index::<M>
+                .run_if(resource_exists::<M>.and_then(resource_changed::<M>))
+                .after(ProcessManifestSet),
+        );
+    }
+}
+
+/// Rebuilds the [`ManifestNetworkIndex`] for the manifest `M`.
+pub fn build_network_index<M: IterableManifest>(manifest: Res<M>, mut commands: Commands) {
+    commands.insert_resource(ManifestNetworkIndex::<M>::from_manifest(&manifest));
+}
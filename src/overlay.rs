@@ -0,0 +1,265 @@
+//! Temporary, copy-on-write overrides on top of a manifest.
+//!
+//! Levels and game modes often tweak a handful of values: "all goblins have double health on this level".
+//! Mutating the manifest itself for this is risky, as the canonical data must be restored afterwards.
+//! Instead, a [`ManifestOverlay`] stores replacement items in a stack of named layers,
+//! which are checked before falling back to the base manifest.
+//!
+//! ```rust,ignore
+//! fn enter_hard_mode(mut overlay: ResMut<ManifestOverlay<MonsterManifest>>, monsters: Res<MonsterManifest>) {
+//!     overlay.push_layer("hard_mode");
+//!     overlay.modify("hard_mode", &*monsters, Id::from_name("goblin"), |goblin| goblin.health *= 2);
+//! }
+//!
+//! fn spawn_monster(monsters: OverlaidManifest<MonsterManifest>) {
+//!     // Sees the doubled health while the "hard_mode" layer is active.
+//!     let goblin = monsters.get(Id::from_name("goblin"));
+//! }
+//! ```
+//!
+//! Systems which read the manifest via [`OverlaidManifest`] see the overrides,
+//! while systems using `Res<M>` directly continue to see the canonical data.
+
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::SystemParam;
+
+use crate::identifier::{Id, IdMap};
+use crate::manifest::Manifest;
+
+/// A single named set of replacement items in a [`ManifestOverlay`].
+pub struct OverlayLayer<M: Manifest> {
+    name: String,
+    items: IdMap<M::Item, M::Item>,
+}
+
+impl<M: Manifest> std::fmt::Debug for OverlayLayer<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayLayer")
+            .field("name", &self.name)
+            .field("items", &self.items.len())
+            .finish()
+    }
+}
+
+impl<M: Manifest> OverlayLayer<M> {
+    /// The name of this layer.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Replaces the item with the given `id` while this layer is active.
+    ///
+    /// Returns the previous replacement in this layer, if any.
+    pub fn set(&mut self, id: Id<M::Item>, item: M::Item) -> Option<M::Item> {
+        self.items.insert(id, item)
+    }
+
+    /// Stops replacing the item with the given `id` in this layer, returning the replacement.
+    pub fn unset(&mut self, id: Id<M::Item>) -> Option<M::Item> {
+        self.items.remove(&id)
+    }
+
+    /// Returns the replacement for the item with the given `id` in this layer, if any.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        self.items.get(&id)
+    }
+
+    /// The number of items replaced by this layer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if this layer does not replace any items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// A stack of [`OverlayLayer`]s, which override items in the manifest `M` without modifying it.
+///
+/// Later layers take priority over earlier ones.
+/// This is only a [`Resource`] if the manifest's items are [`Send`] and [`Sync`].
+#[derive(Resource)]
+pub struct ManifestOverlay<M: Manifest> {
+    layers: Vec<OverlayLayer<M>>,
+}
+
+impl<M: Manifest> Default for ManifestOverlay<M> {
+    fn default() -> Self {
+        Self { layers: Vec::new() }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestOverlay<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestOverlay")
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestOverlay<M> {
+    /// Adds a new, empty layer on top of the stack, returning it so it can be filled in.
+    pub fn push_layer(&mut self, name: impl Into<String>) -> &mut OverlayLayer<M> {
+        self.layers.push(OverlayLayer {
+            name: name.into(),
+            items: IdMap::default(),
+        });
+        self.layers.last_mut().expect("A layer was just pushed.")
+    }
+
+    /// Removes the top layer, returning it.
+    pub fn pop_layer(&mut self) -> Option<OverlayLayer<M>> {
+        self.layers.pop()
+    }
+
+    /// Removes the topmost layer with the given `name`, returning it.
+    pub fn remove_layer(&mut self, name: &str) -> Option<OverlayLayer<M>> {
+        let index = self.layers.iter().rposition(|layer| layer.name == name)?;
+        Some(self.layers.remove(index))
+    }
+
+    /// Returns the topmost layer with the given `name`.
+    #[must_use]
+    pub fn layer(&self, name: &str) -> Option<&OverlayLayer<M>> {
+        self.layers.iter().rev().find(|layer| layer.name == name)
+    }
+
+    /// Returns a mutable reference to the topmost layer with the given `name`.
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut OverlayLayer<M>> {
+        self.layers
+            .iter_mut()
+            .rev()
+            .find(|layer| layer.name == name)
+    }
+
+    /// Iterates over the layers, from bottom to top.
+    pub fn layers(&self) -> impl Iterator<Item = &OverlayLayer<M>> {
+        self.layers.iter()
+    }
+
+    /// Removes every layer.
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Returns the topmost replacement for the item with the given `id`, ignoring the base manifest.
+    #[must_use]
+    pub fn get_override(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        self.layers.iter().rev().find_map(|layer| layer.get(id))
+    }
+
+    /// Returns the item with the given `id`, checking each layer from top to bottom before falling back to the `base` manifest.
+    #[must_use]
+    pub fn get<'a>(&'a self, base: &'a M, id: Id<M::Item>) -> Option<&'a M::Item> {
+        self.get_override(id).or_else(|| base.get(id))
+    }
+
+    /// Replaces the item with the given `id` in the topmost layer named `layer` with a modified copy of the item.
+    ///
+    /// The copy starts from the item as seen just below that layer,
+    /// so the replacements made by lower layers are built upon rather than discarded.
+    /// If the layer already replaces the item, the replacement is modified instead.
+    /// Returns false (and does nothing) if the layer or the item does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leafwing_manifest::test_utils::{TestItem as Monster, TestItemManifest as MonsterManifest};
+    /// use leafwing_manifest::{identifier::Id, manifest::Manifest, overlay::ManifestOverlay};
+    ///
+    /// // The value of each monster is its health.
+    /// let goblin = Id::from_name("goblin");
+    /// let monsters = MonsterManifest::from_items([Monster::new("goblin", 10)]);
+    ///
+    /// let mut overlay = ManifestOverlay::<MonsterManifest>::default();
+    /// overlay.push_layer("hard_mode");
+    /// assert!(overlay.modify("hard_mode", &monsters, goblin, |goblin| goblin.value += 5));
+    /// overlay.push_layer("double_health");
+    /// assert!(overlay.modify("double_health", &monsters, goblin, |goblin| goblin.value *= 2));
+    ///
+    /// // The "double_health" layer doubles the health set by the "hard_mode" layer beneath it.
+    /// assert_eq!(overlay.get(&monsters, goblin), Some(&Monster::new("goblin", 30)));
+    ///
+    /// overlay.pop_layer();
+    /// assert_eq!(overlay.get(&monsters, goblin), Some(&Monster::new("goblin", 15)));
+    /// assert_eq!(monsters.get(goblin), Some(&Monster::new("goblin", 10)));
+    /// ```
+    pub fn modify(
+        &mut self,
+        layer: &str,
+        base: &M,
+        id: Id<M::Item>,
+        modify: impl FnOnce(&mut M::Item),
+    ) -> bool
+    where
+        M::Item: Clone,
+    {
+        let Some(index) = self
+            .layers
+            .iter()
+            .rposition(|candidate| candidate.name == layer)
+        else {
+            return false;
+        };
+        let (below, above) = self.layers.split_at_mut(index);
+        let layer = &mut above[0];
+
+        if let Some(item) = layer.items.get_mut(&id) {
+            modify(item);
+            return true;
+        }
+
+        let below = below.iter().rev().find_map(|layer| layer.get(id));
+        let Some(mut item) = below.or_else(|| base.get(id)).cloned() else {
+            return false;
+        };
+        modify(&mut item);
+        layer.items.insert(id, item);
+        true
+    }
+}
+
+/// A [`SystemParam`] for reading the manifest `M` through its [`ManifestOverlay`], if one exists.
+///
+/// Only lookups made via [`OverlaidManifest::get`] (and [`OverlaidManifest::get_by_name`]) see the overrides.
+/// Other methods of `M` must be called on the [`base`](OverlaidManifest::base) manifest explicitly,
+/// as they ignore the overlay.
+#[derive(SystemParam)]
+pub struct OverlaidManifest<'w, M: Manifest>
+where
+    M::Item: Send + Sync,
+{
+    base: Res<'w, M>,
+    overlay: Option<Res<'w, ManifestOverlay<M>>>,
+}
+
+impl<'w, M: Manifest> OverlaidManifest<'w, M>
+where
+    M::Item: Send + Sync,
+{
+    /// Returns the item with the given `id`, taking any active overlay layers into account.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        match &self.overlay {
+            Some(overlay) => ManifestOverlay::get(overlay, &self.base, id),
+            None => self.base.get(id),
+        }
+    }
+
+    /// Returns the item with the given `name`, taking any active overlay layers into account.
+    #[must_use]
+    pub fn get_by_name(&self, name: impl std::borrow::Borrow<str>) -> Option<&M::Item> {
+        self.get(Id::from_name(name.borrow()))
+    }
+
+    /// Returns the base manifest, ignoring any overlay layers.
+    #[must_use]
+    pub fn base(&self) -> &M {
+        &self.base
+    }
+}
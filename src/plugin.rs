@@ -1,15 +1,42 @@
 use std::any::{type_name, TypeId};
+use std::hash::Hash;
+use std::marker::PhantomData;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use bevy::app::{App, Plugin, PreUpdate, Update};
-use bevy::asset::{AssetApp, AssetLoadFailedEvent, AssetServer, Assets, LoadState, UntypedHandle};
+use bevy::app::{App, Plugin, PreUpdate, StateTransition, Update};
+use bevy::asset::io::embedded::EmbeddedAssetRegistry;
+use bevy::asset::{
+    Asset, AssetApp, AssetEvent, AssetLoadFailedEvent, AssetPath, AssetServer, Assets, Handle,
+    LoadState, UntypedHandle,
+};
 use bevy::ecs::prelude::*;
-use bevy::ecs::system::SystemState;
-use bevy::log::{error, error_once, info};
-use bevy::utils::HashMap;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::ecs::system::{RunSystemOnce, StaticSystemParam, SystemState};
+use bevy::log::{error, error_once, info, warn};
+use bevy::utils::label::DynEq;
+use bevy::utils::{HashMap, Instant};
+use serde::de::DeserializeOwned;
 
 use crate::asset_state::AssetLoadingState;
-use crate::manifest::Manifest;
+use crate::asset_store::{settle_asset_store, ManifestAssetStore};
+use crate::late_registration::prepare_late_registration;
+use crate::lifecycle::{dispatch_lifecycle_events, ManifestLifecycleEvent, ManifestLifecycleStage};
+#[cfg(feature = "csv")]
+use crate::loader::CsvSettings;
+use crate::loader::{Compression, ManifestLoader, RawBytesTransform, Strictness};
+use crate::loading_timeline::record_loading_timeline;
+use crate::manifest::{Manifest, ManifestFormat, ManifestWithContext};
+use crate::manifest_folder::register_manifest_folder;
+use crate::missing_lookups::MissingLookups;
+use crate::processing_hooks::{run_processing_hooks, HookStage};
+use crate::processing_report::ProcessingReport;
+use crate::provenance::{ItemSource, ManifestProvenance};
+use crate::registration::{ManifestPolicy, ManifestRegistration};
+use crate::split_manifest::{register_manifest_section, register_split_manifest, SplitRawManifest};
+use crate::stall_detection::{detect_stalled_manifests, LoadingTimeout, ManifestLoadStalled};
+use crate::summary::{announce_manifests_ready, ManifestsReady};
 
 /// A plugin for loading assets from a [`Manifest`].
 ///
@@ -33,40 +60,221 @@ pub struct ManifestPlugin<S: AssetLoadingState> {
     /// If you want to coordinate with other asset loading steps, you may want to set this to `false`
     /// and handle asset state management on your own.
     pub automatically_advance_states: bool,
-    /// A phantom data field to satisfy the type system.
-    pub _phantom: std::marker::PhantomData<S>,
+    /// If true, [`Manifest::freeze`] will be called on each manifest once it has been processed.
+    ///
+    /// Only manifests which implement [`Manifest::freeze`] are actually frozen: by default, it does nothing.
+    ///
+    /// Defaults to `true`, unless the `mutable` feature is enabled.
+    ///
+    /// Set this to `false` if you intend to modify your manifests at runtime via [`MutableManifest`](crate::manifest::MutableManifest).
+    pub freeze_manifests: bool,
+    /// If true, the asset loading state is inserted into the app, starting in [`AssetLoadingState::LOADING`].
+    /// If false, the state must be added to the app manually.
+    ///
+    /// Defaults to `true`.
+    pub insert_initial_state: bool,
+    /// The schedules that the manifest loading and processing systems are run from.
+    ///
+    /// Defaults to [`ManifestSchedules::default`].
+    pub schedules: ManifestSchedules,
+    /// If set, raw manifests which are still loading after this timeout are reported, and may cause loading to fail.
+    ///
+    /// Defaults to [`None`]. See the [`stall_detection`](crate::stall_detection) module for more information.
+    pub loading_timeout: Option<LoadingTimeout>,
+    _phantom: PhantomData<S>,
+}
+
+/// The schedules that the systems added by the [`ManifestPlugin`] and [`RegisterManifest`] are run from.
+///
+/// The manifest systems themselves live in dedicated schedules, which are run once per update of these schedules.
+/// This keeps them separate from your own systems, so [`drive_manifest_loading`] can run them on their own.
+///
+/// This is inserted as a resource by the [`ManifestPlugin`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ManifestSchedules {
+    /// The schedule which runs the systems that track the loading of raw manifests, and advance the asset loading state.
+    ///
+    /// Defaults to [`Update`].
+    pub loading: InternedScheduleLabel,
+    /// The schedule which runs the systems that process raw manifests into manifests.
+    ///
+    /// Defaults to [`PreUpdate`].
+    pub processing: InternedScheduleLabel,
+}
+
+/// The schedule containing the systems which track the loading of raw manifests.
+///
+/// This is run from [`ManifestSchedules::loading`].
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ManifestLoading;
+
+/// The schedule containing the systems which process raw manifests into manifests.
+///
+/// This is run from [`ManifestSchedules::processing`].
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ManifestProcessing;
+
+impl Default for ManifestSchedules {
+    fn default() -> Self {
+        Self {
+            loading: Update.intern(),
+            processing: PreUpdate.intern(),
+        }
+    }
+}
+
+/// Returns the schedules that manifest systems should be added to.
+///
+/// These are the dedicated [`ManifestLoading`] and [`ManifestProcessing`] schedules,
+/// which the [`ManifestPlugin`] runs from the user-facing [`ManifestSchedules`].
+pub(crate) fn manifest_schedules(_app: &App) -> ManifestSchedules {
+    ManifestSchedules {
+        loading: ManifestLoading.intern(),
+        processing: ManifestProcessing.intern(),
+    }
+}
+
+/// Adds the dedicated manifest schedules to the app, running them from the supplied user-facing `schedules`.
+pub(crate) fn add_manifest_schedules(app: &mut App, schedules: ManifestSchedules) {
+    app.init_schedule(ManifestLoading)
+        .init_schedule(ManifestProcessing)
+        .add_systems(schedules.loading, run_manifest_loading)
+        .add_systems(schedules.processing, run_manifest_processing);
+}
+
+/// Runs the [`ManifestLoading`] schedule.
+fn run_manifest_loading(world: &mut World) {
+    world.run_schedule(ManifestLoading);
+}
+
+/// Runs the [`ManifestProcessing`] schedule.
+fn run_manifest_processing(world: &mut World) {
+    world.run_schedule(ManifestProcessing);
 }
 
 impl Default for ManifestPlugin<crate::asset_state::SimpleAssetState> {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AssetLoadingState> ManifestPlugin<S> {
+    /// Creates a new plugin with the default settings.
+    #[must_use]
+    pub fn new() -> Self {
         Self {
             automatically_advance_states: true,
-            _phantom: std::marker::PhantomData,
+            // Editor and modding builds enable `mutable`, while shipping builds freeze any manifests that implement `Manifest::freeze`.
+            freeze_manifests: !cfg!(feature = "mutable"),
+            insert_initial_state: true,
+            schedules: ManifestSchedules::default(),
+            loading_timeout: None,
+            _phantom: PhantomData,
         }
     }
+
+    /// Does not insert the asset loading state, for apps which add the state themselves.
+    ///
+    /// See [`ManifestPlugin::insert_initial_state`].
+    #[must_use]
+    pub fn without_initial_state(mut self) -> Self {
+        self.insert_initial_state = false;
+        self
+    }
+
+    /// Does not automatically transition between asset loading states as manifests load.
+    ///
+    /// See [`ManifestPlugin::automatically_advance_states`].
+    #[must_use]
+    pub fn manual_state_advancement(mut self) -> Self {
+        self.automatically_advance_states = false;
+        self
+    }
+
+    /// Runs the manifest loading and processing systems in the supplied schedules, rather than [`Update`] and [`PreUpdate`].
+    ///
+    /// This is useful for apps that don't run the main schedules, such as servers with only a fixed timestep.
+    #[must_use]
+    pub fn with_schedules(
+        mut self,
+        loading: impl ScheduleLabel,
+        processing: impl ScheduleLabel,
+    ) -> Self {
+        self.schedules = ManifestSchedules {
+            loading: loading.intern(),
+            processing: processing.intern(),
+        };
+        self
+    }
+
+    /// Reports raw manifests which are still loading after the supplied timeout.
+    ///
+    /// See [`ManifestPlugin::loading_timeout`].
+    #[must_use]
+    pub fn with_loading_timeout(mut self, timeout: LoadingTimeout) -> Self {
+        self.loading_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether [`Manifest::freeze`] is called on each manifest once it has been processed.
+    ///
+    /// See [`ManifestPlugin::freeze_manifests`].
+    #[must_use]
+    pub fn with_freeze_manifests(mut self, freeze_manifests: bool) -> Self {
+        self.freeze_manifests = freeze_manifests;
+        self
+    }
 }
 
 impl<S: AssetLoadingState> Plugin for ManifestPlugin<S> {
     fn build(&self, app: &mut App) {
-        app.insert_state(S::LOADING)
-            .init_resource::<RawManifestTracker>()
+        if self.insert_initial_state {
+            app.insert_state(S::LOADING);
+        }
+
+        add_manifest_schedules(app, self.schedules);
+        let schedules = manifest_schedules(app);
+
+        app.init_resource::<RawManifestTracker>()
+            .init_resource::<MissingLookups>()
+            .insert_resource(self.schedules)
             // Configure *all* manifest processing systems to run when the app is in the PROCESSING state.
             // See the `ProcessManifestSet` struct for more information.
             .configure_sets(
-                PreUpdate,
+                schedules.processing,
                 ProcessManifestSet.run_if(in_state(S::PROCESSING)),
             );
 
+        app.world
+            .resource_mut::<RawManifestTracker>()
+            .set_freeze_manifests(self.freeze_manifests);
+
         if self.automatically_advance_states {
             app.add_systems(
-                Update,
+                schedules.loading,
                 check_if_manifests_have_loaded::<S>.run_if(in_state(S::LOADING)),
             )
             .add_systems(
-                Update,
+                schedules.loading,
                 check_if_manifests_are_processed::<S>.run_if(in_state(S::PROCESSING)),
             );
         }
+
+        if let Some(timeout) = self.loading_timeout {
+            app.insert_resource(timeout)
+                .add_event::<ManifestLoadStalled>()
+                .add_systems(
+                    schedules.loading,
+                    detect_stalled_manifests::<S>.run_if(in_state(S::LOADING)),
+                );
+        }
+
+        app.add_event::<ManifestsReady>()
+            .add_systems(
+                OnEnter(S::READY),
+                (record_loading_timeline, announce_manifests_ready),
+            )
+            .add_systems(schedules.loading, dispatch_lifecycle_events);
     }
 }
 
@@ -77,6 +285,178 @@ pub trait RegisterManifest {
     /// The final manifest type must implement [`Manifest`], while the raw manifest type must implement [`Asset`](bevy::asset::Asset).
     /// This must be called for each type of manifest you wish to load.
     fn register_manifest<M: Manifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+
+    /// Registers a manifest, configuring how its raw manifest is loaded using the supplied [`RawManifestOptions`].
+    ///
+    /// ```rust,ignore
+    /// app.register_manifest_with_options::<ItemManifest>(
+    ///     "items.ron.bin",
+    ///     RawManifestOptions::default().with_transform(XorTransform::new(*b"not a secret")),
+    /// );
+    /// ```
+    fn register_manifest_with_options<M: Manifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        options: RawManifestOptions,
+    ) -> &mut Self;
+
+    /// Registers a manifest using the settings collected in a [`ManifestRegistration`] builder.
+    ///
+    /// ```rust,ignore
+    /// app.register_manifest_with(
+    ///     ManifestRegistration::<ItemManifest>::new()
+    ///         .path("items.ron")
+    ///         .after::<SpriteManifest>()
+    ///         .hot_reload(true),
+    /// );
+    /// ```
+    fn register_manifest_with<M: Manifest>(
+        &mut self,
+        registration: ManifestRegistration<M>,
+    ) -> &mut Self;
+
+    /// Declares that the manifest `M` must be processed after the manifest `Dependency`.
+    ///
+    /// Use this when [`Manifest::from_raw_manifest`] for `M` needs to look up data from `Dependency` in the [`World`].
+    /// The dependency is recorded in the [`RawManifestTracker`], and shows up in its [`processing_report`](RawManifestTracker::processing_report).
+    fn register_manifest_dependency<M: Manifest, Dependency: Manifest>(&mut self) -> &mut Self;
+
+    /// Registers a manifest which is processed using a [`ManifestWithContext::Context`], rather than exclusive [`World`] access.
+    ///
+    /// This should be used instead of [`register_manifest`](RegisterManifest::register_manifest) for manifests that implement [`ManifestWithContext`],
+    /// allowing their processing to run in parallel with other systems.
+    fn register_manifest_with_context<M: ManifestWithContext>(
+        &mut self,
+        path: impl Into<PathBuf>,
+    ) -> &mut Self;
+
+    /// Registers a manifest whose items are each stored in their own file, inside the folder at `path`.
+    ///
+    /// Each file is loaded as a `RawItem`, and the raw items are collected into the raw manifest, sorted by path.
+    /// Files are matched by the standard extension of [`Manifest::FORMAT`]:
+    /// use [`register_manifest_dir_with_options`](RegisterManifest::register_manifest_dir_with_options)
+    /// to choose a more specific extension, such as `item.ron`.
+    ///
+    /// See the [`manifest_folder`](crate::manifest_folder) module for more information.
+    fn register_manifest_dir<M, RawItem>(&mut self, path: impl Into<PathBuf>) -> &mut Self
+    where
+        M: Manifest,
+        M::RawManifest: FromIterator<RawItem>,
+        RawItem: Asset + DeserializeOwned,
+    {
+        self.register_manifest_dir_with_options::<M, RawItem>(path, RawManifestOptions::default())
+    }
+
+    /// Registers a manifest whose items are each stored in their own file, inside the folder at `path`,
+    /// configuring how each file is loaded using the supplied [`RawManifestOptions`].
+    fn register_manifest_dir_with_options<M, RawItem>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        options: RawManifestOptions,
+    ) -> &mut Self
+    where
+        M: Manifest,
+        M::RawManifest: FromIterator<RawItem>,
+        RawItem: Asset + DeserializeOwned;
+
+    /// Starts loading a single file that contains the raw manifests for several different manifests.
+    ///
+    /// Each manifest stored in the file must then be registered with [`register_manifest_section`](RegisterManifest::register_manifest_section).
+    /// See the [`split_manifest`](crate::split_manifest) module for more information.
+    fn register_split_manifest<S: SplitRawManifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+    ) -> &mut Self;
+
+    /// Registers the manifest `M`, whose raw manifest is a section of the split manifest `S`.
+    ///
+    /// `S` must already be registered via [`register_split_manifest`](RegisterManifest::register_split_manifest).
+    fn register_manifest_section<S: SplitRawManifest, M: Manifest>(&mut self) -> &mut Self;
+
+    /// Registers a manifest whose raw manifest has already been constructed in code, rather than loaded from disk.
+    ///
+    /// The raw manifest flows through the same tracking and processing pipeline as one loaded from a file.
+    /// This is useful for tests, demos and procedurally generated content.
+    fn register_manifest_from_value<M: Manifest>(
+        &mut self,
+        raw_manifest: M::RawManifest,
+    ) -> &mut Self;
+
+    /// Registers a manifest from serialized bytes that are embedded in the binary, typically via [`include_bytes!`].
+    ///
+    /// The bytes are parsed using the asset loader for [`Manifest::FORMAT`],
+    /// exactly as if they had been loaded from a file.
+    /// This is useful for platforms without a filesystem, such as the web.
+    fn register_manifest_from_bytes<M: Manifest>(&mut self, bytes: &'static [u8]) -> &mut Self;
+
+    /// Prepares the manifest `M` to be registered after the app has started,
+    /// via [`RegisterManifestAtRuntime`](crate::late_registration::RegisterManifestAtRuntime).
+    ///
+    /// See the [`late_registration`](crate::late_registration) module for more information.
+    fn allow_late_registration<M: Manifest>(&mut self) -> &mut Self;
+
+    /// Prepares the manifest `M` to be registered after the app has started,
+    /// configuring how its raw manifest is loaded using the supplied [`RawManifestOptions`].
+    ///
+    /// The options apply to every late registration of `M`.
+    /// See the [`late_registration`](crate::late_registration) module for more information.
+    fn allow_late_registration_with_options<M: Manifest>(
+        &mut self,
+        options: RawManifestOptions,
+    ) -> &mut Self;
+}
+
+/// A system set containing the [`process_manifest`] system for the manifest type `M`.
+///
+/// Use this to order the processing of manifests relative to each other,
+/// as [`register_manifest_dependency`](RegisterManifest::register_manifest_dependency) does.
+pub struct ManifestProcessingSet<M: Manifest>(PhantomData<fn() -> M>);
+
+impl<M: Manifest> Default for ManifestProcessingSet<M> {
+    fn default() -> Self {
+        ManifestProcessingSet(PhantomData)
+    }
+}
+
+impl<M: Manifest> Clone for ManifestProcessingSet<M> {
+    fn clone(&self) -> Self {
+        ManifestProcessingSet(PhantomData)
+    }
+}
+
+impl<M: Manifest> PartialEq for ManifestProcessingSet<M> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<M: Manifest> Eq for ManifestProcessingSet<M> {}
+
+impl<M: Manifest> Hash for ManifestProcessingSet<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        TypeId::of::<M>().hash(state);
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestProcessingSet<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ManifestProcessingSet<{}>", type_name::<M>())
+    }
+}
+
+impl<M: Manifest> SystemSet for ManifestProcessingSet<M> {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(self.clone())
+    }
+
+    fn as_dyn_eq(&self) -> &dyn DynEq {
+        self
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn std::hash::Hasher) {
+        TypeId::of::<Self>().hash(&mut state);
+        self.hash(&mut state);
+    }
 }
 
 /// A system set used to configure [`process_manifest`] systems,
@@ -85,83 +465,484 @@ pub trait RegisterManifest {
 /// This pattern is required as we do not have access to the app loading state in `register_manifest`,
 /// and adding an extra generic to it would be cumbersome.
 #[derive(SystemSet, PartialEq, Eq, Hash, Debug, Clone)]
-struct ProcessManifestSet;
+pub(crate) struct ProcessManifestSet;
+
+/// Panics with an actionable message if the plugins required to register the manifest `M` have not been added.
+///
+/// Without this check, registration fails with a generic "resource does not exist" panic deep inside this crate.
+pub(crate) fn assert_ready_for_registration<M: Manifest>(app: &App) {
+    assert!(
+        app.world.contains_resource::<AssetServer>(),
+        "Could not register the manifest {}: the `AssetPlugin` has not been added. \
+        Add `DefaultPlugins` (or `AssetPlugin::default()`) before the `ManifestPlugin`.",
+        type_name::<M>()
+    );
+    assert!(
+        app.world.contains_resource::<RawManifestTracker>(),
+        "Could not register the manifest {}: the `ManifestPlugin` has not been added. \
+        Add `app.add_plugins(ManifestPlugin::default())` *before* any calls to `register_manifest`.",
+        type_name::<M>()
+    );
+}
+
+/// An error describing why the [`RawManifestTracker`] could not provide the raw manifest of a manifest type.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ManifestTrackerError {
+    /// The manifest type was never registered with the tracker.
+    #[error(
+        "The manifest {type_name} is being processed, but was never registered. \
+        Register it with `app.register_manifest::<{type_name}>(path)`."
+    )]
+    NotRegistered {
+        /// The name of the manifest type.
+        type_name: &'static str,
+    },
+    /// The manifest was registered, but its raw manifest could not be found in its [`Assets`] collection.
+    ///
+    /// This usually means that it failed to load, or was removed by another system.
+    #[error("The raw manifest for {type_name}, loaded from {}, could not be found.", path.display())]
+    RawManifestMissing {
+        /// The name of the manifest type.
+        type_name: &'static str,
+        /// The path that the raw manifest was registered with.
+        path: PathBuf,
+    },
+}
 
 impl RegisterManifest for App {
     /// Registers the manifest `M`.
     ///
     /// By default, the path root is the `assets` folder, just like all Bevy assets.
     fn register_manifest<M: Manifest>(&mut self, path: impl Into<PathBuf>) -> &mut Self {
-        self.init_asset::<M::RawManifest>()
-            .add_systems(
-                Update,
-                report_failed_raw_manifest_loading::<M>
-                    .run_if(on_event::<AssetLoadFailedEvent<M::RawManifest>>()),
+        self.register_manifest_with_options::<M>(path, RawManifestOptions::default())
+    }
+
+    fn register_manifest_with_options<M: Manifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        options: RawManifestOptions,
+    ) -> &mut Self {
+        self.register_manifest_with(ManifestRegistration::<M>::new().path(path).options(options))
+    }
+
+    fn register_manifest_with<M: Manifest>(
+        &mut self,
+        registration: ManifestRegistration<M>,
+    ) -> &mut Self {
+        registration.register(self);
+        self
+    }
+
+    fn register_manifest_with_context<M: ManifestWithContext>(
+        &mut self,
+        path: impl Into<PathBuf>,
+    ) -> &mut Self {
+        register_raw_manifest::<M>(self, path, RawManifestOptions::default());
+        self.init_resource::<ManifestAssetStore<M>>();
+
+        let schedules = manifest_schedules(self);
+        self.add_systems(
+            schedules.processing,
+            (
+                prepare_manifest_with_context::<M>,
+                process_manifest_with_context::<M>,
             )
-            .add_systems(
-                PreUpdate,
-                process_manifest::<M>
-                    .in_set(ProcessManifestSet)
-                    .run_if(not(resource_exists::<M>)),
+                .chain()
+                .in_set(ProcessManifestSet)
+                .in_set(ManifestProcessingSet::<M>::default())
+                .distributive_run_if(
+                    not(resource_exists::<M>).and_then(raw_manifest_unprocessed::<M>),
+                ),
+        )
+    }
+
+    fn register_manifest_dir_with_options<M, RawItem>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        options: RawManifestOptions,
+    ) -> &mut Self
+    where
+        M: Manifest,
+        M::RawManifest: FromIterator<RawItem>,
+        RawItem: Asset + DeserializeOwned,
+    {
+        register_manifest_folder::<M, RawItem>(self, path, options);
+        self
+    }
+
+    fn register_split_manifest<S: SplitRawManifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+    ) -> &mut Self {
+        register_split_manifest::<S>(self, path);
+        self
+    }
+
+    fn register_manifest_section<S: SplitRawManifest, M: Manifest>(&mut self) -> &mut Self {
+        register_manifest_section::<S, M>(self);
+        self
+    }
+
+    fn register_manifest_from_value<M: Manifest>(
+        &mut self,
+        raw_manifest: M::RawManifest,
+    ) -> &mut Self {
+        register_in_memory_manifest::<M>(self, type_name::<M::RawManifest>(), raw_manifest);
+        self
+    }
+
+    fn register_manifest_from_bytes<M: Manifest>(&mut self, bytes: &'static [u8]) -> &mut Self {
+        // Embedded assets are loaded from the `embedded://` asset source,
+        // using a path that is unique to the manifest type.
+        // Type names contain characters such as `::` and `<>` that are not valid in asset paths,
+        // and the extension of the format is needed for the asset loader to be selected.
+        let mut file_name = file_name_of::<M>();
+        if let Some(extension) = M::FORMAT.extension() {
+            file_name.push('.');
+            file_name.push_str(extension);
+        }
+        let embedded_path = PathBuf::from("leafwing_manifest").join(file_name);
+        self.world
+            .resource_mut::<EmbeddedAssetRegistry>()
+            .insert_asset(embedded_path.clone(), &embedded_path, bytes);
+
+        let path = format!("embedded://{}", embedded_path.to_string_lossy());
+        self.register_manifest::<M>(path)
+    }
+
+    fn allow_late_registration<M: Manifest>(&mut self) -> &mut Self {
+        self.allow_late_registration_with_options::<M>(RawManifestOptions::default())
+    }
+
+    fn allow_late_registration_with_options<M: Manifest>(
+        &mut self,
+        options: RawManifestOptions,
+    ) -> &mut Self {
+        prepare_late_registration::<M>(self, options);
+        self
+    }
+
+    fn register_manifest_dependency<M: Manifest, Dependency: Manifest>(&mut self) -> &mut Self {
+        assert_ready_for_registration::<M>(self);
+
+        let schedules = manifest_schedules(self);
+        self.configure_sets(
+            schedules.processing,
+            ManifestProcessingSet::<M>::default()
+                .after(ManifestProcessingSet::<Dependency>::default()),
+        );
+
+        self.world
+            .resource_mut::<RawManifestTracker>()
+            .register_dependency::<M, Dependency>();
+
+        self
+    }
+}
+
+/// Returns the full type name of `T`, with every character that is not valid in a file name replaced by `_`.
+///
+/// Unlike the short type name, this is unique for types with the same name in different modules.
+pub(crate) fn file_name_of<T>() -> String {
+    type_name::<T>()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Sets up everything needed to load the raw manifest of `M` from disk, regardless of how it is processed.
+pub(crate) fn register_raw_manifest<M: Manifest>(
+    app: &mut App,
+    path: impl Into<PathBuf>,
+    options: RawManifestOptions,
+) {
+    let path: PathBuf = path.into();
+    let format = options.format.unwrap_or(M::FORMAT);
+    let transformed = options.transform.is_some();
+    prepare_raw_manifest::<M>(app, Compression::from_path(&path), options);
+    app.insert_resource(ManifestProvenance::<M>::from_file(ItemSource::new(&path)));
+
+    app.world
+        .resource_scope(|world, mut asset_server: Mut<AssetServer>| {
+            let mut manifest_tracker = world.resource_mut::<RawManifestTracker>();
+            manifest_tracker.register::<M>(path, asset_server.as_mut());
+            if let Some(status) = manifest_tracker.status_mut::<M>() {
+                status.format = format;
+                status.transformed = transformed;
+            }
+        });
+}
+
+/// Registers the manifest `M`, using a raw manifest that is already in memory rather than loading it from disk.
+///
+/// The `path` is only used for reporting purposes.
+pub(crate) fn register_in_memory_manifest<M: Manifest>(
+    app: &mut App,
+    path: impl Into<PathBuf>,
+    raw_manifest: M::RawManifest,
+) {
+    let path: PathBuf = path.into();
+    prepare_raw_manifest::<M>(app, Compression::None, RawManifestOptions::default());
+    app.insert_resource(ManifestProvenance::<M>::from_file(ItemSource::new(&path)));
+
+    let schedules = manifest_schedules(app);
+    app.add_systems(
+        schedules.processing,
+        process_manifest::<M>
+            .in_set(ProcessManifestSet)
+            .in_set(ManifestProcessingSet::<M>::default())
+            .run_if(not(resource_exists::<M>).and_then(raw_manifest_unprocessed::<M>)),
+    );
+
+    let handle = app
+        .world
+        .resource_mut::<Assets<M::RawManifest>>()
+        .add(raw_manifest);
+    app.world
+        .resource_mut::<RawManifestTracker>()
+        .register_in_memory::<M>(path, handle);
+}
+
+/// Initializes the raw manifest asset type for `M`, along with its asset loader and bookkeeping systems.
+///
+/// Compressed or transformed raw manifests, and those which opt into [`RawManifestOptions::error_paths`], are loaded using the built-in [`ManifestLoader`].
+pub(crate) fn prepare_raw_manifest<M: Manifest>(
+    app: &mut App,
+    compression: Compression,
+    options: RawManifestOptions,
+) {
+    assert_ready_for_registration::<M>(app);
+
+    let format = options.format.unwrap_or(M::FORMAT);
+    let schedules = manifest_schedules(app);
+
+    app.init_asset::<M::RawManifest>()
+        .add_systems(
+            schedules.loading,
+            report_failed_raw_manifest_loading::<M>
+                .run_if(on_event::<AssetLoadFailedEvent<M::RawManifest>>()),
+        )
+        .add_systems(
+            schedules.loading,
+            (
+                count_raw_manifest_loads::<M>,
+                preload_asset_dependencies::<M>,
+            )
+                .run_if(on_event::<AssetEvent<M::RawManifest>>()),
+        );
+
+    if compression != Compression::None
+        || options.transform.is_some()
+        || options.strictness != Strictness::Permissive
+        || options.error_paths
+    {
+        app.register_asset_loader(built_in_loader::<M>(compression, options));
+        return;
+    }
+
+    // Add the asset loader to the app via `bevy_common_assets`, where it covers the format.
+    // AIUI, the extension information is only used if a static asset type is not provided.
+    // We always provide this, so by default we provide an empty slice for the extension.
+    // Users can supply their own extensions to avoid conflicts with other loaders of the same format.
+
+    match format {
+        #[cfg(feature = "ron")]
+        crate::manifest::ManifestFormat::Ron => {
+            app.add_plugins(
+                bevy_common_assets::ron::RonAssetPlugin::<M::RawManifest>::new(options.extensions),
+            );
+        }
+        #[cfg(feature = "json")]
+        crate::manifest::ManifestFormat::Json => {
+            app.add_plugins(
+                bevy_common_assets::json::JsonAssetPlugin::<M::RawManifest>::new(
+                    options.extensions,
+                ),
+            );
+        }
+        #[cfg(feature = "yaml")]
+        crate::manifest::ManifestFormat::Yaml => {
+            app.add_plugins(
+                bevy_common_assets::yaml::YamlAssetPlugin::<M::RawManifest>::new(
+                    options.extensions,
+                ),
             );
+        }
+        #[cfg(feature = "toml")]
+        crate::manifest::ManifestFormat::Toml => {
+            app.add_plugins(
+                bevy_common_assets::toml::TomlAssetPlugin::<M::RawManifest>::new(
+                    options.extensions,
+                ),
+            );
+        }
+        #[cfg(feature = "xml")]
+        crate::manifest::ManifestFormat::Xml => {
+            app.add_plugins(
+                bevy_common_assets::xml::XmlAssetPlugin::<M::RawManifest>::new(options.extensions),
+            );
+        }
+        #[cfg(feature = "msgpack")]
+        crate::manifest::ManifestFormat::MsgPack => {
+            app.add_plugins(bevy_common_assets::msgpack::MsgPackAssetPlugin::<
+                M::RawManifest,
+            >::new(options.extensions));
+        }
+        // The built-in CSV loader reads the whole file into a single raw manifest,
+        // so it is only used when explicitly requested via `CsvSettings`.
+        #[cfg(feature = "csv")]
+        crate::manifest::ManifestFormat::Csv => {
+            if options.csv.is_some() {
+                app.register_asset_loader(built_in_loader::<M>(compression, options));
+            } else {
+                app.add_plugins(
+                    bevy_common_assets::csv::CsvAssetPlugin::<M::RawManifest>::new(
+                        options.extensions,
+                    ),
+                );
+            }
+        }
+        // These formats aren't covered by `bevy_common_assets`, so we use our own loader.
+        #[cfg(feature = "bincode")]
+        crate::manifest::ManifestFormat::Bincode => {
+            app.register_asset_loader(built_in_loader::<M>(compression, options));
+        }
+        #[cfg(feature = "postcard")]
+        crate::manifest::ManifestFormat::Postcard => {
+            app.register_asset_loader(built_in_loader::<M>(compression, options));
+        }
+        crate::manifest::ManifestFormat::Custom => (), // Users must register their own asset loader for custom formats.
+    }
+}
+
+/// Constructs the built-in [`ManifestLoader`] for the raw manifest of `M`.
+fn built_in_loader<M: Manifest>(
+    compression: Compression,
+    options: RawManifestOptions,
+) -> ManifestLoader<M::RawManifest> {
+    let loader = ManifestLoader::new(options.format.unwrap_or(M::FORMAT), compression)
+        .with_transform(options.transform)
+        .with_strictness(options.strictness)
+        .with_extensions(options.extensions);
+
+    #[cfg(feature = "csv")]
+    let loader = loader.with_csv_settings(options.csv.unwrap_or_default());
+
+    loader
+}
+
+/// Configures how a raw manifest is loaded from disk.
+///
+/// Pass this to [`RegisterManifest::register_manifest_with_options`].
+#[derive(Clone, Default)]
+pub struct RawManifestOptions {
+    /// The format of the raw manifest file, overriding [`Manifest::FORMAT`].
+    ///
+    /// Defaults to [`None`], which uses [`Manifest::FORMAT`].
+    pub format: Option<ManifestFormat>,
+    /// A transformation applied to the raw bytes of the file before they are deserialized.
+    ///
+    /// Defaults to [`None`].
+    pub transform: Option<Arc<dyn RawBytesTransform>>,
+    /// How fields in the file which the raw manifest type does not have are handled.
+    ///
+    /// Setting this to anything other than [`Strictness::Permissive`] loads the manifest with the built-in [`ManifestLoader`].
+    ///
+    /// Defaults to [`Strictness::Permissive`].
+    pub strictness: Strictness,
+    /// How CSV files are read.
+    ///
+    /// This is only used if the [`Manifest::FORMAT`] is [`ManifestFormat::Csv`].
+    /// If set, the whole file is read into a single raw manifest (such as a [`CsvRows`](crate::raw_manifests::CsvRows))
+    /// by the built-in [`ManifestLoader`].
+    ///
+    /// Defaults to [`None`], which uses the CSV loader from [`bevy_common_assets`].
+    #[cfg(feature = "csv")]
+    pub csv: Option<CsvSettings>,
+    /// If true, errors in RON, JSON, YAML and TOML files report the path to the invalid value,
+    /// such as `items[42].weight (found "heavy")`.
+    ///
+    /// This loads the manifest with the built-in [`ManifestLoader`], rather than the loader from [`bevy_common_assets`],
+    /// so load errors are reported as [`ManifestLoaderError`](crate::loader::ManifestLoaderError)s.
+    ///
+    /// Defaults to `false`.
+    pub error_paths: bool,
+    /// The file extensions that the asset loader for this manifest is registered for, without a leading `.`.
+    ///
+    /// Manifests are always loaded with their asset type known, so this is not required.
+    /// However, registering a specific extension such as `items.ron` avoids conflicts
+    /// when another plugin registers a generic loader for the same format,
+    /// and allows directories of manifest files to be filtered by extension.
+    ///
+    /// Defaults to an empty list.
+    pub extensions: &'static [&'static str],
+}
+
+impl RawManifestOptions {
+    /// Decodes the raw bytes of the file with the supplied [`RawBytesTransform`] before deserializing them.
+    #[must_use]
+    pub fn with_transform(mut self, transform: impl RawBytesTransform) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Handles fields that the raw manifest type does not have according to the supplied [`Strictness`].
+    #[must_use]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Registers the asset loader for this manifest with the supplied file extensions, such as `&["items.ron"]`.
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: &'static [&'static str]) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Reports the path to invalid values when the raw manifest can't be deserialized.
+    ///
+    /// See [`RawManifestOptions::error_paths`] for more details.
+    #[must_use]
+    pub fn with_error_paths(mut self) -> Self {
+        self.error_paths = true;
+        self
+    }
+
+    /// Reads the raw manifest file as the supplied format, rather than [`Manifest::FORMAT`].
+    #[must_use]
+    pub fn with_format(mut self, format: ManifestFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Reads CSV files with the built-in [`ManifestLoader`], using the supplied [`CsvSettings`].
+    ///
+    /// See [`RawManifestOptions::csv`] for more details.
+    #[cfg(feature = "csv")]
+    #[must_use]
+    pub fn with_csv_settings(mut self, csv: CsvSettings) -> Self {
+        self.csv = Some(csv);
+        self
+    }
+}
 
-        // Add the asset loader to the app via `bevy_common_assets`.
-        // AIUI, the extension information is only used if a static asset type is not provided.
-        // We always provide this, so we can provide an empty slice for the extension.
+impl std::fmt::Debug for RawManifestOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("RawManifestOptions");
+        debug
+            .field("format", &self.format)
+            .field("transform", &self.transform.is_some())
+            .field("strictness", &self.strictness);
 
-        match M::FORMAT {
-            #[cfg(feature = "ron")]
-            crate::manifest::ManifestFormat::Ron => {
-                self.add_plugins(
-                    bevy_common_assets::ron::RonAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "json")]
-            crate::manifest::ManifestFormat::Json => {
-                self.add_plugins(
-                    bevy_common_assets::json::JsonAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "yaml")]
-            crate::manifest::ManifestFormat::Yaml => {
-                self.add_plugins(
-                    bevy_common_assets::yaml::YamlAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "toml")]
-            crate::manifest::ManifestFormat::Toml => {
-                self.add_plugins(
-                    bevy_common_assets::toml::TomlAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "csv")]
-            crate::manifest::ManifestFormat::Csv => {
-                self.add_plugins(
-                    bevy_common_assets::csv::CsvAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "xml")]
-            crate::manifest::ManifestFormat::Xml => {
-                self.add_plugins(
-                    bevy_common_assets::xml::XmlAssetPlugin::<M::RawManifest>::new(&[]),
-                );
-            }
-            #[cfg(feature = "msgpack")]
-            crate::manifest::ManifestFormat::MsgPack => {
-                self.add_plugins(bevy_common_assets::msgpack::MsgPackAssetPlugin::<
-                    M::RawManifest,
-                >::new(&[]));
-            }
-            crate::manifest::ManifestFormat::Custom => (), // Users must register their own asset loader for custom formats.
-        }
+        #[cfg(feature = "csv")]
+        debug.field("csv", &self.csv);
 
-        self.world
-            .resource_scope(|world, mut asset_server: Mut<AssetServer>| {
-                let mut manifest_tracker = world.resource_mut::<RawManifestTracker>();
-                manifest_tracker.register::<M>(path, asset_server.as_mut());
-            });
+        debug
+            .field("error_paths", &self.error_paths)
+            .field("extensions", &self.extensions);
 
-        self
+        debug.finish()
     }
 }
 
@@ -169,7 +950,10 @@ impl RegisterManifest for App {
 #[derive(Resource, Debug, Default)]
 pub struct RawManifestTracker {
     raw_manifests: HashMap<TypeId, RawManifestStatus>,
+    dependencies: HashMap<TypeId, Vec<ManifestDependency>>,
     processing_status: ProcessingStatus,
+    freeze_manifests: bool,
+    lifecycle_events: Vec<ManifestLifecycleEvent>,
 }
 
 /// The current processing status of the raw manifests into manifests.
@@ -187,12 +971,66 @@ pub enum ProcessingStatus {
 /// Information about the loading status of a raw manifest.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawManifestStatus {
+    /// The name of the manifest type, as reported by [`type_name`].
+    pub type_name: &'static str,
+    /// The file format of the raw manifest.
+    pub format: ManifestFormat,
     /// The path to the manifest file.
     pub path: PathBuf,
     /// A strong handle to the raw manifest.
     pub handle: UntypedHandle,
+    /// If true, the raw manifest was added directly to the [`Assets`] collection, rather than loaded by the [`AssetServer`].
+    ///
+    /// The load state of in-memory raw manifests is always [`LoadState::Loaded`].
+    pub in_memory: bool,
+    /// How failures to load or process this manifest are handled.
+    pub policy: ManifestPolicy,
+    /// If true, the asset loading state waits for this manifest before advancing.
+    ///
+    /// If false, the manifest loads in the background, and never causes loading to fail.
+    /// See [`ManifestRegistration::required`] for more information.
+    pub required: bool,
+    /// If true, the manifest failed to load or process, and was skipped as permitted by [`ManifestPolicy::SkipInvalid`].
+    pub skipped: bool,
+    /// If true, the most recent attempt to process the raw manifest failed.
+    ///
+    /// Failed manifests are not processed again automatically, so the failure is only reported once.
+    pub processing_failed: bool,
+    /// If true, the raw bytes of the file are decoded by a [`RawBytesTransform`] before they are deserialized.
+    pub transformed: bool,
+    /// If true, the processed manifest was loaded from the cache,
+    /// and the raw manifest was never loaded or processed.
+    pub baked: bool,
     /// The computed loading state of the raw manifest.
     pub load_state: LoadState,
+    /// The number of times the raw manifest has finished loading.
+    ///
+    /// Any loads beyond the first are caused by hot reloading.
+    pub load_count: u32,
+    /// The number of items in the processed manifest, as reported by [`Manifest::item_count`].
+    pub item_count: Option<usize>,
+    /// How long it took to convert the raw manifest into the final manifest via [`Manifest::from_raw_manifest`].
+    pub processing_duration: Option<Duration>,
+    /// When the manifest was registered, and its raw manifest started loading.
+    pub registered_at: Instant,
+    /// When the raw manifest first finished loading (including deserialization).
+    pub load_finished_at: Option<Instant>,
+    /// When [`process_manifest`] started converting the raw manifest.
+    pub processing_started_at: Option<Instant>,
+    /// When the manifest finished processing and was inserted as a resource.
+    pub processed_at: Option<Instant>,
+    /// Strong handles to the assets returned by [`Manifest::asset_dependencies`],
+    /// which were preloaded as soon as the raw manifest finished loading.
+    pub asset_dependencies: Vec<UntypedHandle>,
+}
+
+/// A manifest that must be processed before another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ManifestDependency {
+    /// The [`TypeId`] of the manifest type that is depended on.
+    pub type_id: TypeId,
+    /// The name of the manifest type that is depended on, as reported by [`type_name`].
+    pub type_name: &'static str,
 }
 
 impl RawManifestTracker {
@@ -206,17 +1044,159 @@ impl RawManifestTracker {
     ) {
         let path: PathBuf = path.into();
 
-        let handle: UntypedHandle = asset_server.load::<M::RawManifest>(path.clone()).untyped();
-        let type_id = std::any::TypeId::of::<M>();
+        // Converting via a string allows the path to specify an asset source, such as `embedded://`.
+        let asset_path = AssetPath::from(path.to_string_lossy().into_owned());
+        let handle: UntypedHandle = asset_server.load::<M::RawManifest>(asset_path).untyped();
+        self.insert_status::<M>(RawManifestStatus {
+            type_name: type_name::<M>(),
+            format: M::FORMAT,
+            path: path.clone(),
+            handle,
+            in_memory: false,
+            policy: ManifestPolicy::default(),
+            required: true,
+            skipped: false,
+            processing_failed: false,
+            transformed: false,
+            baked: false,
+            load_state: LoadState::Loading,
+            load_count: 0,
+            item_count: None,
+            processing_duration: None,
+            registered_at: Instant::now(),
+            load_finished_at: None,
+            processing_started_at: None,
+            processed_at: None,
+            asset_dependencies: Vec::new(),
+        });
+    }
+
+    /// Registers a raw manifest which is assembled by another system, rather than loaded directly from a single file.
+    ///
+    /// This is used for raw manifests built from a folder of raw items, or split out of a larger file.
+    /// The `handle` should be reserved in the [`Assets`] collection:
+    /// the raw manifest is considered loaded once it has been inserted there, and its status has been updated.
+    pub fn register_pending<M: Manifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        handle: Handle<M::RawManifest>,
+    ) {
+        let mut status = Self::in_memory_status::<M>(path.into(), handle);
+        status.load_state = LoadState::Loading;
+        status.load_count = 0;
+        status.load_finished_at = None;
+
+        self.insert_status::<M>(status);
+    }
+
+    /// Registers a raw manifest that has already been added to the [`Assets`] collection directly,
+    /// rather than being loaded from disk by the [`AssetServer`].
+    ///
+    /// The `path` is only used for reporting purposes.
+    /// The raw manifest is immediately considered to be loaded.
+    pub fn register_in_memory<M: Manifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        handle: Handle<M::RawManifest>,
+    ) {
+        self.insert_status::<M>(Self::in_memory_status::<M>(path.into(), handle));
+        self.record_lifecycle::<M>(ManifestLifecycleStage::LoadCompleted, None);
+    }
+
+    /// Creates the status of a raw manifest which has already been added to the [`Assets`] collection.
+    fn in_memory_status<M: Manifest>(
+        path: PathBuf,
+        handle: Handle<M::RawManifest>,
+    ) -> RawManifestStatus {
+        let now = Instant::now();
+
+        RawManifestStatus {
+            type_name: type_name::<M>(),
+            format: M::FORMAT,
+            path,
+            handle: handle.untyped(),
+            in_memory: true,
+            policy: ManifestPolicy::default(),
+            required: true,
+            skipped: false,
+            processing_failed: false,
+            transformed: false,
+            baked: false,
+            load_state: LoadState::Loaded,
+            load_count: 1,
+            item_count: None,
+            processing_duration: None,
+            registered_at: now,
+            load_finished_at: Some(now),
+            processing_started_at: None,
+            processed_at: None,
+            asset_dependencies: Vec::new(),
+        }
+    }
+
+    /// Starts tracking the raw manifest for `M`, replacing any existing status.
+    fn insert_status<M: Manifest>(&mut self, status: RawManifestStatus) {
+        self.raw_manifests.insert(TypeId::of::<M>(), status);
+        self.record_lifecycle::<M>(ManifestLifecycleStage::LoadStarted, None);
+    }
+
+    /// Records that the manifest `M` has reached the supplied lifecycle `stage`.
+    ///
+    /// The event is passed to the [`ManifestLifecycleHooks`](crate::lifecycle::ManifestLifecycleHooks) by [`dispatch_lifecycle_events`].
+    pub fn record_lifecycle<M: Manifest>(
+        &mut self,
+        stage: ManifestLifecycleStage,
+        error: Option<String>,
+    ) {
+        self.record_lifecycle_by_type_id(TypeId::of::<M>(), stage, error);
+    }
+
+    /// Records that the manifest with the given [`TypeId`] has reached the supplied lifecycle `stage`.
+    pub fn record_lifecycle_by_type_id(
+        &mut self,
+        type_id: TypeId,
+        stage: ManifestLifecycleStage,
+        error: Option<String>,
+    ) {
+        let Some(status) = self.raw_manifests.get(&type_id) else {
+            return;
+        };
 
-        self.raw_manifests.insert(
-            type_id,
-            RawManifestStatus {
-                path: path.clone(),
-                handle,
-                load_state: LoadState::Loading,
-            },
+        let finished = matches!(
+            stage,
+            ManifestLifecycleStage::ProcessingSucceeded | ManifestLifecycleStage::ProcessingFailed
         );
+        self.lifecycle_events.push(ManifestLifecycleEvent {
+            stage,
+            type_name: status.type_name,
+            path: status.path.clone(),
+            at: Instant::now(),
+            item_count: status.item_count,
+            duration: finished.then_some(status.processing_duration).flatten(),
+            error,
+        });
+    }
+
+    /// Removes and returns the lifecycle events recorded since this was last called.
+    pub fn drain_lifecycle_events(&mut self) -> Vec<ManifestLifecycleEvent> {
+        std::mem::take(&mut self.lifecycle_events)
+    }
+
+    /// Registers a manifest which was loaded directly from the cache,
+    /// and so is already fully processed.
+    #[cfg(feature = "baked_manifests")]
+    pub(crate) fn register_baked<M: Manifest>(
+        &mut self,
+        path: impl Into<PathBuf>,
+        item_count: Option<usize>,
+    ) {
+        self.register_in_memory::<M>(path, Handle::default());
+
+        let now = Instant::now();
+        if let Some(status) = self.status_mut::<M>() {
+            status.baked = true;
+        }
+        self.record_processed::<M>(item_count, now, now);
     }
 
     /// Returns the load state and other metadata for the given manifest.
@@ -224,17 +1204,98 @@ impl RawManifestTracker {
         self.raw_manifests.get(&std::any::TypeId::of::<M>())
     }
 
+    /// Returns the load state and other metadata for the given manifest,
+    /// or a [`ManifestTrackerError::NotRegistered`] error explaining how to register it.
+    pub fn try_status<M: Manifest>(&self) -> Result<&RawManifestStatus, ManifestTrackerError> {
+        self.status::<M>()
+            .ok_or(ManifestTrackerError::NotRegistered {
+                type_name: type_name::<M>(),
+            })
+    }
+
+    /// Records the results of successfully processing the manifest `M`.
+    pub(crate) fn record_processed<M: Manifest>(
+        &mut self,
+        item_count: Option<usize>,
+        start: Instant,
+        end: Instant,
+    ) {
+        if let Some(status) = self.status_mut::<M>() {
+            status.item_count = item_count;
+            status.processing_duration = Some(end - start);
+            status.processing_started_at = Some(start);
+            status.processed_at = Some(end);
+            status.processing_failed = false;
+        }
+        self.record_lifecycle::<M>(ManifestLifecycleStage::ProcessingSucceeded, None);
+    }
+
+    /// Returns a mutable reference to the load state and other metadata for the manifest with the given [`TypeId`].
+    pub fn status_by_type_id_mut(&mut self, type_id: TypeId) -> Option<&mut RawManifestStatus> {
+        self.raw_manifests.get_mut(&type_id)
+    }
+
+    /// Returns a mutable reference to the load state and other metadata for the given manifest.
+    pub fn status_mut<M: Manifest>(&mut self) -> Option<&mut RawManifestStatus> {
+        self.raw_manifests.get_mut(&std::any::TypeId::of::<M>())
+    }
+
+    /// Records that the manifest `M` must be processed after the manifest `Dependency`.
+    ///
+    /// This only records the dependency for introspection:
+    /// use [`RegisterManifest::register_manifest_dependency`] to also order the processing systems.
+    pub fn register_dependency<M: Manifest, Dependency: Manifest>(&mut self) {
+        let dependency = ManifestDependency {
+            type_id: TypeId::of::<Dependency>(),
+            type_name: type_name::<Dependency>(),
+        };
+
+        let dependencies = self.dependencies.entry(TypeId::of::<M>()).or_default();
+        if !dependencies.contains(&dependency) {
+            dependencies.push(dependency);
+        }
+    }
+
+    /// Returns the manifests that the manifest with the given [`TypeId`] must be processed after.
+    pub fn dependencies(&self, type_id: TypeId) -> &[ManifestDependency] {
+        self.dependencies
+            .get(&type_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Summarizes the registered manifests, their formats, paths and dependencies, in processing order.
+    ///
+    /// The returned [`ProcessingReport`] can be printed as text via its [`Display`](std::fmt::Display) implementation,
+    /// or converted into a Graphviz DOT graph via [`ProcessingReport::to_dot`].
+    pub fn processing_report(&self) -> ProcessingReport {
+        ProcessingReport::new(self)
+    }
+
     /// Iterates over all registered raw manifests.
     pub fn iter(&self) -> impl Iterator<Item = (&TypeId, &RawManifestStatus)> {
         self.raw_manifests.iter()
     }
 
     /// Updates the load state of all registered raw manifests.
+    ///
+    /// Some asset backends (notably the HTTP-based backend used on the web) may briefly report no load state at all,
+    /// or report [`LoadState::NotLoaded`] before the request has been dispatched.
+    /// Neither of these are treated as failures: the raw manifest simply continues waiting to load.
     pub fn update_load_states(&mut self, asset_server: &AssetServer) {
         for status in self.raw_manifests.values_mut() {
-            status.load_state = asset_server
-                .get_load_state(status.handle.clone_weak())
-                .unwrap_or(LoadState::Failed);
+            // The asset server knows nothing about assets added directly to `Assets`.
+            if status.in_memory {
+                continue;
+            }
+
+            match asset_server.get_load_state(status.handle.clone_weak()) {
+                // The asset server has not yet started tracking this handle: keep the previous state.
+                None => (),
+                // The load has not been dispatched yet, so we're still waiting on it.
+                Some(LoadState::NotLoaded) => status.load_state = LoadState::Loading,
+                Some(load_state) => status.load_state = load_state,
+            }
         }
     }
 
@@ -242,29 +1303,98 @@ impl RawManifestTracker {
     pub fn all_manifests_loaded(&mut self, asset_server: &AssetServer) -> bool {
         self.update_load_states(asset_server);
 
-        self.raw_manifests
-            .values()
-            .all(|status| status.load_state == LoadState::Loaded)
+        // Optional manifests which failed to load are skipped, so there's no need to wait for them.
+        // Background manifests are never waited for.
+        self.raw_manifests.values().all(|status| {
+            !status.required
+                || status.load_state == LoadState::Loaded
+                || (status.load_state == LoadState::Failed
+                    && status.policy == ManifestPolicy::SkipInvalid)
+        })
     }
 
-    /// Returns true if any registered raw manifests have failed to load.
+    /// Returns true if any required raw manifests have failed to load.
+    ///
+    /// Failures of manifests registered with [`ManifestPolicy::SkipInvalid`], or which are not [`required`](RawManifestStatus::required), are ignored.
     pub fn any_manifests_failed(&mut self, asset_server: &AssetServer) -> bool {
         self.update_load_states(asset_server);
 
+        self.raw_manifests.values().any(|status| {
+            status.required
+                && status.load_state == LoadState::Failed
+                && status.policy == ManifestPolicy::Required
+        })
+    }
+
+    /// Records that the manifest `M` could not be processed, respecting its [`ManifestPolicy`].
+    fn record_failure<M: Manifest>(&mut self, error: String) {
+        self.record_lifecycle::<M>(ManifestLifecycleStage::ProcessingFailed, Some(error));
+        if let Some(status) = self.status_mut::<M>() {
+            status.processing_failed = true;
+        }
+
+        let (policy, required) = self
+            .status::<M>()
+            .map_or((ManifestPolicy::default(), true), |status| {
+                (status.policy, status.required)
+            });
+
+        if !required {
+            warn!(
+                "The background manifest {} could not be processed.",
+                type_name::<M>()
+            );
+            return;
+        }
+
+        match policy {
+            ManifestPolicy::Required => self.processing_status = ProcessingStatus::Failed,
+            ManifestPolicy::SkipInvalid => {
+                warn!("Skipping the manifest {}.", type_name::<M>());
+                if let Some(status) = self.status_mut::<M>() {
+                    status.skipped = true;
+                }
+            }
+        }
+    }
+
+    /// Returns true if every [`required`](RawManifestStatus::required) manifest has been processed,
+    /// or skipped as permitted by [`ManifestPolicy::SkipInvalid`].
+    ///
+    /// Background manifests are ignored, so they can never hold up or complete processing.
+    pub fn all_manifests_processed(&self) -> bool {
         self.raw_manifests
             .values()
-            .any(|status| status.load_state == LoadState::Failed)
+            .all(|status| !status.required || status.skipped || status.processed_at.is_some())
     }
 
     /// Returns the [`ProcessingStatus`] of the raw manifests.
+    ///
+    /// Processing is [`Ready`](ProcessingStatus::Ready) once [`all_manifests_processed`](Self::all_manifests_processed) returns true,
+    /// unless a required manifest has [`Failed`](ProcessingStatus::Failed).
     pub fn processing_status(&self) -> ProcessingStatus {
-        self.processing_status
+        match self.processing_status {
+            ProcessingStatus::Processing if self.all_manifests_processed() => {
+                ProcessingStatus::Ready
+            }
+            status => status,
+        }
     }
 
     /// Sets the [`ProcessingStatus`] of the raw manifests.
     pub fn set_processing_status(&mut self, status: ProcessingStatus) {
         self.processing_status = status;
     }
+
+    /// Returns true if manifests should be frozen via [`Manifest::freeze`] after processing.
+    pub fn freeze_manifests(&self) -> bool {
+        self.freeze_manifests
+    }
+
+    /// Sets whether manifests should be frozen via [`Manifest::freeze`] after processing.
+    pub fn set_freeze_manifests(&mut self, freeze_manifests: bool) {
+        self.freeze_manifests = freeze_manifests;
+    }
 }
 
 /// Checks if all registered assets have loaded,
@@ -300,6 +1430,110 @@ pub fn check_if_manifests_are_processed<S: AssetLoadingState>(
     }
 }
 
+/// Advances manifest loading by a single step, and returns the resulting asset loading state.
+///
+/// This runs only the manifest loading and processing systems (and none of the other systems in the [`ManifestSchedules`]),
+/// checks whether manifests have loaded or been processed,
+/// and applies any resulting state transitions, regardless of whether the [`ManifestPlugin`] advances states automatically.
+/// It is intended for headless servers and sub-apps, which may not run the usual schedules,
+/// and need to wait for their manifests before continuing.
+///
+/// Note that the [`AssetServer`] still relies on the systems added by the [`AssetPlugin`](bevy::asset::AssetPlugin)
+/// to make progress, so the app must keep updating (typically via [`App::update`]) between calls.
+///
+/// ```rust
+/// # #[cfg(feature = "ron")]
+/// # fn main() {
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     asset_state::SimpleAssetState,
+///     identifier::{Id, IdMap},
+///     manifest::{Manifest, ManifestFormat},
+///     plugin::{drive_manifest_loading, ManifestPlugin, RegisterManifest},
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Item {
+///     name: String,
+/// }
+///
+/// #[derive(Resource, Asset, TypePath, Deserialize)]
+/// struct ItemManifest {
+///     items: IdMap<Item, Item>,
+/// }
+///
+/// impl Manifest for ItemManifest {
+///     type Item = Item;
+///     type RawItem = Item;
+///     type RawManifest = ItemManifest;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+///
+///     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+///         self.items.get(&id)
+///     }
+///
+///     fn from_raw_manifest(
+///         raw_manifest: Self::RawManifest,
+///         _world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         Ok(raw_manifest)
+///     }
+/// }
+///
+/// #[derive(Resource, Default)]
+/// struct Ticks(u32);
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     AssetPlugin::default(),
+///     ManifestPlugin::<SimpleAssetState>::new().manual_state_advancement(),
+/// ))
+/// .register_manifest::<ItemManifest>("items.ron")
+/// .init_resource::<Ticks>()
+/// .add_systems(Update, |mut ticks: ResMut<Ticks>| ticks.0 += 1);
+///
+/// let mut updates = 0;
+/// let state = loop {
+///     app.update();
+///     updates += 1;
+///     match drive_manifest_loading::<SimpleAssetState>(&mut app.world) {
+///         SimpleAssetState::Loading | SimpleAssetState::Processing => {
+///             assert!(updates < 10_000, "Manifests took too long to load.");
+///             std::thread::sleep(std::time::Duration::from_millis(1));
+///         }
+///         state => break state,
+///     }
+/// };
+///
+/// assert_eq!(state, SimpleAssetState::Ready);
+/// assert!(app.world.resource::<ItemManifest>().get_by_name("sword").is_some());
+/// // Only the manifest systems are run, so our own systems still run once per update.
+/// assert_eq!(app.world.resource::<Ticks>().0, updates);
+/// # }
+/// # #[cfg(not(feature = "ron"))]
+/// # fn main() {}
+/// ```
+pub fn drive_manifest_loading<S: AssetLoadingState>(world: &mut World) -> S {
+    // The schedules may not have been added yet, in which case there's nothing to run.
+    let _ = world.try_run_schedule(ManifestLoading);
+    if *world.resource::<State<S>>().get() == S::LOADING {
+        world.run_system_once(check_if_manifests_have_loaded::<S>);
+    }
+    let _ = world.try_run_schedule(StateTransition);
+
+    let _ = world.try_run_schedule(ManifestProcessing);
+    if *world.resource::<State<S>>().get() == S::PROCESSING {
+        world.run_system_once(check_if_manifests_are_processed::<S>);
+    }
+    let _ = world.try_run_schedule(StateTransition);
+
+    world.resource::<State<S>>().get().clone()
+}
+
 /// Watches for and reports failed raw manifest loading events.
 ///
 /// This generic system is currently required as [`LoadState::Failed`] does not contain the error that caused the failure.
@@ -307,6 +1541,7 @@ pub fn check_if_manifests_are_processed<S: AssetLoadingState>(
 /// See [bevy#12667](https://github.com/bevyengine/bevy/issues/12667) for more information.0
 pub fn report_failed_raw_manifest_loading<M: Manifest>(
     mut events: EventReader<AssetLoadFailedEvent<M::RawManifest>>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
 ) {
     for event in events.read() {
         error_once!(
@@ -314,13 +1549,299 @@ pub fn report_failed_raw_manifest_loading<M: Manifest>(
             event.path,
             event.error
         );
+        raw_manifest_tracker.record_lifecycle::<M>(
+            ManifestLifecycleStage::LoadFailed,
+            Some(event.error.to_string()),
+        );
+    }
+}
+
+/// Counts how many times the raw manifest for `M` has finished loading, including hot reloads.
+///
+/// The count is stored in [`RawManifestStatus::load_count`],
+/// and the time of the first load is stored in [`RawManifestStatus::load_finished_at`].
+pub fn count_raw_manifest_loads<M: Manifest>(
+    mut events: EventReader<AssetEvent<M::RawManifest>>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+) {
+    let Some(status) = raw_manifest_tracker.status::<M>() else {
+        return;
+    };
+
+    let id = status.handle.id().typed::<M::RawManifest>();
+    for event in events.read() {
+        if event.is_loaded_with_dependencies(id) {
+            if let Some(status) = raw_manifest_tracker.status_mut::<M>() {
+                status.load_count += 1;
+                status.load_finished_at.get_or_insert_with(Instant::now);
+            }
+            raw_manifest_tracker.record_lifecycle::<M>(ManifestLifecycleStage::LoadCompleted, None);
+        }
+    }
+}
+
+/// Starts loading the assets returned by [`Manifest::asset_dependencies`] as soon as the raw manifest for `M` is available.
+///
+/// This runs again whenever the raw manifest is modified, such as by hot reloading.
+/// Strong handles to the assets are stored in [`RawManifestStatus::asset_dependencies`],
+/// so they are not dropped before [`Manifest::from_raw_manifest`] has a chance to load them again.
+pub fn preload_asset_dependencies<M: Manifest>(
+    mut events: EventReader<AssetEvent<M::RawManifest>>,
+    raw_manifests: Res<Assets<M::RawManifest>>,
+    asset_server: Res<AssetServer>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+) {
+    let Some(status) = raw_manifest_tracker.status_mut::<M>() else {
+        return;
+    };
+
+    let id = status.handle.id().typed::<M::RawManifest>();
+    let updated = events
+        .read()
+        .any(|event| *event == AssetEvent::Added { id } || *event == AssetEvent::Modified { id });
+    if !updated {
+        return;
     }
+
+    let Some(raw_manifest) = raw_manifests.get(id) else {
+        return;
+    };
+
+    status.asset_dependencies = M::asset_dependencies(raw_manifest)
+        .into_iter()
+        .map(|path| asset_server.load_untyped(path).untyped())
+        .collect();
+}
+
+/// A run condition which returns true if the raw manifest for `M` has been modified on disk and reloaded.
+///
+/// This is used to reprocess manifests registered with [`ManifestRegistration::hot_reload`].
+///
+/// Processing removes the raw manifest from its [`Assets`] collection,
+/// so a reloaded raw manifest is re-inserted, sending [`AssetEvent::Added`] rather than [`AssetEvent::Modified`].
+/// Any of these events count, as long as the raw manifest is present and waiting to be processed.
+///
+/// # Example
+///
+/// Inserting a new raw manifest with the tracked id, as the [`AssetServer`] does when the file changes,
+/// reprocesses the manifest:
+///
+/// ```
+/// # #[cfg(feature = "ron")]
+/// # fn main() {
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     asset_state::SimpleAssetState,
+///     identifier::{Id, IdMap},
+///     manifest::{Manifest, ManifestFormat},
+///     plugin::{ManifestPlugin, RawManifestTracker, RegisterManifest},
+///     registration::ManifestRegistration,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Item {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// #[derive(Resource, Asset, TypePath, Deserialize)]
+/// struct ItemManifest {
+///     items: IdMap<Item, Item>,
+/// }
+///
+/// impl Manifest for ItemManifest {
+///     type Item = Item;
+///     type RawItem = Item;
+///     type RawManifest = ItemManifest;
+///     type ConversionError = std::convert::Infallible;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+///
+///     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+///         self.items.get(&id)
+///     }
+///
+///     fn from_raw_manifest(
+///         raw_manifest: Self::RawManifest,
+///         _world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         Ok(raw_manifest)
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     AssetPlugin::default(),
+///     ManifestPlugin::<SimpleAssetState>::default(),
+/// ))
+/// .register_manifest_with(
+///     ManifestRegistration::<ItemManifest>::new()
+///         .path("items.ron")
+///         .hot_reload(true),
+/// );
+///
+/// while *app.world.resource::<State<SimpleAssetState>>() != SimpleAssetState::Ready {
+///     app.update();
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+/// assert_eq!(app.world.resource::<ItemManifest>().get_by_name("sword").unwrap().value, 10);
+///
+/// let id = app
+///     .world
+///     .resource::<RawManifestTracker>()
+///     .status::<ItemManifest>()
+///     .unwrap()
+///     .handle
+///     .id()
+///     .typed::<ItemManifest>();
+/// let mut items = IdMap::default();
+/// items.insert(Id::from_name("sword"), Item { name: "sword".to_string(), value: 20 });
+/// app.world
+///     .resource_mut::<Assets<ItemManifest>>()
+///     .insert(id, ItemManifest { items });
+///
+/// // Asset events are sent at the end of each frame, so the manifest is reprocessed in the next one.
+/// app.update();
+/// app.update();
+/// assert_eq!(app.world.resource::<ItemManifest>().get_by_name("sword").unwrap().value, 20);
+/// # }
+/// # #[cfg(not(feature = "ron"))]
+/// # fn main() {}
+/// ```
+pub fn raw_manifest_modified<M: Manifest>(
+    mut events: EventReader<AssetEvent<M::RawManifest>>,
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    raw_manifests: Res<Assets<M::RawManifest>>,
+) -> bool {
+    let Some(status) = raw_manifest_tracker.status::<M>() else {
+        events.clear();
+        return false;
+    };
+    let id = status.handle.id().typed::<M::RawManifest>();
+
+    let reloaded = events
+        .read()
+        .filter(|event| match **event {
+            AssetEvent::Added { id: event_id }
+            | AssetEvent::Modified { id: event_id }
+            | AssetEvent::LoadedWithDependencies { id: event_id } => event_id == id,
+            _ => false,
+        })
+        .count()
+        > 0;
+
+    // The events for the initial load are still readable once the manifest has been processed,
+    // but the raw manifest has already been taken by then.
+    reloaded && raw_manifests.contains(id)
+}
+
+/// A run condition which returns true if the raw manifest of `M` is still waiting to be processed.
+///
+/// This is false once processing has failed, or the manifest has been skipped,
+/// so that [`process_manifest`] does not retry (and report) the same failure every frame.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     asset_state::SimpleAssetState,
+///     identifier::Id,
+///     lifecycle::{ManifestLifecycleEvent, ManifestLifecycleHooks, ManifestLifecycleStage},
+///     manifest::{Manifest, ManifestFormat},
+///     plugin::ManifestPlugin,
+///     test_utils::ManifestTestApp,
+/// };
+///
+/// #[derive(Asset, TypePath)]
+/// struct RawQuestManifest;
+///
+/// /// A manifest which always fails to process.
+/// #[derive(Resource)]
+/// struct QuestManifest;
+///
+/// impl Manifest for QuestManifest {
+///     type Item = String;
+///     type RawItem = String;
+///     type RawManifest = RawQuestManifest;
+///     type ConversionError = std::fmt::Error;
+///
+///     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+///
+///     fn get(&self, _id: Id<String>) -> Option<&Self::Item> {
+///         None
+///     }
+///
+///     fn from_raw_manifest(
+///         _raw_manifest: Self::RawManifest,
+///         _world: &mut World,
+///     ) -> Result<Self, Self::ConversionError> {
+///         Err(std::fmt::Error)
+///     }
+/// }
+///
+/// let failures = Arc::new(AtomicUsize::new(0));
+/// let counter = failures.clone();
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     AssetPlugin::default(),
+///     // The app stays in the processing state, as the states are never advanced.
+///     ManifestPlugin::<SimpleAssetState>::default().manual_state_advancement(),
+/// ))
+/// .insert_resource(ManifestLifecycleHooks::default().with_hook(
+///     move |event: &ManifestLifecycleEvent| {
+///         if event.stage == ManifestLifecycleStage::ProcessingFailed {
+///             counter.fetch_add(1, Ordering::SeqCst);
+///         }
+///     },
+/// ))
+/// .insert_raw_manifest::<QuestManifest>(RawQuestManifest);
+///
+/// app.world
+///     .resource_mut::<NextState<SimpleAssetState>>()
+///     .set(SimpleAssetState::Processing);
+/// for _ in 0..10 {
+///     app.update();
+/// }
+///
+/// assert_eq!(failures.load(Ordering::SeqCst), 1);
+/// ```
+pub fn raw_manifest_unprocessed<M: Manifest>(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+) -> bool {
+    raw_manifest_tracker
+        .status::<M>()
+        .is_some_and(|status| !status.skipped && !status.processing_failed)
+}
+
+/// Removes the raw manifest of `M` from its [`Assets`] collection, so that it can be processed.
+pub(crate) fn take_raw_manifest<M: Manifest>(
+    raw_manifest_tracker: &RawManifestTracker,
+    assets: &mut Assets<M::RawManifest>,
+) -> Result<M::RawManifest, ManifestTrackerError> {
+    let status = raw_manifest_tracker.try_status::<M>()?;
+    let typed_handle = status.handle.clone_weak().typed::<M::RawManifest>();
+
+    assets
+        .remove(typed_handle)
+        .ok_or_else(|| ManifestTrackerError::RawManifestMissing {
+            type_name: type_name::<M>(),
+            path: status.path.clone(),
+        })
 }
 
 /// A system which processes a raw manifest into a completed [`Manifest`],
 /// and then stores the manifest as a [`Resource`] in the [`World`].
 ///
 /// The raw manifest will be removed from the [`AssetServer`] as part of creation.
+///
+/// If the raw manifest cannot be found, a [`ManifestTrackerError`] is logged and the failure is recorded,
+/// respecting the [`ManifestPolicy`] of `M`.
 pub fn process_manifest<M: Manifest>(
     world: &mut World,
     system_state: &mut SystemState<(Res<RawManifestTracker>, ResMut<Assets<M::RawManifest>>)>,
@@ -328,38 +1849,134 @@ pub fn process_manifest<M: Manifest>(
     info!("Processing manifest of type {}.", type_name::<M>());
 
     let (raw_manifest_tracker, mut assets) = system_state.get_mut(world);
-    let Some(status) = raw_manifest_tracker.status::<M>() else {
-        error_once!(
-            "The status of the raw manifest corresponding to the manifest type {} was not found.",
-            type_name::<M>()
-        );
-        return;
-    };
-    let typed_handle = status.handle.clone_weak().typed::<M::RawManifest>();
-    let maybe_raw_manifest = assets.remove(typed_handle);
-
-    let raw_manifest = match maybe_raw_manifest {
-        Some(raw_manifest) => raw_manifest,
-        None => {
-            error_once!(
-                "Failed to get raw manifest for manifest type {} from the asset server.",
-                type_name::<M>()
-            );
+    let freeze_manifests = raw_manifest_tracker.freeze_manifests();
+    let raw_manifest = match take_raw_manifest::<M>(&raw_manifest_tracker, &mut assets) {
+        Ok(raw_manifest) => raw_manifest,
+        Err(err) => {
+            error!("{err}");
+            // The failure is recorded, so that the `raw_manifest_unprocessed` run condition stops retrying every frame.
+            world
+                .resource_mut::<RawManifestTracker>()
+                .record_failure::<M>(err.to_string());
             return;
         }
     };
+    let path = raw_manifest_tracker
+        .status::<M>()
+        .map(|status| status.path.clone())
+        .unwrap_or_default();
+
+    if let Some(mut provenance) = world.get_resource_mut::<ManifestProvenance<M>>() {
+        provenance.clear_items();
+    }
+    world
+        .resource_mut::<RawManifestTracker>()
+        .record_lifecycle::<M>(ManifestLifecycleStage::ProcessingStarted, None);
+
+    world.init_resource::<ManifestAssetStore<M>>();
+    run_processing_hooks::<M>(world, HookStage::Before);
+
+    let start = Instant::now();
+    let result = M::from_raw_manifest(raw_manifest, world);
+    let end = Instant::now();
+
+    settle_asset_store::<M>(world, result.is_ok());
+
+    match result {
+        Ok(mut manifest) => {
+            if freeze_manifests {
+                manifest.freeze();
+            }
 
-    match M::from_raw_manifest(raw_manifest, world) {
-        Ok(manifest) => {
+            let item_count = manifest.item_count();
             world.insert_resource(manifest);
             // We can't just use a ResMut above, since we need to drop the borrow before we can construct the manifest.
             let mut raw_manifest_tracker = world.resource_mut::<RawManifestTracker>();
-            raw_manifest_tracker.set_processing_status(ProcessingStatus::Ready);
+            raw_manifest_tracker.record_processed::<M>(item_count, start, end);
+
+            run_processing_hooks::<M>(world, HookStage::After);
         }
         Err(err) => {
-            error_once!("Failed to process manifest: {:?}", err);
+            error_once!(
+                "Failed to process manifest {} from {}: {:?}",
+                type_name::<M>(),
+                path.display(),
+                err
+            );
             let mut raw_manifest_tracker = world.resource_mut::<RawManifestTracker>();
-            raw_manifest_tracker.set_processing_status(ProcessingStatus::Failed);
+            raw_manifest_tracker.record_failure::<M>(format!("{err:?}"));
+        }
+    }
+}
+
+/// Does the work of [`process_manifest`] that needs exclusive [`World`] access, before [`process_manifest_with_context`] runs.
+///
+/// This clears the item provenance of `M` and runs its "before" processing hooks.
+pub fn prepare_manifest_with_context<M: ManifestWithContext>(world: &mut World) {
+    if let Some(mut provenance) = world.get_resource_mut::<ManifestProvenance<M>>() {
+        provenance.clear_items();
+    }
+    run_processing_hooks::<M>(world, HookStage::Before);
+}
+
+/// A system which processes a raw manifest into a completed [`ManifestWithContext`],
+/// and then stores the manifest as a [`Resource`] in the [`World`].
+///
+/// Unlike [`process_manifest`], this is not an exclusive system:
+/// it only accesses the data requested by [`ManifestWithContext::Context`].
+///
+/// The raw manifest will be removed from the [`AssetServer`] as part of creation.
+/// The "before" processing hooks are run by [`prepare_manifest_with_context`], which must run first,
+/// while the "after" hooks are run once the manifest has been inserted.
+pub fn process_manifest_with_context<M: ManifestWithContext>(
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    mut assets: ResMut<Assets<M::RawManifest>>,
+    mut context: StaticSystemParam<M::Context>,
+    mut commands: Commands,
+) {
+    info!("Processing manifest of type {}.", type_name::<M>());
+
+    let raw_manifest = match take_raw_manifest::<M>(&raw_manifest_tracker, &mut assets) {
+        Ok(raw_manifest) => raw_manifest,
+        Err(err) => {
+            error!("{err}");
+            // The failure is recorded, so that the `raw_manifest_unprocessed` run condition stops retrying every frame.
+            raw_manifest_tracker.record_failure::<M>(err.to_string());
+            return;
+        }
+    };
+    let path = raw_manifest_tracker
+        .status::<M>()
+        .map(|status| status.path.clone())
+        .unwrap_or_default();
+
+    raw_manifest_tracker.record_lifecycle::<M>(ManifestLifecycleStage::ProcessingStarted, None);
+    let start = Instant::now();
+    let result = M::from_raw_manifest_with_context(raw_manifest, &mut context);
+    let end = Instant::now();
+
+    let succeeded = result.is_ok();
+    commands.add(move |world: &mut World| settle_asset_store::<M>(world, succeeded));
+
+    match result {
+        Ok(mut manifest) => {
+            if raw_manifest_tracker.freeze_manifests() {
+                manifest.freeze();
+            }
+
+            raw_manifest_tracker.record_processed::<M>(manifest.item_count(), start, end);
+            commands.insert_resource(manifest);
+            // Commands are applied in order, so the hooks can access the inserted manifest.
+            commands.add(|world: &mut World| run_processing_hooks::<M>(world, HookStage::After));
+        }
+        Err(err) => {
+            error_once!(
+                "Failed to process manifest {} from {}: {:?}",
+                type_name::<M>(),
+                path.display(),
+                err
+            );
+            raw_manifest_tracker.record_failure::<M>(format!("{err:?}"));
         }
     }
 }
@@ -0,0 +1,24 @@
+//! The most commonly used types and traits, for glob importing.
+//!
+//! ```rust
+//! use leafwing_manifest::prelude::*;
+//! ```
+//!
+//! Specialized tools (overlays, transactions, snapshots and so on) are left in their own modules,
+//! so they should be imported from there.
+
+pub use crate::asset_state::{AssetLoadingState, SimpleAssetState};
+pub use crate::dense_manifest::DenseManifest;
+pub use crate::derived::{DerivedFromManifest, RegisterDerived};
+pub use crate::frozen_manifest::FrozenManifest;
+pub use crate::identifier::{Id, IdMap, IdSet};
+pub use crate::manifest::{
+    IterableManifest, Manifest, ManifestFormat, ManifestModificationError, ManifestWithContext,
+    MutableManifest,
+};
+pub use crate::name_registry::NameRegistry;
+pub use crate::plugin::{ManifestPlugin, ManifestTrackerError, RegisterManifest};
+pub use crate::registration::{ManifestPolicy, ManifestRegistration};
+pub use crate::shared_manifest::SharedManifest;
+pub use crate::summary::ManifestsReady;
+pub use crate::transaction::TransactionError;
@@ -0,0 +1,169 @@
+//! Creating assets from the parameters in raw manifests, such as a material for each color.
+//!
+//! Procedural assets are usually created inside [`Manifest::from_raw_manifest`], which has two common pitfalls:
+//! every item with the same parameters gets its own copy of the asset,
+//! and assets whose strong handles are not stored anywhere are dropped as soon as processing ends.
+//! [`ProceduralAssets`] handles both: assets are memoized by their parameters,
+//! and their strong handles are kept in the [`ManifestAssetStore`] of the manifest.
+//!
+//! ```rust,ignore
+//! fn from_raw_manifest(raw_manifest: RawTileManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     let mut materials = ProceduralAssets::<Self, ColorMaterial>::new(world);
+//!
+//!     for raw_tile in raw_manifest.tiles {
+//!         // Floats don't implement `Hash`, so hash their bits instead.
+//!         let material = materials.get_or_add(raw_tile.color.map(f32::to_bits), || {
+//!             ColorMaterial::from(Color::rgb_from_array(raw_tile.color))
+//!         });
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! Memoized assets are reused when the manifest is reprocessed (such as after a hot reload),
+//! as long as they are still used by the new version of the manifest.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use bevy::asset::{Asset, Assets, Handle};
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::asset_store::ManifestAssetStore;
+use crate::manifest::Manifest;
+
+/// The procedural assets of type `A` created for the manifest `M`, keyed by the hash of their parameters.
+///
+/// This resource is managed by [`ProceduralAssets`], and is inserted the first time it is used.
+#[derive(Resource)]
+pub struct ProceduralAssetCache<M: Manifest, A: Asset> {
+    /// The weak handle to each asset, and the [`ManifestAssetStore::generation`] in which it was last kept alive.
+    handles: HashMap<u64, (Handle<A>, u64)>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest, A: Asset> ProceduralAssetCache<M, A> {
+    /// The number of distinct assets that have been created.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns true if no assets have been created.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Forgets every memoized asset, so that new assets are created for all future parameters.
+    ///
+    /// Assets which are still kept alive by the [`ManifestAssetStore`] are not released.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+}
+
+impl<M: Manifest, A: Asset> Default for ProceduralAssetCache<M, A> {
+    fn default() -> Self {
+        Self {
+            handles: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest, A: Asset> std::fmt::Debug for ProceduralAssetCache<M, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProceduralAssetCache")
+            .field("handles", &self.handles.len())
+            .finish()
+    }
+}
+
+/// Creates assets of type `A` while processing the manifest `M`, memoizing them by their parameters.
+///
+/// See the [module docs](crate::procedural_assets) for more information.
+pub struct ProceduralAssets<'w, M: Manifest, A: Asset> {
+    world: &'w mut World,
+    _phantom: PhantomData<fn() -> (M, A)>,
+}
+
+impl<'w, M: Manifest, A: Asset> ProceduralAssets<'w, M, A> {
+    /// Wraps the `world` passed to [`Manifest::from_raw_manifest`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Assets<A>`] resource does not exist.
+    pub fn new(world: &'w mut World) -> Self {
+        assert!(
+            world.contains_resource::<Assets<A>>(),
+            "The Assets<{}> resource does not exist: has the asset type been initialized?",
+            std::any::type_name::<A>()
+        );
+        world.init_resource::<ManifestAssetStore<M>>();
+        world.init_resource::<ProceduralAssetCache<M, A>>();
+
+        Self {
+            world,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a weak handle to the asset created from `params`, calling `make` to create it if it does not exist yet.
+    ///
+    /// Calls with equal `params` return the same asset.
+    /// The asset is kept alive by the [`ManifestAssetStore`] until the manifest is reprocessed without using it.
+    pub fn get_or_add(&mut self, params: impl Hash, make: impl FnOnce() -> A) -> Handle<A> {
+        let mut hasher = DefaultHasher::new();
+        params.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let generation = self.world.resource::<ManifestAssetStore<M>>().generation();
+        let cached = self
+            .world
+            .resource::<ProceduralAssetCache<M, A>>()
+            .handles
+            .get(&key)
+            .cloned();
+
+        let handle = match cached {
+            // Already kept alive during this round of processing.
+            Some((handle, kept)) if kept == generation => return handle,
+            // Created by an earlier round of processing: keep it alive for this one too, if it still exists.
+            Some((handle, _)) => self
+                .world
+                .resource::<ManifestAssetStore<M>>()
+                .strong_handle(handle.id()),
+            None => None,
+        };
+
+        let handle = handle.unwrap_or_else(|| self.world.resource_mut::<Assets<A>>().add(make()));
+        let weak = self
+            .world
+            .resource_mut::<ManifestAssetStore<M>>()
+            .keep_alive(handle);
+        self.world
+            .resource_mut::<ProceduralAssetCache<M, A>>()
+            .handles
+            .insert(key, (weak.clone(), generation));
+        weak
+    }
+
+    /// Adds the `asset` without memoizing it, returning a weak handle to it.
+    ///
+    /// The asset is kept alive by the [`ManifestAssetStore`] until the manifest is reprocessed.
+    pub fn add(&mut self, asset: A) -> Handle<A> {
+        let handle = self.world.resource_mut::<Assets<A>>().add(asset);
+        self.world
+            .resource_mut::<ManifestAssetStore<M>>()
+            .keep_alive(handle)
+    }
+
+    /// Returns the asset with the given `handle`, if it exists.
+    #[must_use]
+    pub fn get(&self, handle: &Handle<A>) -> Option<&A> {
+        self.world.resource::<Assets<A>>().get(handle)
+    }
+}
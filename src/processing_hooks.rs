@@ -0,0 +1,192 @@
+//! Systems which run immediately before and after a manifest is processed.
+//!
+//! Processing often needs some setup (such as a resource that [`Manifest::from_raw_manifest`] reads),
+//! or some follow-up work (such as building an index from the finished manifest).
+//! Rather than guessing which state transition or schedule runs at the right time, register these as hooks:
+//!
+//! ```rust
+//! # #[cfg(feature = "ron")]
+//! # fn main() {
+//! # use bevy::prelude::*;
+//! # use leafwing_manifest::{
+//! #     asset_state::SimpleAssetState,
+//! #     identifier::{Id, IdMap},
+//! #     manifest::{Manifest, ManifestFormat},
+//! #     plugin::RegisterManifest,
+//! #     registration::ManifestRegistration,
+//! #     test_utils::{manifest_test_app, ManifestTestApp},
+//! # };
+//! # use serde::Deserialize;
+//! #
+//! # #[derive(Debug, Deserialize)]
+//! # struct Item {
+//! #     name: String,
+//! # }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct ItemManifest {
+//! #     items: IdMap<Item, Item>,
+//! # }
+//! #
+//! # impl Manifest for ItemManifest {
+//! #     type Item = Item;
+//! #     type RawItem = Item;
+//! #     type RawManifest = ItemManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//! #
+//! #     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+//! #         self.items.get(&id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(
+//! #         raw_manifest: Self::RawManifest,
+//! #         world: &mut World,
+//! #     ) -> Result<Self, Self::ConversionError> {
+//! #         // The resource set up by the `before_processing` hook is available here.
+//! #         assert!(world.contains_resource::<ItemTags>());
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//! #[derive(Resource, Default)]
+//! struct ItemTags(Vec<String>);
+//!
+//! #[derive(Resource)]
+//! struct ItemIndex(Vec<String>);
+//!
+//! fn build_item_index(mut commands: Commands, manifest: Res<ItemManifest>) {
+//!     let mut names: Vec<String> = manifest.items.values().map(|item| item.name.clone()).collect();
+//!     names.sort();
+//!     commands.insert_resource(ItemIndex(names));
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.register_manifest_with(
+//!     ManifestRegistration::<ItemManifest>::new()
+//!         .path("items.ron")
+//!         .before_processing(|mut commands: Commands| commands.init_resource::<ItemTags>())
+//!         .after_processing(build_item_index),
+//! );
+//!
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//! assert_eq!(app.world.resource::<ItemIndex>().0, ["shield", "sword"]);
+//! # }
+//! # #[cfg(not(feature = "ron"))]
+//! # fn main() {}
+//! ```
+//!
+//! Hooks are run by [`process_manifest`](crate::plugin::process_manifest) and [`process_manifest_with_context`](crate::plugin::process_manifest_with_context)
+//! each time the manifest is processed, including hot reloads.
+//! Commands issued by a hook are applied as soon as it finishes.
+
+use std::marker::PhantomData;
+
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::SystemId;
+use bevy::log::error;
+
+use crate::manifest::Manifest;
+
+/// The registered hooks for the manifest `M`.
+///
+/// This resource is inserted by [`ManifestRegistration::before_processing`](crate::registration::ManifestRegistration::before_processing)
+/// and [`ManifestRegistration::after_processing`](crate::registration::ManifestRegistration::after_processing).
+#[derive(Resource)]
+pub struct ProcessingHooks<M: Manifest> {
+    before: Vec<SystemId>,
+    after: Vec<SystemId>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> ProcessingHooks<M> {
+    /// Runs the registered system with the given `id` just before the raw manifest is converted.
+    pub fn add_before(&mut self, id: SystemId) {
+        self.before.push(id);
+    }
+
+    /// Runs the registered system with the given `id` just after the manifest has been successfully processed and inserted.
+    pub fn add_after(&mut self, id: SystemId) {
+        self.after.push(id);
+    }
+
+    /// The systems run before processing, in the order they were added.
+    #[must_use]
+    pub fn before(&self) -> &[SystemId] {
+        &self.before
+    }
+
+    /// The systems run after processing, in the order they were added.
+    #[must_use]
+    pub fn after(&self) -> &[SystemId] {
+        &self.after
+    }
+}
+
+impl<M: Manifest> Default for ProcessingHooks<M> {
+    fn default() -> Self {
+        Self {
+            before: Vec::new(),
+            after: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ProcessingHooks<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessingHooks")
+            .field("before", &self.before)
+            .field("after", &self.after)
+            .finish()
+    }
+}
+
+/// Registers `system` as a hook for the manifest `M`, run before processing.
+pub fn add_before_processing<M: Manifest, Marker>(
+    world: &mut World,
+    system: impl IntoSystem<(), (), Marker> + 'static,
+) {
+    let id = world.register_system(system);
+    world
+        .get_resource_or_insert_with(ProcessingHooks::<M>::default)
+        .add_before(id);
+}
+
+/// Registers `system` as a hook for the manifest `M`, run after processing succeeds.
+pub fn add_after_processing<M: Manifest, Marker>(
+    world: &mut World,
+    system: impl IntoSystem<(), (), Marker> + 'static,
+) {
+    let id = world.register_system(system);
+    world
+        .get_resource_or_insert_with(ProcessingHooks::<M>::default)
+        .add_after(id);
+}
+
+/// Which set of hooks to run.
+#[derive(Clone, Copy)]
+pub(crate) enum HookStage {
+    Before,
+    After,
+}
+
+/// Runs the hooks for the manifest `M` registered for the given `stage`, if any.
+pub(crate) fn run_processing_hooks<M: Manifest>(world: &mut World, stage: HookStage) {
+    let Some(hooks) = world.get_resource::<ProcessingHooks<M>>() else {
+        return;
+    };
+    let ids = match stage {
+        HookStage::Before => hooks.before.clone(),
+        HookStage::After => hooks.after.clone(),
+    };
+
+    for id in ids {
+        if let Err(err) = world.run_system(id) {
+            error!(
+                "Could not run a processing hook for {}: {err:?}",
+                std::any::type_name::<M>()
+            );
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Tools for understanding the order in which manifests are processed.
+//!
+//! As projects grow, manifests start to depend on each other,
+//! and working out why a particular manifest was processed too early (or never at all) gets tricky.
+//! A [`ProcessingReport`], created via [`RawManifestTracker::processing_report`], lays out the full processing graph.
+
+use std::any::TypeId;
+use std::fmt::{Display, Write};
+use std::path::PathBuf;
+
+use bevy::asset::LoadState;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::manifest::ManifestFormat;
+use crate::plugin::{ManifestDependency, RawManifestTracker};
+
+/// A snapshot of all registered manifests, ordered by when they will be processed.
+///
+/// Print this via its [`Display`] implementation for a quick textual overview,
+/// or render [`ProcessingReport::to_dot`] with Graphviz to visualize the dependencies between manifests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessingReport {
+    /// The registered manifests, in a valid processing order.
+    ///
+    /// Manifests with no ordering constraints between them are sorted by name.
+    /// Manifests that are part of a dependency cycle, or depend on one, are listed last.
+    pub manifests: Vec<ManifestReport>,
+    /// The names of any manifests that are part of a dependency cycle.
+    ///
+    /// Manifests that merely depend on a manifest in a cycle are not included,
+    /// even though they can never be processed either.
+    /// If this is not empty, the schedule containing the processing systems will fail to build.
+    pub cycles: Vec<&'static str>,
+}
+
+/// Information about a single registered manifest, as part of a [`ProcessingReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestReport {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The path to the raw manifest file.
+    pub path: PathBuf,
+    /// The file format of the raw manifest.
+    pub format: ManifestFormat,
+    /// The loading state of the raw manifest, as of the last update.
+    pub load_state: LoadState,
+    /// The names of the manifests that must be processed before this one.
+    pub dependencies: Vec<&'static str>,
+    /// The names of any dependencies that have not been registered.
+    ///
+    /// These manifests will never be processed, so their dependents cannot be created successfully.
+    pub missing_dependencies: Vec<&'static str>,
+}
+
+impl ProcessingReport {
+    pub(crate) fn new(tracker: &RawManifestTracker) -> Self {
+        let registered: HashMap<TypeId, &'static str> = tracker
+            .iter()
+            .map(|(type_id, status)| (*type_id, status.type_name))
+            .collect();
+
+        // Kahn's algorithm, always picking the alphabetically-first manifest that is ready,
+        // so the resulting order is deterministic.
+        let mut remaining: Vec<TypeId> = registered.keys().copied().collect();
+        remaining.sort_by_key(|type_id| registered[type_id]);
+
+        let mut processed: HashSet<TypeId> = HashSet::default();
+        let mut order = Vec::with_capacity(remaining.len());
+
+        while let Some(position) = remaining.iter().position(|type_id| {
+            tracker
+                .dependencies(*type_id)
+                .iter()
+                .filter(|dependency| registered.contains_key(&dependency.type_id))
+                .all(|dependency| processed.contains(&dependency.type_id))
+        }) {
+            let type_id = remaining.remove(position);
+            processed.insert(type_id);
+            order.push(type_id);
+        }
+
+        // Everything left over is blocked by a cycle, but only some of it is part of one:
+        // a manifest is on a cycle if it can reach itself by following its dependencies.
+        let blocked: HashSet<TypeId> = remaining.iter().copied().collect();
+        let cycles = remaining
+            .iter()
+            .filter(|type_id| depends_on(tracker, &blocked, **type_id, **type_id))
+            .map(|type_id| registered[type_id])
+            .collect();
+        order.extend(remaining);
+
+        let manifests = order
+            .into_iter()
+            .filter_map(|type_id| {
+                let (_, status) = tracker.iter().find(|(id, _)| **id == type_id)?;
+                let (dependencies, missing_dependencies) = tracker
+                    .dependencies(type_id)
+                    .iter()
+                    .partition::<Vec<&ManifestDependency>, _>(|dependency| {
+                        registered.contains_key(&dependency.type_id)
+                    });
+
+                Some(ManifestReport {
+                    type_name: status.type_name,
+                    path: status.path.clone(),
+                    format: status.format,
+                    load_state: status.load_state,
+                    dependencies: dependencies.iter().map(|d| d.type_name).collect(),
+                    missing_dependencies: missing_dependencies
+                        .iter()
+                        .map(|d| d.type_name)
+                        .collect(),
+                })
+            })
+            .collect();
+
+        ProcessingReport { manifests, cycles }
+    }
+
+    /// Renders the processing graph in the Graphviz DOT format.
+    ///
+    /// Each manifest is a node, labelled with its path and format,
+    /// with an edge pointing from each dependency to the manifests that depend on it.
+    /// Unregistered dependencies are drawn with dashed outlines.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph manifests {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for manifest in &self.manifests {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n{} ({:?})\"];",
+                manifest.type_name,
+                manifest.type_name,
+                manifest.path.display(),
+                manifest.format
+            );
+
+            for missing in &manifest.missing_dependencies {
+                let _ = writeln!(dot, "    \"{missing}\" [style=dashed];");
+            }
+
+            for dependency in manifest
+                .dependencies
+                .iter()
+                .chain(&manifest.missing_dependencies)
+            {
+                let _ = writeln!(dot, "    \"{dependency}\" -> \"{}\";", manifest.type_name);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns true if `from` transitively depends on `target`, following only dependencies within `within`.
+fn depends_on(
+    tracker: &RawManifestTracker,
+    within: &HashSet<TypeId>,
+    from: TypeId,
+    target: TypeId,
+) -> bool {
+    let mut visited: HashSet<TypeId> = HashSet::default();
+    let mut stack = vec![from];
+
+    while let Some(type_id) = stack.pop() {
+        for dependency in tracker.dependencies(type_id) {
+            if dependency.type_id == target {
+                return true;
+            }
+
+            if within.contains(&dependency.type_id) && visited.insert(dependency.type_id) {
+                stack.push(dependency.type_id);
+            }
+        }
+    }
+
+    false
+}
+
+impl Display for ProcessingReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Manifest processing order:")?;
+
+        for (i, manifest) in self.manifests.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. {} from {} ({:?}, {:?})",
+                i + 1,
+                manifest.type_name,
+                manifest.path.display(),
+                manifest.format,
+                manifest.load_state
+            )?;
+
+            if !manifest.dependencies.is_empty() {
+                writeln!(f, "   after: {}", manifest.dependencies.join(", "))?;
+            }
+
+            if !manifest.missing_dependencies.is_empty() {
+                writeln!(
+                    f,
+                    "   missing: {}",
+                    manifest.missing_dependencies.join(", ")
+                )?;
+            }
+        }
+
+        if !self.cycles.is_empty() {
+            writeln!(f, "Dependency cycle between: {}", self.cycles.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Integration with [`iyes_progress`], so manifests are counted in existing loading screens.
+//!
+//! Each registered manifest counts as two units of work: one when its raw manifest has loaded,
+//! and another once it has been processed.
+//! Add the [`ManifestProgressPlugin`] for the state that your loading screen runs in,
+//! and the manifests will be included in the [`ProgressCounter`](iyes_progress::ProgressCounter).
+//!
+//! `bevy_asset_loader` reports its own progress through `iyes_progress` when its `progress_tracking` feature is enabled,
+//! so the same setup works for its loading states.
+//!
+//! ```rust,ignore
+//! app.add_plugins(ProgressPlugin::new(GameState::Loading).continue_to(GameState::Playing))
+//!     .add_plugins(ManifestPlugin::<SimpleAssetState>::new())
+//!     .add_plugins(ManifestProgressPlugin::new(GameState::Loading));
+//! ```
+
+use bevy::app::{App, Plugin};
+use bevy::asset::LoadState;
+use bevy::ecs::prelude::*;
+use iyes_progress::prelude::*;
+
+use crate::plugin::{ManifestSchedules, RawManifestTracker};
+
+/// A plugin which reports the progress of manifest loading to [`iyes_progress`] while the app is in the supplied `state`.
+///
+/// This must be added after the [`ManifestPlugin`](crate::plugin::ManifestPlugin) (or the [`ManifestLoadingDriver`](crate::driver::ManifestLoadingDriver)).
+#[derive(Debug, Clone)]
+pub struct ManifestProgressPlugin<S: States> {
+    state: S,
+}
+
+impl<S: States> ManifestProgressPlugin<S> {
+    /// Creates a new plugin, which tracks progress while the app is in `state`.
+    #[must_use]
+    pub fn new(state: S) -> Self {
+        Self { state }
+    }
+}
+
+impl<S: States> Plugin for ManifestProgressPlugin<S> {
+    fn build(&self, app: &mut App) {
+        let schedules = app
+            .world
+            .get_resource::<ManifestSchedules>()
+            .copied()
+            .unwrap_or_default();
+
+        app.add_systems(
+            schedules.loading,
+            manifest_progress
+                .track_progress()
+                .run_if(in_state(self.state.clone())),
+        );
+    }
+}
+
+/// A system which reports how many manifests have loaded and been processed, for use with [`iyes_progress`].
+pub fn manifest_progress(raw_manifest_tracker: Res<RawManifestTracker>) -> Progress {
+    let mut progress = Progress::default();
+
+    for (_, status) in raw_manifest_tracker.iter() {
+        progress.total += 2;
+
+        if status.baked || status.load_state == LoadState::Loaded {
+            progress.done += 1;
+        }
+        if status.baked || status.processed_at.is_some() {
+            progress.done += 1;
+        }
+    }
+
+    progress
+}
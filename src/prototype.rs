@@ -0,0 +1,221 @@
+//! Tools for spawning entities whose per-instance data is created from a manifest item.
+//!
+//! Manifest items are shared, read-only prototypes: every goblin has the same maximum health,
+//! but each goblin needs its own current health.
+//! The [`Prototype`] trait describes how to create the per-instance components from an item,
+//! and the [`FromPrototype`] component marks an entity that should be populated from a specific item.
+//!
+//! ```rust
+//! use bevy::ecs::system::RunSystemOnce;
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     prototype::{FromPrototype, Prototype, PrototypePlugin},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Monster {
+//!     name: String,
+//!     max_health: u32,
+//! }
+//!
+//! #[derive(Component, Debug, PartialEq)]
+//! struct Health(u32);
+//!
+//! impl Prototype for Monster {
+//!     type Instance = (Name, Health);
+//!
+//!     fn instantiate(&self) -> Self::Instance {
+//!         (Name::new(self.name.clone()), Health(self.max_health))
+//!     }
+//! }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct MonsterManifest {
+//! #     monsters: IdMap<Monster, Monster>,
+//! # }
+//! #
+//! # impl Manifest for MonsterManifest {
+//! #     type Item = Monster;
+//! #     type RawItem = Monster;
+//! #     type RawManifest = MonsterManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//! #
+//! #     fn get(&self, id: Id<Monster>) -> Option<&Monster> {
+//! #         self.monsters.get(&id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(raw_manifest: Self, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//!
+//! let goblin = Monster {
+//!     name: "goblin".to_string(),
+//!     max_health: 7,
+//! };
+//! let mut app = manifest_test_app();
+//! app.add_plugins(PrototypePlugin::<MonsterManifest>::default())
+//!     .insert_raw_manifest::<MonsterManifest>(MonsterManifest {
+//!         monsters: IdMap::from_iter([(Id::from_name(&goblin.name), goblin)]),
+//!     });
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! fn spawn_goblin(mut commands: Commands) {
+//!     commands.spawn(FromPrototype::<MonsterManifest>::from_name("goblin"));
+//! }
+//!
+//! app.world.run_system_once(spawn_goblin);
+//! app.update();
+//!
+//! let health = app.world.query::<&Health>().single(&app.world);
+//! assert_eq!(health, &Health(7));
+//! ```
+//!
+//! The instance components are added by [`instantiate_prototypes`] in [`PostUpdate`],
+//! so entities spawned during [`Update`](bevy::app::Update) are complete before they are rendered.
+//! Use [`PrototypePlugin::with_names`] to also label each instance with the name of its prototype.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::core::Name;
+use bevy::ecs::prelude::*;
+use bevy::log::warn;
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+
+/// A manifest item which can be used to create the components for a new entity.
+pub trait Prototype: Send + Sync + 'static {
+    /// The per-instance components created from this item.
+    type Instance: Bundle;
+
+    /// Creates the per-instance components for a new entity based on this item.
+    fn instantiate(&self) -> Self::Instance;
+}
+
+/// A component which requests that this entity be populated with the [`Prototype::Instance`] of an item in the manifest `M`.
+///
+/// The component is kept after the entity has been populated, so it can be used to look up the prototype later.
+#[derive(Component)]
+pub struct FromPrototype<M: Manifest> {
+    /// The identifier of the prototype item.
+    pub id: Id<M::Item>,
+}
+
+impl<M: Manifest> FromPrototype<M> {
+    /// Creates a new [`FromPrototype`] component, which refers to the item with the given `id`.
+    #[must_use]
+    pub fn new(id: Id<M::Item>) -> Self {
+        Self { id }
+    }
+
+    /// Creates a new [`FromPrototype`] component, which refers to the item with the given `name`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        Self::new(Id::from_name(name))
+    }
+}
+
+impl<M: Manifest> Clone for FromPrototype<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Manifest> Copy for FromPrototype<M> {}
+
+impl<M: Manifest> std::fmt::Debug for FromPrototype<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FromPrototype").field(&self.id).finish()
+    }
+}
+
+/// A plugin which populates entities with a [`FromPrototype<M>`] component once they are spawned.
+pub struct PrototypePlugin<M: Manifest> {
+    insert_names: bool,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for PrototypePlugin<M> {
+    fn default() -> Self {
+        Self {
+            insert_names: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> PrototypePlugin<M> {
+    /// Sets whether instances should be labeled with a [`Name`], via [`name_prototype_instances`].
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn with_names(mut self, insert_names: bool) -> Self {
+        self.insert_names = insert_names;
+        self
+    }
+}
+
+impl<M: Manifest> Plugin for PrototypePlugin<M>
+where
+    M::Item: Prototype,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            instantiate_prototypes::<M>.run_if(resource_exists::<M>),
+        );
+
+        if self.insert_names {
+            app.add_systems(PostUpdate, name_prototype_instances::<M>);
+        }
+    }
+}
+
+/// Inserts the [`Prototype::Instance`] for every entity which has just had a [`FromPrototype<M>`] component added.
+///
+/// If the item cannot be found in the manifest, a warning is logged and the entity is left unchanged.
+pub fn instantiate_prototypes<M: Manifest>(
+    manifest: Res<M>,
+    query: Query<(Entity, &FromPrototype<M>), Added<FromPrototype<M>>>,
+    mut commands: Commands,
+) where
+    M::Item: Prototype,
+{
+    for (entity, from_prototype) in query.iter() {
+        match manifest.get(from_prototype.id) {
+            Some(item) => {
+                commands.entity(entity).insert(item.instantiate());
+            }
+            None => warn!(
+                "Could not instantiate {entity:?}: no item with ID {:?} was found in the {} manifest.",
+                from_prototype.id,
+                std::any::type_name::<M>()
+            ),
+        }
+    }
+}
+
+/// Inserts a [`Name`] for every entity which has just had a [`FromPrototype<M>`] component added.
+///
+/// The name of the prototype is looked up in the [`NameRegistry`]: entities whose prototype has no registered name,
+/// or which already have a [`Name`], are left unchanged.
+pub fn name_prototype_instances<M: Manifest>(
+    query: Query<(Entity, &FromPrototype<M>), (Added<FromPrototype<M>>, Without<Name>)>,
+    mut commands: Commands,
+) {
+    for (entity, from_prototype) in query.iter() {
+        if let Some(name) = NameRegistry::name_of(from_prototype.id) {
+            commands.entity(entity).insert(Name::new(name));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Tracking which file each item in a manifest came from.
+//!
+//! Once data is split across several files (see [`manifest_folder`](crate::manifest_folder)),
+//! or layered on top of each other by mods, it's no longer obvious where a given value comes from.
+//! Each manifest has a [`ManifestProvenance`] resource, which records the source of its raw items.
+//!
+//! The sources of the raw items are recorded automatically while loading.
+//! Only [`Manifest::from_raw_manifest`] knows how raw items map to the final [`Id`]s, so it is responsible for
+//! linking the two via [`ManifestProvenance::record_raw`]:
+//!
+//! ```rust,ignore
+//! fn from_raw_manifest(raw_manifest: RawItemManifest, world: &mut World) -> Result<Self, Self::ConversionError> {
+//!     let mut provenance = world.resource_mut::<ManifestProvenance<Self>>();
+//!     for (index, raw_item) in raw_manifest.items.iter().enumerate() {
+//!         provenance.record_raw(Id::from_name(&raw_item.name), index);
+//!     }
+//!     // ...
+//! }
+//! ```
+//!
+//! Items that were never recorded fall back to the manifest's file, if it was loaded from a single file.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+
+/// Where a single raw item was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemSource {
+    /// The path of the file that the item was read from.
+    pub path: PathBuf,
+    /// The name of the layer (such as a mod) that the file belongs to, if any.
+    pub layer: Option<String>,
+}
+
+impl ItemSource {
+    /// Creates a new source for the file at `path`, which does not belong to any layer.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            layer: None,
+        }
+    }
+
+    /// Sets the layer that this source belongs to.
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl Into<String>) -> Self {
+        self.layer = Some(layer.into());
+        self
+    }
+}
+
+impl Display for ItemSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.layer {
+            Some(layer) => write!(f, "{} ({layer})", self.path.display()),
+            None => write!(f, "{}", self.path.display()),
+        }
+    }
+}
+
+/// Records where each item in the manifest `M` came from.
+///
+/// This resource is inserted before `M` is first processed.
+#[derive(Resource)]
+pub struct ManifestProvenance<M: Manifest> {
+    file: Option<ItemSource>,
+    raw_sources: Vec<ItemSource>,
+    items: HashMap<Id<M::Item>, ItemSource>,
+}
+
+impl<M: Manifest> Default for ManifestProvenance<M> {
+    fn default() -> Self {
+        Self {
+            file: None,
+            raw_sources: Vec::new(),
+            items: HashMap::default(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestProvenance<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestProvenance")
+            .field("file", &self.file)
+            .field("raw_sources", &self.raw_sources)
+            .field("items", &self.items.len())
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestProvenance<M> {
+    /// Creates the provenance for a manifest whose items all come from a single file.
+    #[must_use]
+    pub fn from_file(source: ItemSource) -> Self {
+        Self {
+            file: Some(source),
+            ..Self::default()
+        }
+    }
+
+    /// Creates the provenance for a manifest whose raw items come from different files.
+    ///
+    /// `raw_sources` must be in the same order as the raw items.
+    #[must_use]
+    pub fn from_raw_sources(raw_sources: Vec<ItemSource>) -> Self {
+        Self {
+            raw_sources,
+            ..Self::default()
+        }
+    }
+
+    /// The file that the whole manifest was loaded from, if it was loaded from a single file.
+    #[must_use]
+    pub fn file(&self) -> Option<&ItemSource> {
+        self.file.as_ref()
+    }
+
+    /// The source of the raw item at `index`, in the order that raw items were collected into the raw manifest.
+    ///
+    /// Falls back to the [`file`](Self::file) if the sources of individual raw items are not known.
+    #[must_use]
+    pub fn raw_source(&self, index: usize) -> Option<&ItemSource> {
+        self.raw_sources.get(index).or(self.file.as_ref())
+    }
+
+    /// Replaces the sources of the raw items, such as after raw items have been merged or reordered.
+    pub fn set_raw_sources(&mut self, raw_sources: Vec<ItemSource>) {
+        self.raw_sources = raw_sources;
+    }
+
+    /// Records that the item with the given `id` came from `source`.
+    pub fn record(&mut self, id: Id<M::Item>, source: ItemSource) {
+        self.items.insert(id, source);
+    }
+
+    /// Records that the item with the given `id` came from the raw item at `index`.
+    ///
+    /// Does nothing if the source of that raw item is not known.
+    pub fn record_raw(&mut self, id: Id<M::Item>, index: usize) {
+        if let Some(source) = self.raw_source(index).cloned() {
+            self.items.insert(id, source);
+        }
+    }
+
+    /// Returns the source of the item with the given `id`.
+    ///
+    /// If the item was never recorded, this falls back to the [`file`](Self::file).
+    #[must_use]
+    pub fn provenance(&self, id: Id<M::Item>) -> Option<&ItemSource> {
+        self.items.get(&id).or(self.file.as_ref())
+    }
+
+    /// Clears the recorded items, keeping the sources of the raw items.
+    ///
+    /// This is called before each time the manifest is processed, so that stale entries are not kept across hot reloads.
+    pub fn clear_items(&mut self) {
+        self.items.clear();
+    }
+}
@@ -0,0 +1,208 @@
+//! Ready-made raw manifest types for formats that don't naturally map onto a single struct.
+//!
+//! Most raw manifests are a struct containing a list of raw items.
+//! Some formats make that shape awkward to write by hand:
+//! a CSV file is just a list of rows, and a TOML file must be a table at the top level.
+//! These adapters take care of the serde glue, so you can use a `Vec<RawItem>` directly.
+//!
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # fn main() {
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     raw_manifests::TomlTables,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(TypePath, Deserialize)]
+//! struct RawItem {
+//!     name: String,
+//!     weight: u32,
+//! }
+//!
+//! struct Item {
+//!     weight: u32,
+//! }
+//!
+//! #[derive(Resource)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = RawItem;
+//!     type RawManifest = TomlTables<RawItem>;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Toml;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: TomlTables<RawItem>,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         let items = raw_manifest
+//!             .into_iter()
+//!             .map(|raw_item| (Id::from_name(&raw_item.name), Item { weight: raw_item.weight }))
+//!             .collect();
+//!         Ok(ItemManifest { items })
+//!     }
+//! }
+//!
+//! let raw_manifest: TomlTables<RawItem> = toml::from_str(
+//!     r#"
+//!     [[weapons]]
+//!     name = "sword"
+//!     weight = 3
+//!
+//!     [[armor]]
+//!     name = "shield"
+//!     weight = 5
+//!     "#,
+//! )
+//! .unwrap();
+//! assert_eq!(raw_manifest.len(), 2);
+//!
+//! let manifest = ItemManifest::from_raw_manifest(raw_manifest, &mut World::new()).unwrap();
+//! assert_eq!(manifest.get(Id::from_name("sword")).unwrap().weight, 3);
+//! assert_eq!(manifest.get(Id::from_name("shield")).unwrap().weight, 5);
+//! # }
+//! # #[cfg(not(feature = "toml"))]
+//! # fn main() {}
+//! ```
+//!
+//! [`CsvRows`] works the same way for CSV files: see [`deserialize_csv`](crate::loader::deserialize_csv) for an example.
+
+use std::ops::{Deref, DerefMut};
+
+use bevy::asset::{Asset, UntypedAssetId, VisitAssetDependencies};
+use bevy::reflect::TypePath;
+use serde::Deserialize;
+
+/// A raw manifest that is a list of rows of a CSV file, with one raw item per row.
+///
+/// The whole file is read into a single raw manifest by the built-in CSV loader,
+/// which must be selected by registering the manifest with
+/// [`RawManifestOptions::with_csv_settings`](crate::plugin::RawManifestOptions::with_csv_settings).
+/// Use [`CsvSettings::default`](crate::loader::CsvSettings::default) for comma-separated files whose first row names each column,
+/// which is matched against the field names of `T`.
+#[cfg(feature = "csv")]
+#[derive(Debug, Clone, PartialEq, TypePath, Deserialize)]
+#[serde(transparent)]
+pub struct CsvRows<T: TypePath>(pub Vec<T>);
+
+/// A raw manifest that is one or more arrays of tables in a TOML file, with one raw item per table.
+///
+/// All arrays at the top level of the file are concatenated, in the order they appear,
+/// so both of the following are valid:
+///
+/// ```toml
+/// [[items]]
+/// name = "sword"
+///
+/// [[items]]
+/// name = "shield"
+/// ```
+///
+/// ```toml
+/// [[weapons]]
+/// name = "sword"
+///
+/// [[armor]]
+/// name = "shield"
+/// ```
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone, PartialEq, TypePath)]
+pub struct TomlTables<T: TypePath>(pub Vec<T>);
+
+#[cfg(feature = "toml")]
+impl<'de, T: TypePath + Deserialize<'de>> Deserialize<'de> for TomlTables<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TablesVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> serde::de::Visitor<'de> for TablesVisitor<T> {
+            type Value = Vec<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a table containing arrays of tables")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut items = Vec::new();
+                while let Some((_key, tables)) = map.next_entry::<String, Vec<T>>()? {
+                    items.extend(tables);
+                }
+                Ok(items)
+            }
+        }
+
+        deserializer
+            .deserialize_map(TablesVisitor(std::marker::PhantomData))
+            .map(TomlTables)
+    }
+}
+
+/// Implements the traits shared by all of the adapter types.
+macro_rules! impl_raw_manifest_adapter {
+    ($adapter:ident, $feature:literal) => {
+        #[cfg(feature = $feature)]
+        impl<T: TypePath + Send + Sync> Asset for $adapter<T> {}
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath + Send + Sync> VisitAssetDependencies for $adapter<T> {
+            fn visit_dependencies(&self, _visit: &mut impl FnMut(UntypedAssetId)) {}
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath> Default for $adapter<T> {
+            fn default() -> Self {
+                Self(Vec::new())
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath> Deref for $adapter<T> {
+            type Target = Vec<T>;
+
+            fn deref(&self) -> &Vec<T> {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath> DerefMut for $adapter<T> {
+            fn deref_mut(&mut self) -> &mut Vec<T> {
+                &mut self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath> IntoIterator for $adapter<T> {
+            type Item = T;
+            type IntoIter = std::vec::IntoIter<T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: TypePath> From<Vec<T>> for $adapter<T> {
+            fn from(items: Vec<T>) -> Self {
+                Self(items)
+            }
+        }
+    };
+}
+
+impl_raw_manifest_adapter!(CsvRows, "csv");
+impl_raw_manifest_adapter!(TomlTables, "toml");
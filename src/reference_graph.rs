@@ -0,0 +1,233 @@
+//! Exporting the [`Ref`]s between items as a graph, for visualizing tech trees, loot chains and crafting graphs.
+//!
+//! Build a [`ReferenceGraph`] from the raw items of one or more manifests, then render it with [`ReferenceGraph::to_dot`]
+//! (for Graphviz) or serialize it as JSON:
+//!
+//! ```rust
+//! use leafwing_manifest::{
+//!     reference_graph::ReferenceGraph,
+//!     references::{Ref, ReferencesItems},
+//! };
+//!
+//! struct Item;
+//! struct Recipe;
+//!
+//! struct RawRecipe {
+//!     name: String,
+//!     unlocks: Vec<Ref<Recipe>>,
+//!     ingredients: Vec<Ref<Item>>,
+//! }
+//!
+//! impl ReferencesItems for RawRecipe {
+//!     type Item = Recipe;
+//!
+//!     fn name(&self) -> &str {
+//!         &self.name
+//!     }
+//!
+//!     fn references(&self) -> impl Iterator<Item = &Ref<Recipe>> {
+//!         self.unlocks.iter()
+//!     }
+//! }
+//!
+//! let raw_recipes = [
+//!     RawRecipe {
+//!         name: "smelting".to_string(),
+//!         unlocks: vec![Ref::new("forging")],
+//!         ingredients: vec![Ref::new("ore")],
+//!     },
+//!     RawRecipe {
+//!         name: "forging".to_string(),
+//!         unlocks: Vec::new(),
+//!         ingredients: vec![Ref::new("iron_bar")],
+//!     },
+//! ];
+//!
+//! let mut graph = ReferenceGraph::new();
+//! graph.add_items("recipes", &raw_recipes);
+//! // Recipes also refer to items in a different manifest.
+//! graph.add_cross_references("recipes", "items", &raw_recipes, |recipe| recipe.ingredients.iter().collect());
+//!
+//! assert_eq!(graph.nodes().len(), 4);
+//! assert_eq!(graph.edges().len(), 3);
+//! // Only the first recipe in the tech tree is not unlocked by anything else.
+//! let roots: Vec<&str> = graph.roots().into_iter().map(|index| graph.nodes()[index].name.as_str()).collect();
+//! assert_eq!(roots, ["smelting"]);
+//!
+//! // Usually written to a file, such as `recipes.dot`.
+//! let dot = graph.to_dot();
+//! assert!(dot.starts_with("digraph references {"));
+//! ```
+
+use std::fmt::Write;
+
+use bevy::utils::HashMap;
+use serde::Serialize;
+
+use crate::references::{Ref, ReferencesItems};
+
+/// A single item in a [`ReferenceGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct GraphNode {
+    /// The label of the manifest that the item belongs to.
+    pub manifest: String,
+    /// The name of the item.
+    pub name: String,
+}
+
+/// A reference from one item to another in a [`ReferenceGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct GraphEdge {
+    /// The index of the referring item in [`ReferenceGraph::nodes`].
+    pub from: usize,
+    /// The index of the referenced item in [`ReferenceGraph::nodes`].
+    pub to: usize,
+}
+
+/// The items of one or more manifests, and the references between them.
+///
+/// Nodes are identified by their manifest label and name, so adding the same item twice only creates one node.
+/// Referenced items which were never added themselves still appear, so dangling references are visible.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReferenceGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+    #[serde(skip)]
+    indexes: HashMap<GraphNode, usize>,
+}
+
+impl ReferenceGraph {
+    /// Creates an empty graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph of the references between `items`, which all belong to the same manifest.
+    #[must_use]
+    pub fn from_items<R: ReferencesItems>(manifest: &str, items: &[R]) -> Self {
+        let mut graph = Self::new();
+        graph.add_items(manifest, items);
+        graph
+    }
+
+    /// Adds a node, returning its index.
+    ///
+    /// If the node already exists, its existing index is returned.
+    pub fn add_node(&mut self, manifest: &str, name: &str) -> usize {
+        let node = GraphNode {
+            manifest: manifest.to_string(),
+            name: name.to_string(),
+        };
+        if let Some(&index) = self.indexes.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.indexes.insert(node, index);
+        index
+    }
+
+    /// Adds an edge between two nodes, adding the nodes if needed.
+    pub fn add_edge(&mut self, from: (&str, &str), to: (&str, &str)) {
+        let from = self.add_node(from.0, from.1);
+        let to = self.add_node(to.0, to.1);
+        let edge = GraphEdge { from, to };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Adds every item in `items` as a node, and each of their [`ReferencesItems::references`] as an edge within the same manifest.
+    pub fn add_items<R: ReferencesItems>(&mut self, manifest: &str, items: &[R]) {
+        for item in items {
+            self.add_node(manifest, item.name());
+            for reference in item.references() {
+                self.add_edge((manifest, item.name()), (manifest, reference.name()));
+            }
+        }
+    }
+
+    /// Adds edges from each item in `items` to the items of another manifest, as returned by `references`.
+    pub fn add_cross_references<R: ReferencesItems, T>(
+        &mut self,
+        manifest: &str,
+        target_manifest: &str,
+        items: &[R],
+        references: impl for<'a> Fn(&'a R) -> Vec<&'a Ref<T>>,
+    ) {
+        for item in items {
+            self.add_node(manifest, item.name());
+            for reference in references(item) {
+                self.add_edge((manifest, item.name()), (target_manifest, reference.name()));
+            }
+        }
+    }
+
+    /// The items in the graph, in the order they were added.
+    #[must_use]
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// The references in the graph, in the order they were added.
+    #[must_use]
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Returns the indexes of the nodes which no other node refers to, such as the roots of a tech tree.
+    #[must_use]
+    pub fn roots(&self) -> Vec<usize> {
+        let mut referenced = vec![false; self.nodes.len()];
+        for edge in &self.edges {
+            referenced[edge.to] = true;
+        }
+        (0..self.nodes.len())
+            .filter(|&index| !referenced[index])
+            .collect()
+    }
+
+    /// Renders the graph in the Graphviz DOT language.
+    ///
+    /// Each manifest is drawn as a separate cluster.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut manifests: Vec<&str> = Vec::new();
+        for node in &self.nodes {
+            if !manifests.contains(&node.manifest.as_str()) {
+                manifests.push(&node.manifest);
+            }
+        }
+
+        // Writing to a String cannot fail.
+        let mut dot = String::from("digraph references {\n");
+        for (cluster, manifest) in manifests.iter().enumerate() {
+            let _ = writeln!(dot, "  subgraph cluster_{cluster} {{");
+            let _ = writeln!(dot, "    label=\"{}\";", escape(manifest));
+            for (index, node) in self.nodes.iter().enumerate() {
+                if node.manifest == *manifest {
+                    let _ = writeln!(dot, "    n{index} [label=\"{}\"];", escape(&node.name));
+                }
+            }
+            dot.push_str("  }\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(dot, "  n{} -> n{};", edge.from, edge.to);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Serializes the graph as pretty-printed JSON, with `nodes` and `edges` arrays.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Escapes a string for use inside a quoted DOT label.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
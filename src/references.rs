@@ -0,0 +1,248 @@
+//! References between items in the same manifest.
+//!
+//! Raw items often refer to other items in the same file by name: a species lists its prey, a recipe lists its ingredients.
+//! Store these as [`Ref`] fields, and implement [`ReferencesItems`] for the raw item type.
+//! Then, at the start of [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest):
+//!
+//! - [`validate_references`] checks that every referenced item exists, so no [`Id`] is left dangling.
+//! - [`sort_by_references`] also orders the items so that each comes after every item it references,
+//!   and fails with the offending cycle if the references loop back on themselves.
+//!   Use this when converting an item needs the already-converted data of the items it references.
+//!
+//! ```rust,ignore
+//! let mut species: IdMap<Species, Species> = IdMap::default();
+//! for raw in sort_by_references(raw_manifest.species)? {
+//!     // Every prey species has already been converted.
+//!     let food_energy = raw.prey.iter().map(|prey| species[&prey.id()].energy).sum();
+//!     species.insert(Id::from_name(&raw.name), Species { food_energy, .. });
+//! }
+//! ```
+//!
+//! To visualize the references, see the [`reference_graph`](crate::reference_graph) module.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::identifier::Id;
+
+/// A reference to another item in the same manifest, written as the item's name in the raw manifest.
+///
+/// This serializes as a plain string.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct Ref<T> {
+    name: String,
+    #[serde(skip)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Ref<T> {
+    /// Creates a reference to the item with the given `name`.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The name of the referenced item.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The [`Id`] of the referenced item, as computed by [`Id::from_name`].
+    #[must_use]
+    pub fn id(&self) -> Id<T> {
+        Id::from_name(&self.name)
+    }
+}
+
+impl<T> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.name.clone())
+    }
+}
+
+impl<T> Debug for Ref<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Ref").field(&self.name).finish()
+    }
+}
+
+impl<T> PartialEq for Ref<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<T> Eq for Ref<T> {}
+
+impl<T> Hash for Ref<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl<T> From<&str> for Ref<T> {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A raw item that refers to other items in the same manifest.
+///
+/// ```rust
+/// use leafwing_manifest::references::{ReferencesItems, Ref};
+///
+/// struct Species;
+///
+/// struct RawSpecies {
+///     name: String,
+///     prey: Vec<Ref<Species>>,
+/// }
+///
+/// impl ReferencesItems for RawSpecies {
+///     type Item = Species;
+///
+///     fn name(&self) -> &str {
+///         &self.name
+///     }
+///
+///     fn references(&self) -> impl Iterator<Item = &Ref<Species>> {
+///         self.prey.iter()
+///     }
+/// }
+/// ```
+pub trait ReferencesItems {
+    /// The processed item type, which each [`Ref`] resolves to.
+    type Item;
+
+    /// The unique name of this item, which other items use to refer to it.
+    fn name(&self) -> &str;
+
+    /// Every reference this item makes to other items in the same manifest.
+    fn references(&self) -> impl Iterator<Item = &Ref<Self::Item>>;
+}
+
+/// Errors that can occur when resolving references between raw items.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReferenceError {
+    /// An item refers to an item which does not exist.
+    #[error("The item {item} refers to {reference}, which does not exist.")]
+    MissingReference {
+        /// The name of the item with the dangling reference.
+        item: String,
+        /// The name of the missing item.
+        reference: String,
+    },
+    /// Two items share the same name.
+    #[error("More than one item has the name {0}.")]
+    DuplicateName(String),
+    /// A chain of items refer to each other in a loop.
+    #[error("The items {} refer to each other in a cycle.", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Checks that item names are unique, and that every [`Ref`] points to an item in `items`.
+///
+/// Cycles are allowed: use [`sort_by_references`] if they are not.
+pub fn validate_references<R: ReferencesItems>(items: &[R]) -> Result<(), ReferenceError> {
+    dependency_graph(items).map(|_| ())
+}
+
+/// The progress of visiting a single item.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Unvisited,
+    InProgress,
+    Done,
+}
+
+/// Checks the references between `items`, then orders them so that each item comes after every item it references.
+///
+/// Items which do not depend on each other keep their original relative order.
+pub fn sort_by_references<R: ReferencesItems>(items: Vec<R>) -> Result<Vec<R>, ReferenceError> {
+    let edges = dependency_graph(&items)?;
+    let mut visits = vec![Visit::Unvisited; items.len()];
+    let mut order = Vec::with_capacity(items.len());
+
+    for start in 0..items.len() {
+        if visits[start] != Visit::Unvisited {
+            continue;
+        }
+
+        // An explicit depth-first search, so long reference chains cannot overflow the stack.
+        // Each entry is an item, and the index of the next reference to follow.
+        visits[start] = Visit::InProgress;
+        let mut stack = vec![(start, 0)];
+        while let Some(top) = stack.last_mut() {
+            let index = top.0;
+            let Some(&reference) = edges[index].get(top.1) else {
+                visits[index] = Visit::Done;
+                order.push(index);
+                stack.pop();
+                continue;
+            };
+            top.1 += 1;
+
+            match visits[reference] {
+                Visit::Unvisited => {
+                    visits[reference] = Visit::InProgress;
+                    stack.push((reference, 0));
+                }
+                Visit::InProgress => {
+                    let cycle_start = stack
+                        .iter()
+                        .position(|&(index, _)| index == reference)
+                        .unwrap_or_default();
+                    let mut cycle: Vec<String> = stack[cycle_start..]
+                        .iter()
+                        .map(|&(index, _)| items[index].name().to_string())
+                        .collect();
+                    cycle.push(items[reference].name().to_string());
+                    return Err(ReferenceError::Cycle(cycle));
+                }
+                Visit::Done => {}
+            }
+        }
+    }
+
+    let mut items: Vec<Option<R>> = items.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .filter_map(|index| items[index].take())
+        .collect())
+}
+
+/// Returns the indexes of the items referenced by each item.
+fn dependency_graph<R: ReferencesItems>(items: &[R]) -> Result<Vec<Vec<usize>>, ReferenceError> {
+    let mut indexes: HashMap<&str, usize> =
+        HashMap::with_capacity_and_hasher(items.len(), Default::default());
+    for (index, item) in items.iter().enumerate() {
+        if indexes.insert(item.name(), index).is_some() {
+            return Err(ReferenceError::DuplicateName(item.name().to_string()));
+        }
+    }
+
+    items
+        .iter()
+        .map(|item| {
+            item.references()
+                .map(|reference| {
+                    indexes.get(reference.name()).copied().ok_or_else(|| {
+                        ReferenceError::MissingReference {
+                            item: item.name().to_string(),
+                            reference: reference.name().to_string(),
+                        }
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,524 @@
+//! A builder for registering manifests, for when [`register_manifest`](RegisterManifest::register_manifest) isn't enough.
+//!
+//! Each manifest can be configured in a number of ways: where it's loaded from, how its file is read,
+//! which manifests it depends on and how failures should be handled.
+//! Rather than an ever-growing list of `register_manifest_with_*` methods,
+//! these options are collected in a [`ManifestRegistration`], which is passed to [`RegisterManifest::register_manifest_with`].
+//!
+//! ```rust,ignore
+//! app.register_manifest_with(
+//!     ManifestRegistration::<ItemManifest>::new()
+//!         .path("items.ron")
+//!         .extensions(&["items.ron"])
+//!         .after::<SpriteManifest>()
+//!         .policy(ManifestPolicy::SkipInvalid)
+//!         .hot_reload(true),
+//! );
+//! ```
+//!
+//! Optional content can be loaded in the background, without delaying the rest of the game:
+//!
+//! ```rust,ignore
+//! app.register_manifest_with(
+//!     ManifestRegistration::<CosmeticManifest>::new()
+//!         .path("cosmetics.ron")
+//!         .required(false),
+//! );
+//! ```
+//!
+//! Alternative files can be supplied for each [`ManifestProfile`], such as development data with extra debugging items:
+//!
+//! ```rust,ignore
+//! app.insert_resource(ManifestProfile::Dev).register_manifest_with(
+//!     ManifestRegistration::<ItemManifest>::new()
+//!         .path("items.ron")
+//!         .path_for(ManifestProfile::Dev, "items_dev.ron"),
+//! );
+//! ```
+
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use bevy::app::App;
+use bevy::asset::Assets;
+use bevy::ecs::prelude::*;
+
+use crate::loader::{RawBytesTransform, Strictness};
+use crate::manifest::{Manifest, ManifestFormat};
+use crate::merge::{ConflictPolicy, ManifestConflictPolicy};
+use crate::missing_lookups::{MissingLookupPolicy, MissingLookups};
+use crate::plugin::{
+    manifest_schedules, process_manifest, raw_manifest_modified, raw_manifest_unprocessed,
+    register_raw_manifest, ManifestProcessingSet, ProcessManifestSet, RawManifestOptions,
+    RawManifestTracker, RegisterManifest,
+};
+use crate::processing_hooks::{add_after_processing, add_before_processing};
+
+/// How failures to load or process a manifest are handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestPolicy {
+    /// The manifest is required: if it fails to load or process, the app enters [`AssetLoadingState::FAILED`](crate::asset_state::AssetLoadingState::FAILED).
+    #[default]
+    Required,
+    /// The manifest is optional: if it fails to load or process, the error is logged and the manifest is skipped.
+    ///
+    /// The manifest resource will not exist, so systems which use it should check for it.
+    SkipInvalid,
+}
+
+/// The environment that the app is running in, used to select between alternative raw manifest files.
+///
+/// Insert this as a resource before registering manifests to choose the active profile.
+/// If the resource does not exist, [`ManifestProfile::Dev`] is used in debug builds, and [`ManifestProfile::Release`] otherwise.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ManifestProfile {
+    /// Development builds, which may include debugging or cheat content.
+    Dev,
+    /// Shipping builds.
+    Release,
+    /// Any other environment, such as a playtest or demo build.
+    Custom(String),
+}
+
+impl Default for ManifestProfile {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            ManifestProfile::Dev
+        } else {
+            ManifestProfile::Release
+        }
+    }
+}
+
+impl ManifestProfile {
+    /// Reads the profile from the environment variable `var`.
+    ///
+    /// The values `dev` and `release` map to the matching variants, and any other value becomes [`ManifestProfile::Custom`].
+    /// Returns [`None`] if the variable is not set.
+    #[must_use]
+    pub fn from_env(var: &str) -> Option<Self> {
+        let value = std::env::var(var).ok()?;
+        Some(match value.as_str() {
+            "dev" => ManifestProfile::Dev,
+            "release" => ManifestProfile::Release,
+            _ => ManifestProfile::Custom(value),
+        })
+    }
+}
+
+/// A builder which collects the settings used to register the manifest `M`.
+///
+/// Pass this to [`RegisterManifest::register_manifest_with`] once it is configured.
+pub struct ManifestRegistration<M: Manifest> {
+    path: Option<PathBuf>,
+    profile_paths: Vec<(ManifestProfile, PathBuf)>,
+    options: RawManifestOptions,
+    policy: ManifestPolicy,
+    required: bool,
+    hot_reload: bool,
+    dependencies: Vec<fn(&mut App)>,
+    resources: Vec<Box<dyn FnOnce(&mut App)>>,
+    _phantom: PhantomData<M>,
+}
+
+impl<M: Manifest> Default for ManifestRegistration<M> {
+    fn default() -> Self {
+        Self {
+            path: None,
+            profile_paths: Vec::new(),
+            options: RawManifestOptions::default(),
+            policy: ManifestPolicy::default(),
+            required: true,
+            hot_reload: false,
+            dependencies: Vec::new(),
+            resources: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestRegistration<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestRegistration")
+            .field("path", &self.path)
+            .field("profile_paths", &self.profile_paths)
+            .field("options", &self.options)
+            .field("policy", &self.policy)
+            .field("required", &self.required)
+            .field("hot_reload", &self.hot_reload)
+            .field("dependencies", &self.dependencies.len())
+            .finish()
+    }
+}
+
+impl<M: Manifest> ManifestRegistration<M> {
+    /// Creates a new registration with the default settings.
+    ///
+    /// A [`path`](Self::path) must be supplied before registering, unless the active profile has its own [`path_for`](Self::path_for).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to the raw manifest file.
+    ///
+    /// By default, the path root is the `assets` folder, just like all Bevy assets.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Uses the supplied path instead when the active [`ManifestProfile`] is `profile`.
+    ///
+    /// Profiles without their own path fall back to the [`path`](Self::path).
+    #[must_use]
+    pub fn path_for(mut self, profile: ManifestProfile, path: impl Into<PathBuf>) -> Self {
+        self.profile_paths.push((profile, path.into()));
+        self
+    }
+
+    /// Reads the raw manifest file as the supplied format, rather than [`Manifest::FORMAT`].
+    ///
+    /// This is useful when the same manifest type is shipped in different formats,
+    /// such as a human-readable format during development and a binary format in release builds.
+    #[must_use]
+    pub fn format_override(mut self, format: ManifestFormat) -> Self {
+        self.options.format = Some(format);
+        self
+    }
+
+    /// Sets how failures to load or process this manifest are handled.
+    ///
+    /// Defaults to [`ManifestPolicy::Required`].
+    #[must_use]
+    pub fn policy(mut self, policy: ManifestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// If false, this manifest loads in the background, rather than blocking [`AssetLoadingState::READY`](crate::asset_state::AssetLoadingState::READY).
+    ///
+    /// This is useful for optional content, such as cosmetics or DLC, which should not delay the game from starting.
+    /// Background manifests are processed as soon as their raw manifest has loaded, regardless of the asset loading state,
+    /// and failures are logged rather than causing loading to fail.
+    /// Systems which use the manifest resource should check that it exists.
+    ///
+    /// Only required manifests decide when processing is complete:
+    /// a background manifest which finishes first never lets loading skip past a required manifest which then fails.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "ron")]
+    /// # fn main() {
+    /// use bevy::prelude::*;
+    /// use leafwing_manifest::{
+    ///     asset_state::SimpleAssetState,
+    ///     identifier::{Id, IdMap},
+    ///     manifest::{Manifest, ManifestFormat},
+    ///     plugin::{
+    ///         drive_manifest_loading, ManifestPlugin, ProcessingStatus, RawManifestTracker,
+    ///         RegisterManifest,
+    ///     },
+    ///     registration::ManifestRegistration,
+    ///     test_utils::ManifestTestApp,
+    /// };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Resource, Asset, TypePath, Deserialize)]
+    /// struct CosmeticManifest {
+    ///     items: IdMap<Item, Item>,
+    /// }
+    ///
+    /// impl Manifest for CosmeticManifest {
+    ///     type Item = Item;
+    ///     type RawItem = Item;
+    ///     type RawManifest = CosmeticManifest;
+    ///     type ConversionError = std::convert::Infallible;
+    ///
+    ///     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+    ///
+    ///     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+    ///         self.items.get(&id)
+    ///     }
+    ///
+    ///     fn from_raw_manifest(
+    ///         raw_manifest: Self::RawManifest,
+    ///         _world: &mut World,
+    ///     ) -> Result<Self, Self::ConversionError> {
+    ///         Ok(raw_manifest)
+    ///     }
+    /// }
+    ///
+    /// #[derive(Asset, TypePath, Deserialize)]
+    /// struct RawQuestManifest {
+    ///     quests: Vec<String>,
+    /// }
+    ///
+    /// /// A required manifest which always fails to process.
+    /// #[derive(Resource)]
+    /// struct QuestManifest;
+    ///
+    /// impl Manifest for QuestManifest {
+    ///     type Item = String;
+    ///     type RawItem = String;
+    ///     type RawManifest = RawQuestManifest;
+    ///     type ConversionError = std::fmt::Error;
+    ///
+    ///     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+    ///
+    ///     fn get(&self, _id: Id<String>) -> Option<&Self::Item> {
+    ///         None
+    ///     }
+    ///
+    ///     fn from_raw_manifest(
+    ///         _raw_manifest: Self::RawManifest,
+    ///         _world: &mut World,
+    ///     ) -> Result<Self, Self::ConversionError> {
+    ///         Err(std::fmt::Error)
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((
+    ///     MinimalPlugins,
+    ///     AssetPlugin::default(),
+    ///     // The states are advanced by hand, so the background manifest can finish first.
+    ///     ManifestPlugin::<SimpleAssetState>::default().manual_state_advancement(),
+    /// ))
+    /// .insert_raw_manifest::<QuestManifest>(RawQuestManifest { quests: Vec::new() })
+    /// .register_manifest_with(
+    ///     ManifestRegistration::<CosmeticManifest>::new()
+    ///         .path("items.ron")
+    ///         .required(false),
+    /// );
+    ///
+    /// // The background manifest is processed while the app is still loading...
+    /// while !app.world.contains_resource::<CosmeticManifest>() {
+    ///     app.update();
+    ///     std::thread::sleep(std::time::Duration::from_millis(1));
+    /// }
+    /// // ...but that doesn't complete processing on behalf of the required manifest.
+    /// assert_eq!(
+    ///     app.world.resource::<RawManifestTracker>().processing_status(),
+    ///     ProcessingStatus::Processing
+    /// );
+    ///
+    /// let mut state = SimpleAssetState::Loading;
+    /// while state == SimpleAssetState::Loading || state == SimpleAssetState::Processing {
+    ///     app.update();
+    ///     state = drive_manifest_loading::<SimpleAssetState>(&mut app.world);
+    /// }
+    /// assert_eq!(state, SimpleAssetState::Failed);
+    /// # }
+    /// # #[cfg(not(feature = "ron"))]
+    /// # fn main() {}
+    /// ```
+    #[must_use]
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Processes this manifest after the manifest `Other`.
+    ///
+    /// This is equivalent to calling [`RegisterManifest::register_manifest_dependency`].
+    #[must_use]
+    pub fn after<Other: Manifest>(mut self) -> Self {
+        self.dependencies.push(add_dependency::<M, Other>);
+        self
+    }
+
+    /// If true, the manifest is reprocessed whenever its raw manifest file is modified.
+    ///
+    /// This requires asset hot reloading to be enabled, via [`AssetPlugin::watch_for_changes_override`](bevy::asset::AssetPlugin::watch_for_changes_override)
+    /// or the `file_watcher` feature of Bevy.
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn hot_reload(mut self, hot_reload: bool) -> Self {
+        self.hot_reload = hot_reload;
+        self
+    }
+
+    /// Registers the asset loader for this manifest for the supplied file extensions, such as `&["items.ron"]`.
+    ///
+    /// See [`RawManifestOptions::extensions`] for more details.
+    #[must_use]
+    pub fn extensions(mut self, extensions: &'static [&'static str]) -> Self {
+        self.options = self.options.with_extensions(extensions);
+        self
+    }
+
+    /// Decodes the raw bytes of the file with the supplied [`RawBytesTransform`] before deserializing them.
+    #[must_use]
+    pub fn transform(mut self, transform: impl RawBytesTransform) -> Self {
+        self.options = self.options.with_transform(transform);
+        self
+    }
+
+    /// Sets how fields in the raw manifest file which the raw manifest type does not have are handled.
+    ///
+    /// Use [`Strictness::Deny`] to catch typos in the names of optional fields.
+    #[must_use]
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.options = self.options.with_strictness(strictness);
+        self
+    }
+
+    /// Sets how lookups of items which are missing from this manifest are handled.
+    ///
+    /// See the [`missing_lookups`](crate::missing_lookups) module for more details.
+    #[must_use]
+    pub fn missing_lookups(mut self, policy: MissingLookupPolicy) -> Self {
+        self.resources.push(Box::new(move |app: &mut App| {
+            app.world
+                .get_resource_or_insert_with(MissingLookups::default)
+                .set::<M>(policy);
+        }));
+        self
+    }
+
+    /// Runs `system` immediately before the raw manifest is converted into the manifest, each time it is processed.
+    ///
+    /// See the [`processing_hooks`](crate::processing_hooks) module for more details.
+    #[must_use]
+    pub fn before_processing<Marker: 'static>(
+        mut self,
+        system: impl IntoSystem<(), (), Marker> + 'static,
+    ) -> Self {
+        self.resources.push(Box::new(move |app: &mut App| {
+            add_before_processing::<M, Marker>(&mut app.world, system);
+        }));
+        self
+    }
+
+    /// Runs `system` immediately after the manifest has been successfully processed and inserted, each time it is processed.
+    ///
+    /// See the [`processing_hooks`](crate::processing_hooks) module for more details.
+    #[must_use]
+    pub fn after_processing<Marker: 'static>(
+        mut self,
+        system: impl IntoSystem<(), (), Marker> + 'static,
+    ) -> Self {
+        self.resources.push(Box::new(move |app: &mut App| {
+            add_after_processing::<M, Marker>(&mut app.world, system);
+        }));
+        self
+    }
+
+    /// Sets how conflicts between `T` raw items with the same key are resolved when they are merged.
+    ///
+    /// See the [`merge`](crate::merge) module for more details.
+    #[must_use]
+    pub fn conflict_policy<T: Send + Sync + 'static>(mut self, policy: ConflictPolicy<T>) -> Self {
+        self.resources.push(Box::new(move |app: &mut App| {
+            app.insert_resource(ManifestConflictPolicy::<M, T>::new(policy));
+        }));
+        self
+    }
+
+    /// Replaces all of the [`RawManifestOptions`] used to load the raw manifest file.
+    #[must_use]
+    pub fn options(mut self, options: RawManifestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Registers the manifest with the app, using the collected settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no [`path`](Self::path) was supplied, and there is no path for the active [`ManifestProfile`].
+    pub fn register(self, app: &mut App) {
+        let profile = app
+            .world
+            .get_resource::<ManifestProfile>()
+            .cloned()
+            .unwrap_or_default();
+        let profile_path = self
+            .profile_paths
+            .into_iter()
+            .find_map(|(p, path)| (p == profile).then_some(path));
+
+        let Some(path) = profile_path.or(self.path) else {
+            panic!(
+                "No path was supplied when registering the manifest {}.",
+                std::any::type_name::<M>()
+            );
+        };
+
+        register_raw_manifest::<M>(app, path, self.options);
+        let schedules = manifest_schedules(app);
+
+        if let Some(status) = app
+            .world
+            .resource_mut::<RawManifestTracker>()
+            .status_mut::<M>()
+        {
+            status.policy = self.policy;
+            status.required = self.required;
+        }
+
+        if self.required {
+            app.add_systems(
+                schedules.processing,
+                process_manifest::<M>
+                    .in_set(ProcessManifestSet)
+                    .in_set(ManifestProcessingSet::<M>::default())
+                    .run_if(not(resource_exists::<M>).and_then(raw_manifest_unprocessed::<M>)),
+            );
+        } else {
+            // Background manifests are not part of the `ProcessManifestSet`, which only runs in the processing state.
+            app.add_systems(
+                schedules.processing,
+                process_manifest::<M>
+                    .in_set(ManifestProcessingSet::<M>::default())
+                    .run_if(not(resource_exists::<M>).and_then(raw_manifest_available::<M>)),
+            );
+        }
+
+        if self.hot_reload {
+            // Reloads run in the processing schedule, so they respect the order set by any dependencies.
+            app.add_systems(
+                schedules.processing,
+                process_manifest::<M>
+                    .in_set(ManifestProcessingSet::<M>::default())
+                    .run_if(resource_exists::<M>)
+                    .run_if(raw_manifest_modified::<M>),
+            );
+        }
+
+        for add_dependency in self.dependencies {
+            add_dependency(app);
+        }
+
+        for insert_resource in self.resources {
+            insert_resource(app);
+        }
+    }
+}
+
+/// A run condition which returns true if the raw manifest of `M` has loaded, and has not yet been processed.
+///
+/// Processing removes the raw manifest, so this only returns true once for each load.
+pub fn raw_manifest_available<M: Manifest>(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    raw_manifests: Res<Assets<M::RawManifest>>,
+) -> bool {
+    raw_manifest_tracker
+        .status::<M>()
+        .is_some_and(|status| raw_manifests.contains(status.handle.id().typed::<M::RawManifest>()))
+}
+
+fn add_dependency<M: Manifest, Dependency: Manifest>(app: &mut App) {
+    app.register_manifest_dependency::<M, Dependency>();
+}
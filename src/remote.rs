@@ -0,0 +1,366 @@
+//! JSON endpoints for inspecting and live-tuning manifests from external tools.
+//!
+//! Add a [`ManifestRemotePlugin`] for each manifest that should be exposed,
+//! then forward requests to [`handle_remote_request`] from whichever transport your tooling uses.
+//! Requests follow the same method / params / result shape as the Bevy Remote Protocol:
+//!
+//! - [`LIST_METHOD`]: lists the exposed manifests, and how many items each has.
+//! - [`GET_METHOD`]: fetches a single item as reflected JSON.
+//!   Params: `{ "manifest": "ItemManifest", "name": "sword" }`, or `{ "manifest": "ItemManifest", "id": 1234 }`.
+//! - [`PATCH_METHOD`]: merges a JSON object into a single item, then returns the updated item.
+//!   Params: as for [`GET_METHOD`], plus `"patch": { "damage": 12 }`.
+//!   Only available for [`MutableManifest`]s exposed with [`ManifestRemotePlugin::with_patching`],
+//!   and rejected while the [`ManifestLock`] is locked.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     remote::{handle_remote_request, ManifestRemotePlugin, RemoteError, GET_METHOD, PATCH_METHOD},
+//!     test_utils::{manifest_test_app, ManifestTestApp},
+//! };
+//! use serde_json::{json, Value};
+//! # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+//!
+//! fn on_request(world: &mut World, method: &str, params: Option<Value>) -> Result<Value, RemoteError> {
+//!     handle_remote_request(world, method, params)
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.add_plugins(
+//!     ManifestRemotePlugin::<ItemManifest>::default()
+//!         .with_name("ItemManifest")
+//!         .with_patching(),
+//! )
+//! .insert_raw_manifest::<ItemManifest>(ItemManifest::from_items([Item::new("sword", 10)]));
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//!
+//! let params = json!({ "manifest": "ItemManifest", "name": "sword", "patch": { "value": 12 } });
+//! let patched = on_request(&mut app.world, PATCH_METHOD, Some(params)).unwrap();
+//! assert_eq!(patched, json!({ "name": "sword", "value": 12 }));
+//!
+//! let params = json!({ "manifest": "ItemManifest", "name": "sword" });
+//! let item = on_request(&mut app.world, GET_METHOD, Some(params)).unwrap();
+//! assert_eq!(item["value"], 12);
+//! ```
+//!
+//! Items are converted to and from JSON using reflection, so item types must implement [`Reflect`]
+//! and be registered in the [`AppTypeRegistry`], which the plugin does automatically.
+
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::ecs::reflect::AppTypeRegistry;
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy::reflect::{GetTypeRegistration, Reflect, TypeRegistry};
+use bevy::utils::get_short_name;
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+use crate::identifier::Id;
+use crate::manifest::{Manifest, MutableManifest};
+use crate::manifest_lock::ManifestLock;
+
+/// The method which lists every exposed manifest.
+pub const LIST_METHOD: &str = "manifest/list";
+/// The method which fetches a single manifest item.
+pub const GET_METHOD: &str = "manifest/get";
+/// The method which modifies a single manifest item.
+pub const PATCH_METHOD: &str = "manifest/patch";
+
+/// An error returned by [`handle_remote_request`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RemoteError {
+    /// The method is not one of [`LIST_METHOD`], [`GET_METHOD`] or [`PATCH_METHOD`].
+    #[error("Unknown method {0}.")]
+    UnknownMethod(String),
+    /// The params were missing or malformed.
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+    /// No manifest with this name was exposed with a [`ManifestRemotePlugin`].
+    #[error("Unknown manifest {0}.")]
+    UnknownManifest(String),
+    /// The manifest has not been processed yet.
+    #[error("The manifest {0} has not been loaded.")]
+    NotLoaded(String),
+    /// The requested item does not exist.
+    #[error("The item was not found in the manifest {0}.")]
+    ItemNotFound(String),
+    /// The manifest was not exposed with [`ManifestRemotePlugin::with_patching`].
+    #[error("The manifest {0} cannot be patched remotely.")]
+    PatchingDisabled(String),
+    /// The [`ManifestLock`] is locked.
+    #[error("Manifests are locked and cannot be modified.")]
+    Locked,
+    /// The item could not be converted to or from JSON.
+    #[error("The item could not be converted to or from JSON: {0}")]
+    Serialization(String),
+}
+
+/// Identifies a single item in a remote request.
+#[derive(Debug, Deserialize)]
+struct ItemParams {
+    manifest: String,
+    name: Option<String>,
+    id: Option<u64>,
+    #[serde(default)]
+    patch: Option<Map<String, Value>>,
+}
+
+impl ItemParams {
+    fn id<T>(&self) -> Result<Id<T>, RemoteError> {
+        match (&self.name, self.id) {
+            (Some(name), None) => Ok(Id::from_name(name)),
+            (None, Some(id)) => Ok(Id::from_raw(id)),
+            _ => Err(RemoteError::InvalidParams(
+                "expected exactly one of `name` or `id`".to_string(),
+            )),
+        }
+    }
+}
+
+type GetItemFn = fn(&World, &ItemParams) -> Result<Value, RemoteError>;
+type PatchItemFn = fn(&mut World, &ItemParams) -> Result<Value, RemoteError>;
+
+/// The remote operations for a single manifest type, as registered by a [`ManifestRemotePlugin`].
+struct RemoteManifest {
+    name: String,
+    type_name: &'static str,
+    item_count: fn(&World) -> Option<Option<usize>>,
+    get: GetItemFn,
+    patch: Option<PatchItemFn>,
+}
+
+/// The manifests which can be accessed with [`handle_remote_request`].
+///
+/// Each manifest is added by its [`ManifestRemotePlugin`].
+#[derive(Resource, Default)]
+pub struct ManifestRemote {
+    manifests: Vec<RemoteManifest>,
+}
+
+impl ManifestRemote {
+    /// Returns the names of the exposed manifests, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.manifests.iter().map(|manifest| manifest.name.as_str())
+    }
+
+    fn find(&self, name: &str) -> Result<&RemoteManifest, RemoteError> {
+        self.manifests
+            .iter()
+            .find(|manifest| manifest.name == name)
+            .ok_or_else(|| RemoteError::UnknownManifest(name.to_string()))
+    }
+
+    fn handle(
+        &self,
+        world: &mut World,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<Value, RemoteError> {
+        if method == LIST_METHOD {
+            let manifests: Vec<Value> = self
+                .manifests
+                .iter()
+                .map(|manifest| {
+                    let loaded = (manifest.item_count)(world);
+                    json!({
+                        "name": manifest.name,
+                        "type_name": manifest.type_name,
+                        "loaded": loaded.is_some(),
+                        "item_count": loaded.flatten(),
+                        "patchable": manifest.patch.is_some(),
+                    })
+                })
+                .collect();
+            return Ok(Value::Array(manifests));
+        }
+
+        if method != GET_METHOD && method != PATCH_METHOD {
+            return Err(RemoteError::UnknownMethod(method.to_string()));
+        }
+
+        let params: ItemParams = match params {
+            Some(params) => serde_json::from_value(params)
+                .map_err(|err| RemoteError::InvalidParams(err.to_string()))?,
+            None => return Err(RemoteError::InvalidParams("missing params".to_string())),
+        };
+        let manifest = self.find(&params.manifest)?;
+
+        if method == GET_METHOD {
+            return (manifest.get)(world, &params);
+        }
+
+        match manifest.patch {
+            Some(patch) => patch(world, &params),
+            None => Err(RemoteError::PatchingDisabled(manifest.name.clone())),
+        }
+    }
+}
+
+impl std::fmt::Debug for ManifestRemote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestRemote")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Handles a single remote request, returning the JSON result.
+///
+/// See the [module documentation](self) for the supported methods.
+pub fn handle_remote_request(
+    world: &mut World,
+    method: &str,
+    params: Option<Value>,
+) -> Result<Value, RemoteError> {
+    world.init_resource::<ManifestRemote>();
+    world.resource_scope(|world, remote: Mut<ManifestRemote>| remote.handle(world, method, params))
+}
+
+/// A plugin which exposes the manifest `M` to [`handle_remote_request`].
+pub struct ManifestRemotePlugin<M: Manifest> {
+    name: Option<String>,
+    patch: Option<PatchItemFn>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestRemotePlugin<M> {
+    fn default() -> Self {
+        Self {
+            name: None,
+            patch: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> ManifestRemotePlugin<M> {
+    /// Sets the name that remote requests use to refer to this manifest.
+    ///
+    /// Defaults to the short name of the manifest type.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl<M: MutableManifest> ManifestRemotePlugin<M>
+where
+    M::Item: Reflect,
+{
+    /// Allows items in this manifest to be modified with [`PATCH_METHOD`].
+    #[must_use]
+    pub fn with_patching(mut self) -> Self {
+        self.patch = Some(patch_item::<M>);
+        self
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestRemotePlugin<M>
+where
+    M::Item: Reflect + GetTypeRegistration,
+{
+    fn build(&self, app: &mut App) {
+        let type_name = std::any::type_name::<M>();
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| get_short_name(type_name));
+
+        app.register_type::<M::Item>()
+            .init_resource::<ManifestRemote>()
+            .world
+            .resource_mut::<ManifestRemote>()
+            .manifests
+            .push(RemoteManifest {
+                name,
+                type_name,
+                item_count: |world| {
+                    world
+                        .get_resource::<M>()
+                        .map(|manifest| manifest.item_count())
+                },
+                get: get_item::<M>,
+                patch: self.patch,
+            });
+    }
+}
+
+fn serialize_item(item: &dyn Reflect, registry: &TypeRegistry) -> Result<Value, RemoteError> {
+    serde_json::to_value(TypedReflectSerializer::new(item, registry))
+        .map_err(|err| RemoteError::Serialization(err.to_string()))
+}
+
+fn get_item<M: Manifest>(world: &World, params: &ItemParams) -> Result<Value, RemoteError>
+where
+    M::Item: Reflect,
+{
+    let manifest = world
+        .get_resource::<M>()
+        .ok_or_else(|| RemoteError::NotLoaded(params.manifest.clone()))?;
+    let item = manifest
+        .get(params.id()?)
+        .ok_or_else(|| RemoteError::ItemNotFound(params.manifest.clone()))?;
+
+    let registry = world.resource::<AppTypeRegistry>().read();
+    serialize_item(item, &registry)
+}
+
+fn patch_item<M: MutableManifest>(
+    world: &mut World,
+    params: &ItemParams,
+) -> Result<Value, RemoteError>
+where
+    M::Item: Reflect,
+{
+    if world
+        .get_resource::<ManifestLock>()
+        .is_some_and(ManifestLock::is_locked)
+    {
+        return Err(RemoteError::Locked);
+    }
+    let Some(patch) = &params.patch else {
+        return Err(RemoteError::InvalidParams("missing `patch`".to_string()));
+    };
+
+    let id = params.id()?;
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+    let registration = registry.get(TypeId::of::<M::Item>()).ok_or_else(|| {
+        RemoteError::Serialization(format!(
+            "{} is not registered",
+            std::any::type_name::<M::Item>()
+        ))
+    })?;
+
+    let mut manifest = world
+        .get_resource_mut::<M>()
+        .ok_or_else(|| RemoteError::NotLoaded(params.manifest.clone()))?;
+    let item = manifest
+        .get_mut(id)
+        .ok_or_else(|| RemoteError::ItemNotFound(params.manifest.clone()))?;
+
+    // The patch is merged into the current value, so only the changed fields need to be sent.
+    let mut value = serialize_item(item, &registry)?;
+    let Value::Object(fields) = &mut value else {
+        return Err(RemoteError::InvalidParams(
+            "only items with named fields can be patched".to_string(),
+        ));
+    };
+    for (field, new_value) in patch {
+        fields.insert(field.clone(), new_value.clone());
+    }
+
+    let patched = TypedReflectDeserializer::new(registration, &registry)
+        .deserialize(value)
+        .map_err(|err| RemoteError::Serialization(err.to_string()))?;
+    item.apply(&*patched);
+
+    serialize_item(item, &registry)
+}
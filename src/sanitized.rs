@@ -0,0 +1,314 @@
+//! Validated colors and asset paths, for the fields of hand-authored raw items.
+//!
+//! Raw manifests are usually written by hand, and colors and asset paths are where typos slip in most easily:
+//! a hex code with a missing digit, a path with Windows separators, or a path which reaches outside of the asset folder.
+//! Using [`SrgbColor`] and [`AssetPathBuf`] in your raw items rejects these when the raw manifest is loaded,
+//! rather than leaving every manifest to check them during processing.
+//!
+//! ```ron
+//! (
+//!     name: "slime",
+//!     // Either a hex code, or an array of components from 0 to 1.
+//!     tint: "#3fbf5a",
+//!     // Backslashes are converted to forward slashes.
+//!     sprite: "sprites\\slime.png",
+//! )
+//! ```
+//!
+//! Both types convert into their Bevy equivalents in [`Manifest::from_raw_manifest`](crate::manifest::Manifest::from_raw_manifest).
+//! Converting an [`SrgbColor`] into a Bevy [`Color`](bevy::render::color::Color) requires the `color` feature.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use bevy::asset::AssetPath;
+use serde::{Deserialize, Serialize};
+
+/// Errors that can occur when constructing or parsing the types in this module.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SanitizeError {
+    /// An [`SrgbColor`] could not be parsed from a hex code.
+    #[error("{0:?} is not a valid hex color, such as \"#ff8800\" or \"#f80\"")]
+    InvalidHex(String),
+    /// A component of an [`SrgbColor`] was not a finite number from 0 to 1.
+    #[error("Color components must be from 0 to 1, but {0} was found")]
+    ComponentOutOfRange(f32),
+    /// An [`AssetPathBuf`] was empty.
+    #[error("Asset paths must not be empty")]
+    EmptyPath,
+    /// An [`AssetPathBuf`] was absolute, rather than relative to the asset folder.
+    #[error("{0:?} is an absolute path: asset paths must be relative to the asset folder")]
+    AbsolutePath(String),
+    /// An [`AssetPathBuf`] contained a `..` component.
+    #[error("{0:?} refers to a parent directory: asset paths must not contain \"..\"")]
+    ParentDirectory(String),
+}
+
+/// The forms an [`SrgbColor`] can be written in.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    Hex(String),
+    Components([f32; 3]),
+}
+
+/// An opaque color in the sRGB color space, with each component from 0 to 1.
+///
+/// Colors are written either as a hex code (`"#ff8800"`, `"#f80"` or `"ff8800"`),
+/// or as an array of red, green and blue components (`[1.0, 0.53, 0.0]`).
+/// They are always serialized as an array of components, so no precision is lost.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::sanitized::SrgbColor;
+///
+/// let orange: SrgbColor = "#ff8800".parse().unwrap();
+/// assert_eq!(orange.0[0], 1.0);
+/// assert_eq!(orange, "#F80".parse().unwrap());
+/// assert_eq!(orange.to_string(), "#ff8800");
+///
+/// assert!("#ff88".parse::<SrgbColor>().is_err());
+/// assert!(SrgbColor::new([1.5, 0.0, 0.0]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ColorRepr", into = "[f32; 3]")]
+pub struct SrgbColor(pub [f32; 3]);
+
+impl SrgbColor {
+    /// Creates a color from its red, green and blue components, which must each be from 0 to 1.
+    pub fn new(components: [f32; 3]) -> Result<Self, SanitizeError> {
+        match components
+            .into_iter()
+            .find(|component| !(0.0..=1.0).contains(component))
+        {
+            Some(component) => Err(SanitizeError::ComponentOutOfRange(component)),
+            None => Ok(SrgbColor(components)),
+        }
+    }
+
+    /// Creates a color from its red, green and blue components, from 0 to 255.
+    #[must_use]
+    pub fn from_u8(components: [u8; 3]) -> Self {
+        SrgbColor(components.map(|component| f32::from(component) / 255.0))
+    }
+
+    /// The red, green and blue components of this color, rounded to the nearest of 0 to 255.
+    #[must_use]
+    pub fn to_u8(&self) -> [u8; 3] {
+        self.0.map(|component| (component * 255.0).round() as u8)
+    }
+}
+
+impl FromStr for SrgbColor {
+    type Err = SanitizeError;
+
+    fn from_str(source: &str) -> Result<Self, SanitizeError> {
+        let invalid = || SanitizeError::InvalidHex(source.to_string());
+        let hex = source.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let digit =
+            |index: usize| u8::from_str_radix(&hex[index..=index], 16).map_err(|_| invalid());
+        let pair =
+            |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+        let components = match hex.len() {
+            // Each digit is repeated, so "#f80" is "#ff8800".
+            3 => [digit(0)? * 17, digit(1)? * 17, digit(2)? * 17],
+            6 => [pair(0)?, pair(2)?, pair(4)?],
+            _ => return Err(invalid()),
+        };
+
+        Ok(SrgbColor::from_u8(components))
+    }
+}
+
+impl Display for SrgbColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [red, green, blue] = self.to_u8();
+        write!(f, "#{red:02x}{green:02x}{blue:02x}")
+    }
+}
+
+impl TryFrom<ColorRepr> for SrgbColor {
+    type Error = SanitizeError;
+
+    fn try_from(repr: ColorRepr) -> Result<Self, SanitizeError> {
+        match repr {
+            ColorRepr::Hex(hex) => hex.parse(),
+            ColorRepr::Components(components) => SrgbColor::new(components),
+        }
+    }
+}
+
+impl From<SrgbColor> for [f32; 3] {
+    fn from(color: SrgbColor) -> Self {
+        color.0
+    }
+}
+
+#[cfg(feature = "color")]
+impl From<SrgbColor> for bevy::render::color::Color {
+    fn from(color: SrgbColor) -> Self {
+        let [red, green, blue] = color.0;
+        bevy::render::color::Color::rgb(red, green, blue)
+    }
+}
+
+/// A path to an asset, relative to the asset folder, which is checked and normalized when it is created.
+///
+/// Backslashes are converted to forward slashes, and empty and `.` components are removed,
+/// so `"./sprites\\slime.png"` becomes `"sprites/slime.png"`.
+/// Absolute paths and paths containing `..` are rejected, so raw manifests cannot refer to files outside of the asset folder.
+/// As with Bevy's [`AssetPath`], a path may start with an asset source (`"mods://slime.png"`)
+/// and end with a label (`"slime.gltf#Mesh0"`), which are kept as-is.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::sanitized::AssetPathBuf;
+///
+/// let path = AssetPathBuf::new("./sprites\\slime.png#Idle").unwrap();
+/// assert_eq!(path.as_str(), "sprites/slime.png#Idle");
+/// assert_eq!(path.path(), "sprites/slime.png");
+/// assert_eq!(path.label(), Some("Idle"));
+///
+/// assert!(AssetPathBuf::new("../secrets.txt").is_err());
+/// assert!(AssetPathBuf::new("/etc/passwd").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AssetPathBuf(String);
+
+impl AssetPathBuf {
+    /// Checks and normalizes the supplied `path`.
+    pub fn new(path: impl Into<String>) -> Result<Self, SanitizeError> {
+        let original: String = path.into();
+        let trimmed = original.trim();
+
+        let (source, rest) = match trimmed.split_once("://") {
+            Some((source, rest)) => (Some(source), rest),
+            None => (None, trimmed),
+        };
+        let (path, label) = match rest.split_once('#') {
+            Some((path, label)) => (path, Some(label)),
+            None => (rest, None),
+        };
+
+        let path = path.replace('\\', "/");
+        // Drive letters, such as "C:/", only appear in absolute Windows paths.
+        if path.starts_with('/')
+            || path
+                .split('/')
+                .next()
+                .is_some_and(|first| first.contains(':'))
+        {
+            return Err(SanitizeError::AbsolutePath(original));
+        }
+
+        let mut components = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => return Err(SanitizeError::ParentDirectory(original)),
+                component => components.push(component),
+            }
+        }
+        if components.is_empty() {
+            return Err(SanitizeError::EmptyPath);
+        }
+
+        let mut normalized = String::new();
+        if let Some(source) = source {
+            normalized.push_str(source);
+            normalized.push_str("://");
+        }
+        normalized.push_str(&components.join("/"));
+        if let Some(label) = label {
+            normalized.push('#');
+            normalized.push_str(label);
+        }
+
+        Ok(AssetPathBuf(normalized))
+    }
+
+    /// The full normalized path, including its source and label.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The asset source of this path, if one was given.
+    #[must_use]
+    pub fn source(&self) -> Option<&str> {
+        self.0.split_once("://").map(|(source, _)| source)
+    }
+
+    /// The path within the asset source, without the source or label.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        let rest = self.0.split_once("://").map_or(&*self.0, |(_, rest)| rest);
+        rest.split_once('#').map_or(rest, |(path, _)| path)
+    }
+
+    /// The label of the sub-asset this path refers to, if any.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.0.split_once('#').map(|(_, label)| label)
+    }
+
+    /// Converts this path into Bevy's [`AssetPath`], for use with the [`AssetServer`](bevy::asset::AssetServer).
+    #[must_use]
+    pub fn to_asset_path(&self) -> AssetPath<'static> {
+        AssetPath::from(self.0.clone())
+    }
+}
+
+impl AsRef<str> for AssetPathBuf {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for AssetPathBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for AssetPathBuf {
+    type Err = SanitizeError;
+
+    fn from_str(source: &str) -> Result<Self, SanitizeError> {
+        AssetPathBuf::new(source)
+    }
+}
+
+impl TryFrom<String> for AssetPathBuf {
+    type Error = SanitizeError;
+
+    fn try_from(source: String) -> Result<Self, SanitizeError> {
+        AssetPathBuf::new(source)
+    }
+}
+
+impl From<AssetPathBuf> for String {
+    fn from(path: AssetPathBuf) -> Self {
+        path.0
+    }
+}
+
+impl From<AssetPathBuf> for AssetPath<'static> {
+    fn from(path: AssetPathBuf) -> Self {
+        AssetPath::from(path.0)
+    }
+}
+
+impl From<&AssetPathBuf> for AssetPath<'static> {
+    fn from(path: &AssetPathBuf) -> Self {
+        path.to_asset_path()
+    }
+}
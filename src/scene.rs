@@ -0,0 +1,149 @@
+//! Hydrates entities in spawned Bevy scenes with data from manifests.
+//!
+//! Levels authored as scenes can refer to manifest content, rather than duplicating it.
+//! Scene entities identify their item either with an [`Id`] component, or by name with an [`ItemName`] component,
+//! which is easier to write by hand.
+//! Once the scene instance is ready, each such entity is given the item's [`ToBundle::Bundle`].
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestScenePlugin::<TileManifest>::default());
+//!
+//! fn spawn_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn(SceneBundle {
+//!         scene: asset_server.load("levels/meadow.scn.ron"),
+//!         ..default()
+//!     });
+//! }
+//! ```
+//!
+//! This module requires the `scenes` feature.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::hierarchy::{Children, HierarchyQueryExt};
+use bevy::reflect::{Reflect, TypePath};
+use bevy::scene::SceneInstanceReady;
+
+use crate::bundles::{insert_bundle, name_identified_entities, ToBundle};
+use crate::identifier::Id;
+use crate::manifest::Manifest;
+
+/// A component which identifies a manifest item of type `T` by its name.
+///
+/// When part of a spawned scene, this is replaced by the corresponding [`Id<T>`] during hydration.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ItemName<T: Send + Sync + TypePath> {
+    /// The name of the item, as used in the manifest file.
+    pub name: String,
+    #[reflect(ignore)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + TypePath> ItemName<T> {
+    /// Creates a new [`ItemName`] component.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The [`Id`] of the named item.
+    #[must_use]
+    pub fn id(&self) -> Id<T> {
+        Id::from_name(&self.name)
+    }
+}
+
+impl<T: Send + Sync + TypePath> Default for ItemName<T> {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+/// A plugin which hydrates entities in spawned scenes with the [`ToBundle::Bundle`] of the manifest `M`'s items.
+///
+/// This also registers [`Id<M::Item>`] and [`ItemName<M::Item>`] for reflection, so they can be used in scene files.
+pub struct ManifestScenePlugin<M: Manifest> {
+    insert_names: bool,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestScenePlugin<M> {
+    fn default() -> Self {
+        Self {
+            insert_names: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> ManifestScenePlugin<M> {
+    /// Sets whether hydrated scene entities should be labeled with a [`Name`](bevy::core::Name), via [`name_identified_entities`].
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn with_names(mut self, insert_names: bool) -> Self {
+        self.insert_names = insert_names;
+        self
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestScenePlugin<M>
+where
+    M::Item: ToBundle + TypePath,
+{
+    fn build(&self, app: &mut App) {
+        app.register_type::<Id<M::Item>>()
+            .register_type_data::<Id<M::Item>, ReflectComponent>()
+            .register_type::<ItemName<M::Item>>()
+            .add_systems(
+                PostUpdate,
+                hydrate_scene_entities::<M>.run_if(resource_exists::<M>),
+            );
+
+        if self.insert_names {
+            app.add_systems(PostUpdate, name_identified_entities::<M>);
+        }
+    }
+}
+
+/// Inserts the [`ToBundle::Bundle`] for every entity in a newly spawned scene instance that refers to an item in the manifest `M`.
+///
+/// [`ItemName`] components are replaced with the corresponding [`Id`].
+pub fn hydrate_scene_entities<M: Manifest>(
+    manifest: Res<M>,
+    mut ready_events: EventReader<SceneInstanceReady>,
+    children_query: Query<&Children>,
+    item_query: Query<(Option<&Id<M::Item>>, Option<&ItemName<M::Item>>)>,
+    mut commands: Commands,
+) where
+    M::Item: ToBundle + TypePath,
+{
+    for event in ready_events.read() {
+        for entity in children_query.iter_descendants(event.parent) {
+            let Ok((id, name)) = item_query.get(entity) else {
+                continue;
+            };
+
+            let id = match (id, name) {
+                (Some(&id), _) => id,
+                (None, Some(name)) => {
+                    let id = name.id();
+                    commands
+                        .entity(entity)
+                        .remove::<ItemName<M::Item>>()
+                        .insert(id);
+                    id
+                }
+                (None, None) => continue,
+            };
+
+            insert_bundle(&*manifest, entity, id, &mut commands);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Text search over the items in a manifest, for in-game encyclopedias, crafting search bars and editor palettes.
+//!
+//! Implement [`Searchable`] for the item type, returning the text that players should be able to search by,
+//! then register a [`SearchIndex`] for the manifest:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     manifest::{IterableManifest, Manifest, ManifestFormat},
+//!     search::{SearchIndex, Searchable},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item {
+//!     name: String,
+//!     description: String,
+//! }
+//!
+//! impl Searchable for Item {
+//!     fn search_text(&self) -> Vec<&str> {
+//!         vec![self.name.as_str(), self.description.as_str()]
+//!     }
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! impl IterableManifest for ItemManifest {
+//!     fn iter(&self) -> impl Iterator<Item = (Id<Item>, &Item)> {
+//!         self.items.iter().map(|(id, item)| (*id, item))
+//!     }
+//! }
+//!
+//! let items = ItemManifest {
+//!     items: [
+//!         ("Fire Sword", "A sword wreathed in fire."),
+//!         ("Ice Sword", "A sword of ice."),
+//!         ("Fireball", "Hurls a ball of flame."),
+//!     ]
+//!     .into_iter()
+//!     .map(|(name, description)| {
+//!         let item = Item { name: name.to_string(), description: description.to_string() };
+//!         (Id::from_name(name), item)
+//!     })
+//!     .collect(),
+//! };
+//! let index = SearchIndex::new(&items);
+//! let [fire_sword, ice_sword, fireball] = ["Fire Sword", "Ice Sword", "Fireball"].map(Id::from_name);
+//!
+//! // Query words match the start of words, and items mentioning them more often rank first.
+//! assert_eq!(index.search("fire"), [fire_sword, fireball]);
+//! // Every word in the query must match.
+//! assert_eq!(index.search("FI sw"), [fire_sword]);
+//! assert_eq!(index.search("sword ice"), [ice_sword]);
+//! assert!(index.search("frost").is_empty());
+//! assert!(index.search("").is_empty());
+//! ```
+//!
+//! In an app, register the index with [`RegisterDerived::register_derived`](crate::derived::RegisterDerived::register_derived)
+//! and read it as a resource: `app.register_derived::<SearchIndex<ItemManifest>, ItemManifest>()`.
+//!
+//! The index is built when the manifest is processed, and rebuilt whenever it changes.
+//! Text is split into lowercase words at anything other than letters and digits.
+//! Every word in the query must match the start of a word in the item's text, so partially-typed queries still find results.
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::derived::DerivedFromManifest;
+use crate::identifier::Id;
+use crate::manifest::IterableManifest;
+
+/// An item with text that can be searched by a [`SearchIndex`].
+pub trait Searchable {
+    /// The text of this item to search, such as its display name and description.
+    fn search_text(&self) -> Vec<&str>;
+}
+
+/// Splits `text` into lowercase words, at any character that is not a letter or digit.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// An index of the words in the [`Searchable`] text of each item in the manifest `M`.
+///
+/// Register this with [`RegisterDerived::register_derived`](crate::derived::RegisterDerived::register_derived)
+/// to build it when the manifest is processed.
+#[derive(Resource)]
+pub struct SearchIndex<M: IterableManifest> {
+    /// Each word, and the number of times it appears in the text of each item containing it.
+    words: BTreeMap<String, Vec<(Id<M::Item>, u32)>>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: IterableManifest> SearchIndex<M>
+where
+    M::Item: Searchable,
+{
+    /// Builds the index for every item in the `manifest`.
+    #[must_use]
+    pub fn new(manifest: &M) -> Self {
+        let mut counts: BTreeMap<String, HashMap<Id<M::Item>, u32>> = BTreeMap::new();
+        for (id, item) in manifest.iter() {
+            for text in item.search_text() {
+                for word in tokenize(text) {
+                    *counts.entry(word).or_default().entry(id).or_default() += 1;
+                }
+            }
+        }
+
+        let words = counts
+            .into_iter()
+            .map(|(word, ids)| {
+                let mut ids: Vec<_> = ids.into_iter().collect();
+                ids.sort();
+                (word, ids)
+            })
+            .collect();
+
+        Self {
+            words,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: IterableManifest> SearchIndex<M> {
+    /// Returns the items which match every word in the `query`, from the most to the least relevant.
+    ///
+    /// Each word in the query matches any word in the item's text that starts with it.
+    /// Items are ranked by how often the query words appear in their text, with ties broken by [`Id`].
+    /// An empty query matches nothing.
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<Id<M::Item>> {
+        let mut scores: Option<HashMap<Id<M::Item>, u32>> = None;
+        for word in tokenize(query) {
+            let mut matches: HashMap<Id<M::Item>, u32> = HashMap::default();
+            for (_, ids) in self
+                .words
+                .range(word.clone()..)
+                .take_while(|(indexed, _)| indexed.starts_with(&word))
+            {
+                for (id, count) in ids {
+                    *matches.entry(*id).or_default() += count;
+                }
+            }
+
+            // Only keep items which matched every word so far.
+            scores = Some(match scores {
+                None => matches,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(id, score)| matches.get(&id).map(|count| (id, score + count)))
+                    .collect(),
+            });
+        }
+
+        let mut results: Vec<_> = scores.unwrap_or_default().into_iter().collect();
+        results.sort_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then_with(|| a.cmp(b)));
+        results.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// The number of distinct words in the index.
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+}
+
+impl<M: IterableManifest> std::fmt::Debug for SearchIndex<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("words", &self.words.len())
+            .finish()
+    }
+}
+
+impl<M: IterableManifest> DerivedFromManifest<M> for SearchIndex<M>
+where
+    M::Item: Searchable,
+{
+    fn derive(manifest: &M, _world: &World) -> Self {
+        Self::new(manifest)
+    }
+}
@@ -0,0 +1,191 @@
+//! An [`Arc`]-backed storage strategy for manifest items, which can be shared cheaply between copies.
+//!
+//! Rollback networking, save states and multi-world setups (such as a client prediction world alongside an authoritative one)
+//! all need their own copy of the manifest data, but rarely change it.
+//! Storing the items in a [`SharedManifest`] makes cloning the manifest cost one reference count per item,
+//! no matter how large the items are: unchanged items are shared between every copy.
+//! Modifying an item through [`SharedManifest::make_mut`] only copies that item, and only if it is shared.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::identifier::{Id, IdMap};
+
+/// A storage container for manifest items, where each item is stored behind an [`Arc`].
+///
+/// Store this in your [`Manifest`](crate::manifest::Manifest) type in place of an `IdMap<Item, Item>`,
+/// and forward [`Manifest::get`](crate::manifest::Manifest::get) to [`SharedManifest::get`].
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::{identifier::Id, shared_manifest::SharedManifest};
+/// use std::sync::Arc;
+///
+/// #[derive(Clone)]
+/// struct Level {
+///     tiles: Vec<u8>,
+/// }
+///
+/// let mut levels = SharedManifest::default();
+/// levels.insert(Id::from_name("castle"), Level { tiles: vec![0; 4096] });
+///
+/// // Cloning shares every item, rather than copying the tiles.
+/// let mut prediction = levels.clone();
+/// let castle = Id::from_name("castle");
+/// assert!(Arc::ptr_eq(
+///     &levels.get_shared(castle).unwrap(),
+///     &prediction.get_shared(castle).unwrap()
+/// ));
+///
+/// // Modifying one copy leaves the other untouched.
+/// prediction.make_mut(castle).unwrap().tiles[0] = 1;
+/// assert_eq!(levels.get(castle).unwrap().tiles[0], 0);
+/// assert_eq!(prediction.get(castle).unwrap().tiles[0], 1);
+/// ```
+pub struct SharedManifest<T> {
+    items: IdMap<T, Arc<T>>,
+}
+
+impl<T> SharedManifest<T> {
+    /// Creates a new, empty [`SharedManifest`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty [`SharedManifest`] with space for at least `capacity` items.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        SharedManifest {
+            items: IdMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    /// Inserts an item with the given [`Id`], returning the item it replaced, if any.
+    pub fn insert(&mut self, id: Id<T>, item: T) -> Option<Arc<T>> {
+        self.insert_shared(id, Arc::new(item))
+    }
+
+    /// Inserts an item that is already shared, such as one taken from another [`SharedManifest`].
+    ///
+    /// Returns the item it replaced, if any.
+    pub fn insert_shared(&mut self, id: Id<T>, item: Arc<T>) -> Option<Arc<T>> {
+        self.items.insert(id, item)
+    }
+
+    /// Removes the item with the given [`Id`], returning it if it was found.
+    pub fn remove(&mut self, id: Id<T>) -> Option<Arc<T>> {
+        self.items.remove(&id)
+    }
+
+    /// Gets an item by its [`Id`].
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.items.get(&id).map(Arc::as_ref)
+    }
+
+    /// Gets a shared handle to an item by its [`Id`], which remains valid even if the item is later replaced or removed.
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn get_shared(&self, id: Id<T>) -> Option<Arc<T>> {
+        self.items.get(&id).cloned()
+    }
+
+    /// Returns true if an item with the given [`Id`] is stored.
+    #[must_use]
+    pub fn contains(&self, id: Id<T>) -> bool {
+        self.items.contains_key(&id)
+    }
+
+    /// Returns true if both manifests store the same allocation for the item with the given [`Id`].
+    ///
+    /// This is a cheap way to check whether an item has been modified in either copy since they were cloned.
+    #[must_use]
+    pub fn shares_item(&self, other: &Self, id: Id<T>) -> bool {
+        match (self.items.get(&id), other.items.get(&id)) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of stored items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over all stored items and their [`Id`]s, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.items.iter().map(|(id, item)| (*id, item.as_ref()))
+    }
+
+    /// Iterates over shared handles to all stored items and their [`Id`]s, in arbitrary order.
+    pub fn iter_shared(&self) -> impl Iterator<Item = (Id<T>, &Arc<T>)> {
+        self.items.iter().map(|(id, item)| (*id, item))
+    }
+}
+
+impl<T: Clone> SharedManifest<T> {
+    /// Gets a mutable reference to an item by its [`Id`].
+    ///
+    /// If the item is shared with another copy of the manifest (or an outstanding [`SharedManifest::get_shared`] handle),
+    /// it is cloned first, so the other copies are unaffected.
+    ///
+    /// Returns [`None`] if no item with the given ID is found.
+    #[must_use]
+    pub fn make_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        self.items.get_mut(&id).map(Arc::make_mut)
+    }
+}
+
+impl<T> Default for SharedManifest<T> {
+    fn default() -> Self {
+        SharedManifest {
+            items: IdMap::default(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for SharedManifest<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Cloning a [`SharedManifest`] shares every item with the original, rather than copying them.
+impl<T> Clone for SharedManifest<T> {
+    fn clone(&self) -> Self {
+        SharedManifest {
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Id<T>, T)> for SharedManifest<T> {
+    fn from_iter<I: IntoIterator<Item = (Id<T>, T)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut manifest = SharedManifest::with_capacity(iter.size_hint().0);
+        for (id, item) in iter {
+            manifest.insert(id, item);
+        }
+        manifest
+    }
+}
+
+impl<T> Extend<(Id<T>, T)> for SharedManifest<T> {
+    fn extend<I: IntoIterator<Item = (Id<T>, T)>>(&mut self, iter: I) {
+        for (id, item) in iter {
+            self.insert(id, item);
+        }
+    }
+}
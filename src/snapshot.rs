@@ -0,0 +1,348 @@
+//! Saving and restoring the contents of manifests, for save states, rollback and "revert changes" buttons.
+//!
+//! Any manifest which is both [`IterableManifest`] and [`MutableManifest`], and whose items are [`Clone`],
+//! implements [`SnapshotManifest`]:
+//!
+//! ```rust,ignore
+//! let snapshot = item_manifest.snapshot();
+//! item_manifest.remove(&SWORD)?;
+//! item_manifest.restore(&snapshot)?;
+//! assert!(item_manifest.get(SWORD).is_some());
+//! ```
+//!
+//! To save every manifest at once, add a [`ManifestSnapshotPlugin`] for each manifest,
+//! then use [`snapshot_all_manifests`] and [`restore_all_manifests`].
+//!
+//! Frozen manifests cannot be restored, as they reject modification: see [`Manifest::freeze`].
+
+use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+use thiserror::Error;
+
+use crate::identifier::Id;
+use crate::manifest::{IterableManifest, Manifest, ManifestModificationError, MutableManifest};
+use crate::manifest_lock::ManifestLock;
+
+/// A copy of every item in the manifest `M`, as returned by [`SnapshotManifest::snapshot`].
+pub struct ManifestSnapshot<M: Manifest> {
+    /// Sorted by [`Id`], so items can be found with a binary search.
+    items: Vec<(Id<M::Item>, M::Item)>,
+}
+
+impl<M: Manifest> ManifestSnapshot<M> {
+    /// Returns the item with the given `id`, if it was in the manifest when the snapshot was taken.
+    #[must_use]
+    pub fn get(&self, id: Id<M::Item>) -> Option<&M::Item> {
+        self.items
+            .binary_search_by_key(&id, |(item_id, _)| *item_id)
+            .ok()
+            .map(|index| &self.items[index].1)
+    }
+
+    /// Iterates over every item in the snapshot, in order of [`Id`].
+    pub fn iter(&self) -> impl Iterator<Item = (Id<M::Item>, &M::Item)> {
+        self.items.iter().map(|(id, item)| (*id, item))
+    }
+
+    /// The number of items in the snapshot.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the snapshot has no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<M: Manifest> Clone for ManifestSnapshot<M>
+where
+    M::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestSnapshot<M>
+where
+    M::Item: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestSnapshot")
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+/// A manifest whose contents can be saved and restored.
+///
+/// This is implemented automatically for every manifest that supports it.
+pub trait SnapshotManifest: IterableManifest + MutableManifest
+where
+    Self::Item: Clone,
+{
+    /// Copies every item in the manifest.
+    #[must_use]
+    fn snapshot(&self) -> ManifestSnapshot<Self> {
+        let mut items: Vec<(Id<Self::Item>, Self::Item)> =
+            self.iter().map(|(id, item)| (id, item.clone())).collect();
+        items.sort_unstable_by_key(|(id, _)| *id);
+        ManifestSnapshot { items }
+    }
+
+    /// Returns the manifest to the state recorded in the `snapshot`.
+    ///
+    /// Items added since the snapshot are removed, and every item in the snapshot is written back.
+    /// If an error occurs, the manifest may be partially restored.
+    fn restore(
+        &mut self,
+        snapshot: &ManifestSnapshot<Self>,
+    ) -> Result<(), ManifestModificationError<Self>> {
+        let added: Vec<Id<Self::Item>> = self
+            .ids()
+            .filter(|id| snapshot.get(*id).is_none())
+            .collect();
+        for id in added {
+            self.remove(&id)?;
+        }
+
+        for (id, item) in snapshot.iter() {
+            self.upsert(id, item.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<M: IterableManifest + MutableManifest> SnapshotManifest for M where M::Item: Clone {}
+
+/// An error returned by [`restore_all_manifests`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Could not restore the manifest {type_name}: {message}")]
+pub struct SnapshotRestoreError {
+    /// The name of the manifest type that could not be restored.
+    pub type_name: &'static str,
+    /// Why the manifest could not be restored.
+    pub message: String,
+}
+
+/// The snapshot and restore operations for a single manifest type, as registered by a [`ManifestSnapshotPlugin`].
+struct SnapshotEntry {
+    type_id: TypeId,
+    type_name: &'static str,
+    snapshot: fn(&World) -> Option<Box<dyn Any + Send + Sync>>,
+    restore: fn(&mut World, &(dyn Any + Send + Sync)) -> Result<(), String>,
+}
+
+/// The manifests saved by [`snapshot_all_manifests`].
+///
+/// Each manifest is added by its [`ManifestSnapshotPlugin`].
+#[derive(Resource, Default)]
+pub struct ManifestSnapshotRegistry {
+    entries: Vec<SnapshotEntry>,
+}
+
+impl ManifestSnapshotRegistry {
+    /// Returns the names of the registered manifest types, in the order they were added.
+    pub fn type_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.type_name)
+    }
+}
+
+impl std::fmt::Debug for ManifestSnapshotRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestSnapshotRegistry")
+            .field("type_names", &self.type_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A snapshot of every manifest registered with a [`ManifestSnapshotPlugin`], as returned by [`snapshot_all_manifests`].
+///
+/// Manifests which had not been loaded when the snapshot was taken are not included.
+#[derive(Default)]
+pub struct WorldManifestSnapshot {
+    snapshots: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl WorldManifestSnapshot {
+    /// Returns the snapshot of the manifest `M`, if it was included.
+    #[must_use]
+    pub fn get<M: Manifest>(&self) -> Option<&ManifestSnapshot<M>> {
+        self.snapshots
+            .get(&TypeId::of::<M>())
+            .and_then(|snapshot| snapshot.downcast_ref())
+    }
+
+    /// The number of manifests included in the snapshot.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns true if no manifests were included in the snapshot.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+impl std::fmt::Debug for WorldManifestSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldManifestSnapshot")
+            .field("manifests", &self.snapshots.len())
+            .finish()
+    }
+}
+
+/// Takes a snapshot of every loaded manifest registered with a [`ManifestSnapshotPlugin`].
+#[must_use]
+pub fn snapshot_all_manifests(world: &World) -> WorldManifestSnapshot {
+    let Some(registry) = world.get_resource::<ManifestSnapshotRegistry>() else {
+        return WorldManifestSnapshot::default();
+    };
+
+    let snapshots = registry
+        .entries
+        .iter()
+        .filter_map(|entry| Some((entry.type_id, (entry.snapshot)(world)?)))
+        .collect();
+    WorldManifestSnapshot { snapshots }
+}
+
+/// Restores every manifest included in the `snapshot`.
+///
+/// Manifests which were not included in the snapshot are left unchanged.
+/// Restoration stops at the first manifest which cannot be restored, returning an error.
+///
+/// # Example
+///
+/// ```
+/// # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     identifier::Id,
+///     manifest::{Manifest, MutableManifest},
+///     snapshot::{restore_all_manifests, snapshot_all_manifests, ManifestSnapshotPlugin},
+/// };
+///
+/// let sword = Id::<Item>::from_name("sword");
+/// let shield = Id::<Item>::from_name("shield");
+///
+/// let mut app = App::new();
+/// app.add_plugins(ManifestSnapshotPlugin::<ItemManifest>::default())
+///     .insert_resource(ItemManifest::from_items([
+///         Item::new("sword", 10),
+///         Item::new("shield", 2),
+///     ]));
+///
+/// let snapshot = snapshot_all_manifests(&app.world);
+/// assert_eq!(snapshot.get::<ItemManifest>().unwrap().len(), 2);
+///
+/// let mut manifest = app.world.resource_mut::<ItemManifest>();
+/// manifest.get_mut(sword).unwrap().value = 50;
+/// manifest.remove(&shield).unwrap();
+/// manifest.insert(Item::new("axe", 12)).unwrap();
+///
+/// restore_all_manifests(&mut app.world, &snapshot).unwrap();
+///
+/// // Edits are reverted, removed items come back, and added items are removed again.
+/// let manifest = app.world.resource::<ItemManifest>();
+/// assert_eq!(manifest.get(sword).unwrap().value, 10);
+/// assert_eq!(manifest.get(shield).unwrap().value, 2);
+/// assert!(manifest.get(Id::from_name("axe")).is_none());
+/// assert_eq!(manifest.items.len(), 2);
+/// ```
+pub fn restore_all_manifests(
+    world: &mut World,
+    snapshot: &WorldManifestSnapshot,
+) -> Result<(), SnapshotRestoreError> {
+    world.init_resource::<ManifestSnapshotRegistry>();
+    world.resource_scope(|world, registry: Mut<ManifestSnapshotRegistry>| {
+        for entry in &registry.entries {
+            let Some(manifest_snapshot) = snapshot.snapshots.get(&entry.type_id) else {
+                continue;
+            };
+
+            (entry.restore)(world, manifest_snapshot.as_ref()).map_err(|message| {
+                SnapshotRestoreError {
+                    type_name: entry.type_name,
+                    message,
+                }
+            })?;
+        }
+
+        Ok(())
+    })
+}
+
+/// A plugin which includes the manifest `M` in [`snapshot_all_manifests`] and [`restore_all_manifests`].
+pub struct ManifestSnapshotPlugin<M: SnapshotManifest>
+where
+    M::Item: Clone,
+{
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: SnapshotManifest> Default for ManifestSnapshotPlugin<M>
+where
+    M::Item: Clone,
+{
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: SnapshotManifest> Plugin for ManifestSnapshotPlugin<M>
+where
+    M::Item: Clone + Send + Sync,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ManifestSnapshotRegistry>()
+            .world
+            .resource_mut::<ManifestSnapshotRegistry>()
+            .entries
+            .push(SnapshotEntry {
+                type_id: TypeId::of::<M>(),
+                type_name: std::any::type_name::<M>(),
+                snapshot: |world| {
+                    let manifest = world.get_resource::<M>()?;
+                    Some(Box::new(manifest.snapshot()))
+                },
+                restore: restore_erased::<M>,
+            });
+    }
+}
+
+fn restore_erased<M: SnapshotManifest>(
+    world: &mut World,
+    snapshot: &(dyn Any + Send + Sync),
+) -> Result<(), String>
+where
+    M::Item: Clone + Send + Sync,
+{
+    let Some(snapshot) = snapshot.downcast_ref::<ManifestSnapshot<M>>() else {
+        return Err("the snapshot has the wrong type".to_string());
+    };
+    if let Some(lock) = world.get_resource::<ManifestLock>() {
+        lock.check::<M>().map_err(|error| error.to_string())?;
+    }
+    let Some(mut manifest) = world.get_resource_mut::<M>() else {
+        return Err("the manifest resource does not exist".to_string());
+    };
+
+    manifest
+        .restore(snapshot)
+        .map_err(|error| error.to_string())
+}
@@ -0,0 +1,181 @@
+//! Precomputed indices for answering range queries over manifest items.
+//!
+//! [`IterableManifest::filter`] visits every item, which is fine for occasional lookups.
+//! Queries like "every item worth between 10 and 50 gold" that run every frame are better served by a [`SortedIndex`]:
+//! the items are sorted by a key once, and each query is then a binary search.
+//!
+//! ```
+//! use std::ops::Bound;
+//!
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     manifest::{IterableManifest, Manifest, ManifestFormat},
+//!     sorted_index::SortedIndex,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item {
+//!     name: String,
+//!     value: u32,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! impl IterableManifest for ItemManifest {
+//!     fn iter(&self) -> impl Iterator<Item = (Id<Item>, &Item)> {
+//!         self.items.iter().map(|(id, item)| (*id, item))
+//!     }
+//! }
+//!
+//! let items = ItemManifest {
+//!     items: [("stick", 1), ("dagger", 10), ("sword", 50), ("crown", 500)]
+//!         .into_iter()
+//!         .map(|(name, value)| (Id::from_name(name), Item { name: name.to_string(), value }))
+//!         .collect(),
+//! };
+//! let index = SortedIndex::new(&items, |item: &Item| item.value);
+//! let names = |range: (Bound<u32>, Bound<u32>)| -> Vec<String> {
+//!     index.range(range).map(|id| items.get(id).unwrap().name.clone()).collect()
+//! };
+//!
+//! // Both ends of the range are respected, whether they are included, excluded or unbounded.
+//! assert_eq!(names((Bound::Included(10), Bound::Included(50))), ["dagger", "sword"]);
+//! assert_eq!(names((Bound::Excluded(10), Bound::Excluded(500))), ["sword"]);
+//! assert_eq!(names((Bound::Unbounded, Bound::Excluded(50))), ["stick", "dagger"]);
+//! assert_eq!(names((Bound::Included(50), Bound::Unbounded)), ["sword", "crown"]);
+//!
+//! // Empty and inverted ranges yield no items, rather than panicking.
+//! assert!(names((Bound::Included(10), Bound::Excluded(10))).is_empty());
+//! assert!(names((Bound::Included(500), Bound::Included(1))).is_empty());
+//! ```
+//!
+//! The index is not updated automatically: call [`SortedIndex::rebuild`] whenever the manifest changes.
+
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+use crate::identifier::Id;
+use crate::manifest::{IterableManifest, Manifest};
+
+type KeyFn<M, K> = Arc<dyn Fn(&<M as Manifest>::Item) -> K + Send + Sync>;
+
+/// The [`Id`]s of the items in the manifest `M`, sorted by a key of type `K`.
+///
+/// Items with equal keys are ordered by their [`Id`], so the index is deterministic.
+///
+/// See the [module docs](crate::sorted_index) for more information.
+pub struct SortedIndex<M: IterableManifest, K: Ord> {
+    key: KeyFn<M, K>,
+    entries: Vec<(K, Id<M::Item>)>,
+}
+
+impl<M: IterableManifest, K: Ord> SortedIndex<M, K> {
+    /// Builds an index of the items in the `manifest`, sorted by the value returned by `key`.
+    #[must_use]
+    pub fn new(manifest: &M, key: impl Fn(&M::Item) -> K + Send + Sync + 'static) -> Self {
+        let mut index = Self {
+            key: Arc::new(key),
+            entries: Vec::new(),
+        };
+        index.rebuild(manifest);
+        index
+    }
+
+    /// Rebuilds the index from the supplied `manifest`, using the same key function.
+    pub fn rebuild(&mut self, manifest: &M) {
+        self.entries.clear();
+        self.entries
+            .extend(manifest.iter().map(|(id, item)| ((self.key)(item), id)));
+        self.entries.sort_unstable();
+    }
+
+    /// Iterates over the [`Id`]s of the items whose keys are within the `range`, in ascending order of key.
+    pub fn range(&self, range: impl RangeBounds<K>) -> impl Iterator<Item = Id<M::Item>> + '_ {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.entries.partition_point(|(key, _)| key < start),
+            Bound::Excluded(start) => self.entries.partition_point(|(key, _)| key <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.entries.partition_point(|(key, _)| key <= end),
+            Bound::Excluded(end) => self.entries.partition_point(|(key, _)| key < end),
+            Bound::Unbounded => self.entries.len(),
+        };
+
+        // An empty range, such as `5..5`, may have its start after its end.
+        self.entries[start..end.max(start)]
+            .iter()
+            .map(|(_, id)| *id)
+    }
+
+    /// Iterates over the keys and [`Id`]s of every indexed item, in ascending order of key.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Id<M::Item>)> {
+        self.entries.iter().map(|(key, id)| (key, *id))
+    }
+
+    /// Returns the item with the smallest key, if any.
+    #[must_use]
+    pub fn first(&self) -> Option<(&K, Id<M::Item>)> {
+        self.entries.first().map(|(key, id)| (key, *id))
+    }
+
+    /// Returns the item with the largest key, if any.
+    #[must_use]
+    pub fn last(&self) -> Option<(&K, Id<M::Item>)> {
+        self.entries.last().map(|(key, id)| (key, *id))
+    }
+
+    /// Returns the number of indexed items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no items are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<M: IterableManifest, K: Ord + Clone> Clone for SortedIndex<M, K> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<M: IterableManifest, K: Ord + std::fmt::Debug> std::fmt::Debug for SortedIndex<M, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SortedIndex")
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
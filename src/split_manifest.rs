@@ -0,0 +1,336 @@
+//! Support for a single file containing the raw data for several manifests.
+//!
+//! Tightly-coupled data is often easier to author in one place.
+//! For example, a `world.ron` file might contain the tiles, props and spawners for a level:
+//!
+//! ```ron
+//! (
+//!     tiles: [ /* ... */ ],
+//!     props: [ /* ... */ ],
+//!     spawners: [ /* ... */ ],
+//! )
+//! ```
+//!
+//! The file is loaded as a single [`SplitRawManifest`], which is then split into the raw manifests of each section.
+//! Each section is processed into its own [`Manifest`] resource, exactly as if it had been loaded from its own file.
+//!
+//! ```rust
+//! # #[cfg(feature = "ron")]
+//! # fn main() {
+//! # use bevy::prelude::*;
+//! # use leafwing_manifest::{
+//! #     asset_state::SimpleAssetState,
+//! #     identifier::Id,
+//! #     manifest::{Manifest, ManifestFormat},
+//! #     plugin::RegisterManifest,
+//! #     split_manifest::{ManifestSections, SplitRawManifest},
+//! #     test_utils::{manifest_test_app, ManifestTestApp},
+//! # };
+//! # use serde::Deserialize;
+//! #
+//! # #[derive(Deserialize)]
+//! # struct Tile {
+//! #     name: String,
+//! # }
+//! #
+//! # #[derive(Deserialize)]
+//! # struct Prop {
+//! #     name: String,
+//! # }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct TileManifest {
+//! #     tiles: Vec<Tile>,
+//! # }
+//! #
+//! # #[derive(Resource, Asset, TypePath, Deserialize)]
+//! # struct PropManifest {
+//! #     props: Vec<Prop>,
+//! # }
+//! #
+//! # impl Manifest for TileManifest {
+//! #     type Item = Tile;
+//! #     type RawItem = Tile;
+//! #     type RawManifest = TileManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//! #
+//! #     fn get(&self, id: Id<Tile>) -> Option<&Tile> {
+//! #         self.tiles.iter().find(|tile| Id::from_name(&tile.name) == id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(raw_manifest: Self, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//! #
+//! # impl Manifest for PropManifest {
+//! #     type Item = Prop;
+//! #     type RawItem = Prop;
+//! #     type RawManifest = PropManifest;
+//! #     type ConversionError = std::convert::Infallible;
+//! #
+//! #     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//! #
+//! #     fn get(&self, id: Id<Prop>) -> Option<&Prop> {
+//! #         self.props.iter().find(|prop| Id::from_name(&prop.name) == id)
+//! #     }
+//! #
+//! #     fn from_raw_manifest(raw_manifest: Self, _world: &mut World) -> Result<Self, Self::ConversionError> {
+//! #         Ok(raw_manifest)
+//! #     }
+//! # }
+//! #[derive(Asset, TypePath, Deserialize)]
+//! struct RawWorld {
+//!     tiles: Vec<Tile>,
+//!     props: Vec<Prop>,
+//! }
+//!
+//! impl SplitRawManifest for RawWorld {
+//!     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//!
+//!     fn split(self, sections: &mut ManifestSections) {
+//!         sections.insert::<TileManifest>(TileManifest { tiles: self.tiles });
+//!         sections.insert::<PropManifest>(PropManifest { props: self.props });
+//!     }
+//! }
+//!
+//! let mut app = manifest_test_app();
+//! app.register_split_manifest::<RawWorld>("world.ron")
+//!     .register_manifest_section::<RawWorld, TileManifest>()
+//!     .register_manifest_section::<RawWorld, PropManifest>();
+//!
+//! assert_eq!(app.update_until_loaded::<SimpleAssetState>(), SimpleAssetState::Ready);
+//! assert!(app.manifest::<TileManifest>().get_by_name("water").is_some());
+//! assert!(app.manifest::<PropManifest>().get_by_name("barrel").is_some());
+//! # }
+//! # #[cfg(not(feature = "ron"))]
+//! # fn main() {}
+//! ```
+
+use std::any::{type_name, TypeId};
+use std::path::PathBuf;
+
+use bevy::app::App;
+use bevy::asset::{Asset, AssetApp, AssetPath, AssetServer, Assets, Handle, LoadState};
+use bevy::ecs::prelude::*;
+use bevy::log::error;
+use bevy::utils::Instant;
+use serde::de::DeserializeOwned;
+
+use crate::lifecycle::ManifestLifecycleStage;
+use crate::loader::{Compression, ManifestLoader};
+use crate::manifest::{Manifest, ManifestFormat};
+use crate::plugin::{
+    manifest_schedules, prepare_raw_manifest, process_manifest, raw_manifest_unprocessed,
+    ManifestProcessingSet, ProcessManifestSet, RawManifestOptions, RawManifestTracker,
+};
+use crate::provenance::{ItemSource, ManifestProvenance};
+
+/// A raw asset which contains the raw manifests for several different [`Manifest`] types.
+///
+/// Register this with [`RegisterManifest::register_split_manifest`](crate::plugin::RegisterManifest::register_split_manifest),
+/// and each of its sections with [`RegisterManifest::register_manifest_section`](crate::plugin::RegisterManifest::register_manifest_section).
+pub trait SplitRawManifest: Asset + DeserializeOwned {
+    /// The format of the file on disk.
+    ///
+    /// This is always loaded using the built-in [`ManifestLoader`], so [`ManifestFormat::Custom`] is not supported.
+    const FORMAT: ManifestFormat;
+
+    /// Divides the loaded asset into the raw manifests of each section.
+    ///
+    /// Every registered section must be inserted into `sections`,
+    /// otherwise its manifest will fail to load.
+    fn split(self, sections: &mut ManifestSections);
+}
+
+/// Collects the raw manifests produced by [`SplitRawManifest::split`].
+#[derive(Default)]
+pub struct ManifestSections {
+    sections: Vec<Box<dyn FnOnce(&mut World) + Send + Sync>>,
+}
+
+impl ManifestSections {
+    /// Supplies the raw manifest for the manifest `M`.
+    ///
+    /// `M` must have been registered as a section of this split manifest.
+    pub fn insert<M: Manifest>(&mut self, raw_manifest: M::RawManifest) {
+        self.sections.push(Box::new(move |world: &mut World| {
+            let Some(status) = world.resource::<RawManifestTracker>().status::<M>() else {
+                error!(
+                    "The manifest {} was not registered as a section, so its raw manifest was discarded.",
+                    type_name::<M>()
+                );
+                return;
+            };
+            let id = status.handle.id().typed::<M::RawManifest>();
+
+            world
+                .resource_mut::<Assets<M::RawManifest>>()
+                .insert(id, raw_manifest);
+
+            let mut raw_manifest_tracker = world.resource_mut::<RawManifestTracker>();
+            if let Some(status) = raw_manifest_tracker.status_mut::<M>() {
+                status.load_state = LoadState::Loaded;
+                status.load_count += 1;
+                status.load_finished_at = Some(Instant::now());
+            }
+            raw_manifest_tracker.record_lifecycle::<M>(ManifestLifecycleStage::LoadCompleted, None);
+        }));
+    }
+
+    /// The number of sections that have been supplied.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Returns true if no sections have been supplied.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+}
+
+/// Tracks the loading of the split manifest `S`, and the manifests registered as its sections.
+#[derive(Resource)]
+pub struct SplitManifest<S: SplitRawManifest> {
+    /// A strong handle to the split raw manifest.
+    pub handle: Handle<S>,
+    /// The path that the split raw manifest is loaded from.
+    pub path: PathBuf,
+    /// The [`TypeId`]s of the manifests registered as sections.
+    pub sections: Vec<TypeId>,
+}
+
+/// Starts loading the split raw manifest `S`, and adds the system that splits it once loaded.
+pub(crate) fn register_split_manifest<S: SplitRawManifest>(
+    app: &mut App,
+    path: impl Into<PathBuf>,
+) {
+    let path: PathBuf = path.into();
+    let schedules = manifest_schedules(app);
+
+    app.init_asset::<S>()
+        .register_asset_loader(ManifestLoader::<S>::new(
+            S::FORMAT,
+            Compression::from_path(&path),
+        ))
+        .add_systems(
+            schedules.loading,
+            split_raw_manifest::<S>.run_if(resource_exists::<SplitManifest<S>>),
+        );
+
+    let handle = app
+        .world
+        .resource::<AssetServer>()
+        .load::<S>(AssetPath::from(path.to_string_lossy().into_owned()));
+    app.world.insert_resource(SplitManifest::<S> {
+        handle,
+        path,
+        sections: Vec::new(),
+    });
+}
+
+/// Registers the manifest `M` as a section of the split raw manifest `S`.
+///
+/// # Panics
+///
+/// Panics if `S` has not been registered.
+pub(crate) fn register_manifest_section<S: SplitRawManifest, M: Manifest>(app: &mut App) {
+    prepare_raw_manifest::<M>(app, Compression::None, RawManifestOptions::default());
+    let schedules = manifest_schedules(app);
+    app.add_systems(
+        schedules.processing,
+        process_manifest::<M>
+            .in_set(ProcessManifestSet)
+            .in_set(ManifestProcessingSet::<M>::default())
+            .run_if(not(resource_exists::<M>).and_then(raw_manifest_unprocessed::<M>)),
+    );
+
+    let raw_manifest_handle = app
+        .world
+        .resource::<Assets<M::RawManifest>>()
+        .reserve_handle();
+
+    let Some(mut split_manifest) = app.world.get_resource_mut::<SplitManifest<S>>() else {
+        panic!(
+            "The split manifest {} must be registered before its section {}.",
+            type_name::<S>(),
+            type_name::<M>()
+        );
+    };
+    split_manifest.sections.push(TypeId::of::<M>());
+    let path = split_manifest.path.clone();
+
+    app.insert_resource(ManifestProvenance::<M>::from_file(ItemSource::new(&path)));
+
+    app.world
+        .resource_mut::<RawManifestTracker>()
+        .register_pending::<M>(path, raw_manifest_handle);
+}
+
+/// Once the split raw manifest `S` has loaded, splits it into the raw manifests of each section.
+///
+/// If `S` fails to load, or does not supply a registered section, the affected sections are marked as [`LoadState::Failed`].
+pub fn split_raw_manifest<S: SplitRawManifest>(world: &mut World) {
+    let split_manifest = world.resource::<SplitManifest<S>>();
+    let load_state = world
+        .resource::<AssetServer>()
+        .get_load_state(&split_manifest.handle);
+
+    match load_state {
+        Some(LoadState::Loaded) => (),
+        Some(LoadState::Failed) => {
+            error!("Failed to load the split manifest {}.", type_name::<S>());
+            fail_pending_sections::<S>(world);
+            return;
+        }
+        _ => return,
+    }
+
+    let handle = split_manifest.handle.clone_weak();
+    let Some(raw) = world.resource_mut::<Assets<S>>().remove(&handle) else {
+        return;
+    };
+
+    let mut sections = ManifestSections::default();
+    raw.split(&mut sections);
+    for insert_section in sections.sections {
+        insert_section(world);
+    }
+
+    fail_pending_sections::<S>(world);
+}
+
+/// Marks any sections of `S` which have not been supplied as failed, and stops tracking `S`.
+fn fail_pending_sections<S: SplitRawManifest>(world: &mut World) {
+    let Some(split_manifest) = world.remove_resource::<SplitManifest<S>>() else {
+        return;
+    };
+
+    let mut raw_manifest_tracker = world.resource_mut::<RawManifestTracker>();
+    for type_id in split_manifest.sections {
+        let Some(status) = raw_manifest_tracker.status_by_type_id_mut(type_id) else {
+            continue;
+        };
+
+        if status.load_state != LoadState::Loaded {
+            error!(
+                "The split manifest {} did not supply the section {}.",
+                type_name::<S>(),
+                status.type_name
+            );
+            status.load_state = LoadState::Failed;
+            raw_manifest_tracker.record_lifecycle_by_type_id(
+                type_id,
+                ManifestLifecycleStage::LoadFailed,
+                Some(format!(
+                    "The split manifest {} did not supply this section.",
+                    type_name::<S>()
+                )),
+            );
+        }
+    }
+}
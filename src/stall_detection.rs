@@ -0,0 +1,218 @@
+//! Detecting raw manifests which never finish loading.
+//!
+//! Some asset backends never report a failure for a missing file: a typo in a manifest path can leave the loading screen up forever.
+//! Configuring a [`LoadingTimeout`] on the [`ManifestPlugin`](crate::plugin::ManifestPlugin) reports any manifest
+//! that is still loading after the timeout with a [`ManifestLoadStalled`] event,
+//! and optionally fails loading entirely:
+//!
+//! ```rust
+//! # #[cfg(feature = "ron")]
+//! # fn main() {
+//! use std::time::Duration;
+//!
+//! use bevy::ecs::system::RunSystemOnce;
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     identifier::{Id, IdMap},
+//!     manifest::{Manifest, ManifestFormat},
+//!     plugin::{ManifestPlugin, RegisterManifest},
+//!     stall_detection::{detect_stalled_manifests, LoadingTimeout, ManifestLoadStalled},
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Item {
+//!     name: String,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Ron;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Self::Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((
+//!     MinimalPlugins,
+//!     AssetPlugin::default(),
+//!     ManifestPlugin::<SimpleAssetState>::default()
+//!         .with_loading_timeout(LoadingTimeout::fail(Duration::from_millis(1))),
+//! ))
+//! .register_manifest::<ItemManifest>("does_not_exist.ron");
+//!
+//! // The asset server only reports the missing file once the app updates,
+//! // so check for stalled manifests before then, as a backend that never reports a failure would.
+//! std::thread::sleep(Duration::from_millis(5));
+//! app.world.run_system_once(detect_stalled_manifests::<SimpleAssetState>);
+//!
+//! let stalled: Vec<ManifestLoadStalled> = app
+//!     .world
+//!     .resource_mut::<Events<ManifestLoadStalled>>()
+//!     .drain()
+//!     .collect();
+//! assert_eq!(stalled.len(), 1);
+//! assert_eq!(
+//!     stalled[0].manifests[0].path,
+//!     std::path::Path::new("does_not_exist.ron")
+//! );
+//!
+//! app.update();
+//! assert_eq!(
+//!     *app.world.resource::<State<SimpleAssetState>>().get(),
+//!     SimpleAssetState::Failed
+//! );
+//! # }
+//! # #[cfg(not(feature = "ron"))]
+//! # fn main() {}
+//! ```
+
+use std::any::TypeId;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::asset::{AssetServer, LoadState};
+use bevy::ecs::prelude::*;
+use bevy::log::{error, warn};
+use bevy::utils::HashSet;
+
+use crate::asset_state::AssetLoadingState;
+use crate::plugin::RawManifestTracker;
+
+/// What to do when a raw manifest has been loading for longer than the [`LoadingTimeout`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StallAction {
+    /// Log a warning and send a [`ManifestLoadStalled`] event, but keep waiting.
+    #[default]
+    Warn,
+    /// Also transition to [`AssetLoadingState::FAILED`].
+    Fail,
+}
+
+/// How long raw manifests may spend loading before they are considered stalled.
+///
+/// Only manifests which are [`required`](crate::plugin::RawManifestStatus::required) are checked,
+/// and each stalled manifest is only reported once.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadingTimeout {
+    /// How long each raw manifest may spend loading, measured from when it was registered.
+    pub duration: Duration,
+    /// What to do once a raw manifest has stalled.
+    pub action: StallAction,
+}
+
+impl LoadingTimeout {
+    /// A timeout which only warns about stalled manifests.
+    #[must_use]
+    pub const fn warn(duration: Duration) -> Self {
+        Self {
+            duration,
+            action: StallAction::Warn,
+        }
+    }
+
+    /// A timeout which fails loading if any manifest stalls.
+    #[must_use]
+    pub const fn fail(duration: Duration) -> Self {
+        Self {
+            duration,
+            action: StallAction::Fail,
+        }
+    }
+}
+
+/// A raw manifest which has been loading for longer than the [`LoadingTimeout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalledManifest {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The path that the raw manifest is being loaded from.
+    pub path: PathBuf,
+    /// How long the raw manifest has been loading for.
+    pub elapsed: Duration,
+}
+
+/// An event sent when one or more raw manifests have been loading for longer than the [`LoadingTimeout`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestLoadStalled {
+    /// The manifests which stalled since the last event.
+    pub manifests: Vec<StalledManifest>,
+}
+
+/// Reports any raw manifests which have been loading for longer than the [`LoadingTimeout`].
+///
+/// This should only run while the app is in [`AssetLoadingState::LOADING`].
+pub fn detect_stalled_manifests<S: AssetLoadingState>(
+    timeout: Res<LoadingTimeout>,
+    asset_server: Res<AssetServer>,
+    mut raw_manifest_tracker: ResMut<RawManifestTracker>,
+    mut reported: Local<HashSet<TypeId>>,
+    mut stalled_events: EventWriter<ManifestLoadStalled>,
+    mut next_state: ResMut<NextState<S>>,
+) {
+    raw_manifest_tracker.update_load_states(&asset_server);
+
+    let mut stalled: Vec<StalledManifest> = Vec::new();
+    for (type_id, status) in raw_manifest_tracker.iter() {
+        let still_loading = matches!(status.load_state, LoadState::Loading | LoadState::NotLoaded);
+        let elapsed = status.registered_at.elapsed();
+        if status.required
+            && still_loading
+            && elapsed >= timeout.duration
+            && reported.insert(*type_id)
+        {
+            stalled.push(StalledManifest {
+                type_name: status.type_name,
+                path: status.path.clone(),
+                elapsed,
+            });
+        }
+    }
+
+    if stalled.is_empty() {
+        return;
+    }
+
+    stalled.sort_by_key(|manifest| manifest.type_name);
+    let paths: Vec<String> = stalled
+        .iter()
+        .map(|manifest| format!("{} ({})", manifest.path.display(), manifest.type_name))
+        .collect();
+
+    match timeout.action {
+        StallAction::Warn => warn!(
+            "Manifests have been loading for more than {:?}: {}. Check that these paths exist.",
+            timeout.duration,
+            paths.join(", ")
+        ),
+        StallAction::Fail => {
+            error!(
+                "Manifests did not load within {:?}: {}. Check that these paths exist.",
+                timeout.duration,
+                paths.join(", ")
+            );
+            next_state.set(S::FAILED);
+        }
+    }
+
+    stalled_events.send(ManifestLoadStalled { manifests: stalled });
+}
@@ -0,0 +1,347 @@
+//! Statistics about the size of each manifest, to help decide which data should stay resident.
+//!
+//! [`manifest_stats`] measures a single manifest: how many items it has, roughly how much memory they use,
+//! and which items are the largest.
+//! Memory usage is estimated with the [`MemoryFootprint`] trait, which item types implement by summing their fields:
+//!
+//! ```rust,ignore
+//! impl MemoryFootprint for Item {
+//!     fn heap_size(&self) -> usize {
+//!         self.name.heap_size() + self.description.heap_size() + self.tags.heap_size()
+//!     }
+//! }
+//! ```
+//!
+//! Adding a [`ManifestStatsPlugin`] for each manifest keeps an aggregate [`ManifestStatsReport`] up to date,
+//! which can be logged to compare manifests:
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestStatsPlugin::<ItemManifest>::default());
+//!
+//! fn log_stats(report: Res<ManifestStatsReport>) {
+//!     info!("{}", *report);
+//! }
+//! ```
+//!
+//! These numbers are estimates: allocator overhead and the spare capacity of hash maps are not counted.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+
+use crate::identifier::Id;
+use crate::manifest::{IterableManifest, Manifest};
+use crate::name_registry::NameRegistry;
+use crate::plugin::{manifest_schedules, ProcessManifestSet};
+
+/// The number of largest items recorded in each [`ManifestStats`].
+pub const LARGEST_ITEMS: usize = 5;
+
+/// Estimates the memory used by a value.
+///
+/// Only [`heap_size`](MemoryFootprint::heap_size) needs to be implemented:
+/// it should return the number of bytes this value owns on the heap, excluding the value itself.
+pub trait MemoryFootprint {
+    /// The approximate number of bytes owned by this value on the heap.
+    ///
+    /// The default implementation assumes that the value owns no heap memory.
+    fn heap_size(&self) -> usize {
+        0
+    }
+
+    /// The approximate total number of bytes used by this value, including its own size.
+    fn total_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.heap_size()
+    }
+}
+
+macro_rules! impl_inline_memory_footprint {
+    ($($ty:ty),*) => {
+        $(impl MemoryFootprint for $ty {})*
+    };
+}
+
+impl_inline_memory_footprint!(
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    ()
+);
+
+impl<T> MemoryFootprint for Id<T> {}
+
+impl MemoryFootprint for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, MemoryFootprint::heap_size)
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Box<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().total_size()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint, const N: usize> MemoryFootprint for [T; N] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::heap_size).sum()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint, S> MemoryFootprint for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint, S> MemoryFootprint for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint, S> MemoryFootprint
+    for bevy::utils::hashbrown::HashMap<K, V, S>
+{
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint, S> MemoryFootprint for bevy::utils::hashbrown::HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint> MemoryFootprint for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.iter()
+            .map(|(key, value)| key.total_size() + value.total_size())
+            .sum()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::total_size).sum()
+    }
+}
+
+/// Statistics about a single manifest, as returned by [`manifest_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestStats {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The number of items in the manifest.
+    pub item_count: usize,
+    /// The approximate total number of bytes used by the items, including their heap allocations.
+    ///
+    /// This does not include the overhead of the container that the manifest stores them in.
+    pub total_bytes: usize,
+    /// The largest items, in descending order of size, along with their names if known.
+    pub largest_items: Vec<ItemSize>,
+}
+
+/// The approximate size of a single item, as part of a [`ManifestStats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemSize {
+    /// The name of the item, if it was registered with the [`NameRegistry`].
+    pub name: Option<String>,
+    /// The approximate total number of bytes used by the item.
+    pub bytes: usize,
+}
+
+impl ManifestStats {
+    /// The average size of an item in bytes, or zero if the manifest is empty.
+    #[must_use]
+    pub fn average_item_bytes(&self) -> usize {
+        self.total_bytes.checked_div(self.item_count).unwrap_or(0)
+    }
+}
+
+/// Measures the items in the `manifest`.
+#[must_use]
+pub fn manifest_stats<M>(manifest: &M) -> ManifestStats
+where
+    M: IterableManifest,
+    M::Item: MemoryFootprint,
+{
+    let mut sizes: Vec<(usize, Id<M::Item>)> = manifest
+        .iter()
+        .map(|(id, item)| (item.total_size(), id))
+        .collect();
+    let total_bytes = sizes.iter().map(|(bytes, _)| bytes).sum();
+    let item_count = sizes.len();
+
+    // Ties are broken by Id, so the reported items are stable between runs.
+    sizes.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    let largest_items = sizes
+        .into_iter()
+        .take(LARGEST_ITEMS)
+        .map(|(bytes, id)| ItemSize {
+            name: NameRegistry::name_of(id),
+            bytes,
+        })
+        .collect();
+
+    ManifestStats {
+        type_name: std::any::type_name::<M>(),
+        item_count,
+        total_bytes,
+        largest_items,
+    }
+}
+
+/// The [`ManifestStats`] of every manifest with a [`ManifestStatsPlugin`].
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestStatsReport {
+    /// The statistics of each manifest, sorted by type name.
+    pub manifests: Vec<ManifestStats>,
+}
+
+impl ManifestStatsReport {
+    /// Adds or replaces the statistics for a manifest.
+    pub fn record(&mut self, stats: ManifestStats) {
+        match self
+            .manifests
+            .binary_search_by(|existing| existing.type_name.cmp(stats.type_name))
+        {
+            Ok(index) => self.manifests[index] = stats,
+            Err(index) => self.manifests.insert(index, stats),
+        }
+    }
+
+    /// Returns the statistics for the manifest `M`, if they have been recorded.
+    #[must_use]
+    pub fn get<M: Manifest>(&self) -> Option<&ManifestStats> {
+        let type_name = std::any::type_name::<M>();
+        self.manifests
+            .iter()
+            .find(|stats| stats.type_name == type_name)
+    }
+
+    /// The total number of items across all manifests.
+    #[must_use]
+    pub fn total_items(&self) -> usize {
+        self.manifests.iter().map(|stats| stats.item_count).sum()
+    }
+
+    /// The approximate total number of bytes used by the items of all manifests.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.manifests.iter().map(|stats| stats.total_bytes).sum()
+    }
+}
+
+impl Display for ManifestStatsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} manifests, {} items, ~{} bytes:",
+            self.manifests.len(),
+            self.total_items(),
+            self.total_bytes()
+        )?;
+
+        let mut by_size: Vec<&ManifestStats> = self.manifests.iter().collect();
+        by_size.sort_by_key(|stats| std::cmp::Reverse(stats.total_bytes));
+        for stats in by_size {
+            write!(
+                f,
+                "  {}: {} items, ~{} bytes (~{} per item)",
+                stats.type_name,
+                stats.item_count,
+                stats.total_bytes,
+                stats.average_item_bytes()
+            )?;
+            if let Some(largest) = stats.largest_items.first() {
+                let name = largest.name.as_deref().unwrap_or("<unnamed>");
+                write!(f, ", largest is {name} (~{} bytes)", largest.bytes)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A plugin which records the [`ManifestStats`] of the manifest `M` in the [`ManifestStatsReport`].
+///
+/// The statistics are recomputed in the processing schedule whenever the manifest resource changes.
+pub struct ManifestStatsPlugin<M: IterableManifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: IterableManifest> Default for ManifestStatsPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M> Plugin for ManifestStatsPlugin<M>
+where
+    M: IterableManifest,
+    M::Item: MemoryFootprint,
+{
+    fn build(&self, app: &mut App) {
+        let schedules = manifest_schedules(app);
+        app.init_resource::<ManifestStatsReport>().add_systems(
+            schedules.processing,
+            record_manifest_stats::<M>
+                .run_if(resource_exists::<M>.and_then(resource_changed::<M>))
+                .after(ProcessManifestSet),
+        );
+    }
+}
+
+/// Records the [`ManifestStats`] of the manifest `M` in the [`ManifestStatsReport`].
+pub fn record_manifest_stats<M>(manifest: Res<M>, mut report: ResMut<ManifestStatsReport>)
+where
+    M: IterableManifest,
+    M::Item: MemoryFootprint,
+{
+    report.record(manifest_stats(&*manifest));
+}
@@ -0,0 +1,121 @@
+//! A single summary of every manifest loaded this session, for logging and telemetry.
+//!
+//! Once every manifest is ready, the [`ManifestSummary`] resource is inserted and a [`ManifestsReady`] event is sent.
+//! Either can be used as the one place to record which content was loaded:
+//!
+//! ```rust,ignore
+//! fn log_content(mut events: EventReader<ManifestsReady>) {
+//!     for event in events.read() {
+//!         info!("{}", event.summary);
+//!     }
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::ecs::prelude::*;
+
+use crate::plugin::RawManifestTracker;
+
+/// A summary of every registered manifest, created once they are all ready.
+///
+/// This resource is inserted upon entering [`AssetLoadingState::READY`](crate::asset_state::AssetLoadingState::READY),
+/// or when the [`ManifestLoadingDriver`](crate::driver::ManifestLoadingDriver) reaches [`ManifestReadiness::Ready`](crate::driver::ManifestReadiness::Ready).
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ManifestSummary {
+    /// A summary of each manifest, sorted by type name.
+    pub manifests: Vec<LoadedManifest>,
+    /// The total number of items across all manifests.
+    pub total_items: usize,
+    /// The total time spent converting raw manifests into manifests.
+    pub total_processing_time: Duration,
+}
+
+/// A summary of a single manifest, as part of a [`ManifestSummary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedManifest {
+    /// The name of the manifest type.
+    pub type_name: &'static str,
+    /// The path that the raw manifest was loaded from.
+    pub path: PathBuf,
+    /// The number of items in the manifest, as reported by [`Manifest::item_count`](crate::manifest::Manifest::item_count).
+    pub item_count: Option<usize>,
+    /// How long it took to process the manifest.
+    pub processing_duration: Option<Duration>,
+    /// If true, the manifest was loaded from the cache of baked manifests.
+    pub baked: bool,
+}
+
+impl ManifestSummary {
+    /// Summarizes the manifests recorded by the [`RawManifestTracker`].
+    #[must_use]
+    pub fn from_tracker(raw_manifest_tracker: &RawManifestTracker) -> Self {
+        let mut manifests: Vec<LoadedManifest> = raw_manifest_tracker
+            .iter()
+            .map(|(_, status)| LoadedManifest {
+                type_name: status.type_name,
+                path: status.path.clone(),
+                item_count: status.item_count,
+                processing_duration: status.processing_duration,
+                baked: status.baked,
+            })
+            .collect();
+        manifests.sort_by_key(|manifest| manifest.type_name);
+
+        ManifestSummary {
+            total_items: manifests.iter().filter_map(|m| m.item_count).sum(),
+            total_processing_time: manifests.iter().filter_map(|m| m.processing_duration).sum(),
+            manifests,
+        }
+    }
+}
+
+impl Display for ManifestSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Loaded {} manifests ({} items) in {:?}:",
+            self.manifests.len(),
+            self.total_items,
+            self.total_processing_time
+        )?;
+
+        for manifest in &self.manifests {
+            write!(
+                f,
+                "  {} from {}",
+                manifest.type_name,
+                manifest.path.display()
+            )?;
+            if let Some(item_count) = manifest.item_count {
+                write!(f, ", {item_count} items")?;
+            }
+            if manifest.baked {
+                write!(f, " (baked)")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An event sent once every manifest is ready, containing a summary of what was loaded.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ManifestsReady {
+    /// A summary of every loaded manifest.
+    pub summary: ManifestSummary,
+}
+
+/// Inserts the [`ManifestSummary`] resource, and sends the [`ManifestsReady`] event.
+pub fn announce_manifests_ready(
+    raw_manifest_tracker: Res<RawManifestTracker>,
+    mut ready_events: EventWriter<ManifestsReady>,
+    mut commands: Commands,
+) {
+    let summary = ManifestSummary::from_tracker(&raw_manifest_tracker);
+    commands.insert_resource(summary.clone());
+    ready_events.send(ManifestsReady { summary });
+}
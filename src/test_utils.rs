@@ -0,0 +1,253 @@
+//! Helpers for testing manifests and the code that uses them.
+//!
+//! Setting up an [`App`] that can load and process manifests requires a handful of plugins,
+//! and driving it through the asset loading states by hand is fiddly.
+//! These utilities take care of the boilerplate, so your tests can focus on the data.
+//! The [`TestItemManifest`] is a ready-made manifest for tests that only need some items to work with.
+//!
+//! This module is only available when the `test_utils` feature is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! use leafwing_manifest::{
+//!     asset_state::SimpleAssetState,
+//!     manifest::Manifest,
+//!     test_utils::{manifest_test_app, ManifestTestApp, TestItem, TestItemManifest},
+//! };
+//!
+//! let mut app = manifest_test_app();
+//! let sword = TestItem::new("sword", 10);
+//! app.insert_raw_manifest::<TestItemManifest>(TestItemManifest::from_items([sword]));
+//!
+//! assert_eq!(
+//!     app.update_until_loaded::<SimpleAssetState>(),
+//!     SimpleAssetState::Ready
+//! );
+//! assert_eq!(app.manifest::<TestItemManifest>().get_by_name("sword").unwrap().value, 10);
+//! ```
+
+use std::any::type_name;
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::asset::{Asset, AssetPlugin};
+use bevy::ecs::prelude::*;
+use bevy::reflect::{Reflect, TypePath};
+use bevy::utils::Instant;
+use bevy::MinimalPlugins;
+use serde::{Deserialize, Serialize};
+
+use crate::asset_state::{AssetLoadingState, SimpleAssetState};
+use crate::identifier::{Id, IdMap};
+use crate::manifest::{
+    IterableManifest, Manifest, ManifestFormat, ManifestModificationError, MutableManifest,
+};
+use crate::plugin::{ManifestPlugin, RegisterManifest};
+
+/// How long [`ManifestTestApp::update_until_loaded`] waits before giving up.
+pub const DEFAULT_LOADING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Creates a minimal, headless [`App`] which can load and process manifests.
+///
+/// The app uses [`SimpleAssetState`], and contains the [`MinimalPlugins`], the [`AssetPlugin`] and the [`ManifestPlugin`].
+/// Manifests still need to be registered, either from disk via [`RegisterManifest`],
+/// or from memory via [`ManifestTestApp::insert_raw_manifest`].
+#[must_use]
+pub fn manifest_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        ManifestPlugin::<SimpleAssetState>::default(),
+    ));
+    app
+}
+
+/// Testing-focused extension methods for an [`App`] with the [`ManifestPlugin`] added.
+pub trait ManifestTestApp {
+    /// Registers the manifest `M`, using the supplied raw manifest rather than loading it from disk.
+    ///
+    /// The raw manifest is processed in the same way as one loaded from a file.
+    /// This is a shorthand for [`RegisterManifest::register_manifest_from_value`].
+    fn insert_raw_manifest<M: Manifest>(&mut self, raw_manifest: M::RawManifest) -> &mut Self;
+
+    /// Repeatedly updates the app until it reaches [`AssetLoadingState::READY`] or [`AssetLoadingState::FAILED`],
+    /// returning the final state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if neither state is reached within the [`DEFAULT_LOADING_TIMEOUT`].
+    fn update_until_loaded<S: AssetLoadingState>(&mut self) -> S {
+        self.update_until_loaded_with_timeout(DEFAULT_LOADING_TIMEOUT)
+    }
+
+    /// Repeatedly updates the app until it reaches [`AssetLoadingState::READY`] or [`AssetLoadingState::FAILED`],
+    /// returning the final state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if neither state is reached within the provided `timeout`.
+    fn update_until_loaded_with_timeout<S: AssetLoadingState>(&mut self, timeout: Duration) -> S;
+
+    /// Returns the processed manifest of type `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the manifest has not been processed yet.
+    fn manifest<M: Manifest>(&self) -> &M;
+}
+
+impl ManifestTestApp for App {
+    fn insert_raw_manifest<M: Manifest>(&mut self, raw_manifest: M::RawManifest) -> &mut Self {
+        self.register_manifest_from_value::<M>(raw_manifest)
+    }
+
+    fn update_until_loaded_with_timeout<S: AssetLoadingState>(&mut self, timeout: Duration) -> S {
+        let start = Instant::now();
+
+        loop {
+            self.update();
+
+            let state = self.world.resource::<State<S>>().get().clone();
+            if state == S::READY || state == S::FAILED {
+                return state;
+            }
+
+            assert!(
+                start.elapsed() < timeout,
+                "Manifests did not finish loading within {timeout:?}: stuck in {state:?}."
+            );
+
+            // Give the asset loading tasks a chance to make progress.
+            // Threads cannot be put to sleep on the web, so we simply spin there instead.
+            #[cfg(not(target_arch = "wasm32"))]
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn manifest<M: Manifest>(&self) -> &M {
+        self.world.get_resource::<M>().unwrap_or_else(|| {
+            panic!(
+                "The manifest {} has not been processed. Did you call `update_until_loaded`?",
+                type_name::<M>()
+            )
+        })
+    }
+}
+
+/// An item in the [`TestItemManifest`]: a name, and a single number to inspect and modify.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub struct TestItem {
+    /// The name of the item, from which its [`Id`] is derived.
+    pub name: String,
+    /// A number describing the item, such as its damage or price.
+    pub value: u32,
+}
+
+impl TestItem {
+    /// Creates an item with the supplied `name` and `value`.
+    #[must_use]
+    pub fn new(name: &str, value: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+/// A minimal manifest of [`TestItem`]s, for use in tests and examples.
+///
+/// Items are stored under the [`Id`] of their name, and the manifest is its own raw manifest.
+/// It implements both [`IterableManifest`] and [`MutableManifest`].
+///
+/// ```
+/// use leafwing_manifest::{
+///     manifest::{Manifest, MutableManifest},
+///     test_utils::{TestItem, TestItemManifest},
+/// };
+///
+/// let mut manifest = TestItemManifest::from_items([TestItem::new("sword", 10)]);
+/// manifest.insert(TestItem::new("shield", 2)).unwrap();
+///
+/// assert_eq!(manifest.get_by_name("sword"), Some(&TestItem::new("sword", 10)));
+/// assert!(manifest.insert(TestItem::new("shield", 5)).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Resource, Asset, TypePath, Serialize, Deserialize)]
+pub struct TestItemManifest {
+    /// The items in the manifest, keyed by the [`Id`] of their name.
+    pub items: IdMap<TestItem, TestItem>,
+}
+
+impl TestItemManifest {
+    /// Creates a manifest containing the supplied items.
+    #[must_use]
+    pub fn from_items(items: impl IntoIterator<Item = TestItem>) -> Self {
+        Self {
+            items: items
+                .into_iter()
+                .map(|item| (Id::from_name(&item.name), item))
+                .collect(),
+        }
+    }
+}
+
+impl Manifest for TestItemManifest {
+    type Item = TestItem;
+    type RawItem = TestItem;
+    type RawManifest = TestItemManifest;
+    type ConversionError = std::convert::Infallible;
+
+    const FORMAT: ManifestFormat = ManifestFormat::Custom;
+
+    fn get(&self, id: Id<TestItem>) -> Option<&TestItem> {
+        self.items.get(&id)
+    }
+
+    fn from_raw_manifest(
+        raw_manifest: Self::RawManifest,
+        _world: &mut World,
+    ) -> Result<Self, Self::ConversionError> {
+        Ok(raw_manifest)
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+impl IterableManifest for TestItemManifest {
+    fn iter(&self) -> impl Iterator<Item = (Id<TestItem>, &TestItem)> {
+        self.items.iter().map(|(id, item)| (*id, item))
+    }
+}
+
+impl MutableManifest for TestItemManifest {
+    fn insert(&mut self, item: TestItem) -> Result<Id<TestItem>, ManifestModificationError<Self>> {
+        let id = Id::from_name(&item.name);
+        if self.items.contains_key(&id) {
+            return Err(ManifestModificationError::DuplicateName(item.name));
+        }
+
+        self.items.insert(id, item);
+        Ok(id)
+    }
+
+    fn id_of(&self, item: &TestItem) -> Option<Id<TestItem>> {
+        Some(Id::from_name(&item.name))
+    }
+
+    fn remove(
+        &mut self,
+        id: &Id<TestItem>,
+    ) -> Result<Id<TestItem>, ManifestModificationError<Self>> {
+        self.items
+            .remove(id)
+            .map(|_| *id)
+            .ok_or(ManifestModificationError::NotFound(*id))
+    }
+
+    fn get_mut(&mut self, id: Id<TestItem>) -> Option<&mut TestItem> {
+        self.items.get_mut(&id)
+    }
+}
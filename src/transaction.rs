@@ -0,0 +1,474 @@
+//! All-or-nothing batches of changes to a [`MutableManifest`].
+//!
+//! In-game editors and mod loaders often need to apply a set of related changes together:
+//! adding a new item, and updating the existing items which refer to it.
+//! Applying these one at a time can leave the manifest in a half-modified state if a later change fails.
+//!
+//! A [`ManifestTransaction`] stages a list of changes, and any validation that the final manifest must pass.
+//! When [committed](ManifestTransaction::commit), either every change is applied, or the manifest is rolled back to its original state.
+//!
+//! ```rust,ignore
+//! let summary = ManifestTransaction::<ItemManifest>::new()
+//!     .insert(fire_sword)
+//!     .update(Id::from_name("forge"), |forge| forge.recipes.push(Id::from_name("fire_sword")))
+//!     .validate(|manifest| manifest.check_recipes())
+//!     .commit(&mut item_manifest)?;
+//! ```
+//!
+//! To let players or designers undo committed transactions, record them in a [`ManifestHistory`].
+
+use std::marker::PhantomData;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::history::{HistoryStep, ManifestHistory};
+use crate::identifier::Id;
+use crate::manifest::{Manifest, ManifestModificationError, MutableManifest};
+use crate::manifest_lock::ManifestLock;
+
+type Validator<M> = Box<dyn FnOnce(&M) -> Result<(), String> + Send + Sync>;
+type Updater<M> = Box<dyn FnOnce(&mut <M as Manifest>::Item) + Send + Sync>;
+
+/// A single staged change.
+enum Operation<M: MutableManifest> {
+    Insert(M::Item),
+    InsertWithId(Id<M::Item>, M::Item),
+    Upsert(Id<M::Item>, M::Item),
+    Remove(Id<M::Item>),
+    Update(Id<M::Item>, Updater<M>),
+}
+
+/// How to undo a change that has already been applied.
+enum Undo<M: MutableManifest> {
+    Remove(Id<M::Item>),
+    Restore(Id<M::Item>, M::Item),
+}
+
+/// A batch of changes to the manifest `M`, which are applied atomically.
+///
+/// See the [module docs](crate::transaction) for more information.
+pub struct ManifestTransaction<M: MutableManifest> {
+    operations: Vec<Operation<M>>,
+    validators: Vec<Validator<M>>,
+}
+
+impl<M: MutableManifest> Default for ManifestTransaction<M> {
+    fn default() -> Self {
+        Self {
+            operations: Vec::new(),
+            validators: Vec::new(),
+        }
+    }
+}
+
+impl<M: MutableManifest> std::fmt::Debug for ManifestTransaction<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestTransaction")
+            .field("operations", &self.operations.len())
+            .field("validators", &self.validators.len())
+            .finish()
+    }
+}
+
+/// The changes made by a successfully committed [`ManifestTransaction`].
+pub struct TransactionSummary<M: Manifest> {
+    /// The identifiers of the items that were added.
+    pub inserted: Vec<Id<M::Item>>,
+    /// The identifiers of the items that were replaced or modified.
+    ///
+    /// Each item is only listed once, even if it was changed several times.
+    pub updated: Vec<Id<M::Item>>,
+    /// The identifiers of the items that were removed.
+    pub removed: Vec<Id<M::Item>>,
+}
+
+impl<M: Manifest> Clone for TransactionSummary<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inserted: self.inserted.clone(),
+            updated: self.updated.clone(),
+            removed: self.removed.clone(),
+        }
+    }
+}
+
+impl<M: Manifest> PartialEq for TransactionSummary<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inserted == other.inserted
+            && self.updated == other.updated
+            && self.removed == other.removed
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for TransactionSummary<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionSummary")
+            .field("inserted", &self.inserted)
+            .field("updated", &self.updated)
+            .field("removed", &self.removed)
+            .finish()
+    }
+}
+
+impl<M: Manifest> Default for TransactionSummary<M> {
+    fn default() -> Self {
+        Self {
+            inserted: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+/// An event sent by [`ManifestTransaction::commit_in_world`] when a transaction is committed to the manifest `M`.
+///
+/// This event must be registered via [`App::add_event`](bevy::app::App::add_event).
+#[derive(Event)]
+pub struct ManifestTransactionCommitted<M: Manifest> {
+    /// The changes that were made.
+    pub summary: TransactionSummary<M>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+/// Errors that can occur when committing a [`ManifestTransaction`].
+///
+/// Other than [`TransactionError::RollbackFailed`], the manifest is always left unchanged.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionError<M: Manifest> {
+    /// One of the staged changes could not be applied.
+    #[error("Change {index} of the transaction could not be applied: {error}")]
+    Modification {
+        /// The index of the change that failed, in the order that changes were staged.
+        index: usize,
+        /// The error returned by the manifest.
+        error: ManifestModificationError<M>,
+    },
+    /// An item to be updated was not found.
+    #[error("The item with ID {0:?} could not be updated, as it was not found.")]
+    NotFound(Id<M::Item>),
+    /// The modified manifest failed validation.
+    #[error("The modified manifest failed validation: {0}")]
+    Validation(String),
+    /// The transaction could not be rolled back after a failure, so the manifest may be partially modified.
+    #[error("The transaction could not be rolled back: {0}")]
+    RollbackFailed(ManifestModificationError<M>),
+    /// The manifest resource does not exist.
+    #[error("The manifest resource does not exist.")]
+    MissingManifest,
+    /// The [`ManifestLock`] is locked, so the transaction was not applied.
+    #[error("Manifests are locked and cannot be modified.")]
+    Locked,
+}
+
+impl<M: MutableManifest> ManifestTransaction<M>
+where
+    M::Item: Clone,
+{
+    /// Creates an empty transaction.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stages inserting a new item, via [`MutableManifest::insert`].
+    #[must_use]
+    pub fn insert(mut self, item: M::Item) -> Self {
+        self.operations.push(Operation::Insert(item));
+        self
+    }
+
+    /// Stages inserting a new item with an explicit [`Id`], via [`MutableManifest::insert_with_id`].
+    #[must_use]
+    pub fn insert_with_id(mut self, id: Id<M::Item>, item: M::Item) -> Self {
+        self.operations.push(Operation::InsertWithId(id, item));
+        self
+    }
+
+    /// Stages inserting or replacing an item, via [`MutableManifest::upsert`].
+    #[must_use]
+    pub fn upsert(mut self, id: Id<M::Item>, item: M::Item) -> Self {
+        self.operations.push(Operation::Upsert(id, item));
+        self
+    }
+
+    /// Stages removing an item.
+    ///
+    /// If the transaction is rolled back, the item is restored via [`MutableManifest::upsert`],
+    /// so the manifest must support [`MutableManifest::insert_with_id`].
+    #[must_use]
+    pub fn remove(mut self, id: Id<M::Item>) -> Self {
+        self.operations.push(Operation::Remove(id));
+        self
+    }
+
+    /// Stages modifying an existing item in place.
+    #[must_use]
+    pub fn update(
+        mut self,
+        id: Id<M::Item>,
+        update: impl FnOnce(&mut M::Item) + Send + Sync + 'static,
+    ) -> Self {
+        self.operations
+            .push(Operation::Update(id, Box::new(update)));
+        self
+    }
+
+    /// Adds a check that the manifest must pass once every change has been applied.
+    ///
+    /// If any check returns an error, the transaction is rolled back.
+    #[must_use]
+    pub fn validate(
+        mut self,
+        validator: impl FnOnce(&M) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// The number of staged changes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns true if no changes have been staged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies every staged change to the `manifest`, then runs the validation checks.
+    ///
+    /// If any change or check fails, every applied change is undone, in reverse order, and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+    /// use leafwing_manifest::{
+    ///     identifier::Id,
+    ///     manifest::{Manifest, MutableManifest},
+    ///     transaction::{ManifestTransaction, TransactionError},
+    /// };
+    ///
+    /// let item = Item::new;
+    /// let mut manifest = ItemManifest::from_items([item("sword", 10), item("shield", 0)]);
+    ///
+    /// // The sword is changed twice, so the changes must be undone in reverse order to restore its original value.
+    /// let result = ManifestTransaction::<ItemManifest>::new()
+    ///     .update(Id::from_name("sword"), |sword| sword.value = 20)
+    ///     .remove(Id::from_name("shield"))
+    ///     .insert(item("axe", 15))
+    ///     .update(Id::from_name("sword"), |sword| sword.value *= 2)
+    ///     .validate(|manifest: &ItemManifest| match manifest.get_by_name("sword") {
+    ///         Some(sword) if sword.value > 30 => Err("The sword is too strong".to_string()),
+    ///         _ => Ok(()),
+    ///     })
+    ///     .commit(&mut manifest);
+    ///
+    /// assert!(matches!(result, Err(TransactionError::Validation(_))));
+    /// assert_eq!(manifest.get_by_name("sword"), Some(&item("sword", 10)));
+    /// assert_eq!(manifest.get_by_name("shield"), Some(&item("shield", 0)));
+    /// assert_eq!(manifest.get_by_name("axe"), None);
+    ///
+    /// // A failing change rolls back the changes staged before it.
+    /// let result = ManifestTransaction::<ItemManifest>::new()
+    ///     .insert(item("axe", 15))
+    ///     .remove(Id::from_name("bow"))
+    ///     .commit(&mut manifest);
+    ///
+    /// assert!(matches!(result, Err(TransactionError::NotFound(_))));
+    /// assert_eq!(manifest.get_by_name("axe"), None);
+    ///
+    /// let summary = ManifestTransaction::<ItemManifest>::new()
+    ///     .insert(item("axe", 15))
+    ///     .remove(Id::from_name("shield"))
+    ///     .commit(&mut manifest)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(summary.inserted, vec![Id::from_name("axe")]);
+    /// assert_eq!(summary.removed, vec![Id::from_name("shield")]);
+    /// assert_eq!(manifest.items.len(), 2);
+    ///
+    /// // Items changed more than once are only reported once.
+    /// let summary = ManifestTransaction::<ItemManifest>::new()
+    ///     .update(Id::from_name("sword"), |sword| sword.value += 1)
+    ///     .update(Id::from_name("sword"), |sword| sword.value += 1)
+    ///     .commit(&mut manifest)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(summary.updated, vec![Id::from_name("sword")]);
+    /// ```
+    pub fn commit(self, manifest: &mut M) -> Result<TransactionSummary<M>, TransactionError<M>> {
+        self.commit_logged(manifest).map(|(summary, _)| summary)
+    }
+
+    /// Commits the transaction, returning the [`HistoryStep`] which undoes it.
+    pub(crate) fn commit_with_step(
+        self,
+        manifest: &mut M,
+    ) -> Result<(TransactionSummary<M>, HistoryStep<M>), TransactionError<M>> {
+        let (summary, undo_log) = self.commit_logged(manifest)?;
+
+        // The undo log is in the order changes were applied, so the first entry for each item holds its original state.
+        let mut seen = HashSet::new();
+        let mut changes = Vec::new();
+        for undo in undo_log {
+            let (id, before) = match undo {
+                Undo::Remove(id) => (id, None),
+                Undo::Restore(id, item) => (id, Some(item)),
+            };
+            if seen.insert(id) {
+                changes.push((id, before, manifest.get(id).cloned()));
+            }
+        }
+
+        Ok((summary, HistoryStep::new(changes)))
+    }
+
+    fn commit_logged(
+        self,
+        manifest: &mut M,
+    ) -> Result<(TransactionSummary<M>, Vec<Undo<M>>), TransactionError<M>> {
+        let mut undo_log: Vec<Undo<M>> = Vec::with_capacity(self.operations.len());
+        let mut summary = TransactionSummary::default();
+
+        for (index, operation) in self.operations.into_iter().enumerate() {
+            if let Err(error) = apply(manifest, operation, &mut undo_log, &mut summary) {
+                rollback(manifest, undo_log)?;
+                return Err(match error {
+                    ApplyError::NotFound(id) => TransactionError::NotFound(id),
+                    ApplyError::Modification(error) => {
+                        TransactionError::Modification { index, error }
+                    }
+                });
+            }
+        }
+
+        for validator in self.validators {
+            if let Err(message) = validator(manifest) {
+                rollback(manifest, undo_log)?;
+                return Err(TransactionError::Validation(message));
+            }
+        }
+
+        // Items which were changed several times are only reported once.
+        let mut seen = HashSet::new();
+        summary.updated.retain(|id| seen.insert(*id));
+
+        Ok((summary, undo_log))
+    }
+
+    /// Commits the transaction to the manifest resource `M`, and sends a [`ManifestTransactionCommitted`] event if it succeeds.
+    ///
+    /// If a [`ManifestHistory<M>`] resource exists, the transaction is recorded in it as a single undo step.
+    /// If the [`ManifestLock`] is locked, [`TransactionError::Locked`] is returned and nothing is changed.
+    pub fn commit_in_world(
+        self,
+        world: &mut World,
+    ) -> Result<TransactionSummary<M>, TransactionError<M>>
+    where
+        M::Item: Send + Sync,
+    {
+        if world
+            .get_resource::<ManifestLock>()
+            .is_some_and(ManifestLock::is_locked)
+        {
+            return Err(TransactionError::Locked);
+        }
+
+        let Some(mut manifest) = world.get_resource_mut::<M>() else {
+            return Err(TransactionError::MissingManifest);
+        };
+
+        let (summary, step) = self.commit_with_step(&mut manifest)?;
+        if let Some(mut history) = world.get_resource_mut::<ManifestHistory<M>>() {
+            history.record(step);
+        }
+        world.send_event(ManifestTransactionCommitted::<M> {
+            summary: summary.clone(),
+            _phantom: PhantomData,
+        });
+
+        Ok(summary)
+    }
+}
+
+enum ApplyError<M: Manifest> {
+    NotFound(Id<M::Item>),
+    Modification(ManifestModificationError<M>),
+}
+
+impl<M: Manifest> From<ManifestModificationError<M>> for ApplyError<M> {
+    fn from(error: ManifestModificationError<M>) -> Self {
+        ApplyError::Modification(error)
+    }
+}
+
+fn apply<M: MutableManifest>(
+    manifest: &mut M,
+    operation: Operation<M>,
+    undo_log: &mut Vec<Undo<M>>,
+    summary: &mut TransactionSummary<M>,
+) -> Result<(), ApplyError<M>>
+where
+    M::Item: Clone,
+{
+    match operation {
+        Operation::Insert(item) => {
+            let id = manifest.insert(item)?;
+            undo_log.push(Undo::Remove(id));
+            summary.inserted.push(id);
+        }
+        Operation::InsertWithId(id, item) => {
+            manifest.insert_with_id(id, item)?;
+            undo_log.push(Undo::Remove(id));
+            summary.inserted.push(id);
+        }
+        Operation::Upsert(id, item) => match manifest.upsert(id, item)? {
+            Some(previous) => {
+                undo_log.push(Undo::Restore(id, previous));
+                summary.updated.push(id);
+            }
+            None => {
+                undo_log.push(Undo::Remove(id));
+                summary.inserted.push(id);
+            }
+        },
+        Operation::Remove(id) => {
+            let Some(previous) = manifest.get(id).cloned() else {
+                return Err(ApplyError::NotFound(id));
+            };
+            manifest.remove(&id)?;
+            undo_log.push(Undo::Restore(id, previous));
+            summary.removed.push(id);
+        }
+        Operation::Update(id, update) => {
+            let Some(item) = manifest.get_mut(id) else {
+                return Err(ApplyError::NotFound(id));
+            };
+            let previous = item.clone();
+            update(item);
+            undo_log.push(Undo::Restore(id, previous));
+            summary.updated.push(id);
+        }
+    }
+
+    Ok(())
+}
+
+fn rollback<M: MutableManifest>(
+    manifest: &mut M,
+    undo_log: Vec<Undo<M>>,
+) -> Result<(), TransactionError<M>> {
+    for undo in undo_log.into_iter().rev() {
+        let result = match undo {
+            Undo::Remove(id) => manifest.remove(&id).map(|_| ()),
+            Undo::Restore(id, item) => manifest.upsert(id, item).map(|_| ()),
+        };
+        result.map_err(TransactionError::RollbackFailed)?;
+    }
+
+    Ok(())
+}
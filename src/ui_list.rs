@@ -0,0 +1,357 @@
+//! The data layer for UI lists of manifest items, such as inventories, shops and encyclopedias.
+//!
+//! This is not a UI framework: a [`ManifestList`] only decides which items appear, in what order and on which page,
+//! leaving the widgets themselves to your UI code.
+//! Like a [`ManifestView`](crate::view::ManifestView), the list is only rebuilt when the manifest changes,
+//! or when it is [invalidated](ManifestList::invalidate), and it records which items were added or removed so widgets can be updated in place.
+//!
+//! ```rust,ignore
+//! #[derive(Resource)]
+//! struct ShopList(ManifestList<ItemManifest>);
+//!
+//! app.insert_resource(ShopList(
+//!     ManifestList::new()
+//!         .filtered_by(|item: &Item| item.price.is_some())
+//!         .sorted_by_key(|item: &Item| item.price)
+//!         .with_page_size(12),
+//! ));
+//!
+//! fn shop_ui(mut list: ResMut<ShopList>, items: Res<ItemManifest>, page: Res<ShopPage>) {
+//!     if list.0.refresh(&items) {
+//!         // Respawn the widgets for list.0.changes().added, and despawn those for list.0.changes().removed.
+//!     }
+//!
+//!     let page = list.0.page(page.0);
+//!     for id in page.ids {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! Items which compare equal are ordered by their [`Id`], so the order is stable across rebuilds and platforms.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::component::Tick;
+use bevy::ecs::system::Res;
+
+use crate::identifier::{Id, IdSet};
+use crate::manifest::{IterableManifest, Manifest};
+
+type Filter<M> = Arc<dyn Fn(&<M as Manifest>::Item) -> bool + Send + Sync>;
+type Comparison<M> =
+    Arc<dyn Fn(&<M as Manifest>::Item, &<M as Manifest>::Item) -> Ordering + Send + Sync>;
+
+/// The items which entered or left a [`ManifestList`] the last time it was rebuilt.
+pub struct ListChanges<T> {
+    /// The items which are now in the list, but were not before, in list order.
+    pub added: Vec<Id<T>>,
+    /// The items which were in the list, but are no longer, in their previous list order.
+    pub removed: Vec<Id<T>>,
+    /// True if the items which were in the list both before and after are now in a different order.
+    pub reordered: bool,
+}
+
+impl<T> ListChanges<T> {
+    /// Returns true if the list is unchanged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && !self.reordered
+    }
+}
+
+impl<T> Clone for ListChanges<T> {
+    fn clone(&self) -> Self {
+        Self {
+            added: self.added.clone(),
+            removed: self.removed.clone(),
+            reordered: self.reordered,
+        }
+    }
+}
+
+impl<T> PartialEq for ListChanges<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.added == other.added
+            && self.removed == other.removed
+            && self.reordered == other.reordered
+    }
+}
+
+impl<T> Eq for ListChanges<T> {}
+
+impl<T> std::fmt::Debug for ListChanges<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListChanges")
+            .field("added", &self.added)
+            .field("removed", &self.removed)
+            .field("reordered", &self.reordered)
+            .finish()
+    }
+}
+
+impl<T> Default for ListChanges<T> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            reordered: false,
+        }
+    }
+}
+
+/// A single page of a [`ManifestList`], as returned by [`ManifestList::page`].
+pub struct Page<'a, T> {
+    /// The items on this page, in list order.
+    pub ids: &'a [Id<T>],
+    /// The index of this page, starting from 0.
+    ///
+    /// Requests for pages past the end are clamped to the last page.
+    pub index: usize,
+    /// The total number of pages, which is at least 1, even for an empty list.
+    pub page_count: usize,
+    /// The total number of items in the list.
+    pub total: usize,
+}
+
+impl<'a, T> std::fmt::Debug for Page<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Page")
+            .field("ids", &self.ids)
+            .field("index", &self.index)
+            .field("page_count", &self.page_count)
+            .field("total", &self.total)
+            .finish()
+    }
+}
+
+/// A filtered, sorted and paginated list of the items in the manifest `M`.
+///
+/// See the [module docs](crate::ui_list) for more information.
+pub struct ManifestList<M: IterableManifest> {
+    filter: Option<Filter<M>>,
+    sort: Option<Comparison<M>>,
+    page_size: usize,
+    ids: Vec<Id<M::Item>>,
+    changes: ListChanges<M::Item>,
+    last_refreshed: Option<Tick>,
+    dirty: bool,
+}
+
+impl<M: IterableManifest> ManifestList<M> {
+    /// Creates a list of every item in the manifest, sorted by [`Id`], on a single page.
+    ///
+    /// The list is empty until it is first refreshed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            filter: None,
+            sort: None,
+            page_size: usize::MAX,
+            ids: Vec::new(),
+            changes: ListChanges::default(),
+            last_refreshed: None,
+            dirty: true,
+        }
+    }
+
+    /// Only includes the items for which `filter` returns true.
+    #[must_use]
+    pub fn filtered_by(
+        mut self,
+        filter: impl Fn(&M::Item) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filter = Some(Arc::new(filter));
+        self.dirty = true;
+        self
+    }
+
+    /// Sorts the items using the supplied comparison function, ordering equal items by [`Id`].
+    #[must_use]
+    pub fn sorted_by(
+        mut self,
+        compare: impl Fn(&M::Item, &M::Item) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        self.sort = Some(Arc::new(compare));
+        self.dirty = true;
+        self
+    }
+
+    /// Sorts the items by the key returned by `key`, ordering items with equal keys by [`Id`].
+    #[must_use]
+    pub fn sorted_by_key<K: Ord>(
+        self,
+        key: impl Fn(&M::Item) -> K + Send + Sync + 'static,
+    ) -> Self {
+        self.sorted_by(move |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Splits the list into pages of `page_size` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is 0.
+    #[must_use]
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        assert!(
+            page_size > 0,
+            "The page size of a ManifestList must be at least 1."
+        );
+        self.page_size = page_size;
+        self
+    }
+
+    /// Marks the list as out of date, so it is rebuilt the next time it is refreshed.
+    ///
+    /// Call this when any state used by the filter or sort (other than the manifest itself) changes.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Rebuilds the list if the `manifest` has changed or the list was invalidated.
+    ///
+    /// Returns true if the contents or order of the list changed, in which case [`ManifestList::changes`] describes how.
+    pub fn refresh(&mut self, manifest: &Res<M>) -> bool {
+        if !self.dirty && self.last_refreshed == Some(manifest.last_changed()) {
+            return false;
+        }
+
+        self.last_refreshed = Some(manifest.last_changed());
+        self.rebuild(manifest)
+    }
+
+    /// Rebuilds the list from the supplied `manifest`, regardless of whether it has changed.
+    ///
+    /// Returns true if the contents or order of the list changed.
+    pub fn rebuild(&mut self, manifest: &M) -> bool {
+        let mut ids: Vec<(Id<M::Item>, &M::Item)> = manifest
+            .iter()
+            .filter(|(_, item)| self.filter.as_ref().is_none_or(|filter| filter(item)))
+            .collect();
+        ids.sort_by(|(a_id, a), (b_id, b)| {
+            self.sort
+                .as_ref()
+                .map_or(Ordering::Equal, |sort| sort(a, b))
+                .then_with(|| a_id.cmp(b_id))
+        });
+        let ids: Vec<Id<M::Item>> = ids.into_iter().map(|(id, _)| id).collect();
+
+        let old: IdSet<M::Item> = self.ids.iter().copied().collect();
+        let new: IdSet<M::Item> = ids.iter().copied().collect();
+        let kept_before = self.ids.iter().filter(|id| new.contains(*id));
+        let kept_after = ids.iter().filter(|id| old.contains(*id));
+        self.changes = ListChanges {
+            added: ids
+                .iter()
+                .filter(|id| !old.contains(*id))
+                .copied()
+                .collect(),
+            removed: self
+                .ids
+                .iter()
+                .filter(|id| !new.contains(*id))
+                .copied()
+                .collect(),
+            reordered: !kept_before.eq(kept_after),
+        };
+
+        self.ids = ids;
+        self.dirty = false;
+        !self.changes.is_empty()
+    }
+
+    /// The [`Id`]s of every item in the list, in order, as of the last time it was rebuilt.
+    #[must_use]
+    pub fn ids(&self) -> &[Id<M::Item>] {
+        &self.ids
+    }
+
+    /// How the list changed the last time it was rebuilt.
+    #[must_use]
+    pub fn changes(&self) -> &ListChanges<M::Item> {
+        &self.changes
+    }
+
+    /// The number of items in the list.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns true if the list has no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The number of pages, which is at least 1, even for an empty list.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.ids.len().div_ceil(self.page_size).max(1)
+    }
+
+    /// Returns the page with the given `index`, starting from 0.
+    ///
+    /// Indexes past the last page return the last page, so a page index stays valid when items are removed.
+    #[must_use]
+    pub fn page(&self, index: usize) -> Page<'_, M::Item> {
+        let page_count = self.page_count();
+        let index = index.min(page_count - 1);
+        let start = index.saturating_mul(self.page_size).min(self.ids.len());
+        let end = start.saturating_add(self.page_size).min(self.ids.len());
+
+        Page {
+            ids: &self.ids[start..end],
+            index,
+            page_count,
+            total: self.ids.len(),
+        }
+    }
+
+    /// Returns the index of the page containing the item with the given `id`, if it is in the list.
+    ///
+    /// This is useful for keeping the selected item visible after the list is rebuilt.
+    #[must_use]
+    pub fn page_of(&self, id: Id<M::Item>) -> Option<usize> {
+        self.position(id).map(|position| position / self.page_size)
+    }
+
+    /// Returns the position of the item with the given `id` in the list, if it is in the list.
+    #[must_use]
+    pub fn position(&self, id: Id<M::Item>) -> Option<usize> {
+        self.ids.iter().position(|other| *other == id)
+    }
+}
+
+impl<M: IterableManifest> Default for ManifestList<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: IterableManifest> Clone for ManifestList<M> {
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            sort: self.sort.clone(),
+            page_size: self.page_size,
+            ids: self.ids.clone(),
+            changes: self.changes.clone(),
+            last_refreshed: self.last_refreshed,
+            dirty: self.dirty,
+        }
+    }
+}
+
+impl<M: IterableManifest> std::fmt::Debug for ManifestList<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestList")
+            .field("ids", &self.ids)
+            .field("page_size", &self.page_size)
+            .field("changes", &self.changes)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
@@ -0,0 +1,285 @@
+//! Tracking which manifest items are referenced by live entities.
+//!
+//! Removing an item from a [`MutableManifest`](crate::manifest::MutableManifest) while entities still store its [`Id`]
+//! leaves those entities with a dangling reference: every later lookup quietly returns [`None`].
+//! The [`ManifestUsagePlugin`] counts how many entities have each `Id<M::Item>` component,
+//! and logs a warning whenever the manifest changes such that one of those items can no longer be found.
+//!
+//! ```rust,ignore
+//! app.add_plugins(ManifestUsagePlugin::<ItemManifest>::default());
+//!
+//! fn remove_unused_items(usage: Res<ManifestUsage<ItemManifest>>, mut items: ResMut<ItemManifest>) {
+//!     if usage.usage_count(OLD_SWORD) == 0 {
+//!         items.remove(&OLD_SWORD).unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! Items can also be removed safely with [`MutableManifest::remove_if_unused`],
+//! or with [`try_remove`], which checks the world directly and can clean up the referencing entities according to a [`RemovalPolicy`].
+//!
+//! Only [`Id`] components are counted: ids stored inside other components or resources are not tracked.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::log::warn;
+use bevy::utils::HashMap;
+
+use crate::identifier::{Id, IdMap, IdSet};
+use crate::manifest::{Manifest, ManifestModificationError, MutableManifest};
+use crate::manifest_lock::ManifestLock;
+
+/// The number of live entities with each `Id<M::Item>` component.
+///
+/// This resource is kept up to date by the [`ManifestUsagePlugin`], in [`PostUpdate`].
+///
+/// # Example
+///
+/// ```
+/// # use leafwing_manifest::test_utils::{TestItem as Item, TestItemManifest as ItemManifest};
+/// use bevy::prelude::*;
+/// use leafwing_manifest::{
+///     identifier::Id,
+///     usage::{ManifestUsage, ManifestUsagePlugin},
+/// };
+///
+/// let sword = Id::<Item>::from_name("sword");
+/// let shield = Id::<Item>::from_name("shield");
+///
+/// let mut app = App::new();
+/// app.add_plugins(ManifestUsagePlugin::<ItemManifest>::default())
+///     .insert_resource(ItemManifest::from_items([Item::new("sword", 10)]));
+///
+/// let first = app.world.spawn(sword).id();
+/// let second = app.world.spawn(sword).id();
+/// app.update();
+///
+/// let usage = app.world.resource::<ManifestUsage<ItemManifest>>();
+/// assert_eq!(usage.usage_count(sword), 2);
+/// assert!(!usage.is_used(shield));
+///
+/// // Changing or removing the component moves the count along with it.
+/// *app.world.get_mut::<Id<Item>>(first).unwrap() = shield;
+/// app.world.entity_mut(second).remove::<Id<Item>>();
+/// app.update();
+///
+/// let usage = app.world.resource::<ManifestUsage<ItemManifest>>();
+/// assert_eq!(usage.usage_count(sword), 0);
+/// assert_eq!(usage.usage_count(shield), 1);
+///
+/// // The shield is referenced, but was never part of the manifest.
+/// let manifest = app.world.resource::<ItemManifest>();
+/// assert_eq!(usage.dangling_ids(manifest), vec![shield]);
+/// ```
+#[derive(Resource)]
+pub struct ManifestUsage<M: Manifest> {
+    counts: IdMap<M::Item, usize>,
+    entities: HashMap<Entity, Id<M::Item>>,
+}
+
+impl<M: Manifest> ManifestUsage<M> {
+    /// Returns the number of entities whose `Id<M::Item>` component refers to the item with the given `id`.
+    #[must_use]
+    pub fn usage_count(&self, id: Id<M::Item>) -> usize {
+        self.counts.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Returns true if any entity refers to the item with the given `id`.
+    #[must_use]
+    pub fn is_used(&self, id: Id<M::Item>) -> bool {
+        self.counts.contains_key(&id)
+    }
+
+    /// Iterates over every referenced item and its usage count, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<M::Item>, usize)> + '_ {
+        self.counts.iter().map(|(id, count)| (*id, *count))
+    }
+
+    /// Returns the referenced items which cannot be found in the `manifest`, sorted by [`Id`].
+    #[must_use]
+    pub fn dangling_ids(&self, manifest: &M) -> Vec<Id<M::Item>> {
+        let mut dangling: Vec<Id<M::Item>> = self
+            .counts
+            .keys()
+            .copied()
+            .filter(|id| manifest.get(*id).is_none())
+            .collect();
+        dangling.sort();
+        dangling
+    }
+
+    /// Records that `entity` now refers to the item with the given `id`.
+    fn track(&mut self, entity: Entity, id: Id<M::Item>) {
+        if let Some(previous) = self.entities.insert(entity, id) {
+            self.decrement(previous);
+        }
+        *self.counts.entry(id).or_default() += 1;
+    }
+
+    /// Records that `entity` no longer refers to any item.
+    fn untrack(&mut self, entity: Entity) {
+        if let Some(previous) = self.entities.remove(&entity) {
+            self.decrement(previous);
+        }
+    }
+
+    fn decrement(&mut self, id: Id<M::Item>) {
+        if let Some(count) = self.counts.get_mut(&id) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&id);
+            }
+        }
+    }
+}
+
+impl<M: Manifest> Default for ManifestUsage<M> {
+    fn default() -> Self {
+        Self {
+            counts: IdMap::default(),
+            entities: HashMap::default(),
+        }
+    }
+}
+
+impl<M: Manifest> std::fmt::Debug for ManifestUsage<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestUsage")
+            .field("counts", &self.counts)
+            .finish()
+    }
+}
+
+/// A plugin which tracks the [`ManifestUsage`] of the manifest `M`,
+/// and warns when referenced items are removed from it.
+pub struct ManifestUsagePlugin<M: Manifest> {
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: Manifest> Default for ManifestUsagePlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: Manifest> Plugin for ManifestUsagePlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ManifestUsage<M>>().add_systems(
+            PostUpdate,
+            (
+                track_manifest_usage::<M>,
+                warn_on_dangling_ids::<M>
+                    .run_if(resource_exists::<M>.and_then(resource_changed::<M>)),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Updates the [`ManifestUsage`] of the manifest `M` based on the `Id<M::Item>` components that were added, changed or removed.
+pub fn track_manifest_usage<M: Manifest>(
+    mut usage: ResMut<ManifestUsage<M>>,
+    mut removed: RemovedComponents<Id<M::Item>>,
+    changed: Query<(Entity, &Id<M::Item>), Changed<Id<M::Item>>>,
+) {
+    // Removals are handled first, so components that were removed and then re-added are still counted.
+    for entity in removed.read() {
+        usage.untrack(entity);
+    }
+
+    for (entity, id) in changed.iter() {
+        usage.track(entity, *id);
+    }
+}
+
+/// Logs a warning for each item that is still referenced by an entity, but can no longer be found in the manifest `M`.
+///
+/// Each dangling item is only reported once, until it is added back to the manifest.
+pub fn warn_on_dangling_ids<M: Manifest>(
+    manifest: Res<M>,
+    usage: Res<ManifestUsage<M>>,
+    mut reported: Local<IdSet<M::Item>>,
+) {
+    let dangling = usage.dangling_ids(&manifest);
+    reported.retain(|id| dangling.contains(id));
+
+    for id in dangling {
+        if reported.insert(id) {
+            warn!(
+                "{id:?} was removed from the {} manifest, but is still used by {} entities.",
+                std::any::type_name::<M>(),
+                usage.usage_count(id)
+            );
+        }
+    }
+}
+
+/// What [`try_remove`] should do with entities that still refer to the item being removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemovalPolicy {
+    /// Fail with [`ManifestModificationError::StillInUse`], leaving the manifest and entities unchanged.
+    #[default]
+    Fail,
+    /// Despawn the referencing entities, along with their descendants.
+    Despawn,
+    /// Remove the `Id<M::Item>` component from the referencing entities.
+    StripId,
+}
+
+/// Removes the item with the given `id` from the manifest resource `M`,
+/// handling any entities that still have an `Id<M::Item>` component referring to it according to the `policy`.
+///
+/// Unlike [`MutableManifest::remove_if_unused`], this checks every entity in the `world`,
+/// so does not require the [`ManifestUsagePlugin`].
+/// Entities are only despawned or modified if the item was successfully removed.
+///
+/// If the manifest resource does not exist, [`ManifestModificationError::NotFound`] is returned.
+/// If the [`ManifestLock`] is locked, [`ManifestModificationError::Locked`] is returned.
+pub fn try_remove<M: MutableManifest>(
+    world: &mut World,
+    id: Id<M::Item>,
+    policy: RemovalPolicy,
+) -> Result<Id<M::Item>, ManifestModificationError<M>> {
+    if let Some(lock) = world.get_resource::<ManifestLock>() {
+        lock.check::<M>()?;
+    }
+
+    let referencing: Vec<Entity> = world
+        .query::<(Entity, &Id<M::Item>)>()
+        .iter(world)
+        .filter(|(_, entity_id)| **entity_id == id)
+        .map(|(entity, _)| entity)
+        .collect();
+
+    if policy == RemovalPolicy::Fail && !referencing.is_empty() {
+        return Err(ManifestModificationError::StillInUse {
+            count: referencing.len(),
+        });
+    }
+
+    let Some(mut manifest) = world.get_resource_mut::<M>() else {
+        return Err(ManifestModificationError::NotFound(id));
+    };
+    let removed = manifest.remove(&id)?;
+
+    for entity in referencing {
+        // Entities may have already been despawned as descendants of another referencing entity.
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        match policy {
+            RemovalPolicy::Fail => {}
+            RemovalPolicy::Despawn => entity.despawn_recursive(),
+            RemovalPolicy::StripId => {
+                entity.remove::<Id<M::Item>>();
+            }
+        }
+    }
+
+    Ok(removed)
+}
@@ -0,0 +1,302 @@
+//! Collision-proof identifiers backed by [`Uuid`]s.
+//!
+//! [`Id`]s are hashes of names: renaming an item changes its [`Id`], and two names can (rarely) collide.
+//! Teams that mirror content from a CMS or database usually already have a UUID for each entry,
+//! which never changes when the entry is renamed.
+//! A [`UuidId`] wraps such a UUID, and can be used as a [`GenericManifest::Key`](crate::manifest::GenericManifest::Key),
+//! stored as a component, reflected and serialized just like an [`Id`].
+//!
+//! The [`UuidNameMap`] maps between the human-readable names used while authoring content and their UUIDs.
+//!
+//! This module requires the `uuid` feature.
+
+use std::any::type_name;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use bevy::prelude::Component;
+use bevy::reflect::Reflect;
+use bevy::utils::{get_short_name, HashMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+pub use uuid::Uuid;
+
+use crate::identifier::Id;
+
+/// A unique identifier of type `T`, backed by a [`Uuid`].
+///
+/// This is serialized as a hyphenated UUID string, such as `"67e55044-10b1-426f-9247-bb680e5fe0c8"`.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::uuid_id::{Uuid, UuidId};
+///
+/// struct Item;
+///
+/// let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+/// let sword: UuidId<Item> = UuidId::new(uuid);
+/// assert_eq!(sword.uuid(), uuid);
+///
+/// // UUIDs can also be derived deterministically from names.
+/// assert_eq!(UuidId::<Item>::from_name("sword"), UuidId::from_name("sword"));
+/// ```
+#[derive(Component, Reflect)]
+pub struct UuidId<T> {
+    /// The UUID, stored as an integer so that it can be reflected.
+    value: u128,
+
+    /// Marker to make the compiler happy
+    ///
+    /// Using `fn() -> T` keeps this type [`Send`] and [`Sync`], regardless of `T`.
+    #[reflect(ignore)]
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> UuidId<T> {
+    /// The namespace used by [`UuidId::from_name`].
+    pub const NAMESPACE: Uuid = Uuid::from_u128(0x6c65_6166_7769_6e67_6d61_6e69_6665_7374);
+
+    /// Creates a new identifier from a [`Uuid`].
+    #[must_use]
+    pub const fn new(uuid: Uuid) -> Self {
+        Self::from_u128(uuid.as_u128())
+    }
+
+    /// Creates a new identifier from the integer representation of a [`Uuid`].
+    #[must_use]
+    pub const fn from_u128(value: u128) -> Self {
+        UuidId {
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a deterministic, name-based (version 5) identifier from a human-readable name.
+    ///
+    /// This is useful for content which was authored by name before being assigned a UUID.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        Self::new(Uuid::new_v5(&Self::NAMESPACE, name.as_bytes()))
+    }
+
+    /// Returns the underlying [`Uuid`].
+    #[must_use]
+    pub const fn uuid(&self) -> Uuid {
+        Uuid::from_u128(self.value)
+    }
+
+    /// Returns the integer representation of the underlying [`Uuid`].
+    #[must_use]
+    pub const fn as_u128(&self) -> u128 {
+        self.value
+    }
+
+    /// Returns an [`Id`] derived from the hyphenated form of the UUID.
+    ///
+    /// This allows UUID-identified items to be stored in manifests keyed by [`Id`],
+    /// although the collision resistance of [`Id`]s is lower than that of UUIDs.
+    #[must_use]
+    pub fn to_id(&self) -> Id<T> {
+        Id::from_name(&self.uuid().hyphenated().to_string())
+    }
+}
+
+impl<T> Debug for UuidId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("UuidId<{}>", get_short_name(type_name::<T>())))
+            .field("uuid", &self.uuid())
+            .finish()
+    }
+}
+
+impl<T> Display for UuidId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UuidId<{}>({})",
+            get_short_name(type_name::<T>()),
+            self.uuid()
+        )
+    }
+}
+
+impl<T> PartialEq for UuidId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for UuidId<T> {}
+
+impl<T> PartialOrd for UuidId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for UuidId<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for UuidId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> Clone for UuidId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UuidId<T> {}
+
+impl<T> From<Uuid> for UuidId<T> {
+    fn from(uuid: Uuid) -> Self {
+        Self::new(uuid)
+    }
+}
+
+impl<T> From<UuidId<T>> for Uuid {
+    fn from(id: UuidId<T>) -> Self {
+        id.uuid()
+    }
+}
+
+impl<T> Serialize for UuidId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.uuid().serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for UuidId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Uuid::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// A bidirectional mapping between human-readable names and [`UuidId`]s.
+///
+/// This is serialized as a map from names to UUIDs, so it can be stored alongside the content it describes.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::uuid_id::{UuidId, UuidNameMap};
+///
+/// struct Item;
+///
+/// let mut names = UuidNameMap::<Item>::default();
+/// let sword = UuidId::from_name("sword");
+/// names.insert("sword", sword);
+///
+/// assert_eq!(names.uuid_of("sword"), Some(sword));
+/// assert_eq!(names.name_of(sword), Some("sword"));
+/// ```
+pub struct UuidNameMap<T> {
+    uuids: HashMap<String, UuidId<T>>,
+    names: HashMap<UuidId<T>, String>,
+}
+
+impl<T> UuidNameMap<T> {
+    /// Associates the `name` with the `uuid`, replacing any previous association for either.
+    pub fn insert(&mut self, name: impl Into<String>, uuid: UuidId<T>) {
+        let name = name.into();
+        if let Some(previous_uuid) = self.uuids.insert(name.clone(), uuid) {
+            self.names.remove(&previous_uuid);
+        }
+        if let Some(previous_name) = self.names.insert(uuid, name) {
+            self.uuids.remove(&previous_name);
+        }
+    }
+
+    /// Returns the [`UuidId`] associated with the `name`, if any.
+    #[must_use]
+    pub fn uuid_of(&self, name: &str) -> Option<UuidId<T>> {
+        self.uuids.get(name).copied()
+    }
+
+    /// Returns the name associated with the `uuid`, if any.
+    #[must_use]
+    pub fn name_of(&self, uuid: UuidId<T>) -> Option<&str> {
+        self.names.get(&uuid).map(String::as_str)
+    }
+
+    /// Removes the association for the `name`, returning its [`UuidId`].
+    pub fn remove(&mut self, name: &str) -> Option<UuidId<T>> {
+        let uuid = self.uuids.remove(name)?;
+        self.names.remove(&uuid);
+        Some(uuid)
+    }
+
+    /// Returns the number of names.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.uuids.len()
+    }
+
+    /// Returns true if there are no names.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.uuids.is_empty()
+    }
+
+    /// Iterates over the names and their [`UuidId`]s, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, UuidId<T>)> {
+        self.uuids.iter().map(|(name, uuid)| (name.as_str(), *uuid))
+    }
+}
+
+impl<T> Default for UuidNameMap<T> {
+    fn default() -> Self {
+        Self {
+            uuids: HashMap::default(),
+            names: HashMap::default(),
+        }
+    }
+}
+
+impl<T> Debug for UuidNameMap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Clone for UuidNameMap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            uuids: self.uuids.clone(),
+            names: self.names.clone(),
+        }
+    }
+}
+
+impl<T> FromIterator<(String, UuidId<T>)> for UuidNameMap<T> {
+    fn from_iter<I: IntoIterator<Item = (String, UuidId<T>)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        for (name, uuid) in iter {
+            map.insert(name, uuid);
+        }
+        map
+    }
+}
+
+impl<T> Serialize for UuidNameMap<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(&str, UuidId<T>)> = self.iter().collect();
+        // Sorting keeps the serialized output stable, which makes diffs readable.
+        entries.sort_by_key(|(name, _)| *name);
+        serializer.collect_map(entries)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for UuidNameMap<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = std::collections::BTreeMap::<String, UuidId<T>>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
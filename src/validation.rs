@@ -0,0 +1,124 @@
+//! A shared report of the problems found in the content of manifests.
+//!
+//! Content checks such as the [`DuplicateDetectionPlugin`](crate::duplicates::DuplicateDetectionPlugin),
+//! the [`ItemAliasPlugin`](crate::aliases::ItemAliasPlugin) and the [`ManifestLintPlugin`](crate::lints::ManifestLintPlugin)
+//! all record their findings in the [`ValidationReport`] resource, so they can be reviewed in one place:
+//!
+//! ```rust,ignore
+//! fn check_content(report: Res<ValidationReport>) {
+//!     assert!(report.is_empty(), "{}", *report);
+//! }
+//! ```
+
+use std::fmt::Display;
+
+use bevy::ecs::prelude::*;
+
+use crate::aliases::AliasLookup;
+use crate::duplicates::DuplicateGroup;
+use crate::identifier::Id;
+use crate::lints::LintWarning;
+use crate::manifest::Manifest;
+use crate::name_registry::NameRegistry;
+
+/// Problems found in the content of manifests, such as by a [`DuplicateDetectionPlugin`](crate::duplicates::DuplicateDetectionPlugin), an [`ItemAliasPlugin`](crate::aliases::ItemAliasPlugin)
+/// or a [`ManifestLintPlugin`](crate::lints::ManifestLintPlugin).
+///
+/// These are not errors: the manifests are still usable, but the content is probably not what was intended.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Each group of duplicated items, sorted by type name.
+    pub duplicates: Vec<DuplicateGroup>,
+    /// Each deprecated alias that was used to look up an item, in the order they were first used.
+    pub alias_lookups: Vec<AliasLookup>,
+    /// Each broken lint rule, sorted by type name.
+    pub lints: Vec<LintWarning>,
+}
+
+impl ValidationReport {
+    /// Replaces the duplicate groups recorded for the manifest `M`.
+    pub fn record_duplicates<M: Manifest>(&mut self, groups: &[Vec<Id<M::Item>>]) {
+        let type_name = std::any::type_name::<M>();
+        self.duplicates.retain(|group| group.type_name != type_name);
+
+        let start = self
+            .duplicates
+            .partition_point(|group| group.type_name < type_name);
+        let groups = groups.iter().map(|ids| DuplicateGroup {
+            type_name,
+            items: ids
+                .iter()
+                .map(|id| NameRegistry::name_of(*id).unwrap_or_else(|| format!("{id:?}")))
+                .collect(),
+        });
+        self.duplicates.splice(start..start, groups);
+    }
+
+    /// Returns the duplicate groups recorded for the manifest `M`.
+    pub fn duplicates_of<M: Manifest>(&self) -> impl Iterator<Item = &DuplicateGroup> {
+        let type_name = std::any::type_name::<M>();
+        self.duplicates
+            .iter()
+            .filter(move |group| group.type_name == type_name)
+    }
+
+    /// Replaces the lint warnings recorded for the manifest `M`.
+    pub fn record_lints<M: Manifest>(&mut self, warnings: Vec<LintWarning>) {
+        let type_name = std::any::type_name::<M>();
+        self.lints.retain(|warning| warning.type_name != type_name);
+
+        let start = self
+            .lints
+            .partition_point(|warning| warning.type_name < type_name);
+        self.lints.splice(start..start, warnings);
+    }
+
+    /// Returns the lint warnings recorded for the manifest `M`.
+    pub fn lints_of<M: Manifest>(&self) -> impl Iterator<Item = &LintWarning> {
+        let type_name = std::any::type_name::<M>();
+        self.lints
+            .iter()
+            .filter(move |warning| warning.type_name == type_name)
+    }
+
+    /// Returns true if no problems were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.duplicates.is_empty() && self.alias_lookups.is_empty() && self.lints.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No manifest validation problems found.");
+        }
+
+        if !self.duplicates.is_empty() {
+            writeln!(f, "{} groups of duplicated items:", self.duplicates.len())?;
+            for group in &self.duplicates {
+                writeln!(f, "  {}: {}", group.type_name, group.items.join(", "))?;
+            }
+        }
+
+        if !self.alias_lookups.is_empty() {
+            writeln!(f, "{} deprecated aliases used:", self.alias_lookups.len())?;
+            for lookup in &self.alias_lookups {
+                writeln!(
+                    f,
+                    "  {}: {} (now {})",
+                    lookup.type_name, lookup.alias, lookup.target
+                )?;
+            }
+        }
+
+        if !self.lints.is_empty() {
+            writeln!(f, "{} lint warnings:", self.lints.len())?;
+            for warning in &self.lints {
+                writeln!(f, "  {warning}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,556 @@
+//! Reusable value types for the fields of raw items: curves, ranges, dice and percentages.
+//!
+//! These come up in almost every game's manifests, and each has a compact, hand-editable form:
+//!
+//! ```ron
+//! (
+//!     name: "goblin",
+//!     // Keyframes of (level, health), linearly interpolated between.
+//!     health: [(1, 10), (10, 80), (20, 200)],
+//!     // Either a single value, or a (min, max) pair.
+//!     gold: (5, 12),
+//!     damage: "1d6+2",
+//!     dodge_chance: "15%",
+//! )
+//! ```
+//!
+//! All of the types validate their contents when deserialized, so mistakes such as unsorted keyframes
+//! or a range with its bounds swapped are reported as loading errors.
+//!
+//! This module requires the `value_types` feature.
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Errors that can occur when constructing or parsing the value types in this module.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValueError {
+    /// A [`Curve`] had no keyframes.
+    #[error("A curve must have at least one keyframe")]
+    EmptyCurve,
+    /// The keyframes of a [`Curve`] were not in strictly increasing order, or were not finite.
+    #[error("The keyframes of a curve must be finite and sorted by strictly increasing x values, but {0} was out of order")]
+    UnsortedKeyframes(f32),
+    /// The minimum of a [`ValueRange`] was greater than its maximum.
+    #[error("The minimum of a range must not be greater than its maximum")]
+    InvertedRange,
+    /// A [`DiceExpr`] could not be parsed.
+    #[error("{0:?} is not a valid dice expression, such as \"2d6+3\"")]
+    InvalidDice(String),
+    /// A [`Percent`] could not be parsed.
+    #[error("{0:?} is not a valid percentage, such as \"25%\"")]
+    InvalidPercent(String),
+}
+
+/// How a [`Curve`] is evaluated between its keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Interpolation {
+    /// Values are linearly interpolated between keyframes.
+    #[default]
+    Linear,
+    /// Each keyframe's value is held until the next keyframe.
+    Step,
+}
+
+/// The forms a [`Curve`] can be written in.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum CurveRepr {
+    Keyframes(Vec<(f32, f32)>),
+    Full {
+        keyframes: Vec<(f32, f32)>,
+        #[serde(default)]
+        interpolation: Interpolation,
+    },
+}
+
+/// A value which varies with some input, such as a stat which grows with level, defined by keyframes.
+///
+/// Curves are written as a list of `(x, y)` keyframes, which are linearly interpolated between:
+/// `[(1, 10), (10, 80)]`.
+/// To hold each value until the next keyframe instead, write `(keyframes: [(1, 10), (10, 80)], interpolation: Step)`.
+///
+/// Inputs outside of the keyframes are clamped to the first or last keyframe.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::value_types::Curve;
+///
+/// let health = Curve::new(vec![(1.0, 10.0), (11.0, 110.0)]).unwrap();
+/// assert_eq!(health.evaluate(6.0), 60.0);
+/// assert_eq!(health.evaluate(0.0), 10.0);
+/// assert_eq!(health.evaluate(50.0), 110.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CurveRepr", into = "CurveRepr")]
+pub struct Curve {
+    keyframes: Vec<(f32, f32)>,
+    interpolation: Interpolation,
+}
+
+impl Curve {
+    /// Creates a linearly interpolated curve from its `(x, y)` keyframes.
+    ///
+    /// The keyframes must be finite, and sorted by strictly increasing `x`.
+    pub fn new(keyframes: Vec<(f32, f32)>) -> Result<Self, ValueError> {
+        Self::with_interpolation(keyframes, Interpolation::Linear)
+    }
+
+    /// Creates a curve from its `(x, y)` keyframes, with the given `interpolation`.
+    ///
+    /// The keyframes must be finite, and sorted by strictly increasing `x`.
+    pub fn with_interpolation(
+        keyframes: Vec<(f32, f32)>,
+        interpolation: Interpolation,
+    ) -> Result<Self, ValueError> {
+        if keyframes.is_empty() {
+            return Err(ValueError::EmptyCurve);
+        }
+
+        let mut previous = f32::NEG_INFINITY;
+        for &(x, y) in &keyframes {
+            if !x.is_finite() || !y.is_finite() || x <= previous {
+                return Err(ValueError::UnsortedKeyframes(x));
+            }
+            previous = x;
+        }
+
+        Ok(Self {
+            keyframes,
+            interpolation,
+        })
+    }
+
+    /// Creates a curve with the same value everywhere.
+    #[must_use]
+    pub fn constant(value: f32) -> Self {
+        Self {
+            keyframes: vec![(0.0, value)],
+            interpolation: Interpolation::Linear,
+        }
+    }
+
+    /// Returns the value of the curve at `x`.
+    #[must_use]
+    pub fn evaluate(&self, x: f32) -> f32 {
+        // The index of the first keyframe after `x`.
+        let next = self.keyframes.partition_point(|(key, _)| *key <= x);
+        if next == 0 {
+            return self.keyframes[0].1;
+        }
+        let (x0, y0) = self.keyframes[next - 1];
+        let Some(&(x1, y1)) = self.keyframes.get(next) else {
+            return y0;
+        };
+
+        match self.interpolation {
+            Interpolation::Linear => y0 + (y1 - y0) * (x - x0) / (x1 - x0),
+            Interpolation::Step => y0,
+        }
+    }
+
+    /// The keyframes of the curve, sorted by `x`.
+    #[must_use]
+    pub fn keyframes(&self) -> &[(f32, f32)] {
+        &self.keyframes
+    }
+
+    /// How the curve is evaluated between keyframes.
+    #[must_use]
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// The range of `x` values covered by the keyframes.
+    #[must_use]
+    pub fn domain(&self) -> RangeInclusive<f32> {
+        self.keyframes[0].0..=self.keyframes[self.keyframes.len() - 1].0
+    }
+}
+
+impl TryFrom<CurveRepr> for Curve {
+    type Error = ValueError;
+
+    fn try_from(repr: CurveRepr) -> Result<Self, ValueError> {
+        match repr {
+            CurveRepr::Keyframes(keyframes) => Curve::new(keyframes),
+            CurveRepr::Full {
+                keyframes,
+                interpolation,
+            } => Curve::with_interpolation(keyframes, interpolation),
+        }
+    }
+}
+
+impl From<Curve> for CurveRepr {
+    fn from(curve: Curve) -> Self {
+        match curve.interpolation {
+            Interpolation::Linear => CurveRepr::Keyframes(curve.keyframes),
+            interpolation => CurveRepr::Full {
+                keyframes: curve.keyframes,
+                interpolation,
+            },
+        }
+    }
+}
+
+/// The forms a [`ValueRange`] can be written in.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RangeRepr<T> {
+    Single(T),
+    Pair((T, T)),
+    Fields { min: T, max: T },
+}
+
+/// An inclusive range of values, such as the amount of gold an enemy drops.
+///
+/// Ranges are written as a `(min, max)` pair, or as `(min: 5, max: 12)`.
+/// A single value is a range containing only that value.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::value_types::ValueRange;
+///
+/// let gold = ValueRange::new(5, 12).unwrap();
+/// assert!(gold.contains(&7));
+/// assert_eq!(gold.clamp(20), 12);
+/// assert!(ValueRange::new(12, 5).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(
+    try_from = "RangeRepr<T>",
+    into = "RangeRepr<T>",
+    bound(
+        serialize = "T: Serialize + Clone",
+        deserialize = "T: Deserialize<'de> + PartialOrd + Clone"
+    )
+)]
+pub struct ValueRange<T> {
+    min: T,
+    max: T,
+}
+
+impl<T: PartialOrd> ValueRange<T> {
+    /// Creates a range from `min` to `max`, inclusive.
+    ///
+    /// Returns an error if `min` is greater than `max`.
+    pub fn new(min: T, max: T) -> Result<Self, ValueError> {
+        if min > max {
+            return Err(ValueError::InvertedRange);
+        }
+        Ok(Self { min, max })
+    }
+
+    /// Returns true if `value` is within the range.
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.min <= *value && *value <= self.max
+    }
+
+    /// Restricts `value` to the range.
+    #[must_use]
+    pub fn clamp(&self, value: T) -> T
+    where
+        T: Clone,
+    {
+        if value < self.min {
+            self.min.clone()
+        } else if value > self.max {
+            self.max.clone()
+        } else {
+            value
+        }
+    }
+}
+
+impl<T> ValueRange<T> {
+    /// The smallest value in the range.
+    #[must_use]
+    pub fn min(&self) -> &T {
+        &self.min
+    }
+
+    /// The largest value in the range.
+    #[must_use]
+    pub fn max(&self) -> &T {
+        &self.max
+    }
+
+    /// Converts this into a standard [`RangeInclusive`].
+    #[must_use]
+    pub fn into_inner(self) -> RangeInclusive<T> {
+        self.min..=self.max
+    }
+}
+
+impl ValueRange<f32> {
+    /// Returns the value which is the fraction `t` of the way from the minimum to the maximum.
+    ///
+    /// Pass a random number between 0.0 and 1.0 to pick a random value in the range.
+    #[must_use]
+    pub fn lerp(&self, t: f32) -> f32 {
+        self.min + (self.max - self.min) * t
+    }
+}
+
+impl<T: PartialOrd + Clone> TryFrom<RangeRepr<T>> for ValueRange<T> {
+    type Error = ValueError;
+
+    fn try_from(repr: RangeRepr<T>) -> Result<Self, ValueError> {
+        match repr {
+            RangeRepr::Single(value) => ValueRange::new(value.clone(), value),
+            RangeRepr::Pair((min, max)) | RangeRepr::Fields { min, max } => {
+                ValueRange::new(min, max)
+            }
+        }
+    }
+}
+
+impl<T> From<ValueRange<T>> for RangeRepr<T> {
+    fn from(range: ValueRange<T>) -> Self {
+        RangeRepr::Pair((range.min, range.max))
+    }
+}
+
+impl<T> From<ValueRange<T>> for RangeInclusive<T> {
+    fn from(range: ValueRange<T>) -> Self {
+        range.into_inner()
+    }
+}
+
+/// A roll of dice plus a modifier, such as `2d6+3`: roll two six-sided dice, and add three.
+///
+/// The number of dice can be left out when rolling a single die (`d20`),
+/// and a plain number is a roll with no dice (`5`).
+///
+/// This crate does not depend on a random number generator, so [`DiceExpr::roll`] takes a function which rolls a single die.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::value_types::DiceExpr;
+///
+/// let damage: DiceExpr = "2d6+3".parse().unwrap();
+/// assert_eq!(damage.min(), 5);
+/// assert_eq!(damage.max(), 15);
+/// assert_eq!(damage.average(), 10.0);
+/// // Every die rolls a 4.
+/// assert_eq!(damage.roll(|_sides| 4), 11);
+/// assert_eq!(damage.to_string(), "2d6+3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DiceExpr {
+    /// The number of dice to roll.
+    pub count: u32,
+    /// The number of sides on each die.
+    pub sides: u32,
+    /// The amount added to the total of the dice.
+    pub modifier: i32,
+}
+
+impl DiceExpr {
+    /// The lowest possible result.
+    #[must_use]
+    pub fn min(&self) -> i64 {
+        let dice = if self.sides == 0 {
+            0
+        } else {
+            i64::from(self.count)
+        };
+        dice + i64::from(self.modifier)
+    }
+
+    /// The highest possible result.
+    #[must_use]
+    pub fn max(&self) -> i64 {
+        i64::from(self.count) * i64::from(self.sides) + i64::from(self.modifier)
+    }
+
+    /// The average result.
+    #[must_use]
+    pub fn average(&self) -> f64 {
+        (self.min() + self.max()) as f64 / 2.0
+    }
+
+    /// Rolls the dice, calling `roll_die` with the number of sides for each die.
+    ///
+    /// `roll_die` should return a number from 1 to the number of sides, inclusive.
+    pub fn roll(&self, mut roll_die: impl FnMut(u32) -> u32) -> i64 {
+        let dice: i64 = (0..self.count)
+            .map(|_| i64::from(roll_die(self.sides)))
+            .sum();
+        dice + i64::from(self.modifier)
+    }
+}
+
+impl FromStr for DiceExpr {
+    type Err = ValueError;
+
+    fn from_str(source: &str) -> Result<Self, ValueError> {
+        let invalid = || ValueError::InvalidDice(source.to_string());
+        let text: String = source.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let Some((count, rest)) = text.split_once(['d', 'D']) else {
+            let modifier = text.parse().map_err(|_| invalid())?;
+            return Ok(DiceExpr {
+                count: 0,
+                sides: 0,
+                modifier,
+            });
+        };
+
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| invalid())?
+        };
+        let (sides, modifier) = match rest.find(['+', '-']) {
+            Some(index) => (
+                &rest[..index],
+                rest[index..].parse().map_err(|_| invalid())?,
+            ),
+            None => (rest, 0),
+        };
+        let sides = sides.parse().map_err(|_| invalid())?;
+        if sides == 0 {
+            return Err(invalid());
+        }
+
+        Ok(DiceExpr {
+            count,
+            sides,
+            modifier,
+        })
+    }
+}
+
+impl Display for DiceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.count == 0 {
+            return write!(f, "{}", self.modifier);
+        }
+
+        write!(f, "{}d{}", self.count, self.sides)?;
+        match self.modifier {
+            0 => Ok(()),
+            modifier if modifier > 0 => write!(f, "+{modifier}"),
+            modifier => write!(f, "{modifier}"),
+        }
+    }
+}
+
+impl TryFrom<String> for DiceExpr {
+    type Error = ValueError;
+
+    fn try_from(source: String) -> Result<Self, ValueError> {
+        source.parse()
+    }
+}
+
+impl From<DiceExpr> for String {
+    fn from(dice: DiceExpr) -> Self {
+        dice.to_string()
+    }
+}
+
+/// A percentage, such as a chance to dodge or a bonus to damage.
+///
+/// Percentages are written either as a string with a percent sign (`"15%"`), or as a plain number of percent (`15`).
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::value_types::Percent;
+///
+/// let bonus: Percent = "15%".parse().unwrap();
+/// assert_eq!(bonus.fraction(), 0.15);
+/// assert_eq!(bonus.percent(), 15.0);
+/// assert_eq!(bonus.apply(200.0), 30.0);
+/// assert_eq!(bonus.to_string(), "15%");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[serde(try_from = "PercentRepr", into = "String")]
+pub struct Percent(f32);
+
+/// The forms a [`Percent`] can be written in.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PercentRepr {
+    Number(f32),
+    Text(String),
+}
+
+impl Percent {
+    /// Creates a percentage from a number of percent, such as `15.0` for 15%.
+    #[must_use]
+    pub fn new(percent: f32) -> Self {
+        Self(percent)
+    }
+
+    /// Creates a percentage from a fraction, such as `0.15` for 15%.
+    #[must_use]
+    pub fn from_fraction(fraction: f32) -> Self {
+        Self(fraction * 100.0)
+    }
+
+    /// The percentage as a fraction, such as `0.15` for 15%.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        self.0 / 100.0
+    }
+
+    /// The percentage as a number of percent, such as `15.0` for 15%.
+    #[must_use]
+    pub fn percent(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns this percentage of `value`.
+    #[must_use]
+    pub fn apply(&self, value: f32) -> f32 {
+        value * self.0 / 100.0
+    }
+}
+
+impl FromStr for Percent {
+    type Err = ValueError;
+
+    fn from_str(source: &str) -> Result<Self, ValueError> {
+        let number = source.trim().strip_suffix('%').unwrap_or(source).trim();
+        match number.parse::<f32>() {
+            Ok(percent) if percent.is_finite() => Ok(Percent::new(percent)),
+            _ => Err(ValueError::InvalidPercent(source.to_string())),
+        }
+    }
+}
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.percent())
+    }
+}
+
+impl TryFrom<PercentRepr> for Percent {
+    type Error = ValueError;
+
+    fn try_from(repr: PercentRepr) -> Result<Self, ValueError> {
+        match repr {
+            PercentRepr::Number(percent) if percent.is_finite() => Ok(Percent::new(percent)),
+            PercentRepr::Number(percent) => Err(ValueError::InvalidPercent(percent.to_string())),
+            PercentRepr::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<Percent> for String {
+    fn from(percent: Percent) -> Self {
+        percent.to_string()
+    }
+}
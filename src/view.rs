@@ -0,0 +1,213 @@
+//! Cached, filtered views over the items in a manifest.
+//!
+//! UI code often needs a filtered list of items: only the unlocked items, only the tiles for the current biome.
+//! Rebuilding these lists every frame is wasted work, as manifests rarely change.
+//! A [`ManifestView`] stores the filtered (and optionally transformed) list,
+//! and only rebuilds it when the manifest resource has changed, or when the view is [invalidated](ManifestView::invalidate).
+//!
+//! ```
+//! use bevy::ecs::system::SystemState;
+//! use bevy::prelude::*;
+//! use leafwing_manifest::{
+//!     identifier::{Id, IdMap},
+//!     manifest::{IterableManifest, Manifest, ManifestFormat},
+//!     view::ManifestView,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Item {
+//!     price: Option<u32>,
+//! }
+//!
+//! #[derive(Resource, Asset, TypePath, Deserialize)]
+//! struct ItemManifest {
+//!     items: IdMap<Item, Item>,
+//! }
+//!
+//! impl Manifest for ItemManifest {
+//!     type Item = Item;
+//!     type RawItem = Item;
+//!     type RawManifest = ItemManifest;
+//!     type ConversionError = std::convert::Infallible;
+//!
+//!     const FORMAT: ManifestFormat = ManifestFormat::Custom;
+//!
+//!     fn get(&self, id: Id<Item>) -> Option<&Item> {
+//!         self.items.get(&id)
+//!     }
+//!
+//!     fn from_raw_manifest(
+//!         raw_manifest: Self::RawManifest,
+//!         _world: &mut World,
+//!     ) -> Result<Self, Self::ConversionError> {
+//!         Ok(raw_manifest)
+//!     }
+//! }
+//!
+//! impl IterableManifest for ItemManifest {
+//!     fn iter(&self) -> impl Iterator<Item = (Id<Item>, &Item)> {
+//!         self.items.iter().map(|(id, item)| (*id, item))
+//!     }
+//! }
+//!
+//! let sword = Id::from_name("sword");
+//! let mut items = IdMap::default();
+//! items.insert(sword, Item { price: Some(10) });
+//! items.insert(Id::from_name("rock"), Item { price: None });
+//!
+//! let mut world = World::new();
+//! world.insert_resource(ItemManifest { items });
+//! let mut state = SystemState::<Res<ItemManifest>>::new(&mut world);
+//! let mut buyable = ManifestView::<ItemManifest, Id<Item>>::filter(|item: &Item| item.price.is_some());
+//!
+//! {
+//!     let items = state.get(&world);
+//!     assert!(buyable.is_stale(&items));
+//!     assert_eq!(buyable.get(&items), [sword]);
+//!     // The manifest has not changed since the view was built, so the cached list is reused.
+//!     assert!(!buyable.is_stale(&items));
+//! }
+//!
+//! // Modifying the manifest resource updates its change tick, so the view is rebuilt on the next read.
+//! let shield = Id::from_name("shield");
+//! world.resource_mut::<ItemManifest>().items.insert(shield, Item { price: Some(20) });
+//! let items = state.get(&world);
+//! assert!(buyable.is_stale(&items));
+//! assert_eq!(buyable.get(&items).len(), 2);
+//! assert!(!buyable.is_stale(&items));
+//!
+//! // Invalidating the view forces a rebuild, even though the manifest is unchanged.
+//! buyable.invalidate();
+//! assert!(buyable.is_stale(&items));
+//! ```
+//!
+//! Views that depend on other state, such as which items the player has unlocked,
+//! should be invalidated whenever that state changes.
+
+use std::sync::Arc;
+
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::component::Tick;
+use bevy::ecs::system::Res;
+
+use crate::identifier::Id;
+use crate::manifest::{IterableManifest, Manifest};
+
+type Projection<M, V> =
+    Arc<dyn Fn(Id<<M as Manifest>::Item>, &<M as Manifest>::Item) -> Option<V> + Send + Sync>;
+type Comparison<V> = Arc<dyn Fn(&V, &V) -> std::cmp::Ordering + Send + Sync>;
+
+/// A cached list of values computed from the items in the manifest `M`.
+///
+/// See the [module docs](crate::view) for more information.
+pub struct ManifestView<M: IterableManifest, V> {
+    projection: Projection<M, V>,
+    entries: Vec<V>,
+    sort: Option<Comparison<V>>,
+    last_refreshed: Option<Tick>,
+    dirty: bool,
+}
+
+impl<M: IterableManifest, V> ManifestView<M, V> {
+    /// Creates a new view, which contains the value returned by `projection` for each item, skipping any that return [`None`].
+    ///
+    /// The view is empty until it is first read.
+    #[must_use]
+    pub fn new(
+        projection: impl Fn(Id<M::Item>, &M::Item) -> Option<V> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            projection: Arc::new(projection),
+            entries: Vec::new(),
+            sort: None,
+            last_refreshed: None,
+            dirty: true,
+        }
+    }
+
+    /// Sorts the values in the view using the supplied comparison function.
+    ///
+    /// Without sorting, values are stored in the manifest's iteration order, which is often arbitrary.
+    #[must_use]
+    pub fn sorted_by(
+        mut self,
+        compare: impl Fn(&V, &V) -> std::cmp::Ordering + Send + Sync + 'static,
+    ) -> Self {
+        self.sort = Some(Arc::new(compare));
+        self.dirty = true;
+        self
+    }
+
+    /// Marks the view as out of date, so it is rebuilt the next time it is read.
+    ///
+    /// Call this when any state used by the projection (other than the manifest itself) changes.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Returns true if the view will be rebuilt the next time it is read from the supplied `manifest`.
+    #[must_use]
+    pub fn is_stale(&self, manifest: &Res<M>) -> bool {
+        self.dirty || self.last_refreshed != Some(manifest.last_changed())
+    }
+
+    /// Returns the values in the view, rebuilding them first if the `manifest` has changed or the view was invalidated.
+    pub fn get(&mut self, manifest: &Res<M>) -> &[V] {
+        if self.is_stale(manifest) {
+            self.rebuild(manifest);
+            self.last_refreshed = Some(manifest.last_changed());
+        }
+
+        &self.entries
+    }
+
+    /// Rebuilds the view from the supplied `manifest`, regardless of whether it has changed.
+    pub fn rebuild(&mut self, manifest: &M) {
+        self.entries.clear();
+        self.entries.extend(
+            manifest
+                .iter()
+                .filter_map(|(id, item)| (self.projection)(id, item)),
+        );
+        if let Some(sort) = &self.sort {
+            self.entries.sort_by(|a, b| sort(a, b));
+        }
+        self.dirty = false;
+    }
+
+    /// Returns the values from the last time the view was rebuilt, without checking whether they are out of date.
+    #[must_use]
+    pub fn cached(&self) -> &[V] {
+        &self.entries
+    }
+}
+
+impl<M: IterableManifest> ManifestView<M, Id<M::Item>> {
+    /// Creates a new view containing the [`Id`] of each item for which `filter` returns true.
+    #[must_use]
+    pub fn filter(filter: impl Fn(&M::Item) -> bool + Send + Sync + 'static) -> Self {
+        Self::new(move |id, item| filter(item).then_some(id))
+    }
+}
+
+impl<M: IterableManifest, V: Clone> Clone for ManifestView<M, V> {
+    fn clone(&self) -> Self {
+        Self {
+            projection: self.projection.clone(),
+            entries: self.entries.clone(),
+            sort: self.sort.clone(),
+            last_refreshed: self.last_refreshed,
+            dirty: self.dirty,
+        }
+    }
+}
+
+impl<M: IterableManifest, V: std::fmt::Debug> std::fmt::Debug for ManifestView<M, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManifestView")
+            .field("entries", &self.entries)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
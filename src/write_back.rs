@@ -0,0 +1,182 @@
+//! Saving modified manifests back to their raw manifest files.
+//!
+//! Content is normally authored in raw manifest files, and converted into manifests when it is loaded.
+//! Tools which edit the processed manifest instead, such as the [`ManifestEditorPlugin`](crate::editor::ManifestEditorPlugin),
+//! need to convert the manifest back into its raw form, and save it in the format it was loaded from.
+//! Implement [`WriteBackManifest`] to describe that conversion:
+//!
+//! ```rust,ignore
+//! impl WriteBackManifest for ItemManifest {
+//!     fn to_raw_manifest(&self) -> RawItemManifest {
+//!         RawItemManifest {
+//!             items: self.items.values().map(RawItem::from).collect(),
+//!         }
+//!     }
+//! }
+//!
+//! fn save_items(manifest: Res<ItemManifest>, tracker: Res<RawManifestTracker>) {
+//!     if let Err(err) = write_back_manifest(&*manifest, &tracker, &FileAssetReader::get_base_path().join("assets")) {
+//!         error!("{err}");
+//!     }
+//! }
+//! ```
+//!
+//! Only the human-readable formats can be written: RON, JSON, YAML and TOML.
+//! Compressed raw manifests, and raw manifests decoded by a [`RawBytesTransform`](crate::loader::RawBytesTransform),
+//! are rejected rather than overwritten with plain text that could no longer be loaded.
+//! Files are written in place, so comments and formatting in the original file are not preserved.
+//!
+//! This module is not available on the web, which has no filesystem.
+
+use std::any::type_name;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::manifest::{Manifest, ManifestFormat};
+use crate::plugin::RawManifestTracker;
+
+/// A [`Manifest`] which can be converted back into its raw manifest, so changes made at runtime can be saved.
+pub trait WriteBackManifest: Manifest {
+    /// Converts this manifest back into the raw manifest it could have been processed from.
+    ///
+    /// Processing the returned raw manifest via [`Manifest::from_raw_manifest`] should produce an equivalent manifest.
+    fn to_raw_manifest(&self) -> Self::RawManifest;
+}
+
+/// Errors that can occur when writing a raw manifest back to disk.
+#[derive(Debug, thiserror::Error)]
+pub enum WriteBackError {
+    /// The manifest has not been registered with the [`RawManifestTracker`].
+    #[error("The manifest {0} has not been registered")]
+    NotRegistered(&'static str),
+    /// The raw manifest was added from memory, so there is no file to write back to.
+    #[error("The raw manifest of {0} was not loaded from a file")]
+    InMemory(&'static str),
+    /// The raw manifest file is compressed or transformed, so writing plain text to it would break loading.
+    #[error("The raw manifest of {0} is compressed or transformed, so it cannot be written back")]
+    Encoded(&'static str),
+    /// The format of the raw manifest cannot be written.
+    #[error("Raw manifests in the {0:?} format cannot be written back")]
+    UnsupportedFormat(ManifestFormat),
+    /// The raw manifest could not be serialized.
+    #[error("Could not serialize the raw manifest: {0}")]
+    Serialize(String),
+    /// The raw manifest file could not be written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Serializes the `raw_manifest` in the given `format`.
+///
+/// # Example
+///
+/// ```
+/// use leafwing_manifest::manifest::ManifestFormat;
+/// use leafwing_manifest::write_back::{serialize_raw_manifest, WriteBackError};
+///
+/// let result = serialize_raw_manifest(&vec![1, 2, 3], ManifestFormat::Custom);
+/// assert!(matches!(result, Err(WriteBackError::UnsupportedFormat(ManifestFormat::Custom))));
+/// ```
+#[cfg_attr(
+    not(any(feature = "ron", feature = "json", feature = "yaml", feature = "toml")),
+    allow(unused_variables)
+)]
+pub fn serialize_raw_manifest<R: Serialize>(
+    raw_manifest: &R,
+    format: ManifestFormat,
+) -> Result<String, WriteBackError> {
+    let serialize_error = |err: &dyn std::fmt::Display| WriteBackError::Serialize(err.to_string());
+
+    match format {
+        #[cfg(feature = "ron")]
+        ManifestFormat::Ron => {
+            ron::ser::to_string_pretty(raw_manifest, ron::ser::PrettyConfig::default())
+                .map_err(|err| serialize_error(&err))
+        }
+        #[cfg(feature = "json")]
+        ManifestFormat::Json => {
+            serde_json::to_string_pretty(raw_manifest).map_err(|err| serialize_error(&err))
+        }
+        #[cfg(feature = "yaml")]
+        ManifestFormat::Yaml => {
+            serde_yaml::to_string(raw_manifest).map_err(|err| serialize_error(&err))
+        }
+        #[cfg(feature = "toml")]
+        ManifestFormat::Toml => {
+            toml::to_string_pretty(raw_manifest).map_err(|err| serialize_error(&err))
+        }
+        format => Err(WriteBackError::UnsupportedFormat(format)),
+    }
+}
+
+/// Returns the path of the file that the raw manifest of `M` was loaded from.
+///
+/// Raw manifest paths are relative to the asset folder, so the `asset_dir` is prepended.
+pub fn raw_manifest_file<M: Manifest>(
+    raw_manifest_tracker: &RawManifestTracker,
+    asset_dir: &Path,
+) -> Result<PathBuf, WriteBackError> {
+    let status = raw_manifest_tracker
+        .status::<M>()
+        .ok_or(WriteBackError::NotRegistered(type_name::<M>()))?;
+    if status.in_memory {
+        return Err(WriteBackError::InMemory(type_name::<M>()));
+    }
+    // Compression is detected by extension even when its feature is disabled, as such files were never meant to be plain text.
+    let compressed = matches!(
+        status
+            .path
+            .extension()
+            .and_then(|extension| extension.to_str()),
+        Some("gz" | "zst")
+    );
+    if compressed || status.transformed {
+        return Err(WriteBackError::Encoded(type_name::<M>()));
+    }
+
+    Ok(asset_dir.join(&status.path))
+}
+
+/// Converts the `manifest` back into its raw manifest, and serializes it for [`write_back_manifest`], without writing anything.
+///
+/// Returns the path of the file that would be overwritten, along with its new contents.
+/// This can be used to check that a manifest can be saved, or to preview the changes.
+pub fn prepare_write_back<M>(
+    manifest: &M,
+    raw_manifest_tracker: &RawManifestTracker,
+    asset_dir: &Path,
+) -> Result<(PathBuf, String), WriteBackError>
+where
+    M: WriteBackManifest,
+    M::RawManifest: Serialize,
+{
+    let path = raw_manifest_file::<M>(raw_manifest_tracker, asset_dir)?;
+    let format = raw_manifest_tracker
+        .status::<M>()
+        .map_or(M::FORMAT, |status| status.format);
+
+    let serialized = serialize_raw_manifest(&manifest.to_raw_manifest(), format)?;
+    Ok((path, serialized))
+}
+
+/// Converts the `manifest` back into its raw manifest, and overwrites the file it was loaded from.
+///
+/// The file is found in the `asset_dir`, using the path recorded in the [`RawManifestTracker`],
+/// and written in the format it was loaded from.
+/// The path of the written file is returned.
+pub fn write_back_manifest<M>(
+    manifest: &M,
+    raw_manifest_tracker: &RawManifestTracker,
+    asset_dir: &Path,
+) -> Result<PathBuf, WriteBackError>
+where
+    M: WriteBackManifest,
+    M::RawManifest: Serialize,
+{
+    let (path, serialized) = prepare_write_back(manifest, raw_manifest_tracker, asset_dir)?;
+    fs::write(&path, serialized)?;
+    Ok(path)
+}
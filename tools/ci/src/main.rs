@@ -1,7 +1,6 @@
 //! Modified from [Bevy's CI runner](https://github.com/bevyengine/bevy/tree/main/tools/ci/src)
 
 use bitflags::bitflags;
-use itertools::Itertools;
 use xshell::{cmd, Shell};
 
 bitflags! {
@@ -78,13 +77,45 @@ fn main() {
     }
 
     // The features the lib offers
-    let lib_features = ["all_asset_loaders"];
+    let lib_features = [
+        "all_asset_loaders",
+        "atlas",
+        "audio",
+        "baked_manifests",
+        "bincode",
+        "color",
+        "console",
+        "csv",
+        "diagnostics",
+        "editor",
+        "file_watch",
+        "fuzzy",
+        "gzip",
+        "interned",
+        "json",
+        "lazy",
+        "msgpack",
+        "mutable",
+        "postcard",
+        "progress_tracking",
+        "remote",
+        "ron",
+        "scenes",
+        "search",
+        "test_utils",
+        "toml",
+        "uuid",
+        "value_types",
+        "xml",
+        "yaml",
+        "zstd",
+    ];
 
-    // Generate all possible combinations of lib features
-    // and convert them into '--features=<FEATURE_A,FEATURE_B,...>'
-    let lib_features_options = (1..lib_features.len())
-        .flat_map(|combination_length| lib_features.iter().combinations(combination_length))
-        .map(|combination| String::from("--features=") + &combination.iter().join(","));
+    // Check each feature on its own, as a powerset of this many features is far too large to run.
+    // Interactions between features are covered by the `--all-features` run.
+    let lib_features_options = lib_features
+        .iter()
+        .map(|feature| String::from("--features=") + feature);
 
     let default_feature_options = ["--no-default-features", "--all-features"];
     let all_features_options = default_feature_options